@@ -3,6 +3,7 @@
 //! This module provides efficient bit manipulation utilities used by bloom filters
 //! and other algorithms that need to manage bit arrays.
 
+use crate::error::{Error, Result};
 use std::ops::Index;
 
 /// A memory-efficient bitmap implementation
@@ -248,7 +249,11 @@ impl BitMap {
             } else {
                 self.size % 64
             };
-            let mask = (1u64 << bits_in_last_word) - 1;
+            let mask = if bits_in_last_word == 64 {
+                u64::MAX
+            } else {
+                (1u64 << bits_in_last_word) - 1
+            };
             count += (last_word & mask).count_ones() as usize;
         }
 
@@ -478,6 +483,197 @@ impl BitMap {
         }
     }
 
+    /// Bitwise AND with another bitmap, erroring on a size mismatch
+    ///
+    /// Non-panicking counterpart of [`and`](Self::and).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::algorithms::BitMap;
+    ///
+    /// let mut bitmap1 = BitMap::new(100);
+    /// bitmap1.set(10, true);
+    /// assert!(bitmap1.try_and(&BitMap::new(100)).is_ok());
+    /// assert!(bitmap1.try_and(&BitMap::new(50)).is_err());
+    /// ```
+    pub fn try_and(&mut self, other: &BitMap) -> Result<()> {
+        self.check_same_size(other, "AND")?;
+        self.and(other);
+        Ok(())
+    }
+
+    /// Bitwise OR with another bitmap, erroring on a size mismatch
+    ///
+    /// Non-panicking counterpart of [`or`](Self::or).
+    pub fn try_or(&mut self, other: &BitMap) -> Result<()> {
+        self.check_same_size(other, "OR")?;
+        self.or(other);
+        Ok(())
+    }
+
+    /// Bitwise XOR with another bitmap, erroring on a size mismatch
+    ///
+    /// Non-panicking counterpart of [`xor`](Self::xor).
+    pub fn try_xor(&mut self, other: &BitMap) -> Result<()> {
+        self.check_same_size(other, "XOR")?;
+        self.xor(other);
+        Ok(())
+    }
+
+    fn check_same_size(&self, other: &BitMap, operation: &str) -> Result<()> {
+        if self.size != other.size {
+            return Err(Error::validation(format!(
+                "BitMap sizes must match for {operation} operation: {} vs {}",
+                self.size, other.size
+            )));
+        }
+        Ok(())
+    }
+
+    /// Count the set bits before `index` (the rank of `index`)
+    ///
+    /// `rank(len())` equals [`count_ones`](Self::count_ones).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the bitmap size
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::algorithms::BitMap;
+    ///
+    /// let mut bitmap = BitMap::new(200);
+    /// bitmap.set(3, true);
+    /// bitmap.set(70, true);
+    /// bitmap.set(150, true);
+    ///
+    /// assert_eq!(bitmap.rank(0), 0);
+    /// assert_eq!(bitmap.rank(4), 1);
+    /// assert_eq!(bitmap.rank(150), 2);
+    /// assert_eq!(bitmap.rank(200), 3);
+    /// ```
+    pub fn rank(&self, index: usize) -> usize {
+        assert!(
+            index <= self.size,
+            "Index {index} out of bounds for size {}",
+            self.size
+        );
+
+        let full_words = index / 64;
+        let mut count: usize = self.data[..full_words]
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum();
+
+        let remaining_bits = index % 64;
+        if remaining_bits > 0 {
+            let mask = (1u64 << remaining_bits) - 1;
+            count += (self.data[full_words] & mask).count_ones() as usize;
+        }
+
+        count
+    }
+
+    /// Find the index of the `n`th set bit (0-based)
+    ///
+    /// Returns `None` if fewer than `n + 1` bits are set. This is the
+    /// inverse of [`rank`](Self::rank): `rank(select(n)) == n`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::algorithms::BitMap;
+    ///
+    /// let mut bitmap = BitMap::new(200);
+    /// bitmap.set(3, true);
+    /// bitmap.set(70, true);
+    ///
+    /// assert_eq!(bitmap.select(0), Some(3));
+    /// assert_eq!(bitmap.select(1), Some(70));
+    /// assert_eq!(bitmap.select(2), None);
+    /// ```
+    pub fn select(&self, n: usize) -> Option<usize> {
+        let mut remaining = n;
+        for (word_index, &word) in self.data.iter().enumerate() {
+            let ones = word.count_ones() as usize;
+            if remaining < ones {
+                let mut bits = word;
+                for _ in 0..remaining {
+                    bits &= bits - 1; // Clear the lowest set bit
+                }
+                return Some(word_index * 64 + bits.trailing_zeros() as usize);
+            }
+            remaining -= ones;
+        }
+        None
+    }
+
+    /// Count the set bits in the half-open range `start..end`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start > end` or `end` is greater than the bitmap size
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::algorithms::BitMap;
+    ///
+    /// let mut bitmap = BitMap::new(100);
+    /// for i in [5, 10, 64, 99] {
+    ///     bitmap.set(i, true);
+    /// }
+    ///
+    /// assert_eq!(bitmap.count_ones_in_range(0, 100), 4);
+    /// assert_eq!(bitmap.count_ones_in_range(5, 64), 2);
+    /// assert_eq!(bitmap.count_ones_in_range(11, 64), 0);
+    /// ```
+    pub fn count_ones_in_range(&self, start: usize, end: usize) -> usize {
+        assert!(
+            start <= end,
+            "Range start {start} is greater than end {end}"
+        );
+        self.rank(end) - self.rank(start)
+    }
+
+    /// Get an iterator over the indices of set bits, in ascending order
+    ///
+    /// Scans a word at a time, so sparse bitmaps are iterated quickly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::algorithms::BitMap;
+    ///
+    /// let mut bitmap = BitMap::new(300);
+    /// bitmap.set(1, true);
+    /// bitmap.set(64, true);
+    /// bitmap.set(299, true);
+    ///
+    /// let set_bits: Vec<usize> = bitmap.iter_set_bits().collect();
+    /// assert_eq!(set_bits, vec![1, 64, 299]);
+    /// ```
+    pub fn iter_set_bits(&self) -> impl Iterator<Item = usize> + '_ {
+        let size = self.size;
+        self.data
+            .iter()
+            .enumerate()
+            .flat_map(|(word_index, &word)| {
+                let mut bits = word;
+                std::iter::from_fn(move || {
+                    if bits == 0 {
+                        return None;
+                    }
+                    let offset = bits.trailing_zeros() as usize;
+                    bits &= bits - 1;
+                    Some(word_index * 64 + offset)
+                })
+            })
+            .take_while(move |&index| index < size)
+    }
+
     /// Get an iterator over all set bit indices
     ///
     /// # Examples
@@ -494,7 +690,7 @@ impl BitMap {
     /// assert_eq!(set_bits, vec![10, 20, 30]);
     /// ```
     pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
-        (0..self.size).filter(move |&i| self.get(i))
+        self.iter_set_bits()
     }
 
     /// Get an iterator over all unset bit indices
@@ -562,6 +758,22 @@ impl BitMap {
             }
         }
     }
+
+    /// Raw 64-bit words backing the bitmap (bit `i` is bit `i % 64` of word `i / 64`)
+    pub(crate) fn as_words(&self) -> &[u64] {
+        &self.data
+    }
+
+    /// Rebuild a bitmap from raw words, returning `None` if the word count doesn't match `size`
+    pub(crate) fn from_words(size: usize, words: Vec<u64>) -> Option<Self> {
+        if words.len() != size.div_ceil(64) {
+            return None;
+        }
+        let mut bitmap = BitMap { data: words, size };
+        // Drop any stray bits beyond `size`
+        bitmap.resize(size);
+        Some(bitmap)
+    }
 }
 
 impl Index<usize> for BitMap {
@@ -808,4 +1020,87 @@ mod tests {
         let bitmap2 = BitMap::new(200);
         bitmap1.and(&bitmap2);
     }
+
+    #[test]
+    fn test_count_ones_word_aligned() {
+        let bitmap = BitMap::filled(128);
+        assert_eq!(bitmap.count_ones(), 128);
+        assert_eq!(bitmap.count_zeros(), 0);
+    }
+
+    #[test]
+    fn test_try_bitwise_ops() {
+        let mut a = BitMap::new(130);
+        let mut b = BitMap::new(130);
+        a.set(1, true);
+        a.set(129, true);
+        b.set(129, true);
+        b.set(64, true);
+
+        let mut and = a.clone();
+        and.try_and(&b).unwrap();
+        assert_eq!(and.iter_set_bits().collect::<Vec<_>>(), vec![129]);
+
+        let mut or = a.clone();
+        or.try_or(&b).unwrap();
+        assert_eq!(or.iter_set_bits().collect::<Vec<_>>(), vec![1, 64, 129]);
+
+        let mut xor = a.clone();
+        xor.try_xor(&b).unwrap();
+        assert_eq!(xor.iter_set_bits().collect::<Vec<_>>(), vec![1, 64]);
+
+        let other = BitMap::new(128);
+        let err = a.try_and(&other).unwrap_err().to_string();
+        assert!(err.contains("130 vs 128"));
+        assert!(a.try_or(&other).is_err());
+        assert!(a.try_xor(&other).is_err());
+        // Failed operations leave the bitmap untouched
+        assert_eq!(a.iter_set_bits().collect::<Vec<_>>(), vec![1, 129]);
+    }
+
+    #[test]
+    fn test_rank_select_and_ranges() {
+        let mut bitmap = BitMap::new(1000);
+        let set: Vec<usize> = (0..1000).filter(|i| i % 7 == 0 || i % 64 == 63).collect();
+        for &i in &set {
+            bitmap.set(i, true);
+        }
+
+        assert_eq!(bitmap.iter_set_bits().collect::<Vec<_>>(), set);
+        assert_eq!(bitmap.iter_ones().collect::<Vec<_>>(), set);
+        assert_eq!(bitmap.rank(1000), bitmap.count_ones());
+
+        for (n, &index) in set.iter().enumerate() {
+            assert_eq!(bitmap.select(n), Some(index));
+            assert_eq!(bitmap.rank(index), n);
+        }
+        assert_eq!(bitmap.select(set.len()), None);
+
+        for (start, end) in [
+            (0, 0),
+            (0, 64),
+            (63, 64),
+            (10, 500),
+            (128, 1000),
+            (999, 1000),
+        ] {
+            let expected = set.iter().filter(|&&i| i >= start && i < end).count();
+            assert_eq!(
+                bitmap.count_ones_in_range(start, end),
+                expected,
+                "{start}..{end}"
+            );
+        }
+
+        let mut inverted = bitmap.clone();
+        inverted.not();
+        assert_eq!(inverted.count_ones(), 1000 - set.len());
+        assert_eq!(inverted.iter_set_bits().count(), 1000 - set.len());
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_rank_out_of_bounds() {
+        BitMap::new(10).rank(11);
+    }
 }
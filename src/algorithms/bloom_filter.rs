@@ -4,7 +4,7 @@
 //! with automatic parameter optimization and multiple hash function support.
 
 use super::bitmap::BitMap;
-use super::hash_functions::{HashFunction, Hasher};
+use super::hash_functions::{HashAlgorithm, HashFunction, Hasher};
 use crate::error::{Error, Result};
 use std::hash::Hash;
 
@@ -172,6 +172,70 @@ impl BloomFilter {
         self.num_items = 0;
     }
 
+    /// Estimate how many distinct items have been inserted
+    ///
+    /// Uses the estimator `-(m / k) * ln(1 - X / m)`, where `m` is the
+    /// bitmap size, `k` the number of hash functions and `X` the number of
+    /// set bits. Unlike [`len`](Self::len) this is derived from the bit
+    /// array alone, so duplicates are not counted twice and the value stays
+    /// meaningful after [`union`](Self::union) or [`from_bytes`](Self::from_bytes).
+    ///
+    /// When every bit is set the estimator diverges; the estimate for
+    /// `m - 1` set bits is returned instead, a lower bound on the real count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::algorithms::BloomFilter;
+    ///
+    /// let mut bloom = BloomFilter::new(1000, 0.01).unwrap();
+    /// assert_eq!(bloom.estimated_len(), 0);
+    ///
+    /// for i in 0..100 {
+    ///     bloom.insert(&i);
+    ///     bloom.insert(&i); // duplicates don't change the estimate
+    /// }
+    /// let estimate = bloom.estimated_len();
+    /// assert!((90..=110).contains(&estimate));
+    /// ```
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn estimated_len(&self) -> usize {
+        let bits_set = self.bitmap.count_ones();
+        if bits_set == 0 {
+            return 0;
+        }
+
+        let total_bits = self.bitmap.len() as f64;
+        let num_hashes = self.hash_functions.len() as f64;
+        // A saturated filter would give ln(0); clamp to one unset bit
+        let bits_set = bits_set.min(self.bitmap.len() - 1).max(1) as f64;
+
+        let estimate = -(total_bits / num_hashes) * (1.0 - bits_set / total_bits).ln();
+        estimate.round() as usize
+    }
+
+    /// Get the false positive rate implied by the bits currently set
+    ///
+    /// Computed as `(X / m)^k` from the fraction of set bits, so it reflects
+    /// the actual fill level rather than the configured capacity. Returns
+    /// `0.0` for an empty filter and `1.0` once every bit is set. When this
+    /// approaches or exceeds the target rate, rebuild the filter with a
+    /// larger capacity.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn current_false_positive_rate(&self) -> f64 {
+        let bits_set = self.bitmap.count_ones();
+        if bits_set == 0 {
+            return 0.0;
+        }
+
+        let fill_ratio = bits_set as f64 / self.bitmap.len() as f64;
+        fill_ratio.powf(self.hash_functions.len() as f64)
+    }
+
     /// Get the size of the underlying bitmap in bits
     pub fn bitmap_size(&self) -> usize {
         self.bitmap.len()
@@ -181,6 +245,272 @@ impl BloomFilter {
     pub fn num_hash_functions(&self) -> usize {
         self.hash_functions.len()
     }
+
+    /// Merge another filter into this one (bitwise OR)
+    ///
+    /// The result answers `contains` positively for every item inserted
+    /// into either filter, so there are still no false negatives. Both
+    /// filters must have the same bitmap size and hash functions, which is
+    /// the case for filters created with identical parameters.
+    ///
+    /// The item count becomes the sum of both counts, which overcounts
+    /// items inserted into both filters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::algorithms::BloomFilter;
+    ///
+    /// let mut left = BloomFilter::new(1000, 0.01).unwrap();
+    /// let mut right = BloomFilter::new(1000, 0.01).unwrap();
+    /// left.insert("a");
+    /// right.insert("b");
+    ///
+    /// left.union(&right).unwrap();
+    /// assert!(left.contains("a"));
+    /// assert!(left.contains("b"));
+    /// ```
+    pub fn union(&mut self, other: &BloomFilter) -> Result<()> {
+        self.check_compatible(other)?;
+        self.bitmap.or(&other.bitmap);
+        self.num_items = self.num_items.saturating_add(other.num_items);
+        Ok(())
+    }
+
+    /// Intersect this filter with another one (bitwise AND)
+    ///
+    /// Items inserted into both filters are still reported as present.
+    /// The result is not identical to a filter built from only the common
+    /// items: bits set by different items in each filter can survive the
+    /// AND, so the false positive rate may be higher than that of a filter
+    /// built directly from the intersection.
+    ///
+    /// The item count becomes the smaller of the two counts, an upper bound
+    /// on the number of common items.
+    ///
+    /// Both filters must have the same bitmap size and hash functions.
+    pub fn intersect(&mut self, other: &BloomFilter) -> Result<()> {
+        self.check_compatible(other)?;
+        self.bitmap.and(&other.bitmap);
+        self.num_items = self.num_items.min(other.num_items);
+        Ok(())
+    }
+
+    /// Ensure two filters share bitmap size and hash configuration
+    fn check_compatible(&self, other: &BloomFilter) -> Result<()> {
+        if self.bitmap.len() != other.bitmap.len() {
+            return Err(Error::custom(format!(
+                "Bloom filter bitmap sizes differ: {} vs {}",
+                self.bitmap.len(),
+                other.bitmap.len()
+            )));
+        }
+        if self.hash_functions != other.hash_functions {
+            return Err(Error::custom(
+                "Bloom filter hash function configurations differ",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Magic bytes at the start of a serialized [`BloomFilter`]
+const BLOOM_MAGIC: &[u8; 4] = b"RTBF";
+
+/// Serialization format version of [`BloomFilter::to_bytes`]
+const BLOOM_FORMAT_VERSION: u8 = 1;
+
+/// Header id of the stable FNV-1a based hasher behind every [`HashFunction`]
+const BLOOM_HASH_FNV1A: u8 = 1;
+
+/// Serialized size of one hash function: algorithm tag + base seed + seed
+const HASH_FUNCTION_ENCODED_LEN: usize = 1 + 8 + 8;
+
+impl BloomFilter {
+    /// Serialize the filter to bytes for persistence
+    ///
+    /// Layout (integers little-endian):
+    ///
+    /// ```text
+    /// magic "RTBF" | version u8 | hasher u8 | capacity u64 | items u64 | bits u64 | hash count u32
+    /// per hash function: algorithm u8 | base seed u64 | seed u64
+    /// bit array: ceil(bits / 64) words of u64
+    /// ```
+    ///
+    /// Bit positions come from a seeded FNV-1a hash of each item's [`Hash`]
+    /// output, which is identical across Rust releases and platforms, so a
+    /// saved filter stays valid as long as the item types hash the same data.
+    /// The hasher is recorded in the header (`1` = FNV-1a).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::algorithms::BloomFilter;
+    ///
+    /// let mut bloom = BloomFilter::new(1000, 0.01).unwrap();
+    /// bloom.insert("seen-id-42");
+    ///
+    /// let bytes = bloom.to_bytes();
+    /// let restored = BloomFilter::from_bytes(&bytes).unwrap();
+    /// assert!(restored.contains("seen-id-42"));
+    /// assert_eq!(restored.len(), 1);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let words = self.bitmap.as_words();
+        let mut out = Vec::with_capacity(
+            BLOOM_MAGIC.len()
+                + 2
+                + 8 * 3
+                + 4
+                + self.hash_functions.len() * HASH_FUNCTION_ENCODED_LEN
+                + words.len() * 8,
+        );
+
+        out.extend_from_slice(BLOOM_MAGIC);
+        out.push(BLOOM_FORMAT_VERSION);
+        out.push(BLOOM_HASH_FNV1A);
+        out.extend_from_slice(&(self.capacity as u64).to_le_bytes());
+        out.extend_from_slice(&(self.num_items as u64).to_le_bytes());
+        out.extend_from_slice(&(self.bitmap.len() as u64).to_le_bytes());
+        out.extend_from_slice(
+            &u32::try_from(self.hash_functions.len())
+                .unwrap_or(u32::MAX)
+                .to_le_bytes(),
+        );
+
+        for hash_fn in &self.hash_functions {
+            let (tag, base_seed) = match hash_fn.algorithm() {
+                HashAlgorithm::Default => (0u8, 0u64),
+                HashAlgorithm::Murmur3 => (1, 0),
+                HashAlgorithm::Fnv1a => (2, 0),
+                HashAlgorithm::Seeded(base) => (3, base),
+            };
+            out.push(tag);
+            out.extend_from_slice(&base_seed.to_le_bytes());
+            out.extend_from_slice(&hash_fn.seed().to_le_bytes());
+        }
+
+        for word in words {
+            out.extend_from_slice(&word.to_le_bytes());
+        }
+
+        out
+    }
+
+    /// Restore a filter serialized with [`BloomFilter::to_bytes`]
+    ///
+    /// Fails if the magic bytes, version or hasher are not recognized, or if the
+    /// buffer length does not match the sizes recorded in the header.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = ByteReader { bytes, pos: 0 };
+
+        if reader.take(BLOOM_MAGIC.len())? != BLOOM_MAGIC {
+            return Err(Error::custom(
+                "Not a serialized bloom filter: bad magic bytes",
+            ));
+        }
+        let version = reader.take(1)?[0];
+        if version != BLOOM_FORMAT_VERSION {
+            return Err(Error::custom(format!(
+                "Unsupported bloom filter format version: {version}"
+            )));
+        }
+        let hasher = reader.take(1)?[0];
+        if hasher != BLOOM_HASH_FNV1A {
+            return Err(Error::custom(format!(
+                "Unsupported bloom filter hash function: {hasher}"
+            )));
+        }
+
+        let capacity = reader.read_usize()?;
+        let num_items = reader.read_usize()?;
+        let bitmap_size = reader.read_usize()?;
+        let num_hashes = reader.read_u32()? as usize;
+        if bitmap_size == 0 || num_hashes == 0 {
+            return Err(Error::custom(
+                "Invalid bloom filter: bitmap size and hash count must be non-zero",
+            ));
+        }
+
+        let expected_len = num_hashes
+            .checked_mul(HASH_FUNCTION_ENCODED_LEN)
+            .and_then(|n| n.checked_add(bitmap_size.div_ceil(64).checked_mul(8)?))
+            .and_then(|n| n.checked_add(reader.pos));
+        if expected_len != Some(bytes.len()) {
+            return Err(Error::custom(format!(
+                "Invalid bloom filter: length {} does not match header",
+                bytes.len()
+            )));
+        }
+
+        let mut hash_functions = Vec::with_capacity(num_hashes);
+        for _ in 0..num_hashes {
+            let tag = reader.take(1)?[0];
+            let base_seed = reader.read_u64()?;
+            let seed = reader.read_u64()?;
+            let algorithm = match tag {
+                0 => HashAlgorithm::Default,
+                1 => HashAlgorithm::Murmur3,
+                2 => HashAlgorithm::Fnv1a,
+                3 => HashAlgorithm::Seeded(base_seed),
+                _ => {
+                    return Err(Error::custom(format!(
+                        "Invalid bloom filter: unknown hash algorithm tag {tag}"
+                    )));
+                }
+            };
+            hash_functions.push(HashFunction::with_seed(algorithm, seed));
+        }
+
+        let words = (0..bitmap_size.div_ceil(64))
+            .map(|_| reader.read_u64())
+            .collect::<Result<Vec<u64>>>()?;
+        let bitmap = BitMap::from_words(bitmap_size, words)
+            .ok_or_else(|| Error::custom("Invalid bloom filter: bit array size mismatch"))?;
+
+        Ok(BloomFilter {
+            bitmap,
+            hash_functions,
+            num_items,
+            capacity,
+        })
+    }
+}
+
+/// Bounds-checked cursor over a serialized filter
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| Error::custom("Invalid bloom filter: unexpected end of data"))?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        let mut buf = [0u8; 4];
+        buf.copy_from_slice(self.take(4)?);
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    fn read_u64(&mut self) -> Result<u64> {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    fn read_usize(&mut self) -> Result<usize> {
+        usize::try_from(self.read_u64()?)
+            .map_err(|_| Error::custom("Invalid bloom filter: size exceeds platform limits"))
+    }
 }
 
 /// Builder for creating bloom filters with custom parameters
@@ -277,11 +607,58 @@ impl Default for BloomFilterBuilder {
     }
 }
 
+/// Bit width of each counter in a [`CountingBloomFilter`]
+///
+/// Narrower counters use less memory but saturate sooner. Four bits is the
+/// classic choice and is enough unless the same element is inserted many
+/// times.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CounterWidth {
+    /// 4-bit counters (saturate at 15)
+    Bits4,
+    /// 8-bit counters (saturate at 255)
+    Bits8,
+    /// 16-bit counters (saturate at 65535)
+    Bits16,
+    /// 32-bit counters
+    #[default]
+    Bits32,
+}
+
+impl CounterWidth {
+    /// Number of bits per counter
+    pub fn bits(self) -> usize {
+        match self {
+            CounterWidth::Bits4 => 4,
+            CounterWidth::Bits8 => 8,
+            CounterWidth::Bits16 => 16,
+            CounterWidth::Bits32 => 32,
+        }
+    }
+
+    /// Largest value a counter can hold before it saturates
+    pub fn max_value(self) -> u32 {
+        match self {
+            CounterWidth::Bits4 => 0xF,
+            CounterWidth::Bits8 => 0xFF,
+            CounterWidth::Bits16 => 0xFFFF,
+            CounterWidth::Bits32 => u32::MAX,
+        }
+    }
+}
+
 /// A counting bloom filter that supports element removal
 ///
 /// Unlike standard bloom filters, counting bloom filters use counters
 /// instead of bits, allowing for element removal operations.
 ///
+/// Counters saturate at the maximum of their [`CounterWidth`] instead of
+/// wrapping. A saturated counter no longer knows how many insertions it
+/// represents, so it is never decremented: [`remove`](Self::remove) is a
+/// no-op for any element whose counters include a saturated one, which
+/// keeps the filter free of false negatives. Use
+/// [`is_saturated`](Self::is_saturated) to detect such elements.
+///
 /// # Examples
 ///
 /// ```
@@ -299,15 +676,44 @@ impl Default for BloomFilterBuilder {
 /// ```
 #[derive(Debug, Clone)]
 pub struct CountingBloomFilter {
-    counters: Vec<u32>,
+    /// Counters packed `64 / width` per word
+    counters: Vec<u64>,
+    num_counters: usize,
+    counter_width: CounterWidth,
     hash_functions: Vec<HashFunction>,
     num_items: usize,
     capacity: usize,
 }
 
 impl CountingBloomFilter {
-    /// Create a new counting bloom filter
+    /// Create a new counting bloom filter with 32-bit counters
     pub fn new(capacity: usize, false_positive_rate: f64) -> Result<Self> {
+        Self::with_counter_width(capacity, false_positive_rate, CounterWidth::default())
+    }
+
+    /// Create a new counting bloom filter with the given counter width
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::algorithms::{CounterWidth, CountingBloomFilter};
+    ///
+    /// let mut filter =
+    ///     CountingBloomFilter::with_counter_width(1000, 0.01, CounterWidth::Bits4).unwrap();
+    /// for _ in 0..20 {
+    ///     filter.insert("hot");
+    /// }
+    /// assert!(filter.is_saturated("hot"));
+    ///
+    /// // Removal is refused once counters have saturated
+    /// assert!(!filter.remove("hot"));
+    /// assert!(filter.contains("hot"));
+    /// ```
+    pub fn with_counter_width(
+        capacity: usize,
+        false_positive_rate: f64,
+        counter_width: CounterWidth,
+    ) -> Result<Self> {
         if capacity == 0 {
             return Err(Error::custom("Capacity must be greater than 0"));
         }
@@ -316,47 +722,86 @@ impl CountingBloomFilter {
             return Err(Error::custom("False positive rate must be between 0 and 1"));
         }
 
-        let (counter_size, num_hashes) =
+        let (num_counters, num_hashes) =
             BloomFilter::optimal_parameters(capacity, false_positive_rate);
-        let counters = vec![0u32; counter_size];
+        let per_word = 64 / counter_width.bits();
+        let counters = vec![0u64; num_counters.div_ceil(per_word)];
         let hash_functions = HashFunction::generate_functions(num_hashes);
 
         Ok(CountingBloomFilter {
             counters,
+            num_counters,
+            counter_width,
             hash_functions,
             num_items: 0,
             capacity,
         })
     }
 
+    /// Word index and bit shift of a counter
+    fn counter_position(&self, index: usize) -> (usize, usize) {
+        let bit = index * self.counter_width.bits();
+        (bit / 64, bit % 64)
+    }
+
+    /// Read a counter
+    fn counter(&self, index: usize) -> u32 {
+        let (word, shift) = self.counter_position(index);
+        let value = (self.counters[word] >> shift) & u64::from(self.counter_width.max_value());
+        u32::try_from(value).unwrap_or(u32::MAX)
+    }
+
+    /// Overwrite a counter
+    fn set_counter(&mut self, index: usize, value: u32) {
+        let (word, shift) = self.counter_position(index);
+        let mask = u64::from(self.counter_width.max_value()) << shift;
+        self.counters[word] = (self.counters[word] & !mask) | (u64::from(value) << shift);
+    }
+
+    /// Counter indexes an item maps to
+    fn counter_indexes<T: Hash + ?Sized>(&self, item: &T) -> Vec<usize> {
+        self.compute_hashes(item)
+            .into_iter()
+            .map(|hash_value| hash_value % self.num_counters)
+            .collect()
+    }
+
     /// Insert an item into the counting bloom filter
+    ///
+    /// Counters already at their maximum stay there instead of wrapping.
     pub fn insert<T: Hash + ?Sized>(&mut self, item: &T) {
-        let hashes = self.compute_hashes(item);
-        for &hash_value in &hashes {
-            let index = hash_value % self.counters.len();
-            self.counters[index] = self.counters[index].saturating_add(1);
+        let max = self.counter_width.max_value();
+        for index in self.counter_indexes(item) {
+            let value = self.counter(index);
+            if value < max {
+                self.set_counter(index, value + 1);
+            }
         }
         self.num_items += 1;
     }
 
     /// Remove an item from the counting bloom filter
     ///
-    /// Returns `true` if the item was potentially in the filter, `false` otherwise
+    /// Returns `true` if the item was potentially in the filter and its
+    /// counters were decremented. Returns `false` if the item is definitely
+    /// not present, or if any of its counters is saturated, in which case
+    /// nothing is changed.
     pub fn remove<T: Hash + ?Sized>(&mut self, item: &T) -> bool {
-        let hashes = self.compute_hashes(item);
+        let indexes = self.counter_indexes(item);
+        let max = self.counter_width.max_value();
 
-        // First check if item might be present
-        for &hash_value in &hashes {
-            let index = hash_value % self.counters.len();
-            if self.counters[index] == 0 {
-                return false; // Item definitely not present
+        // First check if item might be present and removal is safe
+        for &index in &indexes {
+            let value = self.counter(index);
+            if value == 0 || value == max {
+                return false;
             }
         }
 
         // Decrement counters
-        for &hash_value in &hashes {
-            let index = hash_value % self.counters.len();
-            self.counters[index] = self.counters[index].saturating_sub(1);
+        for &index in &indexes {
+            let value = self.counter(index);
+            self.set_counter(index, value - 1);
         }
 
         if self.num_items > 0 {
@@ -368,14 +813,20 @@ impl CountingBloomFilter {
 
     /// Test if an item might be in the set
     pub fn contains<T: Hash + ?Sized>(&self, item: &T) -> bool {
-        let hashes = self.compute_hashes(item);
-        for &hash_value in &hashes {
-            let index = hash_value % self.counters.len();
-            if self.counters[index] == 0 {
-                return false;
-            }
-        }
-        true
+        self.counter_indexes(item)
+            .into_iter()
+            .all(|index| self.counter(index) > 0)
+    }
+
+    /// Check whether any counter for an item has saturated
+    ///
+    /// When this returns `true`, [`remove`](Self::remove) is a no-op for
+    /// the item.
+    pub fn is_saturated<T: Hash + ?Sized>(&self, item: &T) -> bool {
+        let max = self.counter_width.max_value();
+        self.counter_indexes(item)
+            .into_iter()
+            .any(|index| self.counter(index) == max)
     }
 
     /// Compute hash values for an item
@@ -409,7 +860,12 @@ impl CountingBloomFilter {
 
     /// Get the size of the counter array
     pub fn counter_size(&self) -> usize {
-        self.counters.len()
+        self.num_counters
+    }
+
+    /// Get the bit width of each counter
+    pub fn counter_width(&self) -> CounterWidth {
+        self.counter_width
     }
 
     /// Get the number of hash functions used
@@ -418,6 +874,216 @@ impl CountingBloomFilter {
     }
 }
 
+/// A bloom filter that grows by adding layers as items are inserted
+///
+/// A plain [`BloomFilter`] is sized for a fixed capacity, and its false
+/// positive rate climbs once more items are inserted. This filter starts with
+/// one layer of `initial_capacity` and, whenever the newest layer is full,
+/// adds a new layer with `growth_factor` times its capacity. Lookups check
+/// every layer.
+///
+/// Each new layer is also built for a lower error rate: layer `i` targets
+/// `p0 * r^i`, where `r` is the tightening ratio. With `p0` chosen as
+/// `false_positive_rate * (1 - r)`, the combined rate stays below
+/// `false_positive_rate` however many layers are added.
+///
+/// # Memory
+///
+/// With the defaults (growth factor 2, tightening ratio 0.5), each layer
+/// costs about 1.44 bits per item more than the previous one, and up to half
+/// of the allocated capacity may be unused right after a layer is added. In
+/// practice this is roughly 1.5–2× the memory of a single [`BloomFilter`]
+/// sized for the final item count, the price of not knowing that count
+/// upfront. A larger growth factor means fewer layers (faster lookups) but
+/// more unused space.
+///
+/// # Examples
+///
+/// ```
+/// use yimi_rutool::algorithms::ScalableBloomFilter;
+///
+/// let mut filter = ScalableBloomFilter::new(100, 0.01).unwrap();
+/// for i in 0..1000 {
+///     filter.insert(&i);
+/// }
+///
+/// assert!(filter.num_layers() > 1);
+/// assert!((0..1000).all(|i| filter.contains(&i)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScalableBloomFilter {
+    layers: Vec<BloomFilter>,
+    initial_capacity: usize,
+    false_positive_rate: f64,
+    growth_factor: usize,
+    tightening_ratio: f64,
+}
+
+impl ScalableBloomFilter {
+    /// Default capacity multiplier between consecutive layers
+    pub const DEFAULT_GROWTH_FACTOR: usize = 2;
+
+    /// Default error rate multiplier between consecutive layers
+    pub const DEFAULT_TIGHTENING_RATIO: f64 = 0.5;
+
+    /// Create a scalable bloom filter with the default growth parameters
+    ///
+    /// # Arguments
+    ///
+    /// * `initial_capacity` - Number of items the first layer holds
+    /// * `false_positive_rate` - Upper bound on the combined false positive rate
+    pub fn new(initial_capacity: usize, false_positive_rate: f64) -> Result<Self> {
+        Self::with_growth(
+            initial_capacity,
+            false_positive_rate,
+            Self::DEFAULT_GROWTH_FACTOR,
+            Self::DEFAULT_TIGHTENING_RATIO,
+        )
+    }
+
+    /// Create a scalable bloom filter with custom growth parameters
+    ///
+    /// # Arguments
+    ///
+    /// * `initial_capacity` - Number of items the first layer holds
+    /// * `false_positive_rate` - Upper bound on the combined false positive rate
+    /// * `growth_factor` - Capacity multiplier for each new layer (at least 1)
+    /// * `tightening_ratio` - Error rate multiplier for each new layer (0.0 to 1.0)
+    pub fn with_growth(
+        initial_capacity: usize,
+        false_positive_rate: f64,
+        growth_factor: usize,
+        tightening_ratio: f64,
+    ) -> Result<Self> {
+        if false_positive_rate <= 0.0 || false_positive_rate >= 1.0 {
+            return Err(Error::custom("False positive rate must be between 0 and 1"));
+        }
+
+        if growth_factor == 0 {
+            return Err(Error::custom("Growth factor must be at least 1"));
+        }
+
+        if tightening_ratio <= 0.0 || tightening_ratio >= 1.0 {
+            return Err(Error::custom("Tightening ratio must be between 0 and 1"));
+        }
+
+        let first = BloomFilter::new(
+            initial_capacity,
+            false_positive_rate * (1.0 - tightening_ratio),
+        )?;
+
+        Ok(ScalableBloomFilter {
+            layers: vec![first],
+            initial_capacity,
+            false_positive_rate,
+            growth_factor,
+            tightening_ratio,
+        })
+    }
+
+    /// Insert an item, adding a layer first if the newest one is full
+    ///
+    /// Items that already test as present are not inserted again, so
+    /// duplicates do not use up capacity. This includes false positives, so
+    /// [`len`](Self::len) may slightly undercount distinct items.
+    pub fn insert<T: Hash + ?Sized>(&mut self, item: &T) {
+        if self.contains(item) {
+            return;
+        }
+
+        let newest = self.layers.last().expect("filter always has a layer");
+        if newest.len() >= newest.capacity() {
+            let layer = BloomFilter::new(
+                newest.capacity().saturating_mul(self.growth_factor),
+                self.layer_false_positive_rate(self.layers.len()),
+            )
+            .expect("layer parameters stay within the validated range");
+            self.layers.push(layer);
+        }
+
+        self.layers
+            .last_mut()
+            .expect("filter always has a layer")
+            .insert(item);
+    }
+
+    /// Test if an item might be in the set
+    pub fn contains<T: Hash + ?Sized>(&self, item: &T) -> bool {
+        self.layers.iter().any(|layer| layer.contains(item))
+    }
+
+    /// Target false positive rate of layer `index`
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn layer_false_positive_rate(&self, index: usize) -> f64 {
+        let first = self.false_positive_rate * (1.0 - self.tightening_ratio);
+        // Keep the rate representable for very deep filters
+        (first * self.tightening_ratio.powi(index.min(1000) as i32)).max(f64::MIN_POSITIVE)
+    }
+
+    /// Get the number of items stored across all layers
+    pub fn len(&self) -> usize {
+        self.layers.iter().map(BloomFilter::len).sum()
+    }
+
+    /// Check if the filter is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the number of items the current layers can hold
+    pub fn capacity(&self) -> usize {
+        self.layers.iter().map(BloomFilter::capacity).sum()
+    }
+
+    /// Get the capacity of the first layer
+    pub fn initial_capacity(&self) -> usize {
+        self.initial_capacity
+    }
+
+    /// Get the upper bound on the combined false positive rate
+    pub fn false_positive_rate(&self) -> f64 {
+        self.false_positive_rate
+    }
+
+    /// Get the false positive rate implied by the bits currently set
+    ///
+    /// Combines each layer's [`BloomFilter::current_false_positive_rate`]
+    /// as `1 - Π(1 - p_i)`.
+    pub fn current_false_positive_rate(&self) -> f64 {
+        1.0 - self
+            .layers
+            .iter()
+            .map(|layer| 1.0 - layer.current_false_positive_rate())
+            .product::<f64>()
+    }
+
+    /// Get the number of layers
+    pub fn num_layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Get the total size of all layer bitmaps in bits
+    pub fn bitmap_size(&self) -> usize {
+        self.layers.iter().map(BloomFilter::bitmap_size).sum()
+    }
+
+    /// Get the capacity multiplier between consecutive layers
+    pub fn growth_factor(&self) -> usize {
+        self.growth_factor
+    }
+
+    /// Get the error rate multiplier between consecutive layers
+    pub fn tightening_ratio(&self) -> f64 {
+        self.tightening_ratio
+    }
+
+    /// Clear all items and drop every layer but a fresh first one
+    pub fn clear(&mut self) {
+        self.layers.truncate(1);
+        self.layers[0].clear();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -562,4 +1228,308 @@ mod tests {
         let (m3, _) = BloomFilter::optimal_parameters(1000, 0.001);
         assert!(m3 > m);
     }
+
+    #[test]
+    fn test_bloom_filter_bytes_round_trip() {
+        let mut bloom = BloomFilter::new(500, 0.01).unwrap();
+        for i in 0..300 {
+            bloom.insert(&format!("id-{i}"));
+        }
+
+        let bytes = bloom.to_bytes();
+        let restored = BloomFilter::from_bytes(&bytes).unwrap();
+
+        assert_eq!(restored.len(), bloom.len());
+        assert_eq!(restored.capacity(), bloom.capacity());
+        assert_eq!(restored.bitmap_size(), bloom.bitmap_size());
+        assert_eq!(restored.num_hash_functions(), bloom.num_hash_functions());
+        for i in 0..2000 {
+            let key = format!("id-{i}");
+            assert_eq!(restored.contains(&key), bloom.contains(&key), "{key}");
+        }
+        assert_eq!(restored.to_bytes(), bytes);
+
+        // Manually configured filters keep their hash functions
+        let mut manual = BloomFilterBuilder::new()
+            .bitmap_size(100)
+            .num_hash_functions(7)
+            .build();
+        manual.insert("x");
+        let restored = BloomFilter::from_bytes(&manual.to_bytes()).unwrap();
+        assert_eq!(restored.num_hash_functions(), 7);
+        assert!(restored.contains("x"));
+    }
+
+    #[test]
+    fn test_bloom_filter_bit_positions_are_stable() {
+        let mut bloom = BloomFilterBuilder::new()
+            .bitmap_size(64)
+            .num_hash_functions(3)
+            .build();
+        bloom.insert("hello");
+        let set: Vec<usize> = (0..64).filter(|&i| bloom.bitmap.get(i)).collect();
+        // Pinned so that filters saved by earlier builds keep loading correctly
+        assert_eq!(set, vec![18, 30, 31]);
+    }
+
+    #[test]
+    fn test_bloom_filter_from_bytes_rejects_invalid() {
+        let mut bloom = BloomFilter::new(100, 0.01).unwrap();
+        bloom.insert("hello");
+        let bytes = bloom.to_bytes();
+
+        let err = |data: &[u8]| BloomFilter::from_bytes(data).unwrap_err().to_string();
+
+        assert!(err(&[]).contains("unexpected end"));
+        assert!(err(b"XXXX\x01").contains("bad magic"));
+
+        let mut bad_version = bytes.clone();
+        bad_version[4] = 99;
+        assert!(err(&bad_version).contains("version: 99"));
+
+        let mut bad_hasher = bytes.clone();
+        bad_hasher[5] = 7;
+        assert!(err(&bad_hasher).contains("hash function: 7"));
+
+        assert!(err(&bytes[..bytes.len() - 1]).contains("does not match header"));
+        let mut extra = bytes.clone();
+        extra.push(0);
+        assert!(err(&extra).contains("does not match header"));
+
+        // Bitmap size field claims more bits than the payload holds
+        let mut bad_size = bytes.clone();
+        bad_size[22..30].copy_from_slice(&(1u64 << 40).to_le_bytes());
+        assert!(BloomFilter::from_bytes(&bad_size).is_err());
+
+        // Unknown hash algorithm tag
+        let mut bad_tag = bytes;
+        bad_tag[34] = 9;
+        assert!(err(&bad_tag).contains("unknown hash algorithm tag 9"));
+    }
+
+    #[test]
+    fn test_bloom_filter_union() {
+        let mut left = BloomFilter::new(1000, 0.01).unwrap();
+        let mut right = BloomFilter::new(1000, 0.01).unwrap();
+        for i in 0..200 {
+            left.insert(&format!("left-{i}"));
+            right.insert(&format!("right-{i}"));
+        }
+
+        left.union(&right).unwrap();
+        assert_eq!(left.len(), 400);
+        for i in 0..200 {
+            assert!(left.contains(&format!("left-{i}")));
+            assert!(left.contains(&format!("right-{i}")));
+        }
+
+        // Union of bitmaps equals a filter built from all items directly
+        let mut combined = BloomFilter::new(1000, 0.01).unwrap();
+        for i in 0..200 {
+            combined.insert(&format!("left-{i}"));
+            combined.insert(&format!("right-{i}"));
+        }
+        assert_eq!(left.to_bytes(), combined.to_bytes());
+    }
+
+    #[test]
+    fn test_bloom_filter_intersect() {
+        let mut left = BloomFilter::new(1000, 0.01).unwrap();
+        let mut right = BloomFilter::new(1000, 0.01).unwrap();
+        for i in 0..100 {
+            left.insert(&format!("shared-{i}"));
+            right.insert(&format!("shared-{i}"));
+            left.insert(&format!("left-{i}"));
+        }
+
+        left.intersect(&right).unwrap();
+        assert_eq!(left.len(), 100);
+        for i in 0..100 {
+            assert!(left.contains(&format!("shared-{i}")));
+        }
+        let left_only = (0..100)
+            .filter(|i| left.contains(&format!("left-{i}")))
+            .count();
+        assert!(left_only < 10, "{left_only} left-only items survived");
+    }
+
+    #[test]
+    fn test_bloom_filter_merge_incompatible() {
+        let mut bloom = BloomFilter::new(1000, 0.01).unwrap();
+        let other_size = BloomFilter::new(2000, 0.01).unwrap();
+        let err = bloom.union(&other_size).unwrap_err().to_string();
+        assert!(err.contains("bitmap sizes differ"));
+        assert!(bloom.intersect(&other_size).is_err());
+
+        let other_hashes = BloomFilterBuilder::new()
+            .bitmap_size(bloom.bitmap_size())
+            .num_hash_functions(bloom.num_hash_functions() + 1)
+            .build();
+        let err = bloom.union(&other_hashes).unwrap_err().to_string();
+        assert!(err.contains("hash function configurations differ"));
+    }
+
+    #[test]
+    fn test_bloom_filter_estimated_len() {
+        let mut bloom = BloomFilter::new(10_000, 0.01).unwrap();
+        assert_eq!(bloom.estimated_len(), 0);
+        assert!(bloom.current_false_positive_rate().abs() < f64::EPSILON);
+
+        for i in 0..5000 {
+            bloom.insert(&i);
+        }
+        let estimate = bloom.estimated_len();
+        assert!((4750..=5250).contains(&estimate), "estimate {estimate}");
+
+        // Re-inserting the same items leaves the estimate unchanged
+        for i in 0..5000 {
+            bloom.insert(&i);
+        }
+        assert_eq!(bloom.len(), 10_000);
+        assert_eq!(bloom.estimated_len(), estimate);
+
+        let rate = bloom.current_false_positive_rate();
+        assert!(rate > 0.0 && rate < 0.01, "rate {rate}");
+
+        // Filling past capacity pushes the rate up
+        for i in 5000..50_000 {
+            bloom.insert(&i);
+        }
+        assert!(bloom.current_false_positive_rate() > 0.1);
+    }
+
+    #[test]
+    fn test_bloom_filter_estimated_len_saturated() {
+        let mut bloom = BloomFilterBuilder::new()
+            .bitmap_size(64)
+            .num_hash_functions(3)
+            .build();
+        for i in 0..10_000 {
+            bloom.insert(&i);
+        }
+        assert_eq!(bloom.bitmap.count_ones(), 64);
+
+        let estimate = bloom.estimated_len();
+        assert!(estimate > 0);
+        assert!((bloom.current_false_positive_rate() - 1.0).abs() < f64::EPSILON);
+        assert!(!bloom.current_false_positive_rate().is_nan());
+    }
+
+    #[test]
+    fn test_counting_bloom_filter_counter_widths() {
+        for width in [
+            CounterWidth::Bits4,
+            CounterWidth::Bits8,
+            CounterWidth::Bits16,
+            CounterWidth::Bits32,
+        ] {
+            let mut filter = CountingBloomFilter::with_counter_width(100, 0.01, width).unwrap();
+            assert_eq!(filter.counter_width(), width);
+
+            filter.insert("a");
+            filter.insert("a");
+            filter.insert("b");
+            assert!(filter.remove("a"));
+            assert!(filter.contains("a"));
+            assert!(filter.remove("a"));
+            assert!(!filter.contains("a"));
+            assert!(filter.contains("b"));
+        }
+    }
+
+    #[test]
+    fn test_counting_bloom_filter_saturation() {
+        let mut filter =
+            CountingBloomFilter::with_counter_width(1000, 0.01, CounterWidth::Bits4).unwrap();
+        let others: Vec<String> = (0..50).map(|i| format!("other-{i}")).collect();
+        for other in &others {
+            filter.insert(other);
+        }
+
+        // Hammer one element well past the 4-bit maximum
+        for _ in 0..1000 {
+            filter.insert("hot");
+        }
+        assert!(filter.is_saturated("hot"));
+        assert!(filter.contains("hot"));
+
+        // Removing a saturated element is a no-op
+        assert!(!filter.remove("hot"));
+        assert!(filter.contains("hot"));
+        assert!(filter.is_saturated("hot"));
+
+        // Other elements are still present and can be removed
+        for other in &others {
+            assert!(filter.contains(other));
+        }
+        let removable: Vec<&String> = others
+            .iter()
+            .filter(|other| !filter.is_saturated(*other))
+            .collect();
+        assert!(!removable.is_empty());
+        for other in removable {
+            assert!(filter.remove(other));
+        }
+        assert!(filter.contains("hot"));
+    }
+
+    #[test]
+    fn test_scalable_bloom_filter_bounded_fpr() {
+        let mut filter = ScalableBloomFilter::new(1000, 0.01).unwrap();
+        for i in 0..10_000 {
+            filter.insert(&i);
+        }
+
+        // Inserts that hit a false positive are skipped
+        assert!(filter.len() > 9_900 && filter.len() <= 10_000);
+        assert!(filter.num_layers() >= 4);
+        assert!(filter.capacity() >= 10_000);
+        assert!((0..10_000).all(|i| filter.contains(&i)));
+
+        let false_positives = (10_000..110_000).filter(|i| filter.contains(i)).count();
+        let rate = false_positives as f64 / 100_000.0;
+        assert!(rate < 0.01, "false positive rate {} exceeds target", rate);
+        assert!(filter.current_false_positive_rate() < 0.01);
+
+        // A plain filter overfilled the same way degrades badly
+        let mut plain = BloomFilter::new(1000, 0.01).unwrap();
+        for i in 0..10_000 {
+            plain.insert(&i);
+        }
+        assert!(plain.current_false_positive_rate() > 0.5);
+    }
+
+    #[test]
+    fn test_scalable_bloom_filter_duplicates_and_clear() {
+        let mut filter = ScalableBloomFilter::new(10, 0.01).unwrap();
+        for _ in 0..100 {
+            filter.insert("same");
+        }
+        assert_eq!(filter.len(), 1);
+        assert_eq!(filter.num_layers(), 1);
+
+        for i in 0..100 {
+            filter.insert(&i);
+        }
+        assert!(filter.num_layers() > 1);
+
+        filter.clear();
+        assert!(filter.is_empty());
+        assert_eq!(filter.num_layers(), 1);
+        assert_eq!(filter.capacity(), filter.initial_capacity());
+        assert!(!filter.contains("same"));
+    }
+
+    #[test]
+    fn test_scalable_bloom_filter_invalid_params() {
+        assert!(ScalableBloomFilter::new(0, 0.01).is_err());
+        assert!(ScalableBloomFilter::new(100, 1.5).is_err());
+        assert!(ScalableBloomFilter::with_growth(100, 0.01, 0, 0.5).is_err());
+        assert!(ScalableBloomFilter::with_growth(100, 0.01, 2, 1.0).is_err());
+
+        let filter = ScalableBloomFilter::with_growth(100, 0.01, 4, 0.8).unwrap();
+        assert_eq!(filter.growth_factor(), 4);
+        assert_eq!(filter.tightening_ratio(), 0.8);
+        assert_eq!(filter.false_positive_rate(), 0.01);
+    }
 }
@@ -0,0 +1,618 @@
+//! Compressed bitmaps for sparse bit sets over large index ranges
+//!
+//! [`CompressedBitMap`] follows the Roaring bitmap layout: the index space is
+//! split into chunks of 65,536 indices, and only chunks containing set bits
+//! are stored. Each chunk picks the smallest of three containers:
+//!
+//! - a sorted array of 16-bit offsets, for up to 4,096 set bits
+//! - a plain 8 KiB bitmap, for more set bits than that
+//! - a list of runs of consecutive set bits, chosen by
+//!   [`CompressedBitMap::optimize`]
+
+use super::bitmap::BitMap;
+use crate::error::{Error, Result};
+use std::collections::BTreeMap;
+
+/// Number of low index bits stored inside a container
+const CHUNK_BITS: u32 = 16;
+
+/// Largest cardinality kept as an array; above it a bitmap container is smaller
+const ARRAY_MAX: usize = 4096;
+
+/// Number of 64-bit words in a bitmap container (2^16 bits)
+const BITMAP_WORDS: usize = 1024;
+
+/// Set bits of one 65,536-index chunk
+#[derive(Debug, Clone)]
+enum Container {
+    /// Sorted offsets of the set bits
+    Array(Vec<u16>),
+    /// All 2^16 bits, with the number of ones cached
+    Bitmap {
+        words: Box<[u64; BITMAP_WORDS]>,
+        ones: usize,
+    },
+    /// Sorted, non-adjacent inclusive ranges of set bits
+    Run(Vec<(u16, u16)>),
+}
+
+fn bit_position(value: u16) -> (usize, u64) {
+    (usize::from(value) / 64, 1u64 << (value % 64))
+}
+
+impl Container {
+    /// Build the smaller of an array or bitmap container from sorted offsets
+    fn from_sorted(values: Vec<u16>) -> Self {
+        if values.len() <= ARRAY_MAX {
+            return Container::Array(values);
+        }
+        let mut words = Box::new([0u64; BITMAP_WORDS]);
+        for &value in &values {
+            let (word, mask) = bit_position(value);
+            words[word] |= mask;
+        }
+        Container::Bitmap {
+            words,
+            ones: values.len(),
+        }
+    }
+
+    /// Build a container from bitmap words, as an array if it is sparse enough
+    fn from_words(words: Box<[u64; BITMAP_WORDS]>) -> Self {
+        let ones = words.iter().map(|w| w.count_ones() as usize).sum();
+        let container = Container::Bitmap { words, ones };
+        if ones <= ARRAY_MAX {
+            Container::Array(container.iter().collect())
+        } else {
+            container
+        }
+    }
+
+    fn to_words(&self) -> Box<[u64; BITMAP_WORDS]> {
+        if let Container::Bitmap { words, .. } = self {
+            return words.clone();
+        }
+        let mut words = Box::new([0u64; BITMAP_WORDS]);
+        for value in self.iter() {
+            let (word, mask) = bit_position(value);
+            words[word] |= mask;
+        }
+        words
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Container::Array(values) => values.len(),
+            Container::Bitmap { ones, .. } => *ones,
+            Container::Run(runs) => runs
+                .iter()
+                .map(|&(start, end)| usize::from(end - start) + 1)
+                .sum(),
+        }
+    }
+
+    fn contains(&self, value: u16) -> bool {
+        match self {
+            Container::Array(values) => values.binary_search(&value).is_ok(),
+            Container::Bitmap { words, .. } => {
+                let (word, mask) = bit_position(value);
+                words[word] & mask != 0
+            }
+            Container::Run(runs) => {
+                let after = runs.partition_point(|&(start, _)| start <= value);
+                after > 0 && runs[after - 1].1 >= value
+            }
+        }
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = u16> + '_> {
+        match self {
+            Container::Array(values) => Box::new(values.iter().copied()),
+            Container::Bitmap { words, .. } => {
+                Box::new(words.iter().enumerate().flat_map(|(index, &word)| {
+                    let mut bits = word;
+                    std::iter::from_fn(move || {
+                        if bits == 0 {
+                            return None;
+                        }
+                        let offset = bits.trailing_zeros() as usize;
+                        bits &= bits - 1;
+                        u16::try_from(index * 64 + offset).ok()
+                    })
+                }))
+            }
+            Container::Run(runs) => Box::new(runs.iter().flat_map(|&(start, end)| start..=end)),
+        }
+    }
+
+    /// Replace a run container with an array or bitmap so it can be mutated
+    fn expand_runs(&mut self) {
+        if let Container::Run(_) = self {
+            *self = Container::from_sorted(self.iter().collect());
+        }
+    }
+
+    fn insert(&mut self, value: u16) -> bool {
+        self.expand_runs();
+        match self {
+            Container::Array(values) => match values.binary_search(&value) {
+                Ok(_) => false,
+                Err(pos) => {
+                    values.insert(pos, value);
+                    if values.len() > ARRAY_MAX {
+                        *self = Container::from_sorted(std::mem::take(values));
+                    }
+                    true
+                }
+            },
+            Container::Bitmap { words, ones } => {
+                let (word, mask) = bit_position(value);
+                let inserted = words[word] & mask == 0;
+                words[word] |= mask;
+                *ones += usize::from(inserted);
+                inserted
+            }
+            Container::Run(_) => unreachable!("runs are expanded before mutation"),
+        }
+    }
+
+    fn remove(&mut self, value: u16) -> bool {
+        self.expand_runs();
+        match self {
+            Container::Array(values) => match values.binary_search(&value) {
+                Ok(pos) => {
+                    values.remove(pos);
+                    true
+                }
+                Err(_) => false,
+            },
+            Container::Bitmap { words, ones } => {
+                let (word, mask) = bit_position(value);
+                let removed = words[word] & mask != 0;
+                words[word] &= !mask;
+                *ones -= usize::from(removed);
+                if *ones <= ARRAY_MAX {
+                    *self = Container::Array(self.iter().collect());
+                }
+                removed
+            }
+            Container::Run(_) => unreachable!("runs are expanded before mutation"),
+        }
+    }
+
+    fn and(&self, other: &Container) -> Container {
+        match (self, other) {
+            (Container::Array(values), other) | (other, Container::Array(values)) => {
+                Container::Array(
+                    values
+                        .iter()
+                        .copied()
+                        .filter(|&value| other.contains(value))
+                        .collect(),
+                )
+            }
+            _ => {
+                let mut words = self.to_words();
+                for (word, other) in words.iter_mut().zip(other.to_words().iter()) {
+                    *word &= other;
+                }
+                Container::from_words(words)
+            }
+        }
+    }
+
+    fn or(&self, other: &Container) -> Container {
+        if let (Container::Array(a), Container::Array(b)) = (self, other)
+            && a.len() + b.len() <= ARRAY_MAX
+        {
+            let mut merged = Vec::with_capacity(a.len() + b.len());
+            merged.extend_from_slice(a);
+            merged.extend_from_slice(b);
+            merged.sort_unstable();
+            merged.dedup();
+            return Container::Array(merged);
+        }
+        let mut words = self.to_words();
+        for (word, other) in words.iter_mut().zip(other.to_words().iter()) {
+            *word |= other;
+        }
+        Container::from_words(words)
+    }
+
+    /// Switch to whichever container representation is smallest
+    fn optimize(&mut self) {
+        let mut runs: Vec<(u16, u16)> = Vec::new();
+        for value in self.iter() {
+            match runs.last_mut() {
+                Some((_, end)) if end.checked_add(1) == Some(value) => *end = value,
+                _ => runs.push((value, value)),
+            }
+        }
+
+        let len = self.len();
+        let run_bytes = runs.len() * 4;
+        let other_bytes = if len <= ARRAY_MAX {
+            len * 2
+        } else {
+            BITMAP_WORDS * 8
+        };
+        if run_bytes < other_bytes {
+            runs.shrink_to_fit();
+            *self = Container::Run(runs);
+        } else if let Container::Run(_) = self {
+            self.expand_runs();
+        }
+        if let Container::Array(values) = self {
+            values.shrink_to_fit();
+        }
+    }
+
+    fn heap_bytes(&self) -> usize {
+        match self {
+            Container::Array(values) => values.capacity() * 2,
+            Container::Bitmap { .. } => BITMAP_WORDS * 8,
+            Container::Run(runs) => runs.capacity() * 4,
+        }
+    }
+}
+
+/// A compressed bitmap for sparse or clustered bits over a large index range
+///
+/// Unlike [`BitMap`], it has no fixed size: any `usize` index can be set,
+/// and memory grows with the number of set bits rather than the range.
+///
+/// # Memory
+///
+/// A dense [`BitMap`] costs one bit per index in its range, i.e. 8 KiB per
+/// 65,536 indices whether or not any are set. A compressed bitmap stores
+/// nothing for empty chunks, and for the others about 2 bytes per set bit,
+/// at most 8 KiB, or 4 bytes per run of consecutive set bits after
+/// [`optimize`](Self::optimize), plus roughly 50 bytes of bookkeeping per
+/// chunk. Compression therefore pays off when fewer than about 1 in 16
+/// indices are set, or when set bits form long runs. Above that density
+/// both use about the same memory, and a dense [`BitMap`] is faster.
+///
+/// # Examples
+///
+/// ```
+/// use yimi_rutool::algorithms::CompressedBitMap;
+///
+/// let mut bitmap = CompressedBitMap::new();
+/// bitmap.set(42, true);
+/// bitmap.set(3_000_000_000, true);
+///
+/// assert!(bitmap.get(3_000_000_000));
+/// assert_eq!(bitmap.count_ones(), 2);
+/// assert_eq!(bitmap.iter_ones().collect::<Vec<_>>(), vec![42, 3_000_000_000]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CompressedBitMap {
+    containers: BTreeMap<usize, Container>,
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn split_index(index: usize) -> (usize, u16) {
+    (index >> CHUNK_BITS, (index & 0xFFFF) as u16)
+}
+
+fn join_index(key: usize, value: u16) -> usize {
+    (key << CHUNK_BITS) | usize::from(value)
+}
+
+impl CompressedBitMap {
+    /// Create an empty compressed bitmap
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the value of the bit at the specified index
+    pub fn get(&self, index: usize) -> bool {
+        let (key, value) = split_index(index);
+        self.containers
+            .get(&key)
+            .is_some_and(|container| container.contains(value))
+    }
+
+    /// Set the value of the bit at the specified index
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::algorithms::CompressedBitMap;
+    ///
+    /// let mut bitmap = CompressedBitMap::new();
+    /// bitmap.set(1_000_000_000, true);
+    /// assert!(bitmap.get(1_000_000_000));
+    ///
+    /// bitmap.set(1_000_000_000, false);
+    /// assert!(bitmap.is_empty());
+    /// ```
+    pub fn set(&mut self, index: usize, value: bool) {
+        let (key, offset) = split_index(index);
+        if value {
+            self.containers
+                .entry(key)
+                .or_insert_with(|| Container::Array(Vec::new()))
+                .insert(offset);
+        } else if let Some(container) = self.containers.get_mut(&key) {
+            container.remove(offset);
+            if container.len() == 0 {
+                self.containers.remove(&key);
+            }
+        }
+    }
+
+    /// Toggle the bit at the specified index
+    pub fn toggle(&mut self, index: usize) {
+        self.set(index, !self.get(index));
+    }
+
+    /// Count the number of bits set to 1
+    pub fn count_ones(&self) -> usize {
+        self.containers.values().map(Container::len).sum()
+    }
+
+    /// Check if no bits are set
+    pub fn is_empty(&self) -> bool {
+        self.containers.is_empty()
+    }
+
+    /// Clear all bits
+    pub fn clear(&mut self) {
+        self.containers.clear();
+    }
+
+    /// Get the lowest set bit index, if any
+    pub fn min(&self) -> Option<usize> {
+        let (&key, container) = self.containers.first_key_value()?;
+        container.iter().next().map(|value| join_index(key, value))
+    }
+
+    /// Get the highest set bit index, if any
+    pub fn max(&self) -> Option<usize> {
+        let (&key, container) = self.containers.last_key_value()?;
+        container.iter().last().map(|value| join_index(key, value))
+    }
+
+    /// Get an iterator over the indices of set bits, in ascending order
+    pub fn iter_set_bits(&self) -> impl Iterator<Item = usize> + '_ {
+        self.containers
+            .iter()
+            .flat_map(|(&key, container)| container.iter().map(move |value| join_index(key, value)))
+    }
+
+    /// Get an iterator over all set bit indices
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.iter_set_bits()
+    }
+
+    /// Keep only the bits also set in `other`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::algorithms::CompressedBitMap;
+    ///
+    /// let mut active: CompressedBitMap = [1, 5, 1_000_000].into_iter().collect();
+    /// let premium: CompressedBitMap = [5, 1_000_000, 2_000_000].into_iter().collect();
+    ///
+    /// active.and(&premium);
+    /// assert_eq!(active.iter_ones().collect::<Vec<_>>(), vec![5, 1_000_000]);
+    /// ```
+    pub fn and(&mut self, other: &CompressedBitMap) {
+        self.containers.retain(|key, container| {
+            match other.containers.get(key) {
+                Some(other) => *container = container.and(other),
+                None => return false,
+            }
+            container.len() > 0
+        });
+    }
+
+    /// Add all bits set in `other`
+    pub fn or(&mut self, other: &CompressedBitMap) {
+        for (&key, other) in &other.containers {
+            match self.containers.get_mut(&key) {
+                Some(container) => *container = container.or(other),
+                None => {
+                    self.containers.insert(key, other.clone());
+                }
+            }
+        }
+    }
+
+    /// Convert containers to run-length encoding where that is smaller
+    ///
+    /// Call after bulk loading bits that form consecutive ranges. Setting or
+    /// clearing a bit in a run-encoded chunk expands it again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::algorithms::CompressedBitMap;
+    ///
+    /// let mut bitmap: CompressedBitMap = (0..60_000).collect();
+    /// let before = bitmap.memory_usage();
+    ///
+    /// bitmap.optimize();
+    /// assert!(bitmap.memory_usage() < before / 100);
+    /// assert_eq!(bitmap.count_ones(), 60_000);
+    /// ```
+    pub fn optimize(&mut self) {
+        for container in self.containers.values_mut() {
+            container.optimize();
+        }
+    }
+
+    /// Estimate the memory used by the bitmap in bytes
+    pub fn memory_usage(&self) -> usize {
+        let per_chunk = std::mem::size_of::<usize>() + std::mem::size_of::<Container>();
+        std::mem::size_of::<Self>()
+            + self
+                .containers
+                .values()
+                .map(|container| per_chunk + container.heap_bytes())
+                .sum::<usize>()
+    }
+
+    /// Convert to a dense bitmap of `size` bits
+    ///
+    /// Fails if a bit at or beyond `size` is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::algorithms::CompressedBitMap;
+    ///
+    /// let bitmap: CompressedBitMap = [3, 7].into_iter().collect();
+    /// let dense = bitmap.to_bitmap(10).unwrap();
+    /// assert!(dense.get(7));
+    /// assert!(bitmap.to_bitmap(5).is_err());
+    /// ```
+    pub fn to_bitmap(&self, size: usize) -> Result<BitMap> {
+        if let Some(max) = self.max().filter(|&max| max >= size) {
+            return Err(Error::validation(format!(
+                "Bit {max} is out of range for a BitMap of {size} bits"
+            )));
+        }
+        let mut bitmap = BitMap::new(size);
+        for index in self.iter_set_bits() {
+            bitmap.set(index, true);
+        }
+        Ok(bitmap)
+    }
+}
+
+impl From<&BitMap> for CompressedBitMap {
+    fn from(bitmap: &BitMap) -> Self {
+        bitmap.iter_set_bits().collect()
+    }
+}
+
+impl FromIterator<usize> for CompressedBitMap {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut bitmap = CompressedBitMap::new();
+        for index in iter {
+            bitmap.set(index, true);
+        }
+        bitmap
+    }
+}
+
+impl PartialEq for CompressedBitMap {
+    fn eq(&self, other: &Self) -> bool {
+        // Containers may differ in representation, so compare contents
+        self.containers.len() == other.containers.len()
+            && self.count_ones() == other.count_ones()
+            && self.iter_set_bits().eq(other.iter_set_bits())
+    }
+}
+
+impl Eq for CompressedBitMap {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparse_bits_up_to_a_billion() {
+        let indices: Vec<usize> = (0..10_000)
+            .map(|i| i * 99_991)
+            .chain([1_000_000_000])
+            .collect();
+        let bitmap: CompressedBitMap = indices.iter().copied().collect();
+
+        assert_eq!(bitmap.count_ones(), indices.len());
+        assert!(indices.iter().all(|&i| bitmap.get(i)));
+        assert!(!bitmap.get(1));
+        assert!(!bitmap.get(999_999_999));
+        assert_eq!(bitmap.min(), Some(0));
+        assert_eq!(bitmap.max(), Some(1_000_000_000));
+        assert_eq!(bitmap.iter_ones().collect::<Vec<_>>(), indices);
+
+        // A dense BitMap over the same range needs 125 MB
+        assert!(
+            bitmap.memory_usage() < 1_000_000,
+            "{} bytes used",
+            bitmap.memory_usage()
+        );
+    }
+
+    #[test]
+    fn test_container_conversions() {
+        let mut bitmap: CompressedBitMap = (0..10_000).map(|i| i * 2).collect();
+        assert!(matches!(
+            bitmap.containers[&0],
+            Container::Bitmap { ones: 10_000, .. }
+        ));
+        assert!(bitmap.memory_usage() < 10_000);
+
+        for i in 0..8_000 {
+            bitmap.set(i * 2, false);
+        }
+        assert!(matches!(&bitmap.containers[&0], Container::Array(v) if v.len() == 2_000));
+        assert_eq!(bitmap.min(), Some(16_000));
+
+        for i in 0..2_000 {
+            bitmap.toggle(16_000 + i * 2);
+        }
+        assert!(bitmap.is_empty());
+        assert_eq!(bitmap.max(), None);
+    }
+
+    #[test]
+    fn test_runs() {
+        let mut bitmap: CompressedBitMap = (100..70_000).chain(200_000..200_010).collect();
+        let expected: Vec<usize> = bitmap.iter_ones().collect();
+
+        bitmap.optimize();
+        assert!(matches!(bitmap.containers[&0], Container::Run(_)));
+        assert!(matches!(bitmap.containers[&1], Container::Run(_)));
+        assert!(bitmap.memory_usage() < 500);
+        assert_eq!(bitmap.iter_ones().collect::<Vec<_>>(), expected);
+        assert!(bitmap.get(100) && bitmap.get(65_535) && bitmap.get(65_536));
+        assert!(!bitmap.get(99) && !bitmap.get(70_000));
+
+        // Mutating a run container expands it
+        bitmap.set(500, false);
+        assert!(!bitmap.get(500));
+        assert_eq!(bitmap.count_ones(), expected.len() - 1);
+        assert!(!matches!(bitmap.containers[&0], Container::Run(_)));
+    }
+
+    #[test]
+    fn test_and_or() {
+        let evens: CompressedBitMap = (0..200_000).step_by(2).collect();
+        let threes: CompressedBitMap = (0..200_000).step_by(3).chain([5_000_000]).collect();
+
+        let mut both = evens.clone();
+        both.and(&threes);
+        assert_eq!(both, (0..200_000).step_by(6).collect::<CompressedBitMap>());
+
+        let mut either = evens.clone();
+        either.or(&threes);
+        assert_eq!(either.count_ones(), 100_000 + 66_667 - 33_334 + 1);
+        assert!(either.get(3) && either.get(4) && !either.get(5) && either.get(5_000_000));
+
+        let mut none = evens.clone();
+        none.and(&CompressedBitMap::from_iter([1, 3, 10_000_001]));
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn test_dense_conversion() {
+        let mut dense = BitMap::new(300_000);
+        for i in (0..300_000).step_by(7) {
+            dense.set(i, true);
+        }
+
+        let compressed = CompressedBitMap::from(&dense);
+        assert_eq!(compressed.count_ones(), dense.count_ones());
+        assert_eq!(compressed.to_bitmap(300_000).unwrap(), dense);
+        assert!(compressed.to_bitmap(299_999).is_err());
+
+        let mut optimized = compressed.clone();
+        optimized.optimize();
+        assert_eq!(optimized, compressed);
+    }
+}
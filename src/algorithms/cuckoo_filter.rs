@@ -0,0 +1,489 @@
+//! Cuckoo filter implementation
+//!
+//! A cuckoo filter answers the same "possibly in the set / definitely not"
+//! question as a bloom filter, but stores a short fingerprint of each item
+//! in one of two candidate buckets. Because each item occupies exactly one
+//! slot, it can be removed again without the counter saturation issues of a
+//! [`CountingBloomFilter`](super::CountingBloomFilter), and at low false
+//! positive rates it uses less space than a bloom filter.
+
+use super::hash_functions::{HashFunction, StableHasher};
+use crate::error::{Error, Result};
+use std::hash::{Hash, Hasher as StdHasher};
+
+/// Number of fingerprint slots per bucket
+const BUCKET_SIZE: usize = 4;
+
+/// Maximum number of evictions before an insert is reported as failed
+const MAX_KICKS: usize = 500;
+
+/// Highest load factor the buckets are sized for
+const MAX_LOAD_FACTOR: f64 = 0.95;
+
+/// Fingerprint value marking an empty slot
+const EMPTY: u16 = 0;
+
+/// Magic bytes at the start of a serialized [`CuckooFilter`]
+const CUCKOO_MAGIC: &[u8; 4] = b"RTCF";
+
+/// Serialization format version of [`CuckooFilter::to_bytes`]
+const CUCKOO_FORMAT_VERSION: u8 = 1;
+
+/// Header id of the stable FNV-1a based item hasher
+const CUCKOO_HASH_FNV1A: u8 = 1;
+
+/// Serialized header size: magic + version + hasher + bucket count + item count
+const CUCKOO_HEADER_LEN: usize = 4 + 1 + 1 + 8 + 8;
+
+/// A probabilistic set supporting deletion, based on cuckoo hashing
+///
+/// Each item is reduced to a 16-bit fingerprint stored in one of two
+/// buckets of four slots. The false positive rate is about
+/// `2 * 4 / 2^16 ≈ 0.012%` regardless of capacity.
+///
+/// Inserting fails once the table is too full to place an item (usually
+/// above 95% load); the filter is left unchanged in that case. Inserting the
+/// same item twice stores two copies, and removing an item that was never
+/// inserted may remove another item with the same fingerprint, so only
+/// remove items known to be present.
+///
+/// # Examples
+///
+/// ```
+/// use yimi_rutool::algorithms::CuckooFilter;
+///
+/// let mut filter = CuckooFilter::new(1000).unwrap();
+/// filter.insert("session-1").unwrap();
+/// assert!(filter.contains("session-1"));
+///
+/// assert!(filter.remove("session-1"));
+/// assert!(!filter.contains("session-1"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CuckooFilter {
+    slots: Vec<u16>,
+    num_buckets: usize,
+    num_items: usize,
+    rng_state: u64,
+}
+
+impl CuckooFilter {
+    /// Create a cuckoo filter able to hold at least `capacity` items
+    ///
+    /// The number of buckets is rounded up to a power of two, so the actual
+    /// [`capacity`](Self::capacity) may be larger.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Expected number of items to be inserted
+    pub fn new(capacity: usize) -> Result<Self> {
+        if capacity == 0 {
+            return Err(Error::custom("Capacity must be greater than 0"));
+        }
+
+        let too_large = || Error::custom("Capacity is too large");
+        let mut num_buckets = capacity
+            .div_ceil(BUCKET_SIZE)
+            .checked_next_power_of_two()
+            .ok_or_else(too_large)?;
+        let slots = num_buckets.checked_mul(BUCKET_SIZE).ok_or_else(too_large)?;
+        if capacity as f64 / slots as f64 > MAX_LOAD_FACTOR {
+            num_buckets = num_buckets.checked_mul(2).ok_or_else(too_large)?;
+            num_buckets.checked_mul(BUCKET_SIZE).ok_or_else(too_large)?;
+        }
+
+        Ok(Self::with_buckets(num_buckets, 0))
+    }
+
+    fn with_buckets(num_buckets: usize, num_items: usize) -> Self {
+        CuckooFilter {
+            slots: vec![EMPTY; num_buckets * BUCKET_SIZE],
+            num_buckets,
+            num_items,
+            rng_state: 0x9e37_79b9_7f4a_7c15,
+        }
+    }
+
+    /// Insert an item into the filter
+    ///
+    /// Returns an error if no slot could be freed for the item after
+    /// relocating existing fingerprints; the filter is unchanged then.
+    pub fn insert<T: Hash + ?Sized>(&mut self, item: &T) -> Result<()> {
+        let (fingerprint, first) = self.fingerprint_and_index(item);
+        let second = self.alt_index(first, fingerprint);
+
+        if self.try_place(first, fingerprint) || self.try_place(second, fingerprint) {
+            self.num_items += 1;
+            return Ok(());
+        }
+
+        // Both buckets are full: evict fingerprints to their alternate
+        // buckets, remembering each swap so it can be undone on failure
+        let mut path = Vec::with_capacity(MAX_KICKS);
+        let mut carried = fingerprint;
+        let mut bucket = if self.next_random() & 1 == 0 {
+            first
+        } else {
+            second
+        };
+
+        for _ in 0..MAX_KICKS {
+            let slot = bucket * BUCKET_SIZE + (self.next_random() as usize % BUCKET_SIZE);
+            std::mem::swap(&mut carried, &mut self.slots[slot]);
+            path.push(slot);
+
+            bucket = self.alt_index(bucket, carried);
+            if self.try_place(bucket, carried) {
+                self.num_items += 1;
+                return Ok(());
+            }
+        }
+
+        for slot in path.into_iter().rev() {
+            std::mem::swap(&mut carried, &mut self.slots[slot]);
+        }
+        Err(Error::custom(format!(
+            "Cuckoo filter is full ({} of {} slots used)",
+            self.num_items,
+            self.capacity()
+        )))
+    }
+
+    /// Test if an item might be in the filter
+    pub fn contains<T: Hash + ?Sized>(&self, item: &T) -> bool {
+        let (fingerprint, first) = self.fingerprint_and_index(item);
+        let second = self.alt_index(first, fingerprint);
+        self.bucket(first).contains(&fingerprint) || self.bucket(second).contains(&fingerprint)
+    }
+
+    /// Remove one copy of an item from the filter
+    ///
+    /// Returns `true` if a matching fingerprint was found and removed.
+    pub fn remove<T: Hash + ?Sized>(&mut self, item: &T) -> bool {
+        let (fingerprint, first) = self.fingerprint_and_index(item);
+        let second = self.alt_index(first, fingerprint);
+
+        for bucket in [first, second] {
+            let start = bucket * BUCKET_SIZE;
+            if let Some(slot) = self.slots[start..start + BUCKET_SIZE]
+                .iter()
+                .position(|&fp| fp == fingerprint)
+            {
+                self.slots[start + slot] = EMPTY;
+                self.num_items -= 1;
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Get the number of items in the filter
+    pub fn len(&self) -> usize {
+        self.num_items
+    }
+
+    /// Check if the filter is empty
+    pub fn is_empty(&self) -> bool {
+        self.num_items == 0
+    }
+
+    /// Get the total number of fingerprint slots
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Get the fraction of slots in use
+    pub fn load_factor(&self) -> f64 {
+        self.num_items as f64 / self.capacity() as f64
+    }
+
+    /// Clear all items from the filter
+    pub fn clear(&mut self) {
+        self.slots.fill(EMPTY);
+        self.num_items = 0;
+    }
+
+    fn bucket(&self, index: usize) -> &[u16] {
+        let start = index * BUCKET_SIZE;
+        &self.slots[start..start + BUCKET_SIZE]
+    }
+
+    fn try_place(&mut self, bucket: usize, fingerprint: u16) -> bool {
+        let start = bucket * BUCKET_SIZE;
+        match self.slots[start..start + BUCKET_SIZE]
+            .iter_mut()
+            .find(|fp| **fp == EMPTY)
+        {
+            Some(slot) => {
+                *slot = fingerprint;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Derive the fingerprint and primary bucket of an item
+    #[allow(clippy::cast_possible_truncation)]
+    fn fingerprint_and_index<T: Hash + ?Sized>(&self, item: &T) -> (u16, usize) {
+        let mut hasher = StableHasher::new();
+        item.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        // Fingerprint from the high bits, bucket from the low bits; 0 is
+        // reserved for empty slots
+        let fingerprint = ((hash >> 48) as u16).max(1);
+        let index = (hash as usize) & (self.num_buckets - 1);
+        (fingerprint, index)
+    }
+
+    /// Alternate bucket of a fingerprint; applying it twice gives `index` back
+    #[allow(clippy::cast_possible_truncation)]
+    fn alt_index(&self, index: usize, fingerprint: u16) -> usize {
+        let hash = HashFunction::xxh3_64(&fingerprint.to_le_bytes(), 0);
+        (index ^ hash as usize) & (self.num_buckets - 1)
+    }
+
+    /// Xorshift step used to pick eviction victims
+    fn next_random(&mut self) -> u64 {
+        let mut x = self.rng_state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.rng_state = x;
+        x
+    }
+
+    /// Serialize the filter to bytes for persistence
+    ///
+    /// Layout (integers little-endian):
+    ///
+    /// ```text
+    /// magic "RTCF" | version u8 | hasher u8 | buckets u64 | items u64
+    /// fingerprints: buckets * 4 values of u16, 0 for empty slots
+    /// ```
+    ///
+    /// Like [`BloomFilter::to_bytes`](super::BloomFilter::to_bytes), items
+    /// are hashed with a seeded FNV-1a over their [`Hash`] output that is
+    /// identical across Rust releases and platforms, recorded in the header
+    /// as `1`. A saved filter stays valid as long as the item types hash the
+    /// same data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::algorithms::CuckooFilter;
+    ///
+    /// let mut filter = CuckooFilter::new(100).unwrap();
+    /// filter.insert("seen-id-42").unwrap();
+    ///
+    /// let restored = CuckooFilter::from_bytes(&filter.to_bytes()).unwrap();
+    /// assert!(restored.contains("seen-id-42"));
+    /// assert_eq!(restored.len(), 1);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(CUCKOO_HEADER_LEN + self.slots.len() * 2);
+        out.extend_from_slice(CUCKOO_MAGIC);
+        out.push(CUCKOO_FORMAT_VERSION);
+        out.push(CUCKOO_HASH_FNV1A);
+        out.extend_from_slice(&(self.num_buckets as u64).to_le_bytes());
+        out.extend_from_slice(&(self.num_items as u64).to_le_bytes());
+        for fingerprint in &self.slots {
+            out.extend_from_slice(&fingerprint.to_le_bytes());
+        }
+        out
+    }
+
+    /// Restore a filter serialized with [`CuckooFilter::to_bytes`]
+    ///
+    /// Fails if the magic bytes, version or hasher are not recognized, or if
+    /// the header does not match the buffer length or the stored fingerprints.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < CUCKOO_HEADER_LEN {
+            return Err(Error::custom(
+                "Invalid cuckoo filter: unexpected end of data",
+            ));
+        }
+        if &bytes[..4] != CUCKOO_MAGIC {
+            return Err(Error::custom(
+                "Not a serialized cuckoo filter: bad magic bytes",
+            ));
+        }
+        let version = bytes[4];
+        if version != CUCKOO_FORMAT_VERSION {
+            return Err(Error::custom(format!(
+                "Unsupported cuckoo filter format version: {version}"
+            )));
+        }
+        let hasher = bytes[5];
+        if hasher != CUCKOO_HASH_FNV1A {
+            return Err(Error::custom(format!(
+                "Unsupported cuckoo filter hash function: {hasher}"
+            )));
+        }
+
+        let read_usize = |offset: usize| {
+            let mut buf = [0u8; 8];
+            buf.copy_from_slice(&bytes[offset..offset + 8]);
+            usize::try_from(u64::from_le_bytes(buf))
+                .map_err(|_| Error::custom("Invalid cuckoo filter: size exceeds platform limits"))
+        };
+        let num_buckets = read_usize(6)?;
+        let num_items = read_usize(14)?;
+        if !num_buckets.is_power_of_two() {
+            return Err(Error::custom(
+                "Invalid cuckoo filter: bucket count must be a power of two",
+            ));
+        }
+
+        let expected_len = num_buckets
+            .checked_mul(BUCKET_SIZE * 2)
+            .and_then(|n| n.checked_add(CUCKOO_HEADER_LEN));
+        if expected_len != Some(bytes.len()) {
+            return Err(Error::custom(format!(
+                "Invalid cuckoo filter: length {} does not match header",
+                bytes.len()
+            )));
+        }
+
+        let mut filter = Self::with_buckets(num_buckets, num_items);
+        for (slot, chunk) in filter
+            .slots
+            .iter_mut()
+            .zip(bytes[CUCKOO_HEADER_LEN..].chunks_exact(2))
+        {
+            *slot = u16::from_le_bytes([chunk[0], chunk[1]]);
+        }
+
+        let occupied = filter.slots.iter().filter(|&&fp| fp != EMPTY).count();
+        if occupied != num_items {
+            return Err(Error::custom(format!(
+                "Invalid cuckoo filter: header records {num_items} items but {occupied} slots are used"
+            )));
+        }
+
+        Ok(filter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cuckoo_filter_basic() {
+        let mut filter = CuckooFilter::new(1000).unwrap();
+        assert!(filter.is_empty());
+        // 1000 items would load 1024 slots past 95%, so the table doubles
+        assert_eq!(filter.capacity(), 2048);
+
+        for i in 0..1000 {
+            filter.insert(&i).unwrap();
+        }
+        assert_eq!(filter.len(), 1000);
+        assert!((0..1000).all(|i| filter.contains(&i)));
+
+        let false_positives = (1000..101_000).filter(|i| filter.contains(i)).count();
+        assert!(
+            false_positives < 50,
+            "{} false positives in 100000 lookups",
+            false_positives
+        );
+    }
+
+    #[test]
+    fn test_cuckoo_filter_remove() {
+        let mut filter = CuckooFilter::new(500).unwrap();
+        for i in 0..500 {
+            filter.insert(&i).unwrap();
+        }
+
+        for i in (0..500).step_by(2) {
+            assert!(filter.remove(&i));
+        }
+        assert_eq!(filter.len(), 250);
+        assert!((1..500).step_by(2).all(|i| filter.contains(&i)));
+        assert!((0..500).step_by(2).filter(|i| filter.contains(i)).count() < 5);
+
+        // Duplicates are stored and removed one copy at a time
+        filter.insert("dup").unwrap();
+        filter.insert("dup").unwrap();
+        assert!(filter.remove("dup"));
+        assert!(filter.contains("dup"));
+        assert!(filter.remove("dup"));
+        assert!(!filter.contains("dup"));
+        assert!(!filter.remove("dup"));
+
+        filter.clear();
+        assert!(filter.is_empty());
+        assert!(!filter.contains(&1));
+    }
+
+    #[test]
+    fn test_cuckoo_filter_full_insert_fails() {
+        let mut filter = CuckooFilter::new(8).unwrap();
+        assert_eq!(filter.capacity(), 16);
+
+        let mut inserted = Vec::new();
+        let mut failed = None;
+        for i in 0..100 {
+            match filter.insert(&i) {
+                Ok(()) => inserted.push(i),
+                Err(err) => {
+                    failed = Some((i, err));
+                    break;
+                }
+            }
+        }
+
+        let (item, err) = failed.expect("a 16-slot filter cannot hold 100 items");
+        assert!(err.to_string().contains("Cuckoo filter is full"));
+        assert!(inserted.len() <= filter.capacity());
+        assert_eq!(filter.len(), inserted.len());
+
+        // The failed insert must not displace anything already stored
+        assert!(inserted.iter().all(|i| filter.contains(i)));
+        let before = filter.to_bytes();
+        assert!(filter.insert(&item).is_err());
+        assert_eq!(filter.to_bytes(), before);
+    }
+
+    #[test]
+    fn test_cuckoo_filter_serialization() {
+        let mut filter = CuckooFilter::new(200).unwrap();
+        for i in 0..150 {
+            filter.insert(&format!("key-{}", i)).unwrap();
+        }
+
+        let bytes = filter.to_bytes();
+        let mut restored = CuckooFilter::from_bytes(&bytes).unwrap();
+        assert_eq!(restored.len(), 150);
+        assert_eq!(restored.capacity(), filter.capacity());
+        assert!((0..150).all(|i| restored.contains(&format!("key-{}", i))));
+        assert!(restored.remove("key-0"));
+        assert_eq!(restored.len(), 149);
+
+        assert!(CuckooFilter::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(CuckooFilter::from_bytes(b"RTBF").is_err());
+
+        let mut bad_count = bytes.clone();
+        bad_count[14] ^= 1;
+        assert!(CuckooFilter::from_bytes(&bad_count).is_err());
+
+        let mut bad_hasher = bytes.clone();
+        bad_hasher[5] = 7;
+        assert!(CuckooFilter::from_bytes(&bad_hasher).is_err());
+    }
+
+    #[test]
+    fn test_cuckoo_filter_fingerprints_are_stable() {
+        let filter = CuckooFilter::new(100).unwrap();
+        // Pinned so that filters saved by earlier builds keep loading correctly
+        assert_eq!(filter.fingerprint_and_index("hello"), (8602, 29));
+    }
+
+    #[test]
+    fn test_cuckoo_filter_invalid_capacity() {
+        assert!(CuckooFilter::new(0).is_err());
+        assert!(CuckooFilter::new(usize::MAX).is_err());
+    }
+}
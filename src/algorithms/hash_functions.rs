@@ -3,9 +3,11 @@
 //! This module provides various hash function implementations optimized
 //! for use in bloom filters and other probabilistic data structures.
 
-use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher as StdHasher};
 
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
 /// Trait for hash functions used in bloom filters
 pub trait Hasher {
     /// Compute hash value for the given item
@@ -15,7 +17,7 @@ pub trait Hasher {
 /// Different hash function algorithms available
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HashAlgorithm {
-    /// Default Rust hasher (SipHash)
+    /// Seeded FNV-1a over the item's `Hash` output
     Default,
     /// Murmur3 hash (32-bit)
     Murmur3,
@@ -25,8 +27,65 @@ pub enum HashAlgorithm {
     Seeded(u64),
 }
 
-/// A hash function with configurable algorithm and seed
+/// Streaming FNV-1a 64 hasher finished with the MurmurHash3 `fmix64` step
+///
+/// Unlike the standard library's `DefaultHasher`, the output does not change
+/// between Rust releases or platforms: integers are fed little-endian and
+/// `usize` as 64 bits. Hashes derived from it can be persisted as long as the
+/// item's `Hash` implementation feeds the same data.
 #[derive(Debug, Clone)]
+pub(crate) struct StableHasher {
+    state: u64,
+}
+
+impl StableHasher {
+    pub(crate) fn new() -> Self {
+        StableHasher {
+            state: FNV_OFFSET_BASIS,
+        }
+    }
+}
+
+impl StdHasher for StableHasher {
+    fn finish(&self) -> u64 {
+        let mut h = self.state;
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xff51afd7ed558ccd);
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xc4ceb9fe1a85ec53);
+        h ^= h >> 33;
+        h
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.state = (self.state ^ u64::from(byte)).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+}
+
+/// A hash function with configurable algorithm and seed
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct HashFunction {
     algorithm: HashAlgorithm,
     seed: u64,
@@ -133,6 +192,41 @@ impl HashFunction {
     pub fn seed(&self) -> u64 {
         self.seed
     }
+
+    /// Compute the 64-bit XXH3 hash of raw bytes with a seed
+    ///
+    /// Matches the reference `XXH3_64bits_withSeed`, so values are stable
+    /// across platforms and releases and can be stored or shared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::algorithms::HashFunction;
+    ///
+    /// assert_eq!(HashFunction::xxh3_64(b"", 0), 0x2d06_8005_38d3_94c2);
+    /// assert_ne!(HashFunction::xxh3_64(b"hello", 1), HashFunction::xxh3_64(b"hello", 2));
+    /// ```
+    pub fn xxh3_64(data: &[u8], seed: u64) -> u64 {
+        twox_hash::XxHash3_64::oneshot_with_seed(seed, data)
+    }
+
+    /// Compute the 64-bit FNV-1a hash of raw bytes
+    ///
+    /// Uses the standard offset basis and prime, so values match other
+    /// FNV-1a 64 implementations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::algorithms::HashFunction;
+    ///
+    /// assert_eq!(HashFunction::fnv1a_64(b"a"), 0xaf63_dc4c_8601_ec8c);
+    /// ```
+    pub fn fnv1a_64(data: &[u8]) -> u64 {
+        data.iter().fold(FNV_OFFSET_BASIS, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+        })
+    }
 }
 
 impl Default for HashFunction {
@@ -145,7 +239,7 @@ impl Hasher for HashFunction {
     fn hash<T: Hash + ?Sized>(&self, item: &T) -> usize {
         match self.algorithm {
             HashAlgorithm::Default => {
-                let mut hasher = DefaultHasher::new();
+                let mut hasher = StableHasher::new();
                 self.seed.hash(&mut hasher);
                 item.hash(&mut hasher);
                 hasher.finish() as usize
@@ -153,7 +247,7 @@ impl Hasher for HashFunction {
             HashAlgorithm::Murmur3 => murmur3_hash(item, self.seed as u32),
             HashAlgorithm::Fnv1a => fnv1a_hash(item, self.seed),
             HashAlgorithm::Seeded(base_seed) => {
-                let mut hasher = DefaultHasher::new();
+                let mut hasher = StableHasher::new();
                 (base_seed ^ self.seed).hash(&mut hasher);
                 item.hash(&mut hasher);
                 hasher.finish() as usize
@@ -167,7 +261,7 @@ impl Hasher for HashFunction {
 /// This is a simplified version of MurmurHash3_x86_32 optimized for speed.
 fn murmur3_hash<T: Hash + ?Sized>(item: &T, seed: u32) -> usize {
     // Convert the item to bytes using a hasher
-    let mut hasher = DefaultHasher::new();
+    let mut hasher = StableHasher::new();
     seed.hash(&mut hasher);
     item.hash(&mut hasher);
     let hash64 = hasher.finish();
@@ -187,14 +281,11 @@ fn murmur3_hash<T: Hash + ?Sized>(item: &T, seed: u32) -> usize {
 ///
 /// Fast hash function with good distribution properties.
 fn fnv1a_hash<T: Hash + ?Sized>(item: &T, seed: u64) -> usize {
-    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
-    const FNV_PRIME: u64 = 0x100000001b3;
-
     // Start with offset basis XORed with seed
     let mut hash = FNV_OFFSET_BASIS ^ seed;
 
     // Hash the item to get bytes
-    let mut hasher = DefaultHasher::new();
+    let mut hasher = StableHasher::new();
     item.hash(&mut hasher);
     let item_hash = hasher.finish();
 
@@ -603,4 +694,32 @@ mod tests {
 
         assert_ne!(hash1, hash2); // Different seeds should produce different hashes
     }
+
+    #[test]
+    fn test_xxh3_64_known_answers() {
+        // Reference values from XXH3_64bits; input i is the bytes 0..len
+        assert_eq!(HashFunction::xxh3_64(b"", 0), 0x2d06_8005_38d3_94c2);
+        assert_eq!(HashFunction::xxh3_64(&[0], 0), 0xc44b_dff4_074e_ecdb);
+        assert_eq!(HashFunction::xxh3_64(&[0, 1], 0), 0xd664_5fc3_051a_9457);
+        assert_eq!(HashFunction::xxh3_64(&[0, 1, 2], 0), 0x5f42_99fc_161c_9cbb);
+
+        // Seeds change the output and are deterministic
+        let data = b"The quick brown fox jumps over the lazy dog";
+        assert_eq!(
+            HashFunction::xxh3_64(data, 42),
+            HashFunction::xxh3_64(data, 42)
+        );
+        assert_ne!(
+            HashFunction::xxh3_64(data, 42),
+            HashFunction::xxh3_64(data, 0)
+        );
+    }
+
+    #[test]
+    fn test_fnv1a_64_known_answers() {
+        // Reference values from the FNV test suite
+        assert_eq!(HashFunction::fnv1a_64(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(HashFunction::fnv1a_64(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(HashFunction::fnv1a_64(b"foobar"), 0x8594_4171_f739_67e8);
+    }
 }
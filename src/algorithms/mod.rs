@@ -1,8 +1,9 @@
 //! Algorithms module for yimi-rutool
 //!
 //! This module provides various algorithms and data structures including:
-//! - Bloom filters (standard and counting)
-//! - Bitmap utilities
+//! - Bloom filters (standard, counting and scalable)
+//! - Cuckoo filters
+//! - Bitmap utilities (dense and compressed)
 //! - Hash functions
 //! - Parameter optimization utilities
 //!
@@ -10,7 +11,10 @@
 //!
 //! - **Bloom Filters**: Memory-efficient probabilistic data structures for set membership testing
 //! - **Counting Bloom Filters**: Enhanced bloom filters supporting element removal
+//! - **Scalable Bloom Filters**: Bloom filters that grow while keeping their false positive rate bounded
+//! - **Cuckoo Filters**: Compact fingerprint-based filters with reliable deletion
 //! - **Bitmap**: Efficient bit manipulation utilities
+//! - **Compressed Bitmap**: Roaring-style bitmaps for sparse bits over huge index ranges
 //! - **Hash Functions**: Multiple hash algorithms for optimal distribution
 //!
 //! # Quick Start
@@ -38,11 +42,17 @@
 
 pub mod bitmap;
 pub mod bloom_filter;
+pub mod compressed_bitmap;
+pub mod cuckoo_filter;
 pub mod hash_functions;
 
 // Re-export main types for convenience
 pub use bitmap::BitMap;
-pub use bloom_filter::{BloomFilter, BloomFilterBuilder, CountingBloomFilter};
+pub use bloom_filter::{
+    BloomFilter, BloomFilterBuilder, CounterWidth, CountingBloomFilter, ScalableBloomFilter,
+};
+pub use compressed_bitmap::CompressedBitMap;
+pub use cuckoo_filter::CuckooFilter;
 pub use hash_functions::{HashFunction, Hasher};
 
 #[cfg(test)]
@@ -0,0 +1,82 @@
+//! Eviction notifications shared by the cache implementations
+
+use crate::error::{Error, Result};
+use std::sync::{Arc, RwLock};
+
+/// Why an entry left a cache
+///
+/// Passed to the listener registered with `on_evict`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum EvictReason {
+    /// Removed to make room for a new entry when the cache was full
+    Capacity,
+    /// Its time-to-live elapsed
+    Expired,
+    /// Removed explicitly through `remove` or `clear`
+    Manual,
+    /// Its value was overwritten by a `put` for the same key
+    Replaced,
+}
+
+type EvictListener<K, V> = Arc<dyn Fn(K, V, EvictReason) + Send + Sync>;
+
+/// Eviction listener shared between clones of a thread-safe cache
+///
+/// Caches collect evicted entries while holding their own lock and hand
+/// them to [`notify`](Self::notify) after releasing it, so the listener
+/// may freely call back into the cache.
+pub(crate) struct ListenerSlot<K, V> {
+    listener: Arc<RwLock<Option<EvictListener<K, V>>>>,
+}
+
+impl<K, V> ListenerSlot<K, V> {
+    pub(crate) fn new() -> Self {
+        Self {
+            listener: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    pub(crate) fn set<F>(&self, listener: F) -> Result<()>
+    where
+        F: Fn(K, V, EvictReason) + Send + Sync + 'static,
+    {
+        let mut slot = self
+            .listener
+            .write()
+            .map_err(|_| Error::concurrency("Failed to acquire write lock".to_string()))?;
+        *slot = Some(Arc::new(listener));
+        Ok(())
+    }
+
+    pub(crate) fn is_set(&self) -> bool {
+        self.listener.read().is_ok_and(|slot| slot.is_some())
+    }
+
+    pub(crate) fn notify(&self, evicted: Vec<(K, V, EvictReason)>) -> Result<()> {
+        if evicted.is_empty() {
+            return Ok(());
+        }
+
+        let listener = self
+            .listener
+            .read()
+            .map_err(|_| Error::concurrency("Failed to acquire read lock".to_string()))?
+            .clone();
+
+        if let Some(listener) = listener {
+            for (key, value, reason) in evicted {
+                listener(key, value, reason);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<K, V> Clone for ListenerSlot<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            listener: Arc::clone(&self.listener),
+        }
+    }
+}
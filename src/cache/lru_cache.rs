@@ -1,12 +1,26 @@
 //! LRU (Least Recently Used) cache implementation
 //!
-//! This module provides a thread-safe LRU cache that automatically evicts
-//! the least recently used items when the cache reaches its capacity limit.
-
+//! This module provides LRU caches that automatically evict the least
+//! recently used items when the cache reaches its capacity limit.
+//!
+//! Two front-ends share the same linked-list core:
+//!
+//! - [`LruCache`] keeps the core behind an `Arc<Mutex<_>>`. Clones share
+//!   the same entries and the cache can be used from several threads, at
+//!   the cost of taking a lock on every operation. Operations return
+//!   [`Result`] because the lock can be poisoned.
+//! - [`LocalLruCache`] keeps the core in a `RefCell`. There is no locking
+//!   or reference counting, so it is the cheaper choice on hot
+//!   single-threaded paths, but it cannot be shared between threads
+//!   (`!Sync`) and operations return values directly.
+
+use super::eviction::{EvictReason, ListenerSlot};
 use crate::error::{Error, Result};
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::ptr::NonNull;
-use std::sync::{Arc, Mutex};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, MutexGuard};
 
 /// A node in the doubly-linked list
 struct Node<K, V> {
@@ -31,6 +45,7 @@ impl<K, V> Node<K, V> {
 ///
 /// The LRU cache maintains a fixed capacity and automatically evicts the least
 /// recently used items when new items are inserted and the cache is at capacity.
+/// See [`LocalLruCache`] for a lock-free single-threaded variant.
 ///
 /// # Examples
 ///
@@ -52,10 +67,44 @@ where
     K: Clone + Eq + std::hash::Hash,
     V: Clone,
 {
-    inner: Arc<Mutex<LruCacheInner<K, V>>>,
+    inner: Arc<Mutex<LruCore<K, V>>>,
+    listener: ListenerSlot<K, V>,
 }
 
-struct LruCacheInner<K, V>
+/// Single-threaded LRU cache without locking
+///
+/// Behaves like [`LruCache`] but stores its entries in a `RefCell`, so
+/// operations avoid the mutex and return their results directly. The
+/// cache is `!Sync`; wrap it in an `Rc` to share it within one thread.
+///
+/// # Examples
+///
+/// ```rust
+/// use yimi_rutool::cache::LocalLruCache;
+///
+/// let cache = LocalLruCache::new(2);
+///
+/// cache.put("key1", 1);
+/// cache.put("key2", 2);
+/// cache.get(&"key1");
+/// cache.put("key3", 3); // Evicts "key2", the least recently used
+///
+/// assert_eq!(cache.get(&"key2"), None);
+/// assert_eq!(cache.keys(), vec!["key3", "key1"]);
+/// ```
+pub struct LocalLruCache<K, V>
+where
+    K: Clone + Eq + std::hash::Hash,
+    V: Clone,
+{
+    inner: RefCell<LruCore<K, V>>,
+    listener: RefCell<Option<LocalEvictListener<K, V>>>,
+}
+
+type LocalEvictListener<K, V> = Rc<dyn Fn(K, V, EvictReason)>;
+
+/// Linked-list LRU bookkeeping shared by both cache front-ends
+struct LruCore<K, V>
 where
     K: Clone + Eq + std::hash::Hash,
     V: Clone,
@@ -67,6 +116,16 @@ where
     len: usize,
 }
 
+// SAFETY: the nodes are heap allocations owned exclusively by the core and
+// only reachable through it, like a `Box`, so moving the core to another
+// thread moves its keys and values with it.
+unsafe impl<K, V> Send for LruCore<K, V>
+where
+    K: Clone + Eq + std::hash::Hash + Send,
+    V: Clone + Send,
+{
+}
+
 impl<K, V> LruCache<K, V>
 where
     K: Clone + Eq + std::hash::Hash,
@@ -82,19 +141,18 @@ where
     /// let cache: LruCache<String, i32> = LruCache::new(100);
     /// ```
     pub fn new(capacity: usize) -> Self {
-        assert!(capacity > 0, "Capacity must be greater than 0");
-
         Self {
-            inner: Arc::new(Mutex::new(LruCacheInner {
-                capacity,
-                map: HashMap::new(),
-                head: None,
-                tail: None,
-                len: 0,
-            })),
+            inner: Arc::new(Mutex::new(LruCore::new(capacity))),
+            listener: ListenerSlot::new(),
         }
     }
 
+    fn lock(&self) -> Result<MutexGuard<'_, LruCore<K, V>>> {
+        self.inner
+            .lock()
+            .map_err(|_| Error::concurrency("Failed to acquire lock".to_string()))
+    }
+
     /// Get a value from the cache
     ///
     /// This operation moves the accessed item to the front of the LRU list.
@@ -111,24 +169,7 @@ where
     /// assert_eq!(cache.get(&"nonexistent".to_string()).unwrap(), None);
     /// ```
     pub fn get(&self, key: &K) -> Result<Option<V>> {
-        let mut inner = self
-            .inner
-            .lock()
-            .map_err(|_| Error::concurrency("Failed to acquire lock".to_string()))?;
-
-        if let Some(&node_ptr) = inner.map.get(key) {
-            unsafe {
-                let node_ref = node_ptr.as_ref();
-                let value = node_ref.value.clone();
-
-                // Move to front
-                inner.move_to_front(node_ptr);
-
-                Ok(Some(value))
-            }
-        } else {
-            Ok(None)
-        }
+        Ok(self.lock()?.get(key))
     }
 
     /// Insert a key-value pair into the cache
@@ -146,39 +187,8 @@ where
     /// cache.put("key".to_string(), "value".to_string()).unwrap();
     /// ```
     pub fn put(&self, key: K, value: V) -> Result<()> {
-        let mut inner = self
-            .inner
-            .lock()
-            .map_err(|_| Error::concurrency("Failed to acquire lock".to_string()))?;
-
-        if let Some(&existing_node) = inner.map.get(&key) {
-            // Update existing node
-            unsafe {
-                let mut existing_node_mut = existing_node;
-                let existing_ref = existing_node_mut.as_mut();
-                existing_ref.value = value;
-                inner.move_to_front(existing_node_mut);
-            }
-        } else {
-            // Create new node
-            let new_node = Box::new(Node::new(key.clone(), value));
-            let new_node_ptr = NonNull::from(Box::leak(new_node));
-
-            inner.map.insert(key, new_node_ptr);
-            unsafe {
-                inner.add_to_front(new_node_ptr);
-            }
-            inner.len += 1;
-
-            // Check capacity and evict if necessary
-            if inner.len > inner.capacity {
-                unsafe {
-                    inner.remove_tail();
-                }
-            }
-        }
-
-        Ok(())
+        let evicted = self.lock()?.put(key, value);
+        self.listener.notify(evicted.into_iter().collect())
     }
 
     /// Remove a key-value pair from the cache
@@ -196,25 +206,16 @@ where
     /// assert_eq!(cache.get(&"key".to_string()).unwrap(), None);
     /// ```
     pub fn remove(&self, key: &K) -> Result<Option<V>> {
-        let mut inner = self
-            .inner
-            .lock()
-            .map_err(|_| Error::concurrency("Failed to acquire lock".to_string()))?;
-
-        if let Some(node_ptr) = inner.map.remove(key) {
-            unsafe {
-                let value = node_ptr.as_ref().value.clone();
-                inner.remove_node(node_ptr);
-                inner.len -= 1;
-
-                // Deallocate the node
-                let _ = Box::from_raw(node_ptr.as_ptr());
-
-                Ok(Some(value))
-            }
-        } else {
-            Ok(None)
+        let removed = self.lock()?.remove(key);
+
+        let Some((key, value)) = removed else {
+            return Ok(None);
+        };
+        if self.listener.is_set() {
+            self.listener
+                .notify(vec![(key, value.clone(), EvictReason::Manual)])?;
         }
+        Ok(Some(value))
     }
 
     /// Check if the cache contains a key
@@ -233,12 +234,7 @@ where
     /// assert!(!cache.contains_key(&"nonexistent".to_string()).unwrap());
     /// ```
     pub fn contains_key(&self, key: &K) -> Result<bool> {
-        let inner = self
-            .inner
-            .lock()
-            .map_err(|_| Error::concurrency("Failed to acquire lock".to_string()))?;
-
-        Ok(inner.map.contains_key(key))
+        Ok(self.lock()?.map.contains_key(key))
     }
 
     /// Get the current number of items in the cache
@@ -255,12 +251,7 @@ where
     /// assert_eq!(cache.len().unwrap(), 1);
     /// ```
     pub fn len(&self) -> Result<usize> {
-        let inner = self
-            .inner
-            .lock()
-            .map_err(|_| Error::concurrency("Failed to acquire lock".to_string()))?;
-
-        Ok(inner.len)
+        Ok(self.lock()?.len)
     }
 
     /// Check if the cache is empty
@@ -307,27 +298,13 @@ where
     /// assert!(cache.is_empty().unwrap());
     /// ```
     pub fn clear(&self) -> Result<()> {
-        let mut inner = self
-            .inner
-            .lock()
-            .map_err(|_| Error::concurrency("Failed to acquire lock".to_string()))?;
-
-        // Deallocate all nodes
-        unsafe {
-            let mut current = inner.head;
-            while let Some(node_ptr) = current {
-                let node_ref = node_ptr.as_ref();
-                current = node_ref.next;
-                let _ = Box::from_raw(node_ptr.as_ptr());
-            }
-        }
-
-        inner.map.clear();
-        inner.head = None;
-        inner.tail = None;
-        inner.len = 0;
-
-        Ok(())
+        let removed = self.lock()?.clear();
+        self.listener.notify(
+            removed
+                .into_iter()
+                .map(|(key, value)| (key, value, EvictReason::Manual))
+                .collect(),
+        )
     }
 
     /// Get all keys in the cache in LRU order (most recent first)
@@ -346,23 +323,7 @@ where
     /// assert_eq!(keys, vec!["key3".to_string(), "key2".to_string(), "key1".to_string()]);
     /// ```
     pub fn keys(&self) -> Result<Vec<K>> {
-        let inner = self
-            .inner
-            .lock()
-            .map_err(|_| Error::concurrency("Failed to acquire lock".to_string()))?;
-
-        let mut keys = Vec::new();
-        let mut current = inner.head;
-
-        unsafe {
-            while let Some(node_ptr) = current {
-                let node_ref = node_ptr.as_ref();
-                keys.push(node_ref.key.clone());
-                current = node_ref.next;
-            }
-        }
-
-        Ok(keys)
+        Ok(self.lock()?.keys())
     }
 
     /// Peek at the least recently used item without removing it
@@ -381,19 +342,8 @@ where
     /// assert_eq!(value, "value1");
     /// ```
     pub fn peek_lru(&self) -> Result<Option<(K, V)>> {
-        let inner = self
-            .inner
-            .lock()
-            .map_err(|_| Error::concurrency("Failed to acquire lock".to_string()))?;
-
-        if let Some(tail_ptr) = inner.tail {
-            unsafe {
-                let tail_ref = tail_ptr.as_ref();
-                Ok(Some((tail_ref.key.clone(), tail_ref.value.clone())))
-            }
-        } else {
-            Ok(None)
-        }
+        let inner = self.lock()?;
+        Ok(LruCore::peek(inner.tail))
     }
 
     /// Peek at the most recently used item without removing it
@@ -412,19 +362,44 @@ where
     /// assert_eq!(value, "value2");
     /// ```
     pub fn peek_mru(&self) -> Result<Option<(K, V)>> {
-        let inner = self
-            .inner
-            .lock()
-            .map_err(|_| Error::concurrency("Failed to acquire lock".to_string()))?;
+        let inner = self.lock()?;
+        Ok(LruCore::peek(inner.head))
+    }
 
-        if let Some(head_ptr) = inner.head {
-            unsafe {
-                let head_ref = head_ptr.as_ref();
-                Ok(Some((head_ref.key.clone(), head_ref.value.clone())))
-            }
-        } else {
-            Ok(None)
-        }
+    /// Register a listener called whenever an entry leaves the cache
+    ///
+    /// The listener receives the key, the value and an [`EvictReason`]:
+    /// `Capacity` for the least recently used entry pushed out by an
+    /// insert, `Manual` for [`remove`](Self::remove) and
+    /// [`clear`](Self::clear), and `Replaced` when a `put` overwrites an
+    /// existing key. It runs after the internal lock has been released, so
+    /// it may use the cache itself. Clones of the cache share the listener;
+    /// registering a new one replaces it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::cache::{EvictReason, LruCache};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let cache = LruCache::new(1);
+    /// let evicted = Arc::new(Mutex::new(Vec::new()));
+    ///
+    /// let log = Arc::clone(&evicted);
+    /// cache
+    ///     .on_evict(move |key, value, reason| log.lock().unwrap().push((key, value, reason)))
+    ///     .unwrap();
+    ///
+    /// cache.put("a", 1).unwrap();
+    /// cache.put("b", 2).unwrap();
+    ///
+    /// assert_eq!(*evicted.lock().unwrap(), vec![("a", 1, EvictReason::Capacity)]);
+    /// ```
+    pub fn on_evict<F>(&self, listener: F) -> Result<()>
+    where
+        F: Fn(K, V, EvictReason) + Send + Sync + 'static,
+    {
+        self.listener.set(listener)
     }
 
     /// Get or insert a value for the given key
@@ -459,11 +434,282 @@ where
     }
 }
 
-impl<K, V> LruCacheInner<K, V>
+impl<K, V> LocalLruCache<K, V>
 where
     K: Clone + Eq + std::hash::Hash,
     V: Clone,
 {
+    /// Create a new single-threaded LRU cache with the specified capacity
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::cache::LocalLruCache;
+    ///
+    /// let cache: LocalLruCache<String, i32> = LocalLruCache::new(100);
+    /// assert_eq!(cache.capacity(), 100);
+    /// ```
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: RefCell::new(LruCore::new(capacity)),
+            listener: RefCell::new(None),
+        }
+    }
+
+    /// Get a value from the cache, marking it as most recently used
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.inner.borrow_mut().get(key)
+    }
+
+    /// Insert a key-value pair, evicting the least recently used item if full
+    pub fn put(&self, key: K, value: V) {
+        let evicted = self.inner.borrow_mut().put(key, value);
+        if let Some((key, value, reason)) = evicted {
+            self.notify(key, value, reason);
+        }
+    }
+
+    /// Remove a key-value pair from the cache
+    pub fn remove(&self, key: &K) -> Option<V> {
+        let (key, value) = self.inner.borrow_mut().remove(key)?;
+        if self.listener.borrow().is_some() {
+            self.notify(key, value.clone(), EvictReason::Manual);
+        }
+        Some(value)
+    }
+
+    /// Check if the cache contains a key without affecting the LRU order
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.inner.borrow().map.contains_key(key)
+    }
+
+    /// Get the current number of items in the cache
+    pub fn len(&self) -> usize {
+        self.inner.borrow().len
+    }
+
+    /// Check if the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the capacity of the cache
+    pub fn capacity(&self) -> usize {
+        self.inner.borrow().capacity
+    }
+
+    /// Clear all items from the cache
+    pub fn clear(&self) {
+        let removed = self.inner.borrow_mut().clear();
+        for (key, value) in removed {
+            self.notify(key, value, EvictReason::Manual);
+        }
+    }
+
+    /// Get all keys in the cache in LRU order (most recent first)
+    pub fn keys(&self) -> Vec<K> {
+        self.inner.borrow().keys()
+    }
+
+    /// Peek at the least recently used item without removing it
+    pub fn peek_lru(&self) -> Option<(K, V)> {
+        LruCore::peek(self.inner.borrow().tail)
+    }
+
+    /// Peek at the most recently used item without removing it
+    pub fn peek_mru(&self) -> Option<(K, V)> {
+        LruCore::peek(self.inner.borrow().head)
+    }
+
+    /// Register a listener called whenever an entry leaves the cache
+    ///
+    /// Works like [`LruCache::on_evict`], except that the listener does not
+    /// need to be `Send` or `Sync`. It is called after the cache's internal
+    /// borrow has ended, so it may use the cache itself.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::cache::{EvictReason, LocalLruCache};
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    ///
+    /// let cache = LocalLruCache::new(2);
+    /// let evicted = Rc::new(RefCell::new(Vec::new()));
+    ///
+    /// let log = Rc::clone(&evicted);
+    /// cache.on_evict(move |key, _, reason| log.borrow_mut().push((key, reason)));
+    ///
+    /// cache.put("a", 1);
+    /// cache.put("a", 2);
+    /// cache.remove(&"a");
+    ///
+    /// assert_eq!(
+    ///     *evicted.borrow(),
+    ///     vec![("a", EvictReason::Replaced), ("a", EvictReason::Manual)]
+    /// );
+    /// ```
+    pub fn on_evict<F>(&self, listener: F)
+    where
+        F: Fn(K, V, EvictReason) + 'static,
+    {
+        *self.listener.borrow_mut() = Some(Rc::new(listener));
+    }
+
+    fn notify(&self, key: K, value: V, reason: EvictReason) {
+        let listener = self.listener.borrow().clone();
+        if let Some(listener) = listener {
+            listener(key, value, reason);
+        }
+    }
+
+    /// Get or insert a value for the given key
+    ///
+    /// The value is computed without holding a borrow of the cache, so
+    /// `compute_fn` may itself use the cache.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::cache::LocalLruCache;
+    ///
+    /// let cache = LocalLruCache::new(10);
+    ///
+    /// assert_eq!(cache.get_or_insert("key", || 1), 1);
+    /// assert_eq!(cache.get_or_insert("key", || 2), 1);
+    /// ```
+    pub fn get_or_insert<F>(&self, key: K, compute_fn: F) -> V
+    where
+        F: FnOnce() -> V,
+    {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+
+        let value = compute_fn();
+        self.put(key, value.clone());
+        value
+    }
+}
+
+impl<K, V> LruCore<K, V>
+where
+    K: Clone + Eq + std::hash::Hash,
+    V: Clone,
+{
+    fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "Capacity must be greater than 0");
+
+        Self {
+            capacity,
+            map: HashMap::new(),
+            head: None,
+            tail: None,
+            len: 0,
+        }
+    }
+
+    fn get(&mut self, key: &K) -> Option<V> {
+        let node_ptr = *self.map.get(key)?;
+        unsafe {
+            let value = node_ptr.as_ref().value.clone();
+
+            // Move to front
+            self.move_to_front(node_ptr);
+
+            Some(value)
+        }
+    }
+
+    /// Insert or update an entry, returning the entry it displaced
+    fn put(&mut self, key: K, value: V) -> Option<(K, V, EvictReason)> {
+        if let Some(&existing_node) = self.map.get(&key) {
+            // Update existing node
+            unsafe {
+                let mut existing_node_mut = existing_node;
+                let existing_ref = existing_node_mut.as_mut();
+                let old_value = std::mem::replace(&mut existing_ref.value, value);
+                self.move_to_front(existing_node_mut);
+                Some((key, old_value, EvictReason::Replaced))
+            }
+        } else {
+            // Create new node
+            let new_node = Box::new(Node::new(key.clone(), value));
+            let new_node_ptr = NonNull::from(Box::leak(new_node));
+
+            self.map.insert(key, new_node_ptr);
+            unsafe {
+                self.add_to_front(new_node_ptr);
+            }
+            self.len += 1;
+
+            // Check capacity and evict if necessary
+            if self.len > self.capacity {
+                let (key, value) = unsafe { self.remove_tail() }?;
+                return Some((key, value, EvictReason::Capacity));
+            }
+            None
+        }
+    }
+
+    fn remove(&mut self, key: &K) -> Option<(K, V)> {
+        let node_ptr = self.map.remove(key)?;
+        unsafe {
+            self.remove_node(node_ptr);
+            self.len -= 1;
+
+            // Deallocate the node
+            let node = Box::from_raw(node_ptr.as_ptr());
+            Some((node.key, node.value))
+        }
+    }
+
+    /// Remove every entry, returning them in LRU order (most recent first)
+    fn clear(&mut self) -> Vec<(K, V)> {
+        let mut removed = Vec::with_capacity(self.len);
+
+        // Deallocate all nodes
+        let mut current = self.head;
+        while let Some(node_ptr) = current {
+            let node = unsafe { Box::from_raw(node_ptr.as_ptr()) };
+            current = node.next;
+            removed.push((node.key, node.value));
+        }
+
+        self.map.clear();
+        self.head = None;
+        self.tail = None;
+        self.len = 0;
+
+        removed
+    }
+
+    fn keys(&self) -> Vec<K> {
+        let mut keys = Vec::with_capacity(self.len);
+        let mut current = self.head;
+
+        unsafe {
+            while let Some(node_ptr) = current {
+                let node_ref = node_ptr.as_ref();
+                keys.push(node_ref.key.clone());
+                current = node_ref.next;
+            }
+        }
+
+        keys
+    }
+
+    fn peek(node: Option<NonNull<Node<K, V>>>) -> Option<(K, V)> {
+        node.map(|node_ptr| unsafe {
+            let node_ref = node_ptr.as_ref();
+            (node_ref.key.clone(), node_ref.value.clone())
+        })
+    }
+
     unsafe fn move_to_front(&mut self, node_ptr: NonNull<Node<K, V>>) {
         unsafe {
             // Remove from current position
@@ -506,17 +752,24 @@ where
         }
     }
 
-    unsafe fn remove_tail(&mut self) {
-        if let Some(tail_ptr) = self.tail {
-            let tail_ref = unsafe { tail_ptr.as_ref() };
-            let key = tail_ref.key.clone();
+    unsafe fn remove_tail(&mut self) -> Option<(K, V)> {
+        let tail_ptr = self.tail?;
+        let tail_ref = unsafe { tail_ptr.as_ref() };
 
-            self.map.remove(&key);
-            unsafe { self.remove_node(tail_ptr) };
-            self.len -= 1;
+        self.map.remove(&tail_ref.key);
+        unsafe { self.remove_node(tail_ptr) };
+        self.len -= 1;
 
-            // Deallocate the node
-            let _ = unsafe { Box::from_raw(tail_ptr.as_ptr()) };
+        // Deallocate the node
+        let node = unsafe { Box::from_raw(tail_ptr.as_ptr()) };
+        Some((node.key, node.value))
+    }
+
+    unsafe fn free_nodes(&mut self) {
+        let mut current = self.head;
+        while let Some(node_ptr) = current {
+            current = unsafe { node_ptr.as_ref() }.next;
+            let _ = unsafe { Box::from_raw(node_ptr.as_ptr()) };
         }
     }
 }
@@ -529,11 +782,12 @@ where
     fn clone(&self) -> Self {
         Self {
             inner: Arc::clone(&self.inner),
+            listener: self.listener.clone(),
         }
     }
 }
 
-impl<K, V> Drop for LruCacheInner<K, V>
+impl<K, V> Drop for LruCore<K, V>
 where
     K: Clone + Eq + std::hash::Hash,
     V: Clone,
@@ -541,12 +795,7 @@ where
     fn drop(&mut self) {
         // Deallocate all nodes
         unsafe {
-            let mut current = self.head;
-            while let Some(node_ptr) = current {
-                let node_ref = node_ptr.as_ref();
-                current = node_ref.next;
-                let _ = Box::from_raw(node_ptr.as_ptr());
-            }
+            self.free_nodes();
         }
     }
 }
@@ -791,4 +1040,139 @@ mod tests {
     fn test_zero_capacity() {
         LruCache::<i32, i32>::new(0);
     }
+
+    #[test]
+    fn test_evict_reasons() {
+        let cache: LruCache<String, i32> = LruCache::new(2);
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&log);
+        cache
+            .on_evict(move |key, value, reason| sink.lock().unwrap().push((key, value, reason)))
+            .unwrap();
+
+        cache.put("a".to_string(), 1).unwrap();
+        cache.put("b".to_string(), 2).unwrap();
+        cache.put("a".to_string(), 3).unwrap();
+        cache.put("c".to_string(), 4).unwrap(); // Evicts "b"
+        assert_eq!(cache.remove(&"a".to_string()).unwrap(), Some(3));
+        cache.clear().unwrap();
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                ("a".to_string(), 1, EvictReason::Replaced),
+                ("b".to_string(), 2, EvictReason::Capacity),
+                ("a".to_string(), 3, EvictReason::Manual),
+                ("c".to_string(), 4, EvictReason::Manual),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_evict_listener_runs_outside_lock() {
+        let cache: LruCache<i32, i32> = LruCache::new(1);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let (reentrant, sink) = (cache.clone(), Arc::clone(&seen));
+        cache
+            .on_evict(move |key, _, _| {
+                // Would deadlock if called while the cache lock was held
+                let present = reentrant.contains_key(&key).unwrap();
+                sink.lock()
+                    .unwrap()
+                    .push((key, present, reentrant.keys().unwrap()));
+            })
+            .unwrap();
+
+        cache.put(1, 1).unwrap();
+        cache.put(2, 2).unwrap();
+        assert_eq!(*seen.lock().unwrap(), vec![(1, false, vec![2])]);
+    }
+
+    #[test]
+    fn test_local_evict_reasons() {
+        let cache: LocalLruCache<&str, i32> = LocalLruCache::new(2);
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&log);
+        cache.on_evict(move |key, value, reason| sink.borrow_mut().push((key, value, reason)));
+
+        cache.put("a", 1);
+        cache.put("b", 2);
+        cache.put("b", 3);
+        cache.put("c", 4); // Evicts "a"
+        cache.remove(&"c");
+        cache.clear();
+
+        assert_eq!(
+            *log.borrow(),
+            vec![
+                ("b", 2, EvictReason::Replaced),
+                ("a", 1, EvictReason::Capacity),
+                ("c", 4, EvictReason::Manual),
+                ("b", 3, EvictReason::Manual),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shared_across_threads() {
+        let cache: LruCache<u32, u32> = LruCache::new(100);
+
+        let handles: Vec<_> = (0..4)
+            .map(|t| {
+                let cache = cache.clone();
+                std::thread::spawn(move || {
+                    for i in 0..50 {
+                        cache.put(t * 50 + i, i).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(cache.len().unwrap(), 100);
+    }
+
+    #[test]
+    fn test_local_cache_matches_sync_cache() {
+        let local = LocalLruCache::new(3);
+        let shared = LruCache::new(3);
+
+        let ops: [(&str, i32); 6] = [("a", 1), ("b", 2), ("c", 3), ("a", 4), ("d", 5), ("e", 6)];
+        for (key, value) in ops {
+            local.put(key, value);
+            shared.put(key, value).unwrap();
+            local.get(&"a");
+            shared.get(&"a").unwrap();
+        }
+
+        assert_eq!(local.keys(), shared.keys().unwrap());
+        assert_eq!(local.keys(), vec!["a", "e", "d"]);
+        assert_eq!(local.peek_lru(), shared.peek_lru().unwrap());
+        assert_eq!(local.peek_mru(), Some(("a", 4)));
+    }
+
+    #[test]
+    fn test_local_cache_operations() {
+        let cache: LocalLruCache<String, usize> = LocalLruCache::new(2);
+        assert!(cache.is_empty());
+
+        cache.put("one".to_string(), 1);
+        cache.put("two".to_string(), 2);
+        assert!(cache.contains_key(&"one".to_string()));
+        assert_eq!(cache.len(), 2);
+
+        assert_eq!(cache.remove(&"one".to_string()), Some(1));
+        assert_eq!(cache.remove(&"one".to_string()), None);
+
+        // The compute closure may use the cache itself
+        let value = cache.get_or_insert("three".to_string(), || cache.len() + 2);
+        assert_eq!(value, 3);
+        assert_eq!(cache.get(&"three".to_string()), Some(3));
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.peek_lru(), None);
+    }
 }
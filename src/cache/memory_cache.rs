@@ -3,11 +3,18 @@
 //! This module provides a thread-safe in-memory cache with time-to-live (TTL)
 //! functionality, inspired by Hutool's CacheUtil.
 
+use super::eviction::{EvictReason, ListenerSlot};
 use crate::error::{Error, Result};
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use std::time::{Duration, Instant};
 
+#[cfg(test)]
+thread_local! {
+    /// Number of lock acquisitions made by the current thread, for tests
+    static LOCK_ACQUISITIONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
 /// Cache entry with value and expiration time
 #[derive(Debug, Clone)]
 struct CacheEntry<V> {
@@ -70,6 +77,7 @@ where
     data: Arc<RwLock<HashMap<K, CacheEntry<V>>>>,
     default_ttl: Option<Duration>,
     max_size: Option<usize>,
+    listener: ListenerSlot<K, V>,
 }
 
 impl<K, V> MemoryCache<K, V>
@@ -91,6 +99,7 @@ where
             data: Arc::new(RwLock::new(HashMap::new())),
             default_ttl: None,
             max_size: None,
+            listener: ListenerSlot::new(),
         }
     }
 
@@ -109,6 +118,7 @@ where
             data: Arc::new(RwLock::new(HashMap::new())),
             default_ttl: Some(default_ttl),
             max_size: None,
+            listener: ListenerSlot::new(),
         }
     }
 
@@ -126,6 +136,7 @@ where
             data: Arc::new(RwLock::new(HashMap::new())),
             default_ttl: None,
             max_size: Some(max_size),
+            listener: ListenerSlot::new(),
         }
     }
 
@@ -147,6 +158,7 @@ where
             data: Arc::new(RwLock::new(HashMap::new())),
             default_ttl: Some(default_ttl),
             max_size: Some(max_size),
+            listener: ListenerSlot::new(),
         }
     }
 
@@ -197,25 +209,99 @@ where
     }
 
     fn put_entry(&self, key: K, entry: CacheEntry<V>) -> Result<()> {
-        let mut data = self
-            .data
-            .write()
-            .map_err(|_| Error::concurrency("Failed to acquire write lock".to_string()))?;
+        let mut evicted = Vec::new();
+        {
+            let mut data = self.write_data()?;
+            self.insert_locked(&mut data, key, entry, &mut evicted);
+        }
+        self.listener.notify(evicted)
+    }
 
-        // Check size limit and evict if necessary
+    /// Store multiple values while holding the write lock once
+    ///
+    /// Each entry carries its own TTL; `None` falls back to the cache's
+    /// default TTL, as with [`put`](Self::put). Entries are inserted in
+    /// iteration order, so later duplicates overwrite earlier ones and the
+    /// size limit evicts the same way as repeated `put` calls would.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::cache::MemoryCache;
+    /// use std::time::Duration;
+    ///
+    /// let cache = MemoryCache::new();
+    /// cache.insert_many(vec![
+    ///     ("a", 1, None),
+    ///     ("b", 2, Some(Duration::from_secs(60))),
+    /// ]).unwrap();
+    ///
+    /// assert_eq!(cache.size().unwrap(), 2);
+    /// ```
+    pub fn insert_many<I>(&self, entries: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (K, V, Option<Duration>)>,
+    {
+        let mut evicted = Vec::new();
+        {
+            let mut data = self.write_data()?;
+            for (key, value, ttl) in entries {
+                let entry = CacheEntry::new(value, ttl.or(self.default_ttl));
+                self.insert_locked(&mut data, key, entry, &mut evicted);
+            }
+        }
+        self.listener.notify(evicted)
+    }
+
+    fn insert_locked(
+        &self,
+        data: &mut HashMap<K, CacheEntry<V>>,
+        key: K,
+        entry: CacheEntry<V>,
+        evicted: &mut Vec<(K, V, EvictReason)>,
+    ) {
+        // Check size limit and evict if necessary; overwriting an existing
+        // key does not grow the cache
         if let Some(max_size) = self.max_size {
-            while data.len() >= max_size {
+            while data.len() >= max_size && !data.contains_key(&key) {
                 // Remove oldest entry
-                if let Some(oldest_key) = self.find_oldest_key(&data) {
-                    data.remove(&oldest_key);
+                if let Some(oldest_key) = self.find_oldest_key(data) {
+                    if let Some((old_key, old_entry)) = data.remove_entry(&oldest_key) {
+                        evicted.push((old_key, old_entry.value, EvictReason::Capacity));
+                    }
                 } else {
                     break;
                 }
             }
         }
 
-        data.insert(key, entry);
-        Ok(())
+        let replaced_key = key.clone();
+        if let Some(old_entry) = data.insert(key, entry) {
+            let reason = if old_entry.is_expired() {
+                EvictReason::Expired
+            } else {
+                EvictReason::Replaced
+            };
+            evicted.push((replaced_key, old_entry.value, reason));
+        }
+    }
+
+    fn read_data(&self) -> Result<RwLockReadGuard<'_, HashMap<K, CacheEntry<V>>>> {
+        #[cfg(test)]
+        LOCK_ACQUISITIONS.with(|count| count.set(count.get() + 1));
+
+        self.data
+            .read()
+            .map_err(|_| Error::concurrency("Failed to acquire read lock".to_string()))
+    }
+
+    fn write_data(&self) -> Result<RwLockWriteGuard<'_, HashMap<K, CacheEntry<V>>>> {
+        #[cfg(test)]
+        LOCK_ACQUISITIONS.with(|count| count.set(count.get() + 1));
+
+        self.data
+            .write()
+            .map_err(|_| Error::concurrency("Failed to acquire write lock".to_string()))
     }
 
     fn find_oldest_key(&self, data: &HashMap<K, CacheEntry<V>>) -> Option<K> {
@@ -238,14 +324,16 @@ where
     /// assert_eq!(result, Some("value".to_string()));
     /// ```
     pub fn get(&self, key: &K) -> Result<Option<V>> {
-        let mut data = self
-            .data
-            .write()
-            .map_err(|_| Error::concurrency("Failed to acquire write lock".to_string()))?;
+        let mut data = self.write_data()?;
 
         if let Some(entry) = data.get_mut(key) {
             if entry.is_expired() {
-                data.remove(key);
+                let expired = data.remove_entry(key);
+                drop(data);
+                if let Some((key, entry)) = expired {
+                    self.listener
+                        .notify(vec![(key, entry.value, EvictReason::Expired)])?;
+                }
                 Ok(None)
             } else {
                 Ok(Some(entry.access().clone()))
@@ -255,6 +343,47 @@ where
         }
     }
 
+    /// Retrieve multiple values while holding the write lock once
+    ///
+    /// Missing and expired keys are omitted from the result; expired
+    /// entries are removed as with [`get`](Self::get).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::cache::MemoryCache;
+    ///
+    /// let cache = MemoryCache::new();
+    /// cache.put("a", 1).unwrap();
+    /// cache.put("b", 2).unwrap();
+    ///
+    /// let found = cache.get_many(&["a", "b", "c"]).unwrap();
+    /// assert_eq!(found.len(), 2);
+    /// assert_eq!(found[&"a"], 1);
+    /// assert!(!found.contains_key(&"c"));
+    /// ```
+    pub fn get_many(&self, keys: &[K]) -> Result<HashMap<K, V>> {
+        let mut found = HashMap::with_capacity(keys.len());
+        let mut evicted = Vec::new();
+        {
+            let mut data = self.write_data()?;
+            for key in keys {
+                if let Some(entry) = data.get_mut(key) {
+                    if entry.is_expired() {
+                        if let Some((key, entry)) = data.remove_entry(key) {
+                            evicted.push((key, entry.value, EvictReason::Expired));
+                        }
+                    } else {
+                        found.insert(key.clone(), entry.access().clone());
+                    }
+                }
+            }
+        }
+
+        self.listener.notify(evicted)?;
+        Ok(found)
+    }
+
     /// Check if a key exists in the cache (without updating access time)
     ///
     /// # Examples
@@ -269,10 +398,7 @@ where
     /// assert!(!cache.contains_key(&"nonexistent".to_string()).unwrap());
     /// ```
     pub fn contains_key(&self, key: &K) -> Result<bool> {
-        let data = self
-            .data
-            .read()
-            .map_err(|_| Error::concurrency("Failed to acquire read lock".to_string()))?;
+        let data = self.read_data()?;
 
         if let Some(entry) = data.get(key) {
             Ok(!entry.is_expired())
@@ -295,12 +421,16 @@ where
     /// assert_eq!(removed, Some("value".to_string()));
     /// ```
     pub fn remove(&self, key: &K) -> Result<Option<V>> {
-        let mut data = self
-            .data
-            .write()
-            .map_err(|_| Error::concurrency("Failed to acquire write lock".to_string()))?;
+        let removed = self.write_data()?.remove_entry(key);
 
-        Ok(data.remove(key).map(|entry| entry.value))
+        let Some((key, entry)) = removed else {
+            return Ok(None);
+        };
+        if self.listener.is_set() {
+            self.listener
+                .notify(vec![(key, entry.value.clone(), EvictReason::Manual)])?;
+        }
+        Ok(Some(entry.value))
     }
 
     /// Clear all entries from the cache
@@ -318,13 +448,13 @@ where
     /// assert_eq!(cache.size().unwrap(), 0);
     /// ```
     pub fn clear(&self) -> Result<()> {
-        let mut data = self
-            .data
-            .write()
-            .map_err(|_| Error::concurrency("Failed to acquire write lock".to_string()))?;
+        let removed: Vec<_> = self
+            .write_data()?
+            .drain()
+            .map(|(key, entry)| (key, entry.value, EvictReason::Manual))
+            .collect();
 
-        data.clear();
-        Ok(())
+        self.listener.notify(removed)
     }
 
     /// Get the number of entries in the cache
@@ -341,10 +471,7 @@ where
     /// assert_eq!(cache.size().unwrap(), 2);
     /// ```
     pub fn size(&self) -> Result<usize> {
-        let data = self
-            .data
-            .read()
-            .map_err(|_| Error::concurrency("Failed to acquire read lock".to_string()))?;
+        let data = self.read_data()?;
 
         Ok(data.len())
     }
@@ -382,22 +509,25 @@ where
     /// assert_eq!(removed, 1);
     /// ```
     pub fn cleanup_expired(&self) -> Result<usize> {
-        let mut data = self
-            .data
-            .write()
-            .map_err(|_| Error::concurrency("Failed to acquire write lock".to_string()))?;
-
-        let expired_keys: Vec<K> = data
-            .iter()
-            .filter(|(_, entry)| entry.is_expired())
-            .map(|(key, _)| key.clone())
-            .collect();
-
-        let count = expired_keys.len();
-        for key in expired_keys {
-            data.remove(&key);
+        let mut evicted = Vec::new();
+        {
+            let mut data = self.write_data()?;
+
+            let expired_keys: Vec<K> = data
+                .iter()
+                .filter(|(_, entry)| entry.is_expired())
+                .map(|(key, _)| key.clone())
+                .collect();
+
+            for key in expired_keys {
+                if let Some((key, entry)) = data.remove_entry(&key) {
+                    evicted.push((key, entry.value, EvictReason::Expired));
+                }
+            }
         }
 
+        let count = evicted.len();
+        self.listener.notify(evicted)?;
         Ok(count)
     }
 
@@ -416,10 +546,7 @@ where
     /// assert_eq!(keys.len(), 2);
     /// ```
     pub fn keys(&self) -> Result<Vec<K>> {
-        let data = self
-            .data
-            .read()
-            .map_err(|_| Error::concurrency("Failed to acquire read lock".to_string()))?;
+        let data = self.read_data()?;
 
         let keys: Vec<K> = data
             .iter()
@@ -445,10 +572,7 @@ where
     /// assert_eq!(stats.total_entries, 2);
     /// ```
     pub fn stats(&self) -> Result<CacheStats> {
-        let data = self
-            .data
-            .read()
-            .map_err(|_| Error::concurrency("Failed to acquire read lock".to_string()))?;
+        let data = self.read_data()?;
 
         let total_entries = data.len();
         let expired_entries = data.values().filter(|entry| entry.is_expired()).count();
@@ -475,6 +599,45 @@ where
         })
     }
 
+    /// Register a listener called whenever an entry leaves the cache
+    ///
+    /// The listener receives the key, the value and an [`EvictReason`]:
+    /// `Capacity` when the size limit pushes out the oldest entry,
+    /// `Expired` when an expired entry is purged, `Manual` for
+    /// [`remove`](Self::remove) and [`clear`](Self::clear), and `Replaced`
+    /// when a `put` overwrites a live value. It runs after the internal
+    /// lock has been released, so it may use the cache itself. Clones of
+    /// the cache share the listener; registering a new one replaces it.
+    ///
+    /// Expired entries are only noticed when they are accessed or purged
+    /// by [`cleanup_expired`](Self::cleanup_expired).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::cache::{EvictReason, MemoryCache};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let cache = MemoryCache::with_max_size(1);
+    /// let evicted = Arc::new(Mutex::new(Vec::new()));
+    ///
+    /// let log = Arc::clone(&evicted);
+    /// cache
+    ///     .on_evict(move |key, value, reason| log.lock().unwrap().push((key, value, reason)))
+    ///     .unwrap();
+    ///
+    /// cache.put("a", 1).unwrap();
+    /// cache.put("b", 2).unwrap();
+    ///
+    /// assert_eq!(*evicted.lock().unwrap(), vec![("a", 1, EvictReason::Capacity)]);
+    /// ```
+    pub fn on_evict<F>(&self, listener: F) -> Result<()>
+    where
+        F: Fn(K, V, EvictReason) + Send + Sync + 'static,
+    {
+        self.listener.set(listener)
+    }
+
     /// Get or compute a value for the given key
     ///
     /// # Examples
@@ -559,6 +722,7 @@ where
             data: Arc::clone(&self.data),
             default_ttl: self.default_ttl,
             max_size: self.max_size,
+            listener: self.listener.clone(),
         }
     }
 }
@@ -776,6 +940,167 @@ mod tests {
         assert!(cache.is_empty().unwrap());
     }
 
+    #[test]
+    fn test_batch_operations() {
+        let cache: MemoryCache<String, i32> = MemoryCache::with_ttl(Duration::from_secs(60));
+
+        cache
+            .insert_many(vec![
+                ("a".to_string(), 1, None),
+                ("b".to_string(), 2, Some(Duration::from_millis(10))),
+                ("c".to_string(), 3, None),
+                ("a".to_string(), 4, None),
+            ])
+            .unwrap();
+        assert_eq!(cache.size().unwrap(), 3);
+
+        thread::sleep(Duration::from_millis(15));
+
+        let keys = ["a", "b", "c", "missing"].map(String::from);
+        let found = cache.get_many(&keys).unwrap();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found["a"], 4);
+        assert_eq!(found["c"], 3);
+
+        // The expired entry was dropped during the lookup
+        assert_eq!(cache.size().unwrap(), 2);
+        assert_eq!(cache.stats().unwrap().total_access_count, 2);
+    }
+
+    #[test]
+    fn test_batch_respects_max_size() {
+        let cache: MemoryCache<i32, i32> = MemoryCache::with_max_size(3);
+        cache
+            .insert_many((0..10).map(|i| (i, i * 10, None)))
+            .unwrap();
+
+        assert_eq!(cache.size().unwrap(), 3);
+        assert_eq!(
+            cache.get_many(&(0..10).collect::<Vec<_>>()).unwrap().len(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_batch_lock_acquisitions() {
+        let cache: MemoryCache<usize, usize> = MemoryCache::new();
+        let keys: Vec<usize> = (0..10_000).collect();
+
+        let before = LOCK_ACQUISITIONS.with(std::cell::Cell::get);
+        cache
+            .insert_many(keys.iter().map(|&k| (k, k * 2, None)))
+            .unwrap();
+        let found = cache.get_many(&keys).unwrap();
+        let acquisitions = LOCK_ACQUISITIONS.with(std::cell::Cell::get) - before;
+
+        assert_eq!(found.len(), 10_000);
+        assert_eq!(found[&1234], 2468);
+        assert_eq!(acquisitions, 2);
+
+        // Per-item access takes the lock once per key
+        let before = LOCK_ACQUISITIONS.with(std::cell::Cell::get);
+        for key in &keys[..100] {
+            cache.get(key).unwrap();
+        }
+        assert_eq!(LOCK_ACQUISITIONS.with(std::cell::Cell::get) - before, 100);
+    }
+
+    type EvictLog<K, V> = Arc<std::sync::Mutex<Vec<(K, V, EvictReason)>>>;
+
+    fn record_evictions(cache: &MemoryCache<String, i32>) -> EvictLog<String, i32> {
+        let log: EvictLog<String, i32> = Arc::default();
+        let sink = Arc::clone(&log);
+        cache
+            .on_evict(move |key, value, reason| sink.lock().unwrap().push((key, value, reason)))
+            .unwrap();
+        log
+    }
+
+    #[test]
+    fn test_evict_on_capacity() {
+        let cache = MemoryCache::with_max_size(2);
+        let log = record_evictions(&cache);
+
+        cache.put("a".to_string(), 1).unwrap();
+        thread::sleep(Duration::from_millis(2));
+        cache.put("b".to_string(), 2).unwrap();
+        // Overwriting an existing key does not push anything out
+        cache.put("b".to_string(), 3).unwrap();
+        cache.put("c".to_string(), 4).unwrap();
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                ("b".to_string(), 2, EvictReason::Replaced),
+                ("a".to_string(), 1, EvictReason::Capacity),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_evict_on_expiry() {
+        let cache = MemoryCache::new();
+        let log = record_evictions(&cache);
+
+        for key in ["a", "b", "c"] {
+            cache
+                .put_with_ttl(key.to_string(), 1, Duration::from_millis(5))
+                .unwrap();
+        }
+        thread::sleep(Duration::from_millis(15));
+
+        assert_eq!(cache.get(&"a".to_string()).unwrap(), None);
+        assert!(cache.get_many(&["b".to_string()]).unwrap().is_empty());
+        assert_eq!(cache.cleanup_expired().unwrap(), 1);
+
+        let mut evicted = log.lock().unwrap().clone();
+        evicted.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            evicted,
+            vec![
+                ("a".to_string(), 1, EvictReason::Expired),
+                ("b".to_string(), 1, EvictReason::Expired),
+                ("c".to_string(), 1, EvictReason::Expired),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_evict_on_manual_removal() {
+        let cache = MemoryCache::new();
+        let log = record_evictions(&cache);
+
+        cache.put("a".to_string(), 1).unwrap();
+        cache.put("b".to_string(), 2).unwrap();
+        assert_eq!(cache.remove(&"a".to_string()).unwrap(), Some(1));
+        assert_eq!(cache.remove(&"a".to_string()).unwrap(), None);
+        cache.clear().unwrap();
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                ("a".to_string(), 1, EvictReason::Manual),
+                ("b".to_string(), 2, EvictReason::Manual),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_evict_listener_runs_outside_lock() {
+        let cache: MemoryCache<String, i32> = MemoryCache::with_max_size(1);
+        let reentrant = cache.clone();
+        cache
+            .on_evict(move |_, _, _| {
+                // Would deadlock if called while the cache lock was held
+                reentrant.size().unwrap();
+            })
+            .unwrap();
+
+        cache.put("a".to_string(), 1).unwrap();
+        cache.put("b".to_string(), 2).unwrap();
+        cache.remove(&"b".to_string()).unwrap();
+    }
+
     #[test]
     fn test_clone() {
         let cache1 = MemoryCache::new();
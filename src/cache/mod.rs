@@ -3,12 +3,15 @@
 //! This module provides comprehensive caching functionality including:
 //! - In-memory cache with TTL support
 //! - LRU (Least Recently Used) cache implementation
-//! - Thread-safe caching solutions
+//! - Thread-safe and single-threaded caching solutions
 //! - Cache statistics and management
+//! - Eviction listeners for cleaning up evicted values
 
+pub mod eviction;
 pub mod lru_cache;
 pub mod memory_cache;
 
-pub use lru_cache::LruCache;
+pub use eviction::EvictReason;
+pub use lru_cache::{LocalLruCache, LruCache};
 /// Re-export commonly used types for convenience
 pub use memory_cache::MemoryCache;
@@ -4,12 +4,39 @@
 //! including Base64, Base58, Base62, and Hex encoding.
 
 use base64::{Engine as _, engine::general_purpose};
+use std::borrow::Cow;
 use urlencoding;
 
+/// Line separator used when wrapping encoded output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineEnding {
+    /// `\n`, as used in PEM files
+    #[default]
+    Lf,
+    /// `\r\n`, as required by MIME
+    CrLf,
+}
+
+impl LineEnding {
+    /// The separator as a string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
 /// Base64 utility functions
 pub struct Base64Util;
 
 impl Base64Util {
+    /// Line width of Base64 data in PEM files (RFC 7468)
+    pub const PEM_LINE_WIDTH: usize = 64;
+
+    /// Maximum line width of Base64 data in MIME bodies (RFC 2045)
+    pub const MIME_LINE_WIDTH: usize = 76;
+
     /// Encode bytes to Base64 string
     ///
     /// # Examples
@@ -24,8 +51,45 @@ impl Base64Util {
         general_purpose::STANDARD.encode(data)
     }
 
+    /// Encode bytes to Base64, breaking the output into lines of `width`
+    /// characters
+    ///
+    /// Lines are separated by `line_ending`; no separator is added after the
+    /// last line. A `width` of 0 disables wrapping.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::{Base64Util, LineEnding};
+    ///
+    /// let data = [0u8; 60];
+    /// let pem_body = Base64Util::encode_wrapped(&data, Base64Util::PEM_LINE_WIDTH, LineEnding::Lf);
+    /// assert_eq!(pem_body.lines().map(str::len).collect::<Vec<_>>(), vec![64, 16]);
+    /// ```
+    pub fn encode_wrapped(data: &[u8], width: usize, line_ending: LineEnding) -> String {
+        let encoded = Self::encode(data);
+        if width == 0 || encoded.len() <= width {
+            return encoded;
+        }
+
+        let separator = line_ending.as_str();
+        let mut wrapped =
+            String::with_capacity(encoded.len() + encoded.len() / width * separator.len());
+        // Base64 output is ASCII, so byte chunks are valid UTF-8
+        for (i, line) in encoded.as_bytes().chunks(width).enumerate() {
+            if i > 0 {
+                wrapped.push_str(separator);
+            }
+            wrapped.push_str(std::str::from_utf8(line).unwrap_or_default());
+        }
+        wrapped
+    }
+
     /// Decode Base64 string to bytes
     ///
+    /// Whitespace, including the line breaks produced by
+    /// [`encode_wrapped`](Self::encode_wrapped), is ignored.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -33,9 +97,21 @@ impl Base64Util {
     ///
     /// let decoded = Base64Util::decode("SGVsbG8sIFdvcmxkIQ==").unwrap();
     /// assert_eq!(String::from_utf8(decoded).unwrap(), "Hello, World!");
+    ///
+    /// let decoded = Base64Util::decode("SGVsbG8s\r\nIFdvcmxk\r\nIQ==\n").unwrap();
+    /// assert_eq!(String::from_utf8(decoded).unwrap(), "Hello, World!");
     /// ```
     pub fn decode(data: &str) -> Result<Vec<u8>, base64::DecodeError> {
-        general_purpose::STANDARD.decode(data)
+        let data = if data.bytes().any(|b| b.is_ascii_whitespace()) {
+            Cow::Owned(
+                data.chars()
+                    .filter(|c| !c.is_ascii_whitespace())
+                    .collect::<String>(),
+            )
+        } else {
+            Cow::Borrowed(data)
+        };
+        general_purpose::STANDARD.decode(data.as_ref())
     }
 
     /// Encode string to Base64 string
@@ -354,6 +430,40 @@ mod tests {
         assert_eq!(decoded, original);
     }
 
+    #[test]
+    fn test_base64_wrapped_round_trip() {
+        let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+
+        let pem = Base64Util::encode_wrapped(&data, Base64Util::PEM_LINE_WIDTH, LineEnding::Lf);
+        let lines: Vec<&str> = pem.split('\n').collect();
+        assert!(lines[..lines.len() - 1].iter().all(|line| line.len() == 64));
+        assert!(!lines.last().unwrap().is_empty());
+        assert!(!pem.contains('\r'));
+        assert_eq!(Base64Util::decode(&pem).unwrap(), data);
+
+        let mime = Base64Util::encode_wrapped(&data, Base64Util::MIME_LINE_WIDTH, LineEnding::CrLf);
+        assert!(mime.split("\r\n").all(|line| line.len() <= 76));
+        assert_eq!(mime.split("\r\n").next().unwrap().len(), 76);
+        assert_eq!(Base64Util::decode(&mime).unwrap(), data);
+
+        // Output is unchanged when no wrapping is needed
+        assert_eq!(
+            Base64Util::encode_wrapped(b"hi", 64, LineEnding::Lf),
+            "aGk="
+        );
+        assert_eq!(
+            Base64Util::encode_wrapped(&data, 0, LineEnding::Lf),
+            Base64Util::encode(&data)
+        );
+        assert_eq!(Base64Util::encode_wrapped(&[], 64, LineEnding::Lf), "");
+    }
+
+    #[test]
+    fn test_base64_decode_ignores_whitespace() {
+        assert_eq!(Base64Util::decode(" aGVs\tbG8=\n").unwrap(), b"hello");
+        assert!(Base64Util::decode("aGVs*bG8=").is_err());
+    }
+
     #[test]
     fn test_base58_encode_decode() {
         let original = "Hello";
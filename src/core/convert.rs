@@ -3,6 +3,24 @@
 //! This module provides comprehensive type conversion utilities,
 //! supporting conversion between different data types.
 
+use crate::error::{Error, Result};
+#[cfg(feature = "json")]
+use crate::json::JsonUtil;
+#[cfg(feature = "json")]
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+#[cfg(feature = "json")]
+use serde::de::{self, Deserializer, IntoDeserializer, Visitor};
+#[cfg(feature = "json")]
+use serde_json::Value;
+#[cfg(feature = "json")]
+use std::collections::HashMap;
+
+/// Units used by [`Convert::human_bytes_with`] in SI mode
+const SI_UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB", "PB", "EB"];
+
+/// Units used by [`Convert::human_bytes_with`] in binary mode
+const BINARY_UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
 /// Type conversion utilities
 pub struct Convert;
 
@@ -399,6 +417,277 @@ impl Convert {
         format!("{:X}", value)
     }
 
+    /// Convert an integer to a string in the given base (2 to 36)
+    ///
+    /// Digits above 9 are written as lowercase letters and negative numbers
+    /// are prefixed with `-`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::Convert;
+    ///
+    /// assert_eq!(Convert::to_radix(255, 16).unwrap(), "ff");
+    /// assert_eq!(Convert::to_radix(-35, 36).unwrap(), "-z");
+    /// assert!(Convert::to_radix(10, 37).is_err());
+    /// ```
+    pub fn to_radix(n: i64, radix: u32) -> Result<String> {
+        Self::check_radix(radix)?;
+
+        let mut magnitude = n.unsigned_abs();
+        let mut digits = Vec::new();
+        loop {
+            #[allow(clippy::cast_possible_truncation)]
+            let digit = (magnitude % u64::from(radix)) as u32;
+            digits.push(std::char::from_digit(digit, radix).unwrap_or('?'));
+            magnitude /= u64::from(radix);
+            if magnitude == 0 {
+                break;
+            }
+        }
+        if n < 0 {
+            digits.push('-');
+        }
+        Ok(digits.iter().rev().collect())
+    }
+
+    /// Parse a string written in the given base (2 to 36) into an integer
+    ///
+    /// Letters are accepted in either case and an optional leading `-` or
+    /// `+` sign is allowed. Digits that are not valid for the base, such as
+    /// `2` in binary, are rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::Convert;
+    ///
+    /// assert_eq!(Convert::from_radix("ff", 16).unwrap(), 255);
+    /// assert_eq!(Convert::from_radix("-101", 2).unwrap(), -5);
+    /// assert!(Convert::from_radix("102", 2).is_err());
+    /// ```
+    pub fn from_radix(s: &str, radix: u32) -> Result<i64> {
+        Self::check_radix(radix)?;
+
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        if digits.is_empty() {
+            return Err(Error::conversion(format!(
+                "No digits in '{}' for base {}",
+                s, radix
+            )));
+        }
+
+        let mut magnitude: u64 = 0;
+        for c in digits.chars() {
+            let digit = c.to_digit(radix).ok_or_else(|| {
+                Error::conversion(format!(
+                    "Invalid digit '{}' for base {} in '{}'",
+                    c, radix, s
+                ))
+            })?;
+            magnitude = magnitude
+                .checked_mul(u64::from(radix))
+                .and_then(|m| m.checked_add(u64::from(digit)))
+                .ok_or_else(|| Error::conversion(format!("'{}' is out of range for i64", s)))?;
+        }
+
+        let value = if negative {
+            0i64.checked_sub_unsigned(magnitude)
+        } else {
+            i64::try_from(magnitude).ok()
+        };
+        value.ok_or_else(|| Error::conversion(format!("'{}' is out of range for i64", s)))
+    }
+
+    /// Convert an integer to a binary string, with `-` for negative numbers
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::Convert;
+    ///
+    /// assert_eq!(Convert::to_binary(10), "1010");
+    /// assert_eq!(Convert::to_binary(-10), "-1010");
+    /// ```
+    pub fn to_binary(n: i64) -> String {
+        Self::to_radix(n, 2).unwrap_or_default()
+    }
+
+    /// Convert an integer to an octal string, with `-` for negative numbers
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::Convert;
+    ///
+    /// assert_eq!(Convert::to_octal(64), "100");
+    /// ```
+    pub fn to_octal(n: i64) -> String {
+        Self::to_radix(n, 8).unwrap_or_default()
+    }
+
+    /// Convert an integer to a lowercase hexadecimal string, with `-` for
+    /// negative numbers
+    ///
+    /// Unlike [`to_hex_string`](Self::to_hex_string), negative numbers are
+    /// not shown in two's complement.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::Convert;
+    ///
+    /// assert_eq!(Convert::to_hex_int(-255), "-ff");
+    /// ```
+    pub fn to_hex_int(n: i64) -> String {
+        Self::to_radix(n, 16).unwrap_or_default()
+    }
+
+    /// Format a byte count for display using binary units (KiB, MiB, ...)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::Convert;
+    ///
+    /// assert_eq!(Convert::human_bytes(512), "512 B");
+    /// assert_eq!(Convert::human_bytes(1536), "1.5 KiB");
+    /// assert_eq!(Convert::human_bytes(1024 * 1024), "1 MiB");
+    /// ```
+    pub fn human_bytes(n: u64) -> String {
+        Self::human_bytes_with(n, false)
+    }
+
+    /// Format a byte count for display
+    ///
+    /// With `si` the units are powers of 1000 (KB, MB, ...), otherwise
+    /// powers of 1024 (KiB, MiB, ...). Values are shown with at most one
+    /// decimal place.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::Convert;
+    ///
+    /// assert_eq!(Convert::human_bytes_with(1500, true), "1.5 KB");
+    /// assert_eq!(Convert::human_bytes_with(1500, false), "1.5 KiB");
+    /// ```
+    pub fn human_bytes_with(n: u64, si: bool) -> String {
+        let (base, units) = if si {
+            (1000.0, SI_UNITS)
+        } else {
+            (1024.0, BINARY_UNITS)
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut value = n as f64;
+        let mut unit = 0;
+        while unit + 1 < units.len() && (value * 10.0).round() / 10.0 >= base {
+            value /= base;
+            unit += 1;
+        }
+
+        if unit == 0 {
+            format!("{} {}", n, units[0])
+        } else {
+            let text = format!("{:.1}", value);
+            let text = text.strip_suffix(".0").unwrap_or(&text);
+            format!("{} {}", text, units[unit])
+        }
+    }
+
+    /// Parse a human-readable byte size such as `"10MB"` or `"1.5 GiB"`
+    ///
+    /// Units are case-insensitive: `B`, SI units `KB`/`K` to `EB`/`E`
+    /// (powers of 1000) and binary units `KiB`/`Ki` to `EiB`/`Ei` (powers of
+    /// 1024). A number without a unit is a byte count. Fractional values are
+    /// rounded to the nearest byte.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::Convert;
+    ///
+    /// assert_eq!(Convert::parse_bytes("10MB").unwrap(), 10_000_000);
+    /// assert_eq!(Convert::parse_bytes("1.5 GiB").unwrap(), 1_610_612_736);
+    /// assert!(Convert::parse_bytes("3 parsecs").is_err());
+    /// ```
+    pub fn parse_bytes(s: &str) -> Result<u64> {
+        let trimmed = s.trim();
+        let split = trimmed
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(trimmed.len());
+        let (number, unit) = trimmed.split_at(split);
+        if number.is_empty() {
+            return Err(Error::conversion(format!("No number in byte size '{}'", s)));
+        }
+
+        let multiplier = Self::byte_unit_multiplier(unit.trim()).ok_or_else(|| {
+            Error::conversion(format!(
+                "Unknown byte unit '{}' in '{}', expected B, KB, KiB, MB, MiB, GB, GiB, TB, TiB, PB, PiB, EB or EiB",
+                unit.trim(),
+                s
+            ))
+        })?;
+        let out_of_range = || Error::conversion(format!("Byte size '{}' is too large", s));
+
+        if !number.contains('.') {
+            let count: u64 = number
+                .parse()
+                .map_err(|_| Error::conversion(format!("Invalid number in byte size '{}'", s)))?;
+            return count.checked_mul(multiplier).ok_or_else(out_of_range);
+        }
+
+        let count: f64 = number
+            .parse()
+            .map_err(|_| Error::conversion(format!("Invalid number in byte size '{}'", s)))?;
+        #[allow(clippy::cast_precision_loss)]
+        let bytes = (count * multiplier as f64).round();
+        #[allow(clippy::cast_precision_loss)]
+        if bytes >= u64::MAX as f64 {
+            return Err(out_of_range());
+        }
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(bytes as u64)
+    }
+
+    fn byte_unit_multiplier(unit: &str) -> Option<u64> {
+        let unit = unit.to_ascii_lowercase();
+        let (prefix, binary) = match unit.as_str() {
+            "" | "b" => return Some(1),
+            _ => match unit.strip_suffix("ib").or_else(|| unit.strip_suffix('i')) {
+                Some(prefix) => (prefix.to_string(), true),
+                None => (unit.strip_suffix('b').unwrap_or(&unit).to_string(), false),
+            },
+        };
+
+        let power = match prefix.as_str() {
+            "k" => 1,
+            "m" => 2,
+            "g" => 3,
+            "t" => 4,
+            "p" => 5,
+            "e" => 6,
+            _ => return None,
+        };
+        let base: u64 = if binary { 1024 } else { 1000 };
+        Some(base.pow(power))
+    }
+
+    fn check_radix(radix: u32) -> Result<()> {
+        if (2..=36).contains(&radix) {
+            Ok(())
+        } else {
+            Err(Error::validation(format!(
+                "Radix must be between 2 and 36, got {}",
+                radix
+            )))
+        }
+    }
+
     /// Convert string to integer with default value
     ///
     /// # Examples
@@ -470,10 +759,295 @@ impl Convert {
     }
 }
 
+#[cfg(feature = "json")]
+impl Convert {
+    /// Flatten a serializable value into a map of dotted keys to strings
+    ///
+    /// Nested structs and maps become `parent.child` keys and sequences
+    /// become indexed keys such as `tags.0`, as in
+    /// [`JsonUtil::to_flat_map`](crate::json::JsonUtil::to_flat_map). `None`
+    /// is written as `"null"`; empty sequences and maps produce no keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error` if `value` cannot be serialized to JSON, e.g. a map
+    /// with non-string keys.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serde::Serialize;
+    /// use yimi_rutool::core::Convert;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Server {
+    ///     host: String,
+    ///     ports: Vec<u16>,
+    /// }
+    ///
+    /// let server = Server { host: "localhost".to_string(), ports: vec![80, 443] };
+    /// let map = Convert::to_flat_map(&server).unwrap();
+    /// assert_eq!(map["host"], "localhost");
+    /// assert_eq!(map["ports.1"], "443");
+    /// ```
+    pub fn to_flat_map<T: serde::Serialize>(value: &T) -> Result<HashMap<String, String>> {
+        Ok(JsonUtil::to_flat_map(&serde_json::to_value(value)?))
+    }
+
+    /// Rebuild a value from a map of dotted keys, the inverse of
+    /// [`Convert::to_flat_map`]
+    ///
+    /// Leaves are parsed according to the target field type, so a `String`
+    /// field keeps values like `"007"` or `"true"` as written. Fields whose
+    /// keys are all absent, such as an empty `Vec`, need `#[serde(default)]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error` if keys conflict (e.g. both `a` and `a.b`), a leaf
+    /// cannot be parsed as its field type, or a required field is missing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use serde::Deserialize;
+    /// use std::collections::HashMap;
+    /// use yimi_rutool::core::Convert;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Login {
+    ///     user: String,
+    ///     remember: bool,
+    /// }
+    ///
+    /// let mut form = HashMap::new();
+    /// form.insert("user".to_string(), "007".to_string());
+    /// form.insert("remember".to_string(), "true".to_string());
+    ///
+    /// let login: Login = Convert::from_flat_map(&form).unwrap();
+    /// assert_eq!(login.user, "007");
+    /// assert!(login.remember);
+    /// ```
+    pub fn from_flat_map<T: serde::de::DeserializeOwned>(
+        map: &HashMap<String, String>,
+    ) -> Result<T> {
+        let value = JsonUtil::unflatten_with(map, false)?;
+        Ok(T::deserialize(FlatValue(value))?)
+    }
+}
+
+/// Deserializer over an unflattened value whose leaves are all strings
+///
+/// Leaves are parsed into whatever type the visitor asks for; untyped
+/// targets get the same type inference as [`JsonUtil::unflatten`].
+#[cfg(feature = "json")]
+struct FlatValue(serde_json::Value);
+
+#[cfg(feature = "json")]
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> std::result::Result<V::Value, Self::Error> {
+                match self.0 {
+                    Value::String(raw) => visitor.$visit(raw.parse().map_err(|e| {
+                        de::Error::custom(format!("invalid value '{raw}': {e}"))
+                    })?),
+                    other => other.$method(visitor),
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "json")]
+impl<'de> Deserializer<'de> for FlatValue {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::String(raw) => JsonUtil::unflatten_leaf(&raw, true).deserialize_any(visitor),
+            Value::Object(map) => visitor.visit_map(MapDeserializer::new(
+                map.into_iter().map(|(k, v)| (k, FlatValue(v))),
+            )),
+            Value::Array(items) => {
+                visitor.visit_seq(SeqDeserializer::new(items.into_iter().map(FlatValue)))
+            }
+            other => other.deserialize_any(visitor),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::String(raw) => visitor.visit_string(raw),
+            other => other.deserialize_string(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        match &self.0 {
+            Value::String(raw) if raw == "null" => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        match &self.0 {
+            Value::String(raw) if raw == "null" => visitor.visit_unit(),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::String(raw) => visitor.visit_enum(raw.into_deserializer()),
+            other => other.deserialize_enum(name, variants, visitor),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bytes byte_buf unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+#[cfg(feature = "json")]
+impl IntoDeserializer<'_, serde_json::Error> for FlatValue {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_flat_map_round_trip() {
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        enum Role {
+            Admin,
+            Guest,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Address {
+            city: String,
+            zip: String,
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct User {
+            name: String,
+            age: u8,
+            score: f64,
+            active: bool,
+            nickname: Option<String>,
+            role: Role,
+            address: Address,
+            previous: Vec<Address>,
+            tags: Vec<String>,
+            #[serde(default)]
+            empty: Vec<u32>,
+        }
+
+        let user = User {
+            name: "Alice".to_string(),
+            age: 30,
+            score: 98.5,
+            active: true,
+            nickname: None,
+            role: Role::Admin,
+            address: Address {
+                city: "Paris".to_string(),
+                zip: "00750".to_string(),
+            },
+            previous: vec![Address {
+                city: "Lyon".to_string(),
+                zip: "69001".to_string(),
+            }],
+            tags: vec!["true".to_string(), "42".to_string()],
+            empty: Vec::new(),
+        };
+
+        let map = Convert::to_flat_map(&user).unwrap();
+        assert_eq!(map["age"], "30");
+        assert_eq!(map["nickname"], "null");
+        assert_eq!(map["role"], "Admin");
+        assert_eq!(map["address.zip"], "00750");
+        assert_eq!(map["previous.0.city"], "Lyon");
+        assert_eq!(map["tags.1"], "42");
+        assert!(!map.keys().any(|key| key.starts_with("empty")));
+
+        let rebuilt: User = Convert::from_flat_map(&map).unwrap();
+        assert_eq!(rebuilt, user);
+
+        let mut bad = map.clone();
+        bad.insert("age".to_string(), "old".to_string());
+        assert!(Convert::from_flat_map::<User>(&bad).is_err());
+        bad.remove("age");
+        assert!(Convert::from_flat_map::<User>(&bad).is_err());
+
+        let mut conflict = map;
+        conflict.insert("address".to_string(), "x".to_string());
+        assert!(Convert::from_flat_map::<User>(&conflict).is_err());
+
+        let untyped: HashMap<String, serde_json::Value> =
+            Convert::from_flat_map(&Convert::to_flat_map(&user.address).unwrap()).unwrap();
+        assert_eq!(untyped["city"], serde_json::json!("Paris"));
+    }
+
     #[test]
     fn test_to_int() {
         assert_eq!(Convert::to_int("123"), Some(123));
@@ -518,6 +1092,87 @@ mod tests {
         assert_eq!(Convert::to_hex_string_upper(&42u8), "2A");
     }
 
+    #[test]
+    fn test_radix_round_trip() {
+        let values = [0, 1, -1, 42, -42, 255, 1_000_000, i64::MAX, i64::MIN];
+        for radix in [2, 3, 8, 10, 16, 36] {
+            for value in values {
+                let text = Convert::to_radix(value, radix).unwrap();
+                assert_eq!(
+                    Convert::from_radix(&text, radix).unwrap(),
+                    value,
+                    "{} in base {}",
+                    value,
+                    radix
+                );
+            }
+        }
+
+        assert_eq!(
+            Convert::to_radix(i64::MIN, 16).unwrap(),
+            "-8000000000000000"
+        );
+        assert_eq!(Convert::from_radix("FF", 16).unwrap(), 255);
+        assert_eq!(Convert::from_radix("+z", 36).unwrap(), 35);
+        assert_eq!(Convert::to_binary(5), "101");
+        assert_eq!(Convert::to_octal(-8), "-10");
+        assert_eq!(Convert::to_hex_int(3054), "bee");
+    }
+
+    #[test]
+    fn test_radix_errors() {
+        assert!(Convert::to_radix(1, 1).is_err());
+        assert!(Convert::to_radix(1, 37).is_err());
+        assert!(Convert::from_radix("1", 0).is_err());
+        assert!(Convert::from_radix("2", 2).is_err());
+        assert!(Convert::from_radix("19", 8).is_err());
+        assert!(Convert::from_radix("g", 16).is_err());
+        assert!(Convert::from_radix("", 10).is_err());
+        assert!(Convert::from_radix("-", 10).is_err());
+        assert!(Convert::from_radix("9223372036854775808", 10).is_err());
+        assert!(Convert::from_radix("-9223372036854775809", 10).is_err());
+    }
+
+    #[test]
+    fn test_human_bytes() {
+        assert_eq!(Convert::human_bytes(0), "0 B");
+        assert_eq!(Convert::human_bytes(1023), "1023 B");
+        assert_eq!(Convert::human_bytes(1024), "1 KiB");
+        assert_eq!(Convert::human_bytes(1536), "1.5 KiB");
+        assert_eq!(Convert::human_bytes(1024 * 1024 - 1), "1 MiB");
+        assert_eq!(Convert::human_bytes(5 * 1024 * 1024 * 1024), "5 GiB");
+        assert_eq!(Convert::human_bytes(u64::MAX), "16 EiB");
+        assert_eq!(Convert::human_bytes_with(999, true), "999 B");
+        assert_eq!(Convert::human_bytes_with(1_000, true), "1 KB");
+        assert_eq!(Convert::human_bytes_with(2_340_000, true), "2.3 MB");
+    }
+
+    #[test]
+    fn test_parse_bytes() {
+        assert_eq!(Convert::parse_bytes("42").unwrap(), 42);
+        assert_eq!(Convert::parse_bytes("42 B").unwrap(), 42);
+        assert_eq!(Convert::parse_bytes("10MB").unwrap(), 10_000_000);
+        assert_eq!(Convert::parse_bytes("10 mib").unwrap(), 10 * 1024 * 1024);
+        assert_eq!(Convert::parse_bytes("2k").unwrap(), 2_000);
+        assert_eq!(Convert::parse_bytes("2Ki").unwrap(), 2_048);
+        assert_eq!(Convert::parse_bytes(" 0.5 KiB ").unwrap(), 512);
+        assert_eq!(Convert::parse_bytes("1.5 GiB").unwrap(), 1_610_612_736);
+        assert!(Convert::parse_bytes("16 EiB").is_err());
+        assert_eq!(Convert::parse_bytes("15 EiB").unwrap(), 15 * (1u64 << 60));
+
+        let error = Convert::parse_bytes("10 XB").unwrap_err().to_string();
+        assert!(error.contains("Unknown byte unit 'XB'"));
+        assert!(Convert::parse_bytes("MB").is_err());
+        assert!(Convert::parse_bytes("1.2.3 MB").is_err());
+        assert!(Convert::parse_bytes("-5 MB").is_err());
+        assert!(Convert::parse_bytes("").is_err());
+
+        for n in [0, 1, 1536, 10_485_760] {
+            let text = Convert::human_bytes(n);
+            assert_eq!(Convert::parse_bytes(&text).unwrap(), n);
+        }
+    }
+
     #[test]
     fn test_default_conversions() {
         assert_eq!(Convert::to_int_default("123", 0), 123);
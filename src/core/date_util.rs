@@ -12,6 +12,25 @@ use chrono::{
 pub struct DateUtil;
 
 impl DateUtil {
+    /// Differences below this many seconds are reported as "just now" by
+    /// [`time_ago`](Self::time_ago)
+    pub const JUST_NOW_SECONDS: i64 = 60;
+    /// Seconds in a minute; below [`SECONDS_PER_HOUR`](Self::SECONDS_PER_HOUR)
+    /// differences are reported in minutes
+    pub const SECONDS_PER_MINUTE: i64 = 60;
+    /// Seconds in an hour; below [`SECONDS_PER_DAY`](Self::SECONDS_PER_DAY)
+    /// differences are reported in hours
+    pub const SECONDS_PER_HOUR: i64 = 3_600;
+    /// Seconds in a day; below [`DAYS_PER_MONTH`](Self::DAYS_PER_MONTH) days
+    /// differences are reported in days
+    pub const SECONDS_PER_DAY: i64 = 86_400;
+    /// Length of a month used for relative times; below
+    /// [`DAYS_PER_YEAR`](Self::DAYS_PER_YEAR) days differences are reported
+    /// in months
+    pub const DAYS_PER_MONTH: i64 = 30;
+    /// Length of a year used for relative times
+    pub const DAYS_PER_YEAR: i64 = 365;
+
     /// Get current date and time
     ///
     /// # Examples
@@ -242,6 +261,61 @@ impl DateUtil {
         date.weekday()
     }
 
+    /// Get the ISO 8601 week-numbering year and week
+    ///
+    /// Weeks start on Monday and week 1 is the week containing the year's
+    /// first Thursday, so the first days of January can belong to the last
+    /// week of the previous year and the last days of December to week 1 of
+    /// the next.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::DateUtil;
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2023, 12, 25).unwrap();
+    /// assert_eq!(DateUtil::iso_week(date), (2023, 52));
+    ///
+    /// // 2021-01-01 is a Friday, still in the last week of 2020
+    /// let date = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+    /// assert_eq!(DateUtil::iso_week(date), (2020, 53));
+    /// ```
+    pub fn iso_week(date: NaiveDate) -> (i32, u32) {
+        let week = date.iso_week();
+        (week.year(), week.week())
+    }
+
+    /// Get the quarter of the year (1-4)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::DateUtil;
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2023, 12, 25).unwrap();
+    /// assert_eq!(DateUtil::quarter(date), 4);
+    /// ```
+    pub fn quarter(date: NaiveDate) -> u32 {
+        (date.month() - 1) / 3 + 1
+    }
+
+    /// Get the day of the year (1-366)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::DateUtil;
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2023, 12, 25).unwrap();
+    /// assert_eq!(DateUtil::day_of_year(date), 359);
+    /// ```
+    pub fn day_of_year(date: NaiveDate) -> u32 {
+        date.ordinal()
+    }
+
     /// Add days to date
     ///
     /// # Examples
@@ -373,6 +447,80 @@ impl DateUtil {
         (date2 - date1).num_days()
     }
 
+    /// Iterate over dates from `start` to `end`, `step` apart
+    ///
+    /// `step` is either a [`Duration`] of whole days or a [`DateStep`] such
+    /// as `DateStep::Months(1)`. The range includes `end` by default; call
+    /// [`DateRange::exclusive`] to stop before it. The last item is the last
+    /// step not past `end`, so `end` itself is skipped when `step` does not
+    /// divide the span, and the range is empty when `start > end`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is shorter than one day.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::{DateStep, DateUtil};
+    /// use chrono::{Duration, NaiveDate};
+    ///
+    /// let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+    ///
+    /// let weeks: Vec<NaiveDate> = DateUtil::date_range(start, end, Duration::weeks(1)).collect();
+    /// assert_eq!(weeks.len(), 3); // Jan 1, 8 and 15
+    ///
+    /// let days = DateUtil::date_range(start, end, Duration::days(1)).exclusive();
+    /// assert_eq!(days.count(), 14);
+    ///
+    /// let months = DateUtil::date_range(start, end, DateStep::Months(1));
+    /// assert_eq!(months.collect::<Vec<_>>(), vec![start]);
+    /// ```
+    pub fn date_range(
+        start: NaiveDate,
+        end: NaiveDate,
+        step: impl Into<DateStep>,
+    ) -> DateRange<NaiveDate> {
+        let step = step.into();
+        if let DateStep::Fixed(step) = step {
+            assert!(
+                step >= Duration::days(1),
+                "date range step must be at least one day"
+            );
+        }
+        DateRange::new(start, end, step)
+    }
+
+    /// Iterate over date-times from `start` to `end`, `step` apart
+    ///
+    /// Works like [`DateUtil::date_range`], but `step` may be any positive
+    /// duration, e.g. `Duration::minutes(15)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero or negative.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::DateUtil;
+    /// use chrono::{Duration, NaiveDate};
+    ///
+    /// let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().and_hms_opt(9, 0, 0).unwrap();
+    /// let end = start + Duration::hours(1);
+    ///
+    /// let slots = DateUtil::datetime_range(start, end, Duration::minutes(15)).exclusive();
+    /// assert_eq!(slots.count(), 4);
+    /// ```
+    pub fn datetime_range(
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+        step: impl Into<DateStep>,
+    ) -> DateRange<NaiveDateTime> {
+        DateRange::new(start, end, step.into())
+    }
+
     /// Check if date is today
     ///
     /// # Examples
@@ -464,6 +612,74 @@ impl DateUtil {
         next_month.pred_opt().unwrap()
     }
 
+    /// Get the Monday starting the ISO week of the date
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::DateUtil;
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2023, 12, 28).unwrap(); // Thursday
+    /// let start = DateUtil::start_of_week(date);
+    /// assert_eq!(start, NaiveDate::from_ymd_opt(2023, 12, 25).unwrap());
+    /// ```
+    pub fn start_of_week(date: NaiveDate) -> NaiveDate {
+        date - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+    }
+
+    /// Get the Sunday ending the ISO week of the date
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::DateUtil;
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2023, 12, 28).unwrap(); // Thursday
+    /// let end = DateUtil::end_of_week(date);
+    /// assert_eq!(end, NaiveDate::from_ymd_opt(2023, 12, 31).unwrap());
+    /// ```
+    pub fn end_of_week(date: NaiveDate) -> NaiveDate {
+        Self::start_of_week(date) + Duration::days(6)
+    }
+
+    /// Get the first day of the month
+    ///
+    /// Same as [`first_day_of_month`](Self::first_day_of_month), named to
+    /// match the other `start_of_*` helpers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::DateUtil;
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2023, 12, 25).unwrap();
+    /// let start = DateUtil::start_of_month(date);
+    /// assert_eq!(start, NaiveDate::from_ymd_opt(2023, 12, 1).unwrap());
+    /// ```
+    pub fn start_of_month(date: NaiveDate) -> NaiveDate {
+        Self::first_day_of_month(date)
+    }
+
+    /// Get the first day of the quarter
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::DateUtil;
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2023, 8, 15).unwrap();
+    /// let start = DateUtil::start_of_quarter(date);
+    /// assert_eq!(start, NaiveDate::from_ymd_opt(2023, 7, 1).unwrap());
+    /// ```
+    pub fn start_of_quarter(date: NaiveDate) -> NaiveDate {
+        let month = (Self::quarter(date) - 1) * 3 + 1;
+        NaiveDate::from_ymd_opt(date.year(), month, 1).unwrap()
+    }
+
     /// Get the first day of the year
     ///
     /// # Examples
@@ -574,6 +790,58 @@ impl DateUtil {
         NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
     }
 
+    /// Describe a point in time relative to `now`
+    ///
+    /// Past times read "5 minutes ago", future times "in 3 hours", and
+    /// anything closer than [`JUST_NOW_SECONDS`](Self::JUST_NOW_SECONDS) in
+    /// either direction is "just now". The largest unit that fits is used
+    /// and the count is rounded down, so 119 seconds is "1 minute ago".
+    /// Months are [`DAYS_PER_MONTH`](Self::DAYS_PER_MONTH) days and years
+    /// [`DAYS_PER_YEAR`](Self::DAYS_PER_YEAR) days.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::DateUtil;
+    /// use chrono::{Duration, TimeZone, Utc};
+    ///
+    /// let now = Utc.with_ymd_and_hms(2023, 12, 25, 12, 0, 0).unwrap();
+    ///
+    /// assert_eq!(DateUtil::time_ago(now - Duration::seconds(30), now), "just now");
+    /// assert_eq!(DateUtil::time_ago(now - Duration::minutes(1), now), "1 minute ago");
+    /// assert_eq!(DateUtil::time_ago(now - Duration::days(2), now), "2 days ago");
+    /// assert_eq!(DateUtil::time_ago(now + Duration::hours(3), now), "in 3 hours");
+    /// ```
+    pub fn time_ago(past: DateTime<Utc>, now: DateTime<Utc>) -> String {
+        let seconds = (now - past).num_seconds();
+        let elapsed = seconds.abs();
+
+        if elapsed < Self::JUST_NOW_SECONDS {
+            return "just now".to_string();
+        }
+
+        let days = elapsed / Self::SECONDS_PER_DAY;
+        let (count, unit) = if elapsed < Self::SECONDS_PER_HOUR {
+            (elapsed / Self::SECONDS_PER_MINUTE, "minute")
+        } else if elapsed < Self::SECONDS_PER_DAY {
+            (elapsed / Self::SECONDS_PER_HOUR, "hour")
+        } else if days < Self::DAYS_PER_MONTH {
+            (days, "day")
+        } else if days < Self::DAYS_PER_YEAR {
+            // 360-364 days would otherwise read "12 months"
+            ((days / Self::DAYS_PER_MONTH).min(11), "month")
+        } else {
+            (days / Self::DAYS_PER_YEAR, "year")
+        };
+
+        let plural = if count == 1 { "" } else { "s" };
+        if seconds >= 0 {
+            format!("{} {}{} ago", count, unit, plural)
+        } else {
+            format!("in {} {}{}", count, unit, plural)
+        }
+    }
+
     /// Get Unix timestamp (seconds since 1970-01-01 00:00:00 UTC)
     ///
     /// # Examples
@@ -612,11 +880,286 @@ impl DateUtil {
     }
 }
 
+/// Distance between consecutive items of a [`DateRange`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateStep {
+    /// A fixed duration, e.g. `Duration::weeks(1)`
+    Fixed(Duration),
+    /// A number of calendar months
+    ///
+    /// Each item is computed from the start, with days past the end of a
+    /// shorter month clamped to its last day: stepping monthly from January
+    /// 31 gives February 29 (or 28), then March 31.
+    Months(u32),
+}
+
+impl From<Duration> for DateStep {
+    fn from(step: Duration) -> Self {
+        Self::Fixed(step)
+    }
+}
+
+/// Iterator over evenly spaced dates or date-times
+///
+/// Created by [`DateUtil::date_range`] and [`DateUtil::datetime_range`].
+#[derive(Debug, Clone)]
+pub struct DateRange<T> {
+    start: T,
+    end: T,
+    step: DateStep,
+    inclusive: bool,
+    index: i32,
+    done: bool,
+}
+
+impl<T> DateRange<T> {
+    fn new(start: T, end: T, step: DateStep) -> Self {
+        let positive = match step {
+            DateStep::Fixed(step) => step > Duration::zero(),
+            DateStep::Months(months) => months > 0,
+        };
+        assert!(positive, "date range step must be positive");
+        Self {
+            start,
+            end,
+            step,
+            inclusive: true,
+            index: 0,
+            done: false,
+        }
+    }
+
+    /// Include `end` in the range if a step lands on it (the default)
+    #[must_use]
+    pub fn inclusive(mut self) -> Self {
+        self.inclusive = true;
+        self
+    }
+
+    /// Stop before `end`
+    #[must_use]
+    pub fn exclusive(mut self) -> Self {
+        self.inclusive = false;
+        self
+    }
+}
+
+/// Dates and date-times a [`DateRange`] can step through
+trait RangePoint: Copy + PartialOrd {
+    fn checked_add_duration(self, duration: Duration) -> Option<Self>;
+    fn checked_add_calendar_months(self, months: chrono::Months) -> Option<Self>;
+}
+
+impl RangePoint for NaiveDate {
+    fn checked_add_duration(self, duration: Duration) -> Option<Self> {
+        self.checked_add_signed(duration)
+    }
+
+    fn checked_add_calendar_months(self, months: chrono::Months) -> Option<Self> {
+        self.checked_add_months(months)
+    }
+}
+
+impl RangePoint for NaiveDateTime {
+    fn checked_add_duration(self, duration: Duration) -> Option<Self> {
+        self.checked_add_signed(duration)
+    }
+
+    fn checked_add_calendar_months(self, months: chrono::Months) -> Option<Self> {
+        self.checked_add_months(months)
+    }
+}
+
+/// Next item of a range; items are derived from the start and the index so
+/// month clamping does not accumulate
+fn next_in_range<T: RangePoint>(range: &mut DateRange<T>) -> Option<T> {
+    if range.done {
+        return None;
+    }
+
+    let item = match range.step {
+        DateStep::Fixed(step) => step
+            .checked_mul(range.index)
+            .and_then(|offset| range.start.checked_add_duration(offset)),
+        DateStep::Months(months) => u32::try_from(range.index)
+            .ok()
+            .and_then(|index| months.checked_mul(index))
+            .and_then(|total| {
+                range
+                    .start
+                    .checked_add_calendar_months(chrono::Months::new(total))
+            }),
+    };
+    let in_range = |item: &T| {
+        if range.inclusive {
+            *item <= range.end
+        } else {
+            *item < range.end
+        }
+    };
+
+    match (item.filter(in_range), range.index.checked_add(1)) {
+        (Some(item), Some(next)) => {
+            range.index = next;
+            Some(item)
+        }
+        (item, _) => {
+            range.done = true;
+            item
+        }
+    }
+}
+
+impl Iterator for DateRange<NaiveDate> {
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<NaiveDate> {
+        next_in_range(self)
+    }
+}
+
+impl Iterator for DateRange<NaiveDateTime> {
+    type Item = NaiveDateTime;
+
+    fn next(&mut self) -> Option<NaiveDateTime> {
+        next_in_range(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use chrono::NaiveDate;
 
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_date_range_days_and_weeks() {
+        let days: Vec<NaiveDate> =
+            DateUtil::date_range(date(2024, 2, 27), date(2024, 3, 1), Duration::days(1)).collect();
+        assert_eq!(
+            days,
+            vec![
+                date(2024, 2, 27),
+                date(2024, 2, 28),
+                date(2024, 2, 29),
+                date(2024, 3, 1)
+            ]
+        );
+
+        let exclusive =
+            DateUtil::date_range(date(2024, 2, 27), date(2024, 3, 1), Duration::days(1))
+                .exclusive();
+        assert_eq!(exclusive.last(), Some(date(2024, 2, 29)));
+
+        // 10 days in steps of 3: the end is not reached exactly
+        let uneven: Vec<NaiveDate> =
+            DateUtil::date_range(date(2024, 1, 1), date(2024, 1, 11), Duration::days(3))
+                .exclusive()
+                .collect();
+        assert_eq!(
+            uneven,
+            vec![
+                date(2024, 1, 1),
+                date(2024, 1, 4),
+                date(2024, 1, 7),
+                date(2024, 1, 10)
+            ]
+        );
+
+        let single = DateUtil::date_range(date(2024, 1, 1), date(2024, 1, 1), Duration::weeks(1));
+        assert_eq!(single.collect::<Vec<_>>(), vec![date(2024, 1, 1)]);
+        let empty = DateUtil::date_range(date(2024, 1, 1), date(2024, 1, 1), Duration::weeks(1));
+        assert_eq!(empty.exclusive().count(), 0);
+        let reversed = DateUtil::date_range(date(2024, 2, 1), date(2024, 1, 1), Duration::days(1));
+        assert_eq!(reversed.count(), 0);
+    }
+
+    #[test]
+    fn test_date_range_months_across_leap_february() {
+        let month_ends: Vec<NaiveDate> =
+            DateUtil::date_range(date(2024, 1, 31), date(2024, 5, 31), DateStep::Months(1))
+                .collect();
+        assert_eq!(
+            month_ends,
+            vec![
+                date(2024, 1, 31),
+                date(2024, 2, 29),
+                date(2024, 3, 31),
+                date(2024, 4, 30),
+                date(2024, 5, 31)
+            ]
+        );
+
+        let non_leap: Vec<NaiveDate> =
+            DateUtil::date_range(date(2023, 1, 31), date(2023, 3, 31), DateStep::Months(1))
+                .exclusive()
+                .collect();
+        assert_eq!(non_leap, vec![date(2023, 1, 31), date(2023, 2, 28)]);
+
+        let leap_days: Vec<NaiveDate> =
+            DateUtil::date_range(date(2020, 2, 29), date(2025, 1, 1), DateStep::Months(12))
+                .collect();
+        assert_eq!(
+            leap_days,
+            vec![
+                date(2020, 2, 29),
+                date(2021, 2, 28),
+                date(2022, 2, 28),
+                date(2023, 2, 28),
+                date(2024, 2, 29)
+            ]
+        );
+
+        let quarters =
+            DateUtil::date_range(date(2024, 1, 1), date(2024, 12, 31), DateStep::Months(3));
+        assert_eq!(quarters.count(), 4);
+    }
+
+    #[test]
+    fn test_datetime_range() {
+        let start = date(2024, 2, 28).and_hms_opt(22, 0, 0).unwrap();
+        let end = date(2024, 2, 29).and_hms_opt(2, 0, 0).unwrap();
+
+        let hours: Vec<NaiveDateTime> =
+            DateUtil::datetime_range(start, end, Duration::minutes(90)).collect();
+        assert_eq!(hours.len(), 3);
+        assert_eq!(hours[2], date(2024, 2, 29).and_hms_opt(1, 0, 0).unwrap());
+
+        let monthly = DateUtil::datetime_range(start, end, DateStep::Months(1));
+        assert_eq!(monthly.collect::<Vec<_>>(), vec![start]);
+        assert_eq!(
+            DateUtil::datetime_range(end, start, Duration::hours(1)).count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_date_range_stops_at_calendar_limit() {
+        let days: Vec<NaiveDate> = DateUtil::date_range(
+            NaiveDate::MAX - Duration::days(1),
+            NaiveDate::MAX,
+            Duration::days(1),
+        )
+        .collect();
+        assert_eq!(days.len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "positive")]
+    fn test_datetime_range_rejects_zero_step() {
+        let start = date(2024, 1, 1).and_hms_opt(0, 0, 0).unwrap();
+        let _ = DateUtil::datetime_range(start, start, Duration::zero());
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one day")]
+    fn test_date_range_rejects_partial_day_step() {
+        let _ = DateUtil::date_range(date(2024, 1, 1), date(2024, 1, 2), Duration::hours(12));
+    }
+
     #[test]
     fn test_now() {
         let now = DateUtil::now();
@@ -667,6 +1210,117 @@ mod tests {
         assert_eq!(last_day, NaiveDate::from_ymd_opt(2023, 12, 31).unwrap());
     }
 
+    #[test]
+    fn test_iso_week_year_boundaries() {
+        let ymd = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        // 2019-12-30 (Monday) starts week 1 of 2020
+        assert_eq!(DateUtil::iso_week(ymd(2019, 12, 29)), (2019, 52));
+        assert_eq!(DateUtil::iso_week(ymd(2019, 12, 30)), (2020, 1));
+        assert_eq!(DateUtil::iso_week(ymd(2020, 1, 5)), (2020, 1));
+        assert_eq!(DateUtil::iso_week(ymd(2020, 1, 6)), (2020, 2));
+
+        // 2020 has 53 weeks, reaching into January 2021
+        assert_eq!(DateUtil::iso_week(ymd(2020, 12, 31)), (2020, 53));
+        assert_eq!(DateUtil::iso_week(ymd(2021, 1, 3)), (2020, 53));
+        assert_eq!(DateUtil::iso_week(ymd(2021, 1, 4)), (2021, 1));
+
+        // 2023-01-01 is a Sunday belonging to the last week of 2022
+        assert_eq!(DateUtil::iso_week(ymd(2023, 1, 1)), (2022, 52));
+        assert_eq!(DateUtil::iso_week(ymd(2023, 1, 2)), (2023, 1));
+
+        // 2024-12-30 (Monday) already belongs to 2025
+        assert_eq!(DateUtil::iso_week(ymd(2024, 12, 29)), (2024, 52));
+        assert_eq!(DateUtil::iso_week(ymd(2024, 12, 30)), (2025, 1));
+        assert_eq!(DateUtil::iso_week(ymd(2024, 12, 31)), (2025, 1));
+    }
+
+    #[test]
+    fn test_quarter_and_day_of_year() {
+        let ymd = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        let quarters: Vec<u32> = (1..=12)
+            .map(|m| DateUtil::quarter(ymd(2023, m, 1)))
+            .collect();
+        assert_eq!(quarters, vec![1, 1, 1, 2, 2, 2, 3, 3, 3, 4, 4, 4]);
+
+        assert_eq!(DateUtil::day_of_year(ymd(2023, 1, 1)), 1);
+        assert_eq!(DateUtil::day_of_year(ymd(2023, 3, 1)), 60);
+        assert_eq!(DateUtil::day_of_year(ymd(2024, 3, 1)), 61);
+        assert_eq!(DateUtil::day_of_year(ymd(2023, 12, 31)), 365);
+        assert_eq!(DateUtil::day_of_year(ymd(2024, 12, 31)), 366);
+    }
+
+    #[test]
+    fn test_truncation_helpers() {
+        let ymd = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        // Week spanning the year boundary
+        let date = ymd(2025, 1, 1); // Wednesday
+        assert_eq!(DateUtil::start_of_week(date), ymd(2024, 12, 30));
+        assert_eq!(DateUtil::end_of_week(date), ymd(2025, 1, 5));
+        assert_eq!(
+            DateUtil::start_of_week(ymd(2024, 12, 30)),
+            ymd(2024, 12, 30)
+        );
+        assert_eq!(DateUtil::end_of_week(ymd(2025, 1, 5)), ymd(2025, 1, 5));
+
+        assert_eq!(DateUtil::start_of_month(ymd(2024, 2, 29)), ymd(2024, 2, 1));
+        assert_eq!(
+            DateUtil::start_of_quarter(ymd(2024, 3, 31)),
+            ymd(2024, 1, 1)
+        );
+        assert_eq!(DateUtil::start_of_quarter(ymd(2024, 4, 1)), ymd(2024, 4, 1));
+        assert_eq!(
+            DateUtil::start_of_quarter(ymd(2024, 12, 31)),
+            ymd(2024, 10, 1)
+        );
+    }
+
+    #[test]
+    fn test_time_ago_boundaries() {
+        let now = Utc.with_ymd_and_hms(2023, 12, 25, 12, 0, 0).unwrap();
+        let ago = |seconds: i64| DateUtil::time_ago(now - Duration::seconds(seconds), now);
+        let day = DateUtil::SECONDS_PER_DAY;
+
+        let cases = [
+            (0, "just now"),
+            (59, "just now"),
+            (60, "1 minute ago"),
+            (119, "1 minute ago"),
+            (120, "2 minutes ago"),
+            (3_599, "59 minutes ago"),
+            (3_600, "1 hour ago"),
+            (7_199, "1 hour ago"),
+            (7_200, "2 hours ago"),
+            (day - 1, "23 hours ago"),
+            (day, "1 day ago"),
+            (2 * day - 1, "1 day ago"),
+            (2 * day, "2 days ago"),
+            (30 * day - 1, "29 days ago"),
+            (30 * day, "1 month ago"),
+            (60 * day, "2 months ago"),
+            (364 * day, "11 months ago"),
+            (365 * day, "1 year ago"),
+            (730 * day, "2 years ago"),
+        ];
+        for (seconds, expected) in cases {
+            assert_eq!(ago(seconds), expected, "{} seconds", seconds);
+        }
+    }
+
+    #[test]
+    fn test_time_ago_future() {
+        let now = Utc.with_ymd_and_hms(2023, 12, 25, 12, 0, 0).unwrap();
+        let until = |seconds: i64| DateUtil::time_ago(now + Duration::seconds(seconds), now);
+
+        assert_eq!(until(59), "just now");
+        assert_eq!(until(60), "in 1 minute");
+        assert_eq!(until(3 * 3_600), "in 3 hours");
+        assert_eq!(until(DateUtil::SECONDS_PER_DAY), "in 1 day");
+        assert_eq!(until(400 * DateUtil::SECONDS_PER_DAY), "in 1 year");
+    }
+
     #[test]
     fn test_is_leap_year() {
         assert!(DateUtil::is_leap_year(2020));
@@ -9,9 +9,9 @@ pub mod convert;
 pub mod date_util;
 pub mod str_util;
 
-pub use codec::{Base58Util, Base64Util, HexUtil};
+pub use codec::{Base58Util, Base64Util, HexUtil, LineEnding};
 pub use collection_util::CollUtil;
 pub use convert::Convert;
-pub use date_util::DateUtil;
+pub use date_util::{DateRange, DateStep, DateUtil};
 /// Re-export commonly used types for convenience
 pub use str_util::StrUtil;
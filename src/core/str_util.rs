@@ -6,6 +6,8 @@
 //! 本模块提供全面的字符串操作工具，灵感来源于Hutool的字符序列工具。
 
 use regex::Regex;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
 
 #[cfg(feature = "core")]
 use rand::Rng;
@@ -244,6 +246,59 @@ impl StrUtil {
         result
     }
 
+    /// Remove accents and other diacritical marks from letters
+    ///
+    /// The string is decomposed (Unicode NFD), combining diacritical marks
+    /// are dropped and the rest is recomposed. Letters that have no
+    /// decomposition, such as `ß`, `ø` or `æ`, are kept as they are, and
+    /// marks that are part of non-Latin writing systems (Japanese dakuten,
+    /// Indic vowel signs, ...) are not touched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::StrUtil;
+    ///
+    /// assert_eq!(StrUtil::remove_accents("Crème Brûlée"), "Creme Brulee");
+    /// assert_eq!(StrUtil::remove_accents("Tōkyō 東京"), "Tokyo 東京");
+    /// ```
+    pub fn remove_accents(s: &str) -> String {
+        s.nfd().filter(|&c| !is_diacritical_mark(c)).nfc().collect()
+    }
+
+    /// Fold case for caseless comparison
+    ///
+    /// Lowercases the string and applies the full case folding mappings
+    /// that differ from lowercasing, so `"Straße"` and `"STRASSE"` fold to
+    /// the same value. The result is normalized to NFC, so precomposed and
+    /// decomposed accents compare equal.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::StrUtil;
+    ///
+    /// assert_eq!(StrUtil::fold_case("Straße"), StrUtil::fold_case("STRASSE"));
+    /// assert_eq!(StrUtil::fold_case("ΣΊΣΥΦΟΣ"), StrUtil::fold_case("σίσυφος"));
+    /// ```
+    pub fn fold_case(s: &str) -> String {
+        let mut folded = String::with_capacity(s.len());
+        for c in s.nfd().collect::<String>().to_lowercase().chars() {
+            match c {
+                'ß' | 'ẞ' => folded.push_str("ss"),
+                'ς' => folded.push('σ'),
+                'ﬀ' => folded.push_str("ff"),
+                'ﬁ' => folded.push_str("fi"),
+                'ﬂ' => folded.push_str("fl"),
+                'ﬃ' => folded.push_str("ffi"),
+                'ﬄ' => folded.push_str("ffl"),
+                'ﬅ' | 'ﬆ' => folded.push_str("st"),
+                _ => folded.push(c),
+            }
+        }
+        folded.nfc().collect()
+    }
+
     /// Check if string starts with the specified prefix
     ///
     /// # Examples
@@ -483,6 +538,81 @@ impl StrUtil {
         Ok(regex.find_iter(s).map(|m| m.as_str().to_string()).collect())
     }
 
+    /// Match text against a shell-style wildcard pattern
+    ///
+    /// The whole text must match. Supported syntax:
+    /// - `*` matches any run of characters, including none
+    /// - `?` matches exactly one character
+    /// - `[abc]`, `[a-z]` match one character from the set; `[!a-z]` or
+    ///   `[^a-z]` match one character outside it, and a `]` right after the
+    ///   opening bracket is literal
+    /// - `\` makes the next character literal
+    ///
+    /// Unlike file system globs, `*` also matches `/`. Matching takes at most
+    /// `O(pattern × text)` steps, whatever the pattern.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::StrUtil;
+    ///
+    /// assert!(StrUtil::glob_match("*.rs", "main.rs"));
+    /// assert!(StrUtil::glob_match("a?c", "abc"));
+    /// assert!(StrUtil::glob_match("[0-9]*", "2024-report.pdf"));
+    /// assert!(!StrUtil::glob_match("*.rs", "main.RS"));
+    /// ```
+    pub fn glob_match(pattern: &str, text: &str) -> bool {
+        Self::glob_match_with(pattern, text, false)
+    }
+
+    /// Match text against a wildcard pattern, optionally ignoring case
+    ///
+    /// See [`StrUtil::glob_match`] for the pattern syntax. With
+    /// `case_insensitive`, literals and character classes match both the
+    /// lowercase and uppercase forms of a character.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::StrUtil;
+    ///
+    /// assert!(StrUtil::glob_match_with("*.JPG", "holiday.jpg", true));
+    /// assert!(StrUtil::glob_match_with("[a-c]*", "Banana", true));
+    /// assert!(!StrUtil::glob_match_with("*.JPG", "holiday.jpg", false));
+    /// ```
+    pub fn glob_match_with(pattern: &str, text: &str, case_insensitive: bool) -> bool {
+        let tokens = parse_glob(pattern);
+        let text: Vec<char> = text.chars().collect();
+
+        // Two pointers, remembering only the most recent `*`: on a mismatch
+        // the star absorbs one more character and matching resumes after it
+        let (mut t, mut p) = (0, 0);
+        let mut star: Option<(usize, usize)> = None;
+        while t < text.len() {
+            match tokens.get(p) {
+                Some(GlobToken::Star) => {
+                    star = Some((p, t));
+                    p += 1;
+                }
+                Some(token) if token.matches(text[t], case_insensitive) => {
+                    p += 1;
+                    t += 1;
+                }
+                _ => match star {
+                    Some((star_p, star_t)) => {
+                        star = Some((star_p, star_t + 1));
+                        p = star_p + 1;
+                        t = star_t + 1;
+                    }
+                    None => return false,
+                },
+            }
+        }
+        tokens[p..]
+            .iter()
+            .all(|token| matches!(token, GlobToken::Star))
+    }
+
     /// Reverse a string
     ///
     /// # Examples
@@ -497,6 +627,84 @@ impl StrUtil {
         s.chars().rev().collect()
     }
 
+    /// Count user-perceived characters (extended grapheme clusters)
+    ///
+    /// Follows the Unicode text segmentation rules (UAX #29), so an emoji
+    /// with a skin tone modifier, a ZWJ family emoji or a letter followed by
+    /// combining accents each count as one, unlike `s.chars().count()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::StrUtil;
+    ///
+    /// assert_eq!(StrUtil::count_graphemes("hello"), 5);
+    /// assert_eq!(StrUtil::count_graphemes("👨‍👩‍👧‍👦"), 1);
+    /// assert_eq!(StrUtil::count_graphemes("e\u{301}"), 1);
+    /// ```
+    pub fn count_graphemes(s: &str) -> usize {
+        s.graphemes(true).count()
+    }
+
+    /// Count words using the Unicode word boundary rules (UAX #29)
+    ///
+    /// Punctuation and whitespace are not words, while contractions and
+    /// decimal numbers such as `"can't"` or `"3.14"` count as one. Scripts
+    /// written without spaces are not segmented by dictionary: each Han or
+    /// kana character counts as a word.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::StrUtil;
+    ///
+    /// assert_eq!(StrUtil::count_words("Hello, world! It's 3.14."), 4);
+    /// assert_eq!(StrUtil::count_words("  "), 0);
+    /// ```
+    pub fn count_words(s: &str) -> usize {
+        s.unicode_words().count()
+    }
+
+    /// Count lines, treating `\n`, `\r\n` and a lone `\r` as line endings
+    ///
+    /// A line ending at the very end does not start another line, so
+    /// `"a\n"` has one line and `""` has none.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::StrUtil;
+    ///
+    /// assert_eq!(StrUtil::count_lines("one\r\ntwo\nthree"), 3);
+    /// assert_eq!(StrUtil::count_lines("one\r\n"), 1);
+    /// assert_eq!(StrUtil::count_lines("\n\n"), 2);
+    /// assert_eq!(StrUtil::count_lines(""), 0);
+    /// ```
+    pub fn count_lines(s: &str) -> usize {
+        let bytes = s.as_bytes();
+        let mut lines = 0;
+        let mut line_open = false;
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\r' => {
+                    lines += 1;
+                    line_open = false;
+                    if bytes.get(i + 1) == Some(&b'\n') {
+                        i += 1;
+                    }
+                }
+                b'\n' => {
+                    lines += 1;
+                    line_open = false;
+                }
+                _ => line_open = true,
+            }
+            i += 1;
+        }
+        lines + usize::from(line_open)
+    }
+
     /// Pad string to the left with specified character to reach target length
     ///
     /// # Examples
@@ -560,6 +768,167 @@ impl StrUtil {
         }
     }
 
+    /// Mask the middle of a string, keeping `keep_start` leading and
+    /// `keep_end` trailing characters visible
+    ///
+    /// Every hidden character is replaced by `mask_char`. If the kept
+    /// characters would cover the whole string, at most half of it is left
+    /// visible so short values are never shown in full.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::StrUtil;
+    ///
+    /// assert_eq!(StrUtil::mask("secret-token", 2, 2, '*'), "se********en");
+    /// assert_eq!(StrUtil::mask("abc", 2, 2, '*'), "a**");
+    /// ```
+    pub fn mask(s: &str, keep_start: usize, keep_end: usize, mask_char: char) -> String {
+        let len = s.chars().count();
+        let (start, end) = Self::visible_counts(len, keep_start, keep_end);
+        s.chars()
+            .enumerate()
+            .map(|(i, c)| {
+                if i < start || i >= len - end {
+                    c
+                } else {
+                    mask_char
+                }
+            })
+            .collect()
+    }
+
+    /// Mask an email address for display, e.g. `j***e@example.com`
+    ///
+    /// Shows the first and last character of the local part; see
+    /// [`mask_email_with`](Self::mask_email_with).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::StrUtil;
+    ///
+    /// assert_eq!(StrUtil::mask_email("john.doe@example.com"), "j***e@example.com");
+    /// ```
+    pub fn mask_email(email: &str) -> String {
+        Self::mask_email_with(email, 1, 1)
+    }
+
+    /// Mask the local part of an email address, keeping the domain
+    ///
+    /// The hidden part is always written as `***` so the length of the
+    /// local part is not revealed. Short local parts show at most half of
+    /// their characters. A value without `@` is masked as a whole.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::StrUtil;
+    ///
+    /// assert_eq!(StrUtil::mask_email_with("john.doe@example.com", 2, 0), "jo***@example.com");
+    /// assert_eq!(StrUtil::mask_email_with("al@example.com", 1, 1), "a***@example.com");
+    /// ```
+    pub fn mask_email_with(email: &str, keep_start: usize, keep_end: usize) -> String {
+        let Some((local, domain)) = email.rsplit_once('@') else {
+            return Self::mask(email, keep_start, keep_end, '*');
+        };
+
+        let chars: Vec<char> = local.chars().collect();
+        let (start, end) = Self::visible_counts(chars.len(), keep_start, keep_end);
+        let mut masked: String = chars[..start].iter().collect();
+        masked.push_str("***");
+        masked.extend(&chars[chars.len() - end..]);
+        masked.push('@');
+        masked.push_str(domain);
+        masked
+    }
+
+    /// Mask a phone number, keeping the first 3 and last 4 digits
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::StrUtil;
+    ///
+    /// assert_eq!(StrUtil::mask_phone("13812345678"), "138****5678");
+    /// ```
+    pub fn mask_phone(phone: &str) -> String {
+        Self::mask_phone_with(phone, 3, 4)
+    }
+
+    /// Mask the digits of a phone number, keeping separators in place
+    ///
+    /// Only ASCII digits are masked, so spaces, dashes, parentheses and a
+    /// leading `+` stay where they are.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::StrUtil;
+    ///
+    /// assert_eq!(StrUtil::mask_phone_with("+1 (555) 123-4567", 1, 2), "+1 (***) ***-**67");
+    /// ```
+    pub fn mask_phone_with(phone: &str, keep_start: usize, keep_end: usize) -> String {
+        Self::mask_digits(phone, keep_start, keep_end)
+    }
+
+    /// Mask a credit card number, showing only the last 4 digits
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::StrUtil;
+    ///
+    /// assert_eq!(StrUtil::mask_credit_card("4111 1111 1111 1234"), "**** **** **** 1234");
+    /// ```
+    pub fn mask_credit_card(card: &str) -> String {
+        Self::mask_credit_card_with(card, 0, 4)
+    }
+
+    /// Mask the digits of a credit card number, keeping separators in place
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::core::StrUtil;
+    ///
+    /// assert_eq!(StrUtil::mask_credit_card_with("4111-1111-1111-1234", 6, 4), "4111-11**-****-1234");
+    /// ```
+    pub fn mask_credit_card_with(card: &str, keep_start: usize, keep_end: usize) -> String {
+        Self::mask_digits(card, keep_start, keep_end)
+    }
+
+    /// Mask ASCII digits, leaving every other character untouched
+    fn mask_digits(s: &str, keep_start: usize, keep_end: usize) -> String {
+        let digits = s.chars().filter(char::is_ascii_digit).count();
+        let (start, end) = Self::visible_counts(digits, keep_start, keep_end);
+        let mut index = 0;
+        s.chars()
+            .map(|c| {
+                if !c.is_ascii_digit() {
+                    return c;
+                }
+                let visible = index < start || index >= digits - end;
+                index += 1;
+                if visible { c } else { '*' }
+            })
+            .collect()
+    }
+
+    /// How many leading and trailing characters of `len` may stay visible
+    ///
+    /// If the requested counts would reveal everything, only half of the
+    /// characters (rounded down) are shown, leading ones first.
+    fn visible_counts(len: usize, keep_start: usize, keep_end: usize) -> (usize, usize) {
+        if keep_start.saturating_add(keep_end) < len {
+            return (keep_start, keep_end);
+        }
+        let budget = len / 2;
+        let start = keep_start.min(budget);
+        let end = keep_end.min(budget - start);
+        (start, end)
+    }
+
     /// Check if all strings in the slice are blank
     ///
     /// # Examples
@@ -667,6 +1036,122 @@ impl StrUtil {
     }
 }
 
+/// Whether a character is a combining diacritical mark used with Latin,
+/// Greek or Cyrillic letters
+fn is_diacritical_mark(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+    )
+}
+
+/// One element of a parsed wildcard pattern
+#[derive(Debug)]
+enum GlobToken {
+    Literal(char),
+    AnyChar,
+    Star,
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+impl GlobToken {
+    /// Whether this single-character token matches `c`; never true for `Star`
+    fn matches(&self, c: char, case_insensitive: bool) -> bool {
+        let candidates = || {
+            let mut forms = vec![c];
+            if case_insensitive {
+                forms.extend(c.to_lowercase());
+                forms.extend(c.to_uppercase());
+            }
+            forms
+        };
+        match self {
+            Self::Literal(expected) => {
+                c == *expected || (case_insensitive && candidates().contains(expected))
+            }
+            Self::AnyChar => true,
+            Self::Star => false,
+            Self::Class { negated, ranges } => {
+                let found = candidates()
+                    .into_iter()
+                    .any(|c| ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi));
+                found != *negated
+            }
+        }
+    }
+}
+
+/// Parse a wildcard pattern, merging consecutive `*`
+///
+/// A `[` without a closing `]` is taken literally, as is a trailing `\`.
+fn parse_glob(pattern: &str) -> Vec<GlobToken> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let token = match chars[i] {
+            '*' => {
+                if matches!(tokens.last(), Some(GlobToken::Star)) {
+                    i += 1;
+                    continue;
+                }
+                GlobToken::Star
+            }
+            '?' => GlobToken::AnyChar,
+            '\\' if i + 1 < chars.len() => {
+                i += 1;
+                GlobToken::Literal(chars[i])
+            }
+            '[' => match parse_glob_class(&chars[i + 1..]) {
+                Some((token, consumed)) => {
+                    i += consumed;
+                    token
+                }
+                None => GlobToken::Literal('['),
+            },
+            c => GlobToken::Literal(c),
+        };
+        tokens.push(token);
+        i += 1;
+    }
+    tokens
+}
+
+/// Parse the body of a `[...]` class, returning the token and how many
+/// characters were consumed including the closing `]`
+fn parse_glob_class(chars: &[char]) -> Option<(GlobToken, usize)> {
+    let mut i = 0;
+    let negated = matches!(chars.first(), Some('!' | '^'));
+    if negated {
+        i += 1;
+    }
+
+    let mut ranges = Vec::new();
+    let start = i;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == ']' && i > start {
+            return Some((GlobToken::Class { negated, ranges }, i + 1));
+        }
+        if chars.get(i + 1) == Some(&'-') && chars.get(i + 2).is_some_and(|&hi| hi != ']') {
+            let hi = chars[i + 2];
+            ranges.push((c.min(hi), c.max(hi)));
+            i += 3;
+        } else {
+            ranges.push((c, c));
+            i += 1;
+        }
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -742,6 +1227,202 @@ mod tests {
         assert_eq!(StrUtil::center("hello", 3, ' '), "hello");
     }
 
+    #[test]
+    fn test_remove_accents() {
+        // French
+        assert_eq!(StrUtil::remove_accents("Crème Brûlée"), "Creme Brulee");
+        assert_eq!(
+            StrUtil::remove_accents("Ça, où êtes-vous Noël ?"),
+            "Ca, ou etes-vous Noel ?"
+        );
+        // German: umlauts lose their dots, ß has no decomposition
+        assert_eq!(StrUtil::remove_accents("Müller Straße"), "Muller Straße");
+        // Already decomposed input
+        assert_eq!(StrUtil::remove_accents("e\u{0301}te\u{0301}"), "ete");
+        // Mixed scripts: CJK and Hangul are unchanged, Greek loses its tonos
+        assert_eq!(
+            StrUtil::remove_accents("café 東京 서울 Ελληνικά"),
+            "cafe 東京 서울 Ελληνικα"
+        );
+        // Script-specific marks are kept
+        assert_eq!(StrUtil::remove_accents("がぎ हिन्दी"), "がぎ हिन्दी");
+        assert_eq!(StrUtil::remove_accents("ø æ ł"), "ø æ ł");
+    }
+
+    #[test]
+    fn test_fold_case() {
+        assert_eq!(StrUtil::fold_case("Straße"), "strasse");
+        assert_eq!(StrUtil::fold_case("STRASSE"), "strasse");
+        assert_eq!(
+            StrUtil::fold_case("CRÈME"),
+            StrUtil::fold_case("cre\u{0300}me")
+        );
+        assert_eq!(
+            StrUtil::fold_case("Οδυσσεύς"),
+            StrUtil::fold_case("ΟΔΥΣΣΕΎΣ")
+        );
+        assert_eq!(StrUtil::fold_case("ﬁle"), "file");
+        assert_eq!(StrUtil::fold_case("東京 Tokyo"), "東京 tokyo");
+        assert_ne!(StrUtil::fold_case("café"), StrUtil::fold_case("cafe"));
+    }
+
+    #[test]
+    fn test_count_graphemes() {
+        // Thumbs up with a skin tone: two chars, eight bytes, one grapheme
+        let thumbs = "\u{1F44D}\u{1F3FD}";
+        assert_eq!(thumbs.chars().count(), 2);
+        assert_eq!(StrUtil::count_graphemes(thumbs), 1);
+
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        assert_eq!(family.chars().count(), 5);
+        assert_eq!(StrUtil::count_graphemes(family), 1);
+
+        assert_eq!(StrUtil::count_graphemes("ok 👍🏽🇫🇷"), 5);
+        assert_eq!(StrUtil::count_graphemes("Cafe\u{301}"), 4);
+        assert_eq!(StrUtil::count_graphemes("한국어"), 3);
+        assert_eq!(StrUtil::count_graphemes("a\r\nb"), 3);
+        assert_eq!(StrUtil::count_graphemes(""), 0);
+    }
+
+    #[test]
+    fn test_count_words() {
+        assert_eq!(StrUtil::count_words("The quick brown fox"), 4);
+        assert_eq!(StrUtil::count_words("  spaced\tout\r\nwords  "), 3);
+        assert_eq!(StrUtil::count_words("don't stop, 2.5 times!"), 4);
+        assert_eq!(StrUtil::count_words("great 👍🏽 job"), 2);
+        assert_eq!(StrUtil::count_words("Crème brûlée"), 2);
+        assert_eq!(StrUtil::count_words("--- ..."), 0);
+        assert_eq!(StrUtil::count_words(""), 0);
+    }
+
+    #[test]
+    fn test_count_lines() {
+        assert_eq!(StrUtil::count_lines(""), 0);
+        assert_eq!(StrUtil::count_lines("single"), 1);
+        assert_eq!(StrUtil::count_lines("a\r\nb\r\nc"), 3);
+        assert_eq!(StrUtil::count_lines("a\r\nb\r\n"), 2);
+        assert_eq!(StrUtil::count_lines("a\nb\rc"), 3);
+        assert_eq!(StrUtil::count_lines("\r\n\r\n"), 2);
+        assert_eq!(StrUtil::count_lines("a\n\r\nb"), 3);
+        assert_eq!(StrUtil::count_lines("👍🏽\r\n"), 1);
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(StrUtil::glob_match("*.rs", "main.rs"));
+        assert!(StrUtil::glob_match("*.rs", ".rs"));
+        assert!(StrUtil::glob_match("*.rs", "src/lib.rs"));
+        assert!(!StrUtil::glob_match("*.rs", "main.rs.bak"));
+        assert!(!StrUtil::glob_match("*.rs", "main.r"));
+
+        assert!(StrUtil::glob_match("a?c", "abc"));
+        assert!(StrUtil::glob_match("a?c", "a€c"));
+        assert!(!StrUtil::glob_match("a?c", "ac"));
+        assert!(!StrUtil::glob_match("a?c", "abbc"));
+
+        assert!(StrUtil::glob_match("[0-9]*", "7"));
+        assert!(StrUtil::glob_match("[0-9]*", "2024-report"));
+        assert!(!StrUtil::glob_match("[0-9]*", "report-2024"));
+        assert!(!StrUtil::glob_match("[0-9]*", ""));
+
+        assert!(StrUtil::glob_match("", ""));
+        assert!(StrUtil::glob_match("*", ""));
+        assert!(!StrUtil::glob_match("", "a"));
+        assert!(StrUtil::glob_match("a*b*c", "a-b-b-c"));
+        assert!(!StrUtil::glob_match("a*b*c", "a-c-b"));
+        assert!(StrUtil::glob_match("user:*:name", "user:42:name"));
+    }
+
+    #[test]
+    fn test_glob_match_classes() {
+        assert!(StrUtil::glob_match("[abc]x", "bx"));
+        assert!(!StrUtil::glob_match("[abc]x", "dx"));
+        assert!(StrUtil::glob_match("[!abc]x", "dx"));
+        assert!(StrUtil::glob_match("[^abc]x", "dx"));
+        assert!(!StrUtil::glob_match("[!abc]x", "ax"));
+        assert!(StrUtil::glob_match("[a-cx-z]", "y"));
+        assert!(StrUtil::glob_match("[]]", "]"));
+        assert!(StrUtil::glob_match("[a-]", "-"));
+        assert!(StrUtil::glob_match("[", "["));
+        assert!(StrUtil::glob_match("a[b", "a[b"));
+        assert!(StrUtil::glob_match(r"\*\?", "*?"));
+        assert!(!StrUtil::glob_match(r"\*", "x"));
+        assert!(StrUtil::glob_match(r"a\", r"a\"));
+    }
+
+    #[test]
+    fn test_glob_match_case_insensitive() {
+        assert!(StrUtil::glob_match_with("*.RS", "main.rs", true));
+        assert!(StrUtil::glob_match_with("[A-C]?", "bz", true));
+        assert!(StrUtil::glob_match_with("[a-c]?", "BZ", true));
+        assert!(!StrUtil::glob_match_with("[!a-c]?", "BZ", true));
+        assert!(StrUtil::glob_match_with("ÉTÉ", "été", true));
+        assert!(!StrUtil::glob_match_with("*.RS", "main.rs", false));
+    }
+
+    #[test]
+    fn test_glob_match_no_catastrophic_backtracking() {
+        let text = "a".repeat(10_000);
+        let pattern = format!("{}b", "*a".repeat(50));
+        let start = std::time::Instant::now();
+        assert!(!StrUtil::glob_match(&pattern, &text));
+        assert!(StrUtil::glob_match(&"*a".repeat(50), &text));
+        assert!(start.elapsed() < std::time::Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_mask() {
+        assert_eq!(StrUtil::mask("password", 1, 1, '#'), "p######d");
+        assert_eq!(StrUtil::mask("中文名字", 1, 1, '*'), "中**字");
+        assert_eq!(StrUtil::mask("ab", 1, 1, '*'), "a*");
+        assert_eq!(StrUtil::mask("a", 1, 1, '*'), "*");
+        assert_eq!(StrUtil::mask("", 1, 1, '*'), "");
+    }
+
+    #[test]
+    fn test_mask_email() {
+        assert_eq!(
+            StrUtil::mask_email("john.doe@example.com"),
+            "j***e@example.com"
+        );
+        assert_eq!(StrUtil::mask_email("jo@example.com"), "j***@example.com");
+        assert_eq!(StrUtil::mask_email("j@example.com"), "***@example.com");
+        assert_eq!(StrUtil::mask_email("@example.com"), "***@example.com");
+        assert_eq!(
+            StrUtil::mask_email_with("john.doe@example.com", 0, 3),
+            "***doe@example.com"
+        );
+        assert_eq!(StrUtil::mask_email("not-an-email"), "n**********l");
+    }
+
+    #[test]
+    fn test_mask_phone() {
+        assert_eq!(StrUtil::mask_phone("13812345678"), "138****5678");
+        assert_eq!(StrUtil::mask_phone("138-1234-5678"), "138-****-5678");
+        assert_eq!(
+            StrUtil::mask_phone_with("+44 20 7946 0958", 2, 3),
+            "+44 ** **** *958"
+        );
+        // Too short to keep 3 + 4 digits: at most half stay visible
+        assert_eq!(StrUtil::mask_phone("12345"), "12***");
+        assert_eq!(StrUtil::mask_phone("110"), "1**");
+        assert_eq!(StrUtil::mask_phone("7"), "*");
+    }
+
+    #[test]
+    fn test_mask_credit_card() {
+        assert_eq!(
+            StrUtil::mask_credit_card("4111111111111234"),
+            "************1234"
+        );
+        assert_eq!(
+            StrUtil::mask_credit_card("4111 1111 1111 1234"),
+            "**** **** **** 1234"
+        );
+        assert_eq!(StrUtil::mask_credit_card("1234"), "**34");
+        assert_eq!(StrUtil::mask_credit_card("12"), "*2");
+    }
+
     #[test]
     fn test_random_string() {
         let s1 = StrUtil::random_string(10);
@@ -11,6 +11,25 @@ use std::str::FromStr;
 #[cfg(feature = "chrono")]
 use chrono::{DateTime, Datelike, TimeZone, Timelike, Utc};
 
+/// Month names accepted in the month field, starting at 1
+const MONTH_NAMES: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+/// Weekday names accepted in the day-of-week field, starting at 0 (Sunday)
+const DAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Predefined schedules accepted in place of the five standard fields
+const MACROS: [(&str, &str); 7] = [
+    ("@yearly", "0 0 1 1 *"),
+    ("@annually", "0 0 1 1 *"),
+    ("@monthly", "0 0 1 * *"),
+    ("@weekly", "0 0 * * 0"),
+    ("@daily", "0 0 * * *"),
+    ("@midnight", "0 0 * * *"),
+    ("@hourly", "0 * * * *"),
+];
+
 /// Represents a cron expression with parsed fields
 #[derive(Debug, Clone, PartialEq)]
 pub struct CronExpression {
@@ -51,6 +70,9 @@ pub enum CronField {
     LastWeekday(u32),
     /// Nth occurrence of weekday in month (e.g., 3#2 for second Tuesday)
     NthWeekday(u32, u32),
+    /// No specific value (?), day-of-month and day-of-week only; matches
+    /// every value so the other day field alone decides
+    Unspecified,
 }
 
 impl CronExpression {
@@ -61,6 +83,14 @@ impl CronExpression {
     /// - With seconds: "second minute hour day-of-month month day-of-week"
     /// - With year: "minute hour day-of-month month day-of-week year"
     /// - Full: "second minute hour day-of-month month day-of-week year"
+    /// - Macros: `@yearly` (or `@annually`), `@monthly`, `@weekly`, `@daily`
+    ///   (or `@midnight`) and `@hourly`
+    ///
+    /// The month and day-of-week fields also accept case-insensitive names
+    /// (`JAN`-`DEC`, `SUN`-`SAT`), including in ranges and lists. The day
+    /// fields accept `?` for "no specific value". Use
+    /// [`parse_quartz`](Self::parse_quartz) for Quartz expressions, whose
+    /// day-of-week numbers differ.
     ///
     /// # Examples
     ///
@@ -78,9 +108,22 @@ impl CronExpression {
     ///
     /// // With seconds: every 30 seconds
     /// let expr = CronExpression::parse("*/30 * * * * *").unwrap();
+    ///
+    /// // Named weekdays and macros
+    /// let expr = CronExpression::parse("0 9 * * MON-FRI").unwrap();
+    /// let expr = CronExpression::parse("@daily").unwrap();
     /// ```
     pub fn parse(expression: &str) -> Result<Self> {
-        let fields: Vec<&str> = expression.trim().split_whitespace().collect();
+        let expression = expression.trim();
+        if expression.starts_with('@') {
+            let (_, expanded) = MACROS
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(expression))
+                .ok_or_else(|| Error::validation(format!("Unknown cron macro: {}", expression)))?;
+            return Self::parse(expanded);
+        }
+
+        let fields: Vec<&str> = expression.split_whitespace().collect();
 
         match fields.len() {
             5 => {
@@ -126,6 +169,123 @@ impl CronExpression {
         }
     }
 
+    /// Parse a Quartz cron expression
+    ///
+    /// Quartz expressions have 6 or 7 fields: "second minute hour
+    /// day-of-month month day-of-week [year]". Days of the week run from
+    /// 1 (`SUN`) to 7 (`SAT`), a lone `L` in the day-of-week field means
+    /// Saturday, and `?` marks the day field that does not apply. The
+    /// result uses the same numbering as [`parse`](Self::parse), with
+    /// Sunday as 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::cron::{CronExpression, CronField};
+    ///
+    /// // Weekdays at noon
+    /// let expr = CronExpression::parse_quartz("0 0 12 ? * MON-FRI").unwrap();
+    /// assert_eq!(expr.day_of_month, CronField::Unspecified);
+    /// assert_eq!(expr.day_of_week, CronField::Range(1, 5));
+    /// assert_eq!(expr.to_unix_string(), "0 12 * * 1-5");
+    ///
+    /// // Quartz numbers Sunday as 1
+    /// let expr = CronExpression::parse_quartz("0 30 9 ? * 1,7").unwrap();
+    /// assert_eq!(expr.day_of_week, CronField::List(vec![0, 6]));
+    /// ```
+    pub fn parse_quartz(expression: &str) -> Result<Self> {
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if fields.len() != 6 && fields.len() != 7 {
+            return Err(Error::validation(format!(
+                "Invalid Quartz cron expression format. Expected 6 or 7 fields, got {}",
+                fields.len()
+            )));
+        }
+
+        let day_of_month = CronField::parse(fields[3], 1, 31)?;
+        let day_of_week = parse_quartz_weekday(fields[5])?;
+        if day_of_month == CronField::Unspecified && day_of_week == CronField::Unspecified {
+            return Err(Error::validation(
+                "'?' can only be used in one of day-of-month and day-of-week".to_string(),
+            ));
+        }
+
+        Ok(CronExpression {
+            seconds: Some(CronField::parse(fields[0], 0, 59)?),
+            minutes: CronField::parse(fields[1], 0, 59)?,
+            hours: CronField::parse(fields[2], 0, 23)?,
+            day_of_month,
+            month: CronField::parse(fields[4], 1, 12)?,
+            day_of_week,
+            year: fields
+                .get(6)
+                .map(|year| CronField::parse(year, 1970, 3000))
+                .transpose()?,
+        })
+    }
+
+    /// Format this expression as a 7-field Quartz expression
+    ///
+    /// Missing seconds become `0` and a missing year `*`. Quartz needs `?` in
+    /// one day field, so an unrestricted day-of-week (or else day-of-month)
+    /// is written as `?`. When both day fields are restricted they are
+    /// written as they are, which Quartz itself rejects.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::cron::CronExpression;
+    ///
+    /// let expr = CronExpression::parse("30 9 * * 0,6").unwrap();
+    /// assert_eq!(expr.to_quartz_string(), "0 30 9 ? * 1,7 *");
+    ///
+    /// let expr = CronExpression::parse("@monthly").unwrap();
+    /// assert_eq!(expr.to_quartz_string(), "0 0 0 1 * ? *");
+    /// ```
+    pub fn to_quartz_string(&self) -> String {
+        use CronField::{All, Unspecified};
+
+        let (day_of_month, day_of_week) = match (&self.day_of_month, &self.day_of_week) {
+            (Unspecified, day_of_week) => (self.day_of_month.to_string(), day_of_week.clone()),
+            (_, Unspecified | All) => (self.day_of_month.to_string(), Unspecified),
+            (All, day_of_week) => ("?".to_string(), day_of_week.clone()),
+            (_, day_of_week) => (self.day_of_month.to_string(), day_of_week.clone()),
+        };
+
+        format!(
+            "{} {} {} {} {} {} {}",
+            self.seconds
+                .as_ref()
+                .map_or("0".to_string(), ToString::to_string),
+            self.minutes,
+            self.hours,
+            day_of_month,
+            self.month,
+            day_of_week.map_weekdays(&|day| day % 7 + 1),
+            self.year
+                .as_ref()
+                .map_or("*".to_string(), ToString::to_string)
+        )
+    }
+
+    /// Format this expression as a standard 5-field Unix expression
+    ///
+    /// Seconds and year are dropped and `?` becomes `*`.
+    pub fn to_unix_string(&self) -> String {
+        let day = |field: &CronField| match field {
+            CronField::Unspecified => "*".to_string(),
+            field => field.to_string(),
+        };
+        format!(
+            "{} {} {} {} {}",
+            self.minutes,
+            self.hours,
+            day(&self.day_of_month),
+            self.month,
+            day(&self.day_of_week)
+        )
+    }
+
     /// Validate the cron expression
     pub fn validate(&self) -> Result<()> {
         // Validate each field
@@ -217,6 +377,46 @@ impl CronExpression {
         None
     }
 
+    /// Get the most recent execution time strictly before the given time
+    ///
+    /// Scans backward up to one year, mirroring [`next_execution`](Self::next_execution).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use yimi_rutool::cron::CronExpression;
+    ///
+    /// let expr = CronExpression::parse("0 9 * * 1").unwrap(); // Mondays at 9 AM
+    /// let wednesday = Utc.with_ymd_and_hms(2023, 10, 4, 12, 0, 0).unwrap();
+    /// let monday = Utc.with_ymd_and_hms(2023, 10, 2, 9, 0, 0).unwrap();
+    /// assert_eq!(expr.previous_execution(&wednesday), Some(monday));
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn previous_execution(&self, before: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut previous = *before - chrono::Duration::minutes(1);
+
+        // Truncate to minute precision if seconds are not specified; the
+        // minute containing `before` counts when `before` is past its start
+        if self.seconds.is_none() {
+            previous = before.with_second(0).unwrap().with_nanosecond(0).unwrap();
+            if previous >= *before {
+                previous -= chrono::Duration::minutes(1);
+            }
+        }
+
+        // Look for the previous matching time within a reasonable window
+        for _ in 0..366 * 24 * 60 {
+            // Max one year
+            if self.matches(&previous) {
+                return Some(previous);
+            }
+            previous -= chrono::Duration::minutes(1);
+        }
+
+        None
+    }
+
     /// Get all values that this field matches within its range
     pub fn get_matching_values(&self, field: &CronField, min: u32, max: u32) -> Vec<u32> {
         let mut values = Vec::new();
@@ -231,13 +431,26 @@ impl CronExpression {
 
 impl CronField {
     /// Parse a cron field from string
+    ///
+    /// Month names (`JAN`-`DEC`) are accepted when the bounds are those of
+    /// the month field (1-12), and weekday names (`SUN`-`SAT`) when they are
+    /// those of the day-of-week field (0-7). Names are case-insensitive.
     pub fn parse(field: &str, min: u32, max: u32) -> Result<Self> {
-        let field = field.trim();
+        let field = match (min, max) {
+            (1, 12) => replace_names(field.trim(), &MONTH_NAMES, 1),
+            (0, 7) => replace_names(field.trim(), &DAY_NAMES, 0),
+            _ => field.trim().to_string(),
+        };
+        let field = field.as_str();
 
         if field == "*" {
             return Ok(CronField::All);
         }
 
+        if field == "?" && matches!((min, max), (1, 31) | (0, 7)) {
+            return Ok(CronField::Unspecified);
+        }
+
         if field == "L" {
             return Ok(CronField::Last);
         }
@@ -385,7 +598,7 @@ impl CronField {
     /// Check if this field matches a given value
     pub fn matches(&self, value: u32) -> bool {
         match self {
-            CronField::All => true,
+            CronField::All | CronField::Unspecified => true,
             CronField::Value(v) => *v == value,
             CronField::List(values) => values.contains(&value),
             CronField::Range(start, end) => value >= *start && value <= *end,
@@ -410,6 +623,16 @@ impl CronField {
     pub fn validate(&self, min: u32, max: u32, field_name: &str) -> Result<()> {
         match self {
             CronField::All => Ok(()),
+            CronField::Unspecified => {
+                if field_name == "day_of_month" || field_name == "day_of_week" {
+                    Ok(())
+                } else {
+                    Err(Error::validation(format!(
+                        "'?' is not allowed in the {} field",
+                        field_name
+                    )))
+                }
+            }
             CronField::Value(v) => {
                 if *v < min || *v > max {
                     Err(Error::validation(format!(
@@ -494,7 +717,7 @@ impl CronField {
         let mut values = HashSet::new();
 
         match self {
-            CronField::All => {
+            CronField::All | CronField::Unspecified => {
                 for i in min..=max {
                     values.insert(i);
                 }
@@ -532,6 +755,94 @@ impl CronField {
 
         values
     }
+
+    /// Renumber the days of a day-of-week field
+    fn map_weekdays(&self, map: &dyn Fn(u32) -> u32) -> CronField {
+        let list = |days: &mut dyn Iterator<Item = u32>| {
+            let mut days: Vec<u32> = days.map(map).collect();
+            days.sort();
+            days.dedup();
+            CronField::List(days)
+        };
+
+        match self {
+            CronField::Value(day) => CronField::Value(map(*day)),
+            CronField::List(days) => list(&mut days.iter().copied()),
+            CronField::Range(start, end) => {
+                let (low, high) = (map(*start), map(*end));
+                // A range wrapping around the end of the week becomes a list
+                if low <= high && high - low == end - start {
+                    CronField::Range(low, high)
+                } else {
+                    list(&mut (*start..=*end))
+                }
+            }
+            CronField::Step(base, step) => CronField::Step(Box::new(base.map_weekdays(map)), *step),
+            CronField::LastWeekday(day) => CronField::LastWeekday(map(*day)),
+            CronField::NthWeekday(day, nth) => CronField::NthWeekday(map(*day), *nth),
+            field => field.clone(),
+        }
+    }
+}
+
+/// Parse a Quartz day-of-week field into the 0 (Sunday) based numbering
+fn parse_quartz_weekday(field: &str) -> Result<CronField> {
+    let field = field.trim();
+    if field == "?" {
+        return Ok(CronField::Unspecified);
+    }
+
+    let parsed = match CronField::parse(&replace_names(field, &DAY_NAMES, 1), 1, 7)? {
+        // A lone `L` is the last day of the week
+        CronField::Last => CronField::Value(7),
+        parsed => parsed,
+    };
+    parsed.validate(1, 7, "day_of_week")?;
+    if matches!(
+        parsed,
+        CronField::LastWeekday(0) | CronField::NthWeekday(0, _)
+    ) {
+        return Err(Error::validation(format!(
+            "Quartz day-of-week values run from 1 (SUN) to 7 (SAT): {}",
+            field
+        )));
+    }
+
+    Ok(parsed.map_weekdays(&|day| day - 1))
+}
+
+/// Replace each name from `names` with its number
+///
+/// Other letters, such as the `L` and `W` modifiers, are left in place.
+fn replace_names(field: &str, names: &[&str], first: u32) -> String {
+    let mut replaced = String::with_capacity(field.len());
+    let mut rest = field;
+    while let Some(start) = rest.find(|c: char| c.is_ascii_alphabetic()) {
+        replaced.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        // A weekday name may carry the `L` modifier, as in `FRIL`
+        let run = &rest[..end];
+        let (word, modifier) = match run.len() {
+            4 if run[3..].eq_ignore_ascii_case("L") => run.split_at(3),
+            _ => (run, ""),
+        };
+        match names
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(word))
+        {
+            Some(index) => {
+                replaced.push_str(&(first + index as u32).to_string());
+                replaced.push_str(modifier);
+            }
+            None => replaced.push_str(run),
+        }
+        rest = &rest[end..];
+    }
+    replaced.push_str(rest);
+    replaced
 }
 
 impl FromStr for CronExpression {
@@ -579,6 +890,7 @@ impl fmt::Display for CronField {
             CronField::Weekday(day) => write!(f, "{}W", day),
             CronField::LastWeekday(weekday) => write!(f, "{}L", weekday),
             CronField::NthWeekday(weekday, nth) => write!(f, "{}#{}", weekday, nth),
+            CronField::Unspecified => write!(f, "?"),
         }
     }
 }
@@ -757,6 +1069,34 @@ mod tests {
         assert!(!expr.matches(&tuesday_9am));
     }
 
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_previous_execution_weekly() {
+        use chrono::{TimeZone, Utc};
+
+        let expr = CronExpression::parse("0 9 * * 1").unwrap(); // Every Monday at 9 AM
+        let monday_9am = Utc.with_ymd_and_hms(2023, 10, 2, 9, 0, 0).unwrap();
+        let previous_monday_9am = Utc.with_ymd_and_hms(2023, 9, 25, 9, 0, 0).unwrap();
+
+        // Later in the same week
+        let thursday = Utc.with_ymd_and_hms(2023, 10, 5, 18, 30, 0).unwrap();
+        assert_eq!(expr.previous_execution(&thursday), Some(monday_9am));
+
+        // Strictly before: the exact fire time yields the week before
+        assert_eq!(
+            expr.previous_execution(&monday_9am),
+            Some(previous_monday_9am)
+        );
+
+        // Seconds into the fire minute still count
+        let just_after = Utc.with_ymd_and_hms(2023, 10, 2, 9, 0, 30).unwrap();
+        assert_eq!(expr.previous_execution(&just_after), Some(monday_9am));
+
+        // Symmetric with next_execution
+        let next = expr.next_execution(&thursday).unwrap();
+        assert_eq!(expr.previous_execution(&next), Some(monday_9am));
+    }
+
     #[test]
     fn test_display() {
         let expr = CronExpression::parse("*/5 0 1-15 * 1,3,5").unwrap();
@@ -766,6 +1106,182 @@ mod tests {
         assert!(displayed.contains("1,3,5"));
     }
 
+    #[test]
+    fn test_named_months_and_weekdays() {
+        let expr = CronExpression::parse("0 0 * * MON-FRI").unwrap();
+        assert_eq!(expr.day_of_week, CronField::Range(1, 5));
+        assert_eq!(expr, CronExpression::parse("0 0 * * 1-5").unwrap());
+
+        let expr = CronExpression::parse("0 12 1 jan,Jul * ").unwrap();
+        assert_eq!(expr.month, CronField::List(vec![1, 7]));
+
+        let expr = CronExpression::parse("0 0 * MAR-NOV/2 sun").unwrap();
+        assert_eq!(
+            expr.month,
+            CronField::Step(Box::new(CronField::Range(3, 11)), 2)
+        );
+        assert_eq!(expr.day_of_week, CronField::Value(0));
+
+        // Modifiers keep working next to names
+        assert_eq!(
+            CronField::parse("FRIL", 0, 7).unwrap(),
+            CronField::LastWeekday(5)
+        );
+        assert_eq!(
+            CronField::parse("MON#2", 0, 7).unwrap(),
+            CronField::NthWeekday(1, 2)
+        );
+
+        // Names are only valid in their own field
+        assert!(CronExpression::parse("0 0 * MON *").is_err());
+        assert!(CronExpression::parse("0 0 * * JAN").is_err());
+        assert!(CronExpression::parse("0 0 MON * *").is_err());
+        assert!(CronExpression::parse("0 0 * * FOO").is_err());
+        assert!(CronExpression::parse("0 0 * * SUNMON").is_err());
+    }
+
+    #[test]
+    fn test_macros() {
+        let daily = CronExpression::parse("@daily").unwrap();
+        assert_eq!(daily, CronExpression::parse("0 0 * * *").unwrap());
+        assert_eq!(daily, CronExpression::parse("@MIDNIGHT").unwrap());
+
+        assert_eq!(
+            CronExpression::parse("@yearly").unwrap(),
+            CronExpression::parse("0 0 1 1 *").unwrap()
+        );
+        assert_eq!(
+            CronExpression::parse("@annually").unwrap(),
+            CronExpression::parse("@yearly").unwrap()
+        );
+        assert_eq!(
+            CronExpression::parse("@monthly").unwrap(),
+            CronExpression::parse("0 0 1 * *").unwrap()
+        );
+        assert_eq!(
+            CronExpression::parse("@weekly").unwrap(),
+            CronExpression::parse("0 0 * * 0").unwrap()
+        );
+        assert_eq!(
+            CronExpression::parse(" @hourly ").unwrap(),
+            CronExpression::parse("0 * * * *").unwrap()
+        );
+        assert!(CronExpression::parse("@reboot").is_err());
+    }
+
+    #[test]
+    fn test_parse_quartz() {
+        let expr = CronExpression::parse_quartz("0 0 12 ? * MON-FRI").unwrap();
+        assert_eq!(expr.seconds, Some(CronField::Value(0)));
+        assert_eq!(expr.minutes, CronField::Value(0));
+        assert_eq!(expr.hours, CronField::Value(12));
+        assert_eq!(expr.day_of_month, CronField::Unspecified);
+        assert_eq!(expr.day_of_week, CronField::Range(1, 5));
+        assert_eq!(expr.year, None);
+        assert_eq!(expr.to_quartz_string(), "0 0 12 ? * 2-6 *");
+        assert_eq!(expr.to_unix_string(), "0 12 * * 1-5");
+        assert!(expr.validate().is_ok());
+
+        // Quartz numbers the week from Sunday = 1
+        let expr = CronExpression::parse_quartz("0 0 8 ? * 1,7 2030").unwrap();
+        assert_eq!(expr.day_of_week, CronField::List(vec![0, 6]));
+        assert_eq!(expr.year, Some(CronField::Value(2030)));
+        // A lone L is Saturday
+        assert_eq!(
+            CronExpression::parse_quartz("0 0 8 ? * L")
+                .unwrap()
+                .day_of_week,
+            CronField::Value(6)
+        );
+        assert_eq!(
+            CronExpression::parse_quartz("0 0 8 ? * 6#3")
+                .unwrap()
+                .day_of_week,
+            CronField::NthWeekday(5, 3)
+        );
+        assert_eq!(
+            CronExpression::parse_quartz("0 0 8 ? * 2-6/2")
+                .unwrap()
+                .day_of_week,
+            CronField::Step(Box::new(CronField::Range(1, 5)), 2)
+        );
+
+        let expr = CronExpression::parse_quartz("0 15 10 L * ?").unwrap();
+        assert_eq!(expr.day_of_month, CronField::Last);
+        assert_eq!(expr.day_of_week, CronField::Unspecified);
+        assert_eq!(expr.to_quartz_string(), "0 15 10 L * ? *");
+
+        // Quartz output parses back to the same expression
+        for quartz in [
+            "0 0 12 ? * 2-6 *",
+            "30 */5 * 1,15 * ? 2025",
+            "0 0 0 ? * 1#2 *",
+        ] {
+            let expr = CronExpression::parse_quartz(quartz).unwrap();
+            assert_eq!(expr.to_quartz_string(), quartz);
+        }
+
+        assert!(CronExpression::parse_quartz("0 12 ? * MON").is_err());
+        assert!(CronExpression::parse_quartz("0 0 12 ? * ?").is_err());
+        assert!(CronExpression::parse_quartz("0 0 12 ? * 0").is_err());
+        assert!(CronExpression::parse_quartz("0 0 12 ? * 8").is_err());
+        assert!(CronExpression::parse_quartz("0 0 12 ? * 0L").is_err());
+        assert!(CronExpression::parse_quartz("0 ? 12 1 * ?").is_err());
+    }
+
+    #[test]
+    fn test_unix_to_quartz() {
+        // Unix 0 and 7 are both Sunday
+        let expr = CronExpression::parse("30 9 * * 5-7").unwrap();
+        assert_eq!(expr.to_quartz_string(), "0 30 9 ? * 1,6,7 *");
+        assert_eq!(
+            CronExpression::parse("0 0 1 * *")
+                .unwrap()
+                .to_quartz_string(),
+            "0 0 0 1 * ? *"
+        );
+        assert_eq!(
+            CronExpression::parse("0 0 * * *")
+                .unwrap()
+                .to_quartz_string(),
+            "0 0 0 * * ? *"
+        );
+        assert_eq!(
+            CronExpression::parse("*/10 * * * * *")
+                .unwrap()
+                .to_quartz_string(),
+            "*/10 * * * * ? *"
+        );
+
+        // `?` is accepted in the day fields of regular expressions too
+        let expr = CronExpression::parse("0 12 ? * 1").unwrap();
+        assert_eq!(expr.day_of_month, CronField::Unspecified);
+        assert_eq!(expr.to_unix_string(), "0 12 * * 1");
+        assert!(CronExpression::parse("? 12 * * 1").is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_quartz_matches() {
+        use chrono::{TimeZone, Utc};
+
+        let expr = CronExpression::parse_quartz("0 0 12 ? * MON-FRI").unwrap();
+        // October 2, 2023 was a Monday
+        assert!(expr.matches(&Utc.with_ymd_and_hms(2023, 10, 2, 12, 0, 0).unwrap()));
+        assert!(expr.matches(&Utc.with_ymd_and_hms(2023, 10, 6, 12, 0, 0).unwrap()));
+        assert!(!expr.matches(&Utc.with_ymd_and_hms(2023, 10, 7, 12, 0, 0).unwrap()));
+        assert!(!expr.matches(&Utc.with_ymd_and_hms(2023, 10, 2, 12, 0, 1).unwrap()));
+
+        let saturday = Utc.with_ymd_and_hms(2023, 10, 7, 12, 0, 0).unwrap();
+        let monday = Utc.with_ymd_and_hms(2023, 10, 9, 12, 0, 0).unwrap();
+        assert_eq!(expr.next_execution(&saturday), Some(monday));
+
+        // `?` in the day-of-week field leaves matching to the day of month
+        let expr = CronExpression::parse_quartz("0 0 0 15 * ?").unwrap();
+        assert!(expr.matches(&Utc.with_ymd_and_hms(2023, 10, 15, 0, 0, 0).unwrap()));
+        assert!(!expr.matches(&Utc.with_ymd_and_hms(2023, 10, 16, 0, 0, 0).unwrap()));
+    }
+
     #[test]
     fn test_error_cases() {
         // Invalid number of fields
@@ -10,8 +10,12 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+
 #[cfg(feature = "tokio")]
 use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
 
 /// Type alias for sync job functions
 pub type SyncJobFn = dyn Fn() -> Result<()> + Send + Sync + 'static;
@@ -20,6 +24,12 @@ pub type SyncJobFn = dyn Fn() -> Result<()> + Send + Sync + 'static;
 pub type AsyncJobFn =
     dyn Fn() -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'static>> + Send + Sync + 'static;
 
+/// Type alias for async job functions that observe a cancellation token
+pub type CancellableJobFn = dyn Fn(CancellationToken) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'static>>
+    + Send
+    + Sync
+    + 'static;
+
 /// A job that can be scheduled and executed by the scheduler
 #[derive(Clone)]
 pub struct Job {
@@ -40,6 +50,8 @@ enum JobFunction {
     Sync(Arc<SyncJobFn>),
     /// Asynchronous job function
     Async(Arc<AsyncJobFn>),
+    /// Asynchronous job function that receives a cancellation token
+    Cancellable(Arc<CancellableJobFn>),
 }
 
 /// Metadata associated with a job
@@ -55,8 +67,81 @@ pub struct JobMetadata {
     pub max_retries: u32,
     /// Whether to retry on failure
     pub retry_on_failure: bool,
+    /// Delay between retry attempts
+    pub backoff: Backoff,
     /// Custom tags for job organization
     pub tags: Vec<String>,
+    /// What to do when the job is due while a previous run is still going
+    pub overlap_policy: OverlapPolicy,
+    /// What to do about scheduled times missed while the scheduler was stopped
+    pub misfire_policy: MisfirePolicy,
+    /// When the job last ran, e.g. as recorded before a restart
+    #[cfg(feature = "chrono")]
+    pub last_run: Option<DateTime<Utc>>,
+}
+
+/// Delay between retries of a failed job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backoff {
+    /// Wait the same time before every retry
+    Fixed(Duration),
+    /// Wait the given time before the first retry, doubling it each time
+    Exponential(Duration),
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        // 1s, 2s, 4s, 8s, ...
+        Backoff::Exponential(Duration::from_secs(1))
+    }
+}
+
+impl Backoff {
+    /// Delay to wait after the given failed attempt (starting at 1)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::cron::Backoff;
+    /// use std::time::Duration;
+    ///
+    /// let backoff = Backoff::Exponential(Duration::from_millis(100));
+    /// assert_eq!(backoff.delay(1), Duration::from_millis(100));
+    /// assert_eq!(backoff.delay(3), Duration::from_millis(400));
+    /// ```
+    pub fn delay(&self, attempt: u32) -> Duration {
+        match *self {
+            Backoff::Fixed(delay) => delay,
+            Backoff::Exponential(initial) => {
+                let factor = 2_u32.saturating_pow(attempt.saturating_sub(1));
+                initial.saturating_mul(factor)
+            }
+        }
+    }
+}
+
+/// How the scheduler handles a job that comes due while it is still running
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverlapPolicy {
+    /// Skip the run; the job fires again at its next scheduled time
+    #[default]
+    Skip,
+    /// Run again as soon as the current run finishes
+    Queue,
+    /// Start another run concurrently with the current one
+    Allow,
+}
+
+/// How the scheduler handles scheduled times that passed while it was stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MisfirePolicy {
+    /// Run once when the scheduler starts, however many times were missed
+    #[default]
+    FireImmediately,
+    /// Drop the missed runs and wait for the next scheduled time
+    SkipToNext,
+    /// Run once for every missed time, one after another
+    FireAll,
 }
 
 impl Default for JobMetadata {
@@ -67,7 +152,12 @@ impl Default for JobMetadata {
             timeout: Some(Duration::from_secs(300)), // 5 minutes default
             max_retries: 0,
             retry_on_failure: false,
+            backoff: Backoff::default(),
             tags: Vec::new(),
+            overlap_policy: OverlapPolicy::default(),
+            misfire_policy: MisfirePolicy::default(),
+            #[cfg(feature = "chrono")]
+            last_run: None,
         }
     }
 }
@@ -149,6 +239,34 @@ impl Job {
         }
     }
 
+    /// Create a new asynchronous job that can be cancelled
+    ///
+    /// The function receives a [`CancellationToken`] which the scheduler
+    /// cancels on [`Scheduler::shutdown_now`](crate::cron::Scheduler::shutdown_now).
+    /// Long-running jobs should check it and return early.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::cron::{CancellationToken, Job};
+    /// use std::time::Duration;
+    ///
+    /// let job = Job::new_cancellable("poller", Box::new(|token: CancellationToken| async move {
+    ///     while !token.is_cancelled() {
+    ///         // Poll for work here
+    ///         tokio::time::sleep(Duration::from_millis(100)).await;
+    ///     }
+    ///     Ok(())
+    /// }));
+    /// ```
+    pub fn new_cancellable<F, Fut>(name: &str, job_fn: Box<F>) -> Self
+    where
+        F: Fn(CancellationToken) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        JobBuilder::new(name).build_cancellable(job_fn)
+    }
+
     /// Set the job description
     pub fn with_description(mut self, description: &str) -> Self {
         self.description = Some(description.to_string());
@@ -180,6 +298,18 @@ impl Job {
         self
     }
 
+    /// Set how overlapping runs are handled
+    pub fn with_overlap_policy(mut self, policy: OverlapPolicy) -> Self {
+        self.metadata.overlap_policy = policy;
+        self
+    }
+
+    /// Set how missed scheduled times are handled
+    pub fn with_misfire_policy(mut self, policy: MisfirePolicy) -> Self {
+        self.metadata.misfire_policy = policy;
+        self
+    }
+
     /// Add tags to the job
     pub fn with_tags(mut self, tags: Vec<String>) -> Self {
         self.metadata.tags = tags;
@@ -195,6 +325,14 @@ impl Job {
     /// Execute the job
     #[cfg(feature = "tokio")]
     pub async fn execute(&self) -> Result<()> {
+        self.execute_with_token(CancellationToken::new()).await
+    }
+
+    /// Execute the job, handing `token` to cancellable job functions
+    ///
+    /// Sync and plain async jobs ignore the token and always run to completion.
+    #[cfg(feature = "tokio")]
+    pub async fn execute_with_token(&self, token: CancellationToken) -> Result<()> {
         let start_time = Instant::now();
 
         let result = match &self.job_fn {
@@ -209,6 +347,7 @@ impl Job {
                 // Execute async job directly
                 job_fn().await
             }
+            JobFunction::Cancellable(job_fn) => job_fn(token).await,
         };
 
         // Apply timeout if specified
@@ -228,43 +367,41 @@ impl Job {
     #[cfg(feature = "tokio")]
     pub async fn execute_with_retries(&self) -> JobResult {
         let started_at = Instant::now();
-        let mut last_error = None;
-
-        for attempt in 0..=self.metadata.max_retries {
-            let _attempt_start = Instant::now();
-
-            match self.execute().await {
-                Ok(_) => {
-                    return JobResult {
-                        success: true,
-                        error: None,
-                        duration: started_at.elapsed(),
-                        started_at,
-                        completed_at: Instant::now(),
-                    };
-                }
-                Err(e) => {
-                    last_error = Some(e.to_string());
-
-                    // Don't sleep after the last attempt
-                    if attempt < self.metadata.max_retries {
-                        // Exponential backoff: 1s, 2s, 4s, 8s, ...
-                        let delay = Duration::from_secs(2_u64.pow(attempt));
-                        tokio::time::sleep(delay).await;
-                    }
-                }
-            }
-        }
+        let (result, _) = self.run_with_retries(CancellationToken::new()).await;
 
         JobResult {
-            success: false,
-            error: last_error,
+            success: result.is_ok(),
+            error: result.err().map(|e| e.to_string()),
             duration: started_at.elapsed(),
             started_at,
             completed_at: Instant::now(),
         }
     }
 
+    /// Execute the job, retrying failures according to its metadata
+    ///
+    /// Returns the final result and the number of attempts made. Waiting
+    /// between attempts stops early if `token` is cancelled.
+    #[cfg(feature = "tokio")]
+    pub(crate) async fn run_with_retries(&self, token: CancellationToken) -> (Result<()>, u32) {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let result = self.execute_with_token(token.clone()).await;
+
+            // Don't sleep after the last attempt
+            if result.is_ok() || attempt > self.metadata.max_retries {
+                return (result, attempt);
+            }
+
+            let delay = self.metadata.backoff.delay(attempt);
+            tokio::select! {
+                () = tokio::time::sleep(delay) => {}
+                () = token.cancelled() => return (result, attempt),
+            }
+        }
+    }
+
     /// Get job metadata
     pub fn metadata(&self) -> &JobMetadata {
         &self.metadata
@@ -377,6 +514,78 @@ impl JobBuilder {
         self
     }
 
+    /// Set how overlapping runs are handled
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::cron::{JobBuilder, OverlapPolicy};
+    ///
+    /// let job = JobBuilder::new("report")
+    ///     .overlap_policy(OverlapPolicy::Queue)
+    ///     .build_sync(Box::new(|| Ok(())));
+    /// assert_eq!(job.metadata().overlap_policy, OverlapPolicy::Queue);
+    /// ```
+    pub fn overlap_policy(mut self, policy: OverlapPolicy) -> Self {
+        self.metadata.overlap_policy = policy;
+        self
+    }
+
+    /// Set how missed scheduled times are handled
+    pub fn misfire_policy(mut self, policy: MisfirePolicy) -> Self {
+        self.metadata.misfire_policy = policy;
+        self
+    }
+
+    /// Set when the job last ran, e.g. as persisted by a previous process
+    ///
+    /// The scheduler counts the scheduled times after `last_run` as missed
+    /// and handles them according to the job's [`MisfirePolicy`] when it
+    /// starts.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use chrono::{Duration, Utc};
+    /// use yimi_rutool::cron::{JobBuilder, MisfirePolicy};
+    ///
+    /// let last_run = Utc::now() - Duration::hours(3);
+    /// let job = JobBuilder::new("report")
+    ///     .misfire_policy(MisfirePolicy::FireAll)
+    ///     .last_run(last_run)
+    ///     .build_sync(Box::new(|| Ok(())));
+    /// assert_eq!(job.metadata().last_run, Some(last_run));
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn last_run(mut self, last_run: DateTime<Utc>) -> Self {
+        self.metadata.last_run = Some(last_run);
+        self
+    }
+
+    /// Retry a failing run up to `max_attempts` attempts in total
+    ///
+    /// The run only counts as failed once every attempt has failed. Waiting
+    /// between attempts happens on the async runtime and does not hold up
+    /// other jobs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::cron::{Backoff, JobBuilder};
+    /// use std::time::Duration;
+    ///
+    /// let job = JobBuilder::new("upload")
+    ///     .retry(3, Backoff::Exponential(Duration::from_secs(5)))
+    ///     .build_sync(Box::new(|| Ok(())));
+    /// assert_eq!(job.metadata().max_retries, 2);
+    /// ```
+    pub fn retry(mut self, max_attempts: u32, backoff: Backoff) -> Self {
+        self.metadata.max_retries = max_attempts.saturating_sub(1);
+        self.metadata.retry_on_failure = self.metadata.max_retries > 0;
+        self.metadata.backoff = backoff;
+        self
+    }
+
     /// Add a tag to the job
     pub fn tag(mut self, tag: &str) -> Self {
         self.metadata.tags.push(tag.to_string());
@@ -421,6 +630,26 @@ impl JobBuilder {
             metadata: self.metadata,
         }
     }
+
+    /// Build an asynchronous job that receives a cancellation token
+    pub fn build_cancellable<F, Fut>(self, job_fn: Box<F>) -> Job
+    where
+        F: Fn(CancellationToken) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        let cancellable_fn = Arc::new(
+            move |token| -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'static>> {
+                Box::pin(job_fn(token))
+            },
+        );
+
+        Job {
+            name: self.name,
+            description: self.description,
+            job_fn: JobFunction::Cancellable(cancellable_fn),
+            metadata: self.metadata,
+        }
+    }
 }
 
 /// Job registry for managing and organizing jobs
@@ -633,6 +862,21 @@ mod tests {
         assert_eq!(counter.load(Ordering::SeqCst), 1);
     }
 
+    #[tokio::test]
+    async fn test_job_cancellable_execution() {
+        let job = Job::new_cancellable(
+            "cancellable_job",
+            Box::new(|token: CancellationToken| async move {
+                token.cancelled().await;
+                Err(Error::custom("Cancelled".to_string()))
+            }),
+        );
+
+        let token = CancellationToken::new();
+        token.cancel();
+        assert!(job.execute_with_token(token).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_job_retries() {
         let attempt_count = Arc::new(AtomicU32::new(0));
@@ -13,5 +13,6 @@ pub mod scheduler;
 
 /// Re-export commonly used types for convenience
 pub use cron_parser::{CronExpression, CronField};
-pub use job::{Job, JobBuilder};
-pub use scheduler::{Scheduler, SchedulerConfig, TaskHandle};
+pub use job::{Backoff, Job, JobBuilder, MisfirePolicy, OverlapPolicy};
+pub use scheduler::{JobStatus, Scheduler, SchedulerConfig, TaskHandle};
+pub use tokio_util::sync::CancellationToken;
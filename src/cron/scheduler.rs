@@ -4,7 +4,7 @@
 //! jobs based on cron expressions with support for async operations.
 
 use crate::cron::cron_parser::CronExpression;
-use crate::cron::job::Job;
+use crate::cron::job::{Job, MisfirePolicy, OverlapPolicy};
 use crate::error::{Error, Result};
 use std::collections::HashMap;
 use std::fmt;
@@ -21,6 +21,10 @@ use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
 #[cfg(feature = "tokio")]
 use tokio::time::interval;
+use tokio_util::sync::CancellationToken;
+
+/// Upper bound on the missed runs replayed for a [`MisfirePolicy::FireAll`] job
+const MAX_MISFIRES: usize = 1000;
 
 /// A task scheduler that manages and executes cron jobs
 #[derive(Debug)]
@@ -39,6 +43,8 @@ pub struct Scheduler {
     /// Shutdown signal sender
     #[cfg(feature = "tokio")]
     shutdown_tx: Option<mpsc::UnboundedSender<()>>,
+    /// Token handed to running jobs, cancelled by `shutdown_now`
+    cancel_token: CancellationToken,
 }
 
 /// Configuration for the scheduler
@@ -84,8 +90,52 @@ struct ScheduledJob {
     enabled: bool,
     /// Number of times this job has been executed
     execution_count: u64,
-    /// Whether this job is currently running
-    is_running: bool,
+    /// Number of runs of this job currently executing
+    running: usize,
+    /// Number of runs waiting for the current one to finish
+    queued: usize,
+    /// Outcomes of finished runs
+    stats: RunStats,
+}
+
+/// Outcomes of a job's finished runs
+#[derive(Debug, Default)]
+struct RunStats {
+    /// Duration of the last finished run, including retries
+    last_duration: Option<Duration>,
+    /// Attempts made by the last finished run
+    last_attempts: Option<u32>,
+    /// Whether the last finished run succeeded
+    last_success: Option<bool>,
+    /// Error returned by the last finished run, if it failed
+    last_error: Option<String>,
+    /// Failed runs since the last successful one
+    consecutive_failures: u32,
+    /// Total successful runs
+    success_count: u64,
+    /// Total failed runs
+    failure_count: u64,
+}
+
+impl RunStats {
+    /// Record the outcome of a finished run
+    fn record(&mut self, duration: Duration, attempts: u32, result: &Result<()>) {
+        self.last_duration = Some(duration);
+        self.last_attempts = Some(attempts);
+        self.last_success = Some(result.is_ok());
+        match result {
+            Ok(()) => {
+                self.last_error = None;
+                self.consecutive_failures = 0;
+                self.success_count += 1;
+            }
+            Err(e) => {
+                self.last_error = Some(e.to_string());
+                self.consecutive_failures += 1;
+                self.failure_count += 1;
+            }
+        }
+    }
 }
 
 /// Handle to a scheduled task that can be used to control it
@@ -136,6 +186,7 @@ impl Scheduler {
             task_handle: None,
             #[cfg(feature = "tokio")]
             shutdown_tx: None,
+            cancel_token: CancellationToken::new(),
         }
     }
 
@@ -170,8 +221,12 @@ impl Scheduler {
         // Validate the cron expression
         cron_expr.validate()?;
 
+        // A job that ran before is next due at the first time after that
+        // run, which may already have passed
+        #[cfg(feature = "chrono")]
+        let last_run = job.metadata().last_run;
         #[cfg(feature = "chrono")]
-        let next_run = cron_expr.next_execution(&Utc::now());
+        let next_run = cron_expr.next_execution(&last_run.unwrap_or_else(Utc::now));
 
         let scheduled_job = ScheduledJob {
             id: job_id.clone(),
@@ -180,10 +235,12 @@ impl Scheduler {
             #[cfg(feature = "chrono")]
             next_run,
             #[cfg(feature = "chrono")]
-            last_run: None,
+            last_run,
             enabled: true,
             execution_count: 0,
-            is_running: false,
+            running: 0,
+            queued: 0,
+            stats: RunStats::default(),
         };
 
         if let Ok(mut jobs) = self.jobs.lock() {
@@ -214,6 +271,9 @@ impl Scheduler {
 
     /// Start the scheduler
     ///
+    /// Jobs whose scheduled time passed while the scheduler was stopped are
+    /// handled according to their [`MisfirePolicy`].
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -239,7 +299,12 @@ impl Scheduler {
             ));
         }
 
+        self.apply_misfire_policies(Utc::now())?;
+
         self.is_running.store(true, Ordering::SeqCst);
+        if self.cancel_token.is_cancelled() {
+            self.cancel_token = CancellationToken::new();
+        }
 
         let (shutdown_tx, mut shutdown_rx) = mpsc::unbounded_channel();
         self.shutdown_tx = Some(shutdown_tx);
@@ -248,6 +313,7 @@ impl Scheduler {
         let is_running = self.is_running.clone();
         let tick_interval = self.config.tick_interval;
         let run_missed_jobs = self.config.run_missed_jobs;
+        let cancel_token = self.cancel_token.clone();
 
         let task_handle = tokio::spawn(async move {
             let mut interval = interval(tick_interval);
@@ -260,7 +326,7 @@ impl Scheduler {
                         }
 
                         // Check for jobs to execute
-                        Self::check_and_execute_jobs(&jobs, run_missed_jobs).await;
+                        Self::check_and_execute_jobs(&jobs, run_missed_jobs, &is_running, &cancel_token);
                     }
                     _ = shutdown_rx.recv() => {
                         break;
@@ -273,7 +339,59 @@ impl Scheduler {
         Ok(())
     }
 
+    /// Reschedule jobs that missed one or more scheduled times before `now`
+    ///
+    /// Missed times are counted from each job's next run, which starts at
+    /// the first time after its last run (see
+    /// [`JobBuilder::last_run`](crate::cron::JobBuilder::last_run)).
+    /// Jobs that should still fire are made due immediately, so they run on
+    /// the first tick regardless of `run_missed_jobs`; extra runs for
+    /// [`MisfirePolicy::FireAll`] are queued behind the first one.
+    fn apply_misfire_policies(&self, now: DateTime<Utc>) -> Result<()> {
+        let mut jobs = self
+            .jobs
+            .lock()
+            .map_err(|_| Error::concurrency("Failed to acquire jobs lock".to_string()))?;
+
+        for scheduled_job in jobs.values_mut() {
+            let Some(next_run) = scheduled_job.next_run else {
+                continue;
+            };
+            if !scheduled_job.enabled || next_run > now {
+                continue;
+            }
+
+            match scheduled_job.job.metadata().misfire_policy {
+                MisfirePolicy::FireImmediately => {
+                    scheduled_job.next_run = Some(now);
+                }
+                MisfirePolicy::SkipToNext => {
+                    scheduled_job.next_run = scheduled_job.cron_expr.next_execution(&now);
+                }
+                MisfirePolicy::FireAll => {
+                    let mut missed = 0;
+                    let mut slot = Some(next_run);
+                    while let Some(time) = slot
+                        && time <= now
+                        && missed < MAX_MISFIRES
+                    {
+                        missed += 1;
+                        slot = scheduled_job.cron_expr.next_execution(&time);
+                    }
+                    scheduled_job.next_run = Some(now);
+                    scheduled_job.queued += missed - 1;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Stop the scheduler
+    ///
+    /// No new jobs are fired after this returns, but jobs that are already
+    /// running are left to finish in the background. Use
+    /// [`shutdown`](Self::shutdown) to wait for them.
     #[cfg(feature = "tokio")]
     pub async fn stop(&mut self) -> Result<()> {
         if !self.is_running.load(Ordering::SeqCst) {
@@ -295,11 +413,90 @@ impl Scheduler {
         Ok(())
     }
 
-    /// Check for jobs that need to be executed and run them
+    /// Stop firing jobs and wait for running jobs to finish
+    ///
+    /// Waits at most `timeout` for in-flight jobs to complete and returns the
+    /// IDs of the jobs that were still running when it gave up. An empty
+    /// list means every job finished. Outstanding jobs are not cancelled;
+    /// call [`shutdown_now`](Self::shutdown_now) afterwards to signal them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::cron::Scheduler;
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut scheduler = Scheduler::new();
+    ///     scheduler.start().await?;
+    ///
+    ///     let outstanding = scheduler.shutdown(Duration::from_secs(30)).await;
+    ///     assert!(outstanding.is_empty());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn shutdown(&self, timeout: Duration) -> Vec<String> {
+        self.stop_firing();
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            let running = self.running_job_ids();
+            let now = Instant::now();
+            if running.is_empty() || now >= deadline {
+                return running;
+            }
+            tokio::time::sleep((deadline - now).min(Duration::from_millis(10))).await;
+        }
+    }
+
+    /// Stop firing jobs and cancel the ones that are running
+    ///
+    /// Cancels the [`CancellationToken`] passed to jobs created with
+    /// [`Job::new_cancellable`] and returns the IDs of the jobs that were
+    /// running at that moment. Cancellation is cooperative: sync jobs and
+    /// jobs that ignore the token keep running until they return.
+    #[cfg(feature = "tokio")]
+    pub fn shutdown_now(&self) -> Vec<String> {
+        self.stop_firing();
+        self.cancel_token.cancel();
+        self.running_job_ids()
+    }
+
+    /// Stop the scheduler loop without waiting for it
+    #[cfg(feature = "tokio")]
+    fn stop_firing(&self) {
+        self.is_running.store(false, Ordering::SeqCst);
+        if let Some(shutdown_tx) = &self.shutdown_tx {
+            let _ = shutdown_tx.send(());
+        }
+    }
+
+    /// IDs of jobs that are currently executing, sorted
+    fn running_job_ids(&self) -> Vec<String> {
+        let mut running: Vec<String> = match self.jobs.lock() {
+            Ok(jobs) => jobs
+                .values()
+                .filter(|job| job.running > 0)
+                .map(|job| job.id.clone())
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        running.sort();
+        running
+    }
+
+    /// Check for jobs that need to be executed and spawn them
+    ///
+    /// Jobs run detached from the scheduler loop so a slow job does not
+    /// delay the next tick.
     #[cfg(feature = "tokio")]
-    async fn check_and_execute_jobs(
+    fn check_and_execute_jobs(
         jobs: &Arc<Mutex<HashMap<String, ScheduledJob>>>,
         run_missed_jobs: bool,
+        is_running: &Arc<AtomicBool>,
+        cancel_token: &CancellationToken,
     ) {
         let now = Utc::now();
         let mut jobs_to_execute = Vec::new();
@@ -307,7 +504,7 @@ impl Scheduler {
         // Collect jobs that need to be executed
         if let Ok(mut jobs_guard) = jobs.lock() {
             for (job_id, scheduled_job) in jobs_guard.iter_mut() {
-                if !scheduled_job.enabled || scheduled_job.is_running {
+                if !scheduled_job.enabled {
                     continue;
                 }
 
@@ -319,51 +516,85 @@ impl Scheduler {
                         next_run <= now && (now - next_run).num_seconds() < 60 // Within 1 minute
                     };
 
-                    if should_run {
-                        scheduled_job.is_running = true;
-                        scheduled_job.last_run = Some(now);
-                        scheduled_job.execution_count += 1;
-                        scheduled_job.next_run = scheduled_job.cron_expr.next_execution(&now);
-
-                        jobs_to_execute.push((job_id.clone(), scheduled_job.job.clone()));
+                    if !should_run {
+                        continue;
+                    }
+                    scheduled_job.next_run = scheduled_job.cron_expr.next_execution(&now);
+
+                    if scheduled_job.running > 0 {
+                        match scheduled_job.job.metadata().overlap_policy {
+                            OverlapPolicy::Skip => continue,
+                            OverlapPolicy::Queue => {
+                                scheduled_job.queued += 1;
+                                continue;
+                            }
+                            OverlapPolicy::Allow => {}
+                        }
                     }
+
+                    scheduled_job.running += 1;
+                    scheduled_job.last_run = Some(now);
+                    scheduled_job.execution_count += 1;
+
+                    jobs_to_execute.push((job_id.clone(), scheduled_job.job.clone()));
                 }
             }
         }
 
         // Execute jobs concurrently
-        let mut handles = Vec::new();
         for (job_id, job) in jobs_to_execute {
-            let jobs_ref = jobs.clone();
-            let handle = tokio::spawn(async move {
+            Self::spawn_run(jobs.clone(), job_id, job, is_running.clone(), cancel_token);
+        }
+    }
+
+    /// Run a job in the background, then any runs queued behind it
+    ///
+    /// Queued runs are dropped once the scheduler stops firing.
+    #[cfg(feature = "tokio")]
+    fn spawn_run(
+        jobs: Arc<Mutex<HashMap<String, ScheduledJob>>>,
+        job_id: String,
+        job: Job,
+        is_running: Arc<AtomicBool>,
+        cancel_token: &CancellationToken,
+    ) {
+        let cancel_token = cancel_token.clone();
+        tokio::spawn(async move {
+            loop {
                 let start_time = Instant::now();
-                let result = job.execute().await;
+                let (result, attempts) = job.run_with_retries(cancel_token.child_token()).await;
                 let duration = start_time.elapsed();
 
-                // Mark job as not running
-                if let Ok(mut jobs_guard) = jobs_ref.lock() {
-                    if let Some(scheduled_job) = jobs_guard.get_mut(&job_id) {
-                        scheduled_job.is_running = false;
-                    }
-                }
-
                 // Log execution result
-                match result {
-                    Ok(_) => {
+                match &result {
+                    Ok(()) => {
                         println!("Job {} completed successfully in {:?}", job_id, duration);
                     }
                     Err(e) => {
                         eprintln!("Job {} failed: {} (duration: {:?})", job_id, e, duration);
                     }
                 }
-            });
-            handles.push(handle);
-        }
 
-        // Wait for all jobs to complete
-        for handle in handles {
-            let _ = handle.await;
-        }
+                // Record the outcome, then mark job as not running or start
+                // the next queued run
+                let Ok(mut jobs_guard) = jobs.lock() else {
+                    break;
+                };
+                let Some(scheduled_job) = jobs_guard.get_mut(&job_id) else {
+                    break;
+                };
+                scheduled_job.stats.record(duration, attempts, &result);
+                if scheduled_job.queued > 0 && is_running.load(Ordering::SeqCst) {
+                    scheduled_job.queued -= 1;
+                    scheduled_job.last_run = Some(Utc::now());
+                    scheduled_job.execution_count += 1;
+                } else {
+                    scheduled_job.queued = 0;
+                    scheduled_job.running -= 1;
+                    break;
+                }
+            }
+        });
     }
 
     /// Get information about all scheduled jobs
@@ -381,7 +612,7 @@ impl Scheduler {
                     last_run: scheduled_job.last_run,
                     enabled: scheduled_job.enabled,
                     execution_count: scheduled_job.execution_count,
-                    is_running: scheduled_job.is_running,
+                    is_running: scheduled_job.running > 0,
                 });
             }
             Ok(job_infos)
@@ -392,6 +623,42 @@ impl Scheduler {
         }
     }
 
+    /// Get the health of a scheduled job
+    ///
+    /// Returns `None` if the job has been removed from this scheduler.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::cron::{CronExpression, Job, Scheduler};
+    ///
+    /// let mut scheduler = Scheduler::new();
+    /// let job = Job::new("report", Box::new(|| Ok(())));
+    /// let handle = scheduler
+    ///     .add_job("report", job, CronExpression::parse("0 * * * *").unwrap())
+    ///     .unwrap();
+    ///
+    /// let status = scheduler.job_status(&handle).unwrap();
+    /// assert_eq!(status.last_success, None);
+    /// assert!(status.next_run.is_some());
+    /// ```
+    pub fn job_status(&self, handle: &TaskHandle) -> Option<JobStatus> {
+        let jobs = self.jobs.lock().ok()?;
+        let scheduled_job = jobs.get(&handle.id)?;
+        let stats = &scheduled_job.stats;
+        Some(JobStatus {
+            last_run: scheduled_job.last_run,
+            last_duration: stats.last_duration,
+            last_attempts: stats.last_attempts,
+            last_success: stats.last_success,
+            last_error: stats.last_error.clone(),
+            consecutive_failures: stats.consecutive_failures,
+            success_count: stats.success_count,
+            failure_count: stats.failure_count,
+            next_run: scheduled_job.next_run,
+        })
+    }
+
     /// Enable or disable a specific job
     pub fn set_job_enabled(&mut self, job_id: &str, enabled: bool) -> Result<()> {
         if let Ok(mut jobs) = self.jobs.lock() {
@@ -431,10 +698,12 @@ impl Scheduler {
         let job = {
             if let Ok(mut jobs) = self.jobs.lock() {
                 if let Some(scheduled_job) = jobs.get_mut(job_id) {
-                    if scheduled_job.is_running {
+                    if scheduled_job.running > 0
+                        && scheduled_job.job.metadata().overlap_policy != OverlapPolicy::Allow
+                    {
                         return Err(Error::validation("Job is already running".to_string()));
                     }
-                    scheduled_job.is_running = true;
+                    scheduled_job.running += 1;
                     scheduled_job.execution_count += 1;
                     scheduled_job.last_run = Some(Utc::now());
                     scheduled_job.job.clone()
@@ -453,14 +722,18 @@ impl Scheduler {
 
         let jobs_ref = self.jobs.clone();
         let job_id = job_id.to_string();
+        let token = self.cancel_token.child_token();
 
         tokio::spawn(async move {
-            let result = job.execute().await;
+            let start_time = Instant::now();
+            let (result, attempts) = job.run_with_retries(token).await;
+            let duration = start_time.elapsed();
 
-            // Mark job as not running
+            // Record the outcome and mark job as not running
             if let Ok(mut jobs_guard) = jobs_ref.lock() {
                 if let Some(scheduled_job) = jobs_guard.get_mut(&job_id) {
-                    scheduled_job.is_running = false;
+                    scheduled_job.stats.record(duration, attempts, &result);
+                    scheduled_job.running -= 1;
                 }
             }
 
@@ -524,6 +797,31 @@ pub struct JobInfo {
     pub is_running: bool,
 }
 
+/// Health of a scheduled job, as reported by [`Scheduler::job_status`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobStatus {
+    /// When the most recent run started
+    #[cfg(feature = "chrono")]
+    pub last_run: Option<DateTime<Utc>>,
+    /// How long the last finished run took, including retries
+    pub last_duration: Option<Duration>,
+    /// Attempts made by the last finished run; above one means it was retried
+    pub last_attempts: Option<u32>,
+    /// Whether the last finished run succeeded, `None` before the first run
+    pub last_success: Option<bool>,
+    /// Error message of the last finished run, if it failed
+    pub last_error: Option<String>,
+    /// Number of failed runs since the last successful one
+    pub consecutive_failures: u32,
+    /// Total number of successful runs
+    pub success_count: u64,
+    /// Total number of failed runs
+    pub failure_count: u64,
+    /// Next scheduled execution time
+    #[cfg(feature = "chrono")]
+    pub next_run: Option<DateTime<Utc>>,
+}
+
 impl fmt::Display for JobInfo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "Job: {} ({})", self.name, self.id)?;
@@ -588,7 +886,7 @@ impl TaskHandle {
                     last_run: scheduled_job.last_run,
                     enabled: scheduled_job.enabled,
                     execution_count: scheduled_job.execution_count,
-                    is_running: scheduled_job.is_running,
+                    is_running: scheduled_job.running > 0,
                 })
             } else {
                 Err(Error::not_found(format!(
@@ -606,12 +904,23 @@ impl TaskHandle {
     /// Check if this job is currently running
     pub fn is_running(&self) -> bool {
         if let Ok(jobs) = self.scheduler.lock() {
-            jobs.get(&self.id).map_or(false, |job| job.is_running)
+            jobs.get(&self.id).map_or(false, |job| job.running > 0)
         } else {
             false
         }
     }
 
+    /// Get the number of runs of this job currently executing
+    ///
+    /// This is only ever above one for jobs with [`OverlapPolicy::Allow`].
+    pub fn running_count(&self) -> usize {
+        if let Ok(jobs) = self.scheduler.lock() {
+            jobs.get(&self.id).map_or(0, |job| job.running)
+        } else {
+            0
+        }
+    }
+
     /// Get the execution count for this job
     pub fn execution_count(&self) -> u64 {
         if let Ok(jobs) = self.scheduler.lock() {
@@ -625,7 +934,8 @@ impl TaskHandle {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cron::job::Job;
+    use crate::cron::job::{Backoff, Job, JobBuilder, MisfirePolicy};
+    use chrono::Timelike;
     use std::sync::atomic::{AtomicU32, Ordering};
 
     #[test]
@@ -734,6 +1044,351 @@ mod tests {
         assert_eq!(info.execution_count, 1);
     }
 
+    /// Make a job due so the next scheduler tick fires it
+    fn make_due(scheduler: &Scheduler, job_id: &str) {
+        let mut jobs = scheduler.jobs.lock().unwrap();
+        jobs.get_mut(job_id).unwrap().next_run = Some(Utc::now());
+    }
+
+    fn fast_scheduler() -> Scheduler {
+        Scheduler::with_config(SchedulerConfig {
+            tick_interval: Duration::from_millis(10),
+            ..Default::default()
+        })
+    }
+
+    async fn wait_for(condition: impl Fn() -> bool) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !condition() {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("condition not reached in time");
+    }
+
+    async fn wait_until_running(handle: &TaskHandle) {
+        wait_for(|| handle.is_running()).await;
+    }
+
+    /// Start a job that runs longer than its interval: it is fired, then
+    /// comes due again while the first run is still going
+    async fn start_overlapping(policy: OverlapPolicy) -> (Scheduler, TaskHandle, Arc<AtomicU32>) {
+        let mut scheduler = fast_scheduler();
+        let active = Arc::new(AtomicU32::new(0));
+        let peak = Arc::new(AtomicU32::new(0));
+        let peak_clone = peak.clone();
+
+        let job = JobBuilder::new("overlapping_job")
+            .overlap_policy(policy)
+            .build_async(Box::new(move || {
+                let active = active.clone();
+                let peak = peak_clone.clone();
+                async move {
+                    let now_active = active.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now_active, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    active.fetch_sub(1, Ordering::SeqCst);
+                    Ok(())
+                }
+            }));
+        let cron_expr = CronExpression::parse("0 0 1 1 0").unwrap();
+        let handle = scheduler.add_job("overlapping", job, cron_expr).unwrap();
+        make_due(&scheduler, &handle.id);
+
+        scheduler.start().await.unwrap();
+        wait_until_running(&handle).await;
+        make_due(&scheduler, &handle.id);
+        tokio::time::sleep(Duration::from_millis(40)).await;
+
+        (scheduler, handle, peak)
+    }
+
+    /// Start a scheduler with an every-minute job that last ran before the
+    /// last two scheduled times, as after a restart
+    async fn resume_after_downtime(policy: MisfirePolicy) -> (Scheduler, TaskHandle) {
+        // Keep clear of a real minute boundary firing the job mid-test
+        if Utc::now().second() >= 58 {
+            tokio::time::sleep(Duration::from_secs(3)).await;
+        }
+
+        // Slots in (now - 2min, now] are exactly two minute boundaries
+        let mut scheduler = fast_scheduler();
+        let job = JobBuilder::new("misfire_job")
+            .misfire_policy(policy)
+            .last_run(Utc::now() - chrono::Duration::minutes(2))
+            .build_sync(Box::new(|| Ok(())));
+        let cron_expr = CronExpression::parse("* * * * *").unwrap();
+        let handle = scheduler.add_job("misfire", job, cron_expr).unwrap();
+
+        scheduler.start().await.unwrap();
+        (scheduler, handle)
+    }
+
+    #[tokio::test]
+    async fn test_misfire_fire_immediately() {
+        let (_scheduler, handle) = resume_after_downtime(MisfirePolicy::FireImmediately).await;
+        wait_for(|| handle.execution_count() == 1 && !handle.is_running()).await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(handle.execution_count(), 1);
+        assert!(handle.get_info().unwrap().next_run.unwrap() > Utc::now());
+    }
+
+    #[tokio::test]
+    async fn test_misfire_skip_to_next() {
+        let (_scheduler, handle) = resume_after_downtime(MisfirePolicy::SkipToNext).await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(handle.execution_count(), 0);
+        assert!(handle.get_info().unwrap().next_run.unwrap() > Utc::now());
+    }
+
+    #[tokio::test]
+    async fn test_last_run_sets_first_due_time() {
+        let mut scheduler = fast_scheduler();
+        let cron_expr = CronExpression::parse("0 * * * *").unwrap();
+        let last_run = Utc::now() - chrono::Duration::hours(5);
+        let job = JobBuilder::new("hourly")
+            .last_run(last_run)
+            .build_sync(Box::new(|| Ok(())));
+        let handle = scheduler.add_job("hourly", job, cron_expr.clone()).unwrap();
+
+        let info = handle.get_info().unwrap();
+        assert_eq!(info.last_run, Some(last_run));
+        assert_eq!(info.next_run, cron_expr.next_execution(&last_run));
+
+        // Without a last run nothing counts as missed
+        let job = JobBuilder::new("fresh").build_sync(Box::new(|| Ok(())));
+        let handle = scheduler.add_job("fresh", job, cron_expr).unwrap();
+        assert!(handle.get_info().unwrap().next_run.unwrap() > Utc::now());
+        assert!(handle.get_info().unwrap().last_run.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_misfire_fire_all() {
+        let (_scheduler, handle) = resume_after_downtime(MisfirePolicy::FireAll).await;
+        wait_for(|| handle.execution_count() == 2 && !handle.is_running()).await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(handle.execution_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_job_status_tracks_failures() {
+        let mut scheduler = fast_scheduler();
+        let fail = Arc::new(AtomicBool::new(true));
+        let fail_clone = fail.clone();
+
+        let job = Job::new(
+            "flaky_job",
+            Box::new(move || {
+                if fail_clone.load(Ordering::SeqCst) {
+                    Err(Error::custom("Disk full".to_string()))
+                } else {
+                    Ok(())
+                }
+            }),
+        );
+        let cron_expr = CronExpression::parse("0 0 1 1 *").unwrap();
+        let handle = scheduler.add_job("flaky", job, cron_expr).unwrap();
+
+        let status = scheduler.job_status(&handle).unwrap();
+        assert_eq!(status.last_success, None);
+        assert_eq!(status.consecutive_failures, 0);
+
+        scheduler.start().await.unwrap();
+        for expected in 1..=2 {
+            make_due(&scheduler, &handle.id);
+            wait_for(|| scheduler.job_status(&handle).unwrap().failure_count == expected).await;
+        }
+
+        let status = scheduler.job_status(&handle).unwrap();
+        assert_eq!(status.last_success, Some(false));
+        assert_eq!(status.consecutive_failures, 2);
+        assert!(status.last_error.unwrap().contains("Disk full"));
+        assert!(status.last_duration.is_some());
+        assert!(status.last_run.is_some());
+        assert!(status.next_run.unwrap() > Utc::now());
+
+        fail.store(false, Ordering::SeqCst);
+        scheduler.trigger_job(&handle.id).await.unwrap();
+        let status = scheduler.job_status(&handle).unwrap();
+        assert_eq!(status.last_success, Some(true));
+        assert_eq!(status.consecutive_failures, 0);
+        assert_eq!(status.success_count, 1);
+        assert_eq!(status.failure_count, 2);
+        assert_eq!(status.last_error, None);
+
+        scheduler.remove_job(&handle.id).unwrap();
+        assert!(scheduler.job_status(&handle).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_retry_until_success() {
+        let mut scheduler = fast_scheduler();
+        let attempts = Arc::new(AtomicU32::new(0));
+        let attempts_clone = attempts.clone();
+
+        let job = JobBuilder::new("retrying_job")
+            .retry(3, Backoff::Fixed(Duration::from_millis(10)))
+            .build_sync(Box::new(move || {
+                if attempts_clone.fetch_add(1, Ordering::SeqCst) < 2 {
+                    Err(Error::custom("Service unavailable".to_string()))
+                } else {
+                    Ok(())
+                }
+            }));
+        let cron_expr = CronExpression::parse("0 0 1 1 *").unwrap();
+        let handle = scheduler.add_job("retrying", job, cron_expr).unwrap();
+        make_due(&scheduler, &handle.id);
+
+        scheduler.start().await.unwrap();
+        wait_for(|| {
+            scheduler
+                .job_status(&handle)
+                .unwrap()
+                .last_success
+                .is_some()
+        })
+        .await;
+
+        let status = scheduler.job_status(&handle).unwrap();
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(status.last_success, Some(true));
+        assert_eq!(status.last_attempts, Some(3));
+        assert_eq!(status.failure_count, 0);
+        assert_eq!(handle.execution_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_exhausted() {
+        let mut scheduler = Scheduler::new();
+        let job = JobBuilder::new("failing_job")
+            .retry(2, Backoff::Fixed(Duration::from_millis(10)))
+            .build_sync(Box::new(|| Err(Error::custom("Still down".to_string()))));
+        let cron_expr = CronExpression::parse("0 0 1 1 *").unwrap();
+        let handle = scheduler.add_job("failing", job, cron_expr).unwrap();
+
+        assert!(scheduler.trigger_job(&handle.id).await.is_err());
+        let status = scheduler.job_status(&handle).unwrap();
+        assert_eq!(status.last_success, Some(false));
+        assert_eq!(status.last_attempts, Some(2));
+        assert_eq!(status.consecutive_failures, 1);
+    }
+
+    #[tokio::test]
+    async fn test_overlap_skip() {
+        let (_scheduler, handle, peak) = start_overlapping(OverlapPolicy::Skip).await;
+        assert_eq!(handle.running_count(), 1);
+
+        wait_for(|| !handle.is_running()).await;
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        assert_eq!(handle.execution_count(), 1);
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_overlap_queue() {
+        let (_scheduler, handle, peak) = start_overlapping(OverlapPolicy::Queue).await;
+        assert_eq!(handle.running_count(), 1);
+        assert_eq!(handle.execution_count(), 1);
+
+        wait_for(|| handle.execution_count() == 2 && !handle.is_running()).await;
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_overlap_allow() {
+        let (_scheduler, handle, peak) = start_overlapping(OverlapPolicy::Allow).await;
+        assert_eq!(handle.running_count(), 2);
+        assert_eq!(handle.execution_count(), 2);
+
+        wait_for(|| !handle.is_running()).await;
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_waits_for_running_job() {
+        let mut scheduler = fast_scheduler();
+        let finished = Arc::new(AtomicBool::new(false));
+        let finished_clone = finished.clone();
+
+        let job = Job::new_async(
+            "slow_job",
+            Box::new(move || {
+                let finished = finished_clone.clone();
+                async move {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    finished.store(true, Ordering::SeqCst);
+                    Ok(())
+                }
+            }),
+        );
+        let cron_expr = CronExpression::parse("0 0 1 1 0").unwrap();
+        let handle = scheduler.add_job("slow", job, cron_expr).unwrap();
+        make_due(&scheduler, &handle.id);
+
+        scheduler.start().await.unwrap();
+        wait_until_running(&handle).await;
+
+        let outstanding = scheduler.shutdown(Duration::from_secs(5)).await;
+        assert!(outstanding.is_empty());
+        assert!(finished.load(Ordering::SeqCst));
+        assert!(!scheduler.is_running());
+        assert_eq!(handle.execution_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_reports_outstanding_job() {
+        let mut scheduler = fast_scheduler();
+        let job = Job::new_async(
+            "stuck_job",
+            Box::new(|| async {
+                tokio::time::sleep(Duration::from_secs(30)).await;
+                Ok(())
+            }),
+        );
+        let cron_expr = CronExpression::parse("0 0 1 1 0").unwrap();
+        let handle = scheduler.add_job("stuck", job, cron_expr).unwrap();
+        make_due(&scheduler, &handle.id);
+
+        scheduler.start().await.unwrap();
+        wait_until_running(&handle).await;
+
+        let outstanding = scheduler.shutdown(Duration::from_millis(50)).await;
+        assert_eq!(outstanding, vec![handle.id.clone()]);
+        assert!(handle.is_running());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_now_cancels_jobs() {
+        let mut scheduler = fast_scheduler();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let cancelled_clone = cancelled.clone();
+
+        let job = Job::new_cancellable(
+            "cancellable_job",
+            Box::new(move |token: CancellationToken| {
+                let cancelled = cancelled_clone.clone();
+                async move {
+                    tokio::select! {
+                        () = token.cancelled() => cancelled.store(true, Ordering::SeqCst),
+                        () = tokio::time::sleep(Duration::from_secs(30)) => {}
+                    }
+                    Ok(())
+                }
+            }),
+        );
+        let cron_expr = CronExpression::parse("0 0 1 1 0").unwrap();
+        let handle = scheduler.add_job("cancellable", job, cron_expr).unwrap();
+        make_due(&scheduler, &handle.id);
+
+        scheduler.start().await.unwrap();
+        wait_until_running(&handle).await;
+
+        assert_eq!(scheduler.shutdown_now(), vec![handle.id.clone()]);
+        assert!(scheduler.shutdown(Duration::from_secs(5)).await.is_empty());
+        assert!(cancelled.load(Ordering::SeqCst));
+    }
+
     #[test]
     fn test_scheduler_config() {
         let config = SchedulerConfig {
@@ -6,7 +6,27 @@
 use crate::error::{Error, Result};
 use hmac::{Hmac, Mac};
 use md5::Md5;
+use sha1::Sha1;
 use sha2::{Digest, Sha256, Sha512};
+use std::io::Read;
+
+/// Buffer size used when hashing from a reader
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Feed a reader into a hasher chunk by chunk and return the digest
+fn digest_reader<D: Digest, R: Read + ?Sized>(reader: &mut R) -> Result<Vec<u8>> {
+    let mut hasher = D::new();
+    let mut buf = vec![0u8; READ_BUFFER_SIZE];
+    loop {
+        match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => hasher.update(&buf[..n]),
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(hasher.finalize().to_vec())
+}
 
 /// MD5 digest utility
 pub struct Md5Util;
@@ -56,12 +76,68 @@ impl Md5Util {
     pub fn digest_str(data: &str) -> String {
         Self::digest_hex(data.as_bytes())
     }
+
+    /// Calculate MD5 hash of everything read from `reader`, as hexadecimal string
+    ///
+    /// Data is read in fixed-size chunks, so large files never need to be
+    /// loaded into memory.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::crypto::Md5Util;
+    ///
+    /// let mut reader: &[u8] = b"hello world";
+    /// let hash_hex = Md5Util::digest_reader(&mut reader).unwrap();
+    /// assert_eq!(hash_hex, "5eb63bbbe01eeed093cb22bb8f5acdc3");
+    /// ```
+    pub fn digest_reader<R: Read + ?Sized>(reader: &mut R) -> Result<String> {
+        digest_reader::<Md5, R>(reader).map(hex::encode)
+    }
 }
 
 /// SHA digest utility
 pub struct ShaUtil;
 
 impl ShaUtil {
+    /// Calculate SHA-1 hash of input data
+    ///
+    /// SHA-1 is not collision resistant; use it only for compatibility
+    /// (checksums, legacy protocols), not for security.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::crypto::ShaUtil;
+    ///
+    /// let hash = ShaUtil::sha1(b"hello world");
+    /// assert_eq!(hash.len(), 20); // SHA-1 produces 20 bytes
+    /// ```
+    pub fn sha1(data: &[u8]) -> Vec<u8> {
+        let mut hasher = Sha1::new();
+        hasher.update(data);
+        hasher.finalize().to_vec()
+    }
+
+    /// Calculate SHA-1 hash and return as hexadecimal string
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::crypto::ShaUtil;
+    ///
+    /// let hash_hex = ShaUtil::sha1_hex(b"hello world");
+    /// assert_eq!(hash_hex, "2aae6c35c94fcfb415dbe95f408b9ce91ee846ed");
+    /// ```
+    pub fn sha1_hex(data: &[u8]) -> String {
+        hex::encode(Self::sha1(data))
+    }
+
+    /// Calculate SHA-1 hash of everything read from `reader`, as hexadecimal string
+    pub fn sha1_reader<R: Read + ?Sized>(reader: &mut R) -> Result<String> {
+        digest_reader::<Sha1, R>(reader).map(hex::encode)
+    }
+
     /// Calculate SHA-256 hash of input data
     ///
     /// # Examples
@@ -107,6 +183,25 @@ impl ShaUtil {
         Self::sha256_hex(data.as_bytes())
     }
 
+    /// Calculate SHA-256 hash of everything read from `reader`, as hexadecimal string
+    ///
+    /// Data is read in fixed-size chunks, so large files never need to be
+    /// loaded into memory.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use yimi_rutool::crypto::ShaUtil;
+    /// use std::fs::File;
+    ///
+    /// let mut file = File::open("large.iso").unwrap();
+    /// let hash_hex = ShaUtil::sha256_reader(&mut file).unwrap();
+    /// println!("{hash_hex}");
+    /// ```
+    pub fn sha256_reader<R: Read + ?Sized>(reader: &mut R) -> Result<String> {
+        digest_reader::<Sha256, R>(reader).map(hex::encode)
+    }
+
     /// Calculate SHA-512 hash of input data
     ///
     /// # Examples
@@ -151,12 +246,91 @@ impl ShaUtil {
     pub fn sha512_str(data: &str) -> String {
         Self::sha512_hex(data.as_bytes())
     }
+
+    /// Calculate SHA-512 hash of everything read from `reader`, as hexadecimal string
+    pub fn sha512_reader<R: Read + ?Sized>(reader: &mut R) -> Result<String> {
+        digest_reader::<Sha512, R>(reader).map(hex::encode)
+    }
+}
+
+/// Incremental SHA-256 hasher for data that arrives in pieces
+///
+/// Also implements [`std::io::Write`], so it can be the target of [`std::io::copy`].
+///
+/// # Examples
+///
+/// ```rust
+/// use yimi_rutool::crypto::{Sha256Hasher, ShaUtil};
+///
+/// let mut hasher = Sha256Hasher::new();
+/// hasher.update(b"hello ");
+/// hasher.update(b"world");
+/// assert_eq!(hasher.finalize_hex(), ShaUtil::sha256_hex(b"hello world"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Sha256Hasher {
+    inner: Sha256,
+}
+
+impl Sha256Hasher {
+    /// Create a new hasher
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed more data into the hasher
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Consume the hasher and return the digest bytes
+    pub fn finalize(self) -> Vec<u8> {
+        self.inner.finalize().to_vec()
+    }
+
+    /// Consume the hasher and return the digest as hexadecimal string
+    pub fn finalize_hex(self) -> String {
+        hex::encode(self.finalize())
+    }
+}
+
+impl std::io::Write for Sha256Hasher {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
 }
 
 /// HMAC utility for message authentication codes
 pub struct HmacUtil;
 
 impl HmacUtil {
+    /// Calculate HMAC-SHA1
+    ///
+    /// Only use this where a protocol requires it, such as HOTP and TOTP;
+    /// prefer [`hmac_sha256`](Self::hmac_sha256) otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::crypto::HmacUtil;
+    ///
+    /// let hmac = HmacUtil::hmac_sha1(b"my-secret-key", b"hello world").unwrap();
+    /// assert_eq!(hmac.len(), 20);
+    /// ```
+    pub fn hmac_sha1(key: &[u8], message: &[u8]) -> Result<Vec<u8>> {
+        type HmacSha1 = Hmac<Sha1>;
+
+        let mut mac = HmacSha1::new_from_slice(key)
+            .map_err(|e| Error::crypto(format!("Invalid key length: {}", e)))?;
+        mac.update(message);
+        Ok(mac.finalize().into_bytes().to_vec())
+    }
+
     /// Calculate HMAC-SHA256
     ///
     /// # Examples
@@ -328,9 +502,86 @@ mod tests {
         assert!(!HmacUtil::verify_hmac_sha256(key, b"wrong message", &hmac).unwrap());
     }
 
+    #[test]
+    fn test_hmac_sha1() {
+        // RFC 2202 test case 1
+        let hmac = HmacUtil::hmac_sha1(&[0x0b; 20], b"Hi There").unwrap();
+        assert_eq!(
+            hex::encode(hmac),
+            "b617318655057264e28bc0b6fb378c8ef146be00"
+        );
+    }
+
     #[test]
     fn test_hmac_string() {
         let hmac_hex = HmacUtil::hmac_sha256_str("my-secret-key", "hello world").unwrap();
         assert_eq!(hmac_hex.len(), 64);
     }
+
+    #[test]
+    fn test_reader_digests_match_one_shot() {
+        use std::io::{Seek, SeekFrom, Write};
+
+        // Spans several read buffers and ends mid-buffer
+        let data: Vec<u8> = (0..READ_BUFFER_SIZE * 2 + 777)
+            .map(|i| u8::try_from(i % 256).unwrap())
+            .collect();
+        let mut file = tempfile::tempfile().unwrap();
+        file.write_all(&data).unwrap();
+
+        let rewind = |file: &mut std::fs::File| {
+            file.seek(SeekFrom::Start(0)).unwrap();
+        };
+
+        rewind(&mut file);
+        assert_eq!(
+            ShaUtil::sha256_reader(&mut file).unwrap(),
+            ShaUtil::sha256_hex(&data)
+        );
+        rewind(&mut file);
+        assert_eq!(
+            ShaUtil::sha512_reader(&mut file).unwrap(),
+            ShaUtil::sha512_hex(&data)
+        );
+        rewind(&mut file);
+        assert_eq!(
+            ShaUtil::sha1_reader(&mut file).unwrap(),
+            ShaUtil::sha1_hex(&data)
+        );
+        rewind(&mut file);
+        assert_eq!(
+            Md5Util::digest_reader(&mut file).unwrap(),
+            Md5Util::digest_hex(&data)
+        );
+
+        let mut empty: &[u8] = b"";
+        assert_eq!(
+            ShaUtil::sha256_reader(&mut empty).unwrap(),
+            ShaUtil::sha256_hex(b"")
+        );
+    }
+
+    #[test]
+    fn test_sha1() {
+        assert_eq!(
+            ShaUtil::sha1_hex(b"abc"),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+    }
+
+    #[test]
+    fn test_sha256_hasher() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+
+        let mut hasher = Sha256Hasher::new();
+        for piece in data.chunks(5) {
+            hasher.update(piece);
+        }
+        assert_eq!(hasher.clone().finalize(), ShaUtil::sha256(data));
+        assert_eq!(hasher.finalize_hex(), ShaUtil::sha256_hex(data));
+
+        let mut hasher = Sha256Hasher::new();
+        std::io::copy(&mut &data[..], &mut hasher).unwrap();
+        assert_eq!(hasher.finalize_hex(), ShaUtil::sha256_hex(data));
+    }
 }
@@ -6,6 +6,7 @@
 //! - Asymmetric encryption (RSA)
 //! - Message authentication codes (HMAC)
 //! - Key derivation functions (PBKDF2)
+//! - Password hashing (salted PBKDF2 in PHC string format)
 //! - Secure random number generation
 
 pub mod asymmetric;
@@ -13,8 +14,8 @@ pub mod digest;
 pub mod secure_util;
 pub mod symmetric;
 
-pub use asymmetric::RsaUtil;
+pub use asymmetric::{OaepHash, RsaUtil};
 /// Re-export commonly used types for convenience
-pub use digest::{HmacUtil, Md5Util, ShaUtil};
-pub use secure_util::SecureUtil;
+pub use digest::{HmacUtil, Md5Util, Sha256Hasher, ShaUtil};
+pub use secure_util::{Alphabet, PasswordHashParams, SecureUtil};
 pub use symmetric::AesUtil;
@@ -1,14 +1,195 @@
 //! Security utilities
 //!
 //! This module provides various security utilities including
-//! secure random number generation, password generation, and key generation.
+//! secure random number generation, password generation, key generation,
+//! password hashing, one-time passwords (HOTP and TOTP) and signed,
+//! expiring tokens.
 
+use crate::crypto::HmacUtil;
+use crate::error::{Error, Result};
 use base64::Engine;
 use rand::{
     RngCore,
     distributions::{Alphanumeric, Distribution},
     thread_rng,
 };
+use std::time::Duration;
+
+/// Standard base64 without padding, as used by the PHC string format
+const PHC_B64: base64::engine::GeneralPurpose = base64::engine::general_purpose::STANDARD_NO_PAD;
+
+/// URL-safe base64 without padding, used for the parts of signed tokens
+const TOKEN_B64: base64::engine::GeneralPurpose = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+
+/// Time step of TOTP codes used by [`SecureUtil::verify_totp`], in seconds
+const TOTP_PERIOD: u64 = 30;
+
+/// Largest clock drift window, in periods, accepted when verifying TOTP codes
+const MAX_TOTP_WINDOW: u64 = 10;
+
+/// Shortest decoded OTP secret accepted; RFC 4226 requires 128 bits but
+/// many authenticator setups still use 80-bit secrets
+const MIN_OTP_SECRET_LEN: usize = 10;
+
+/// Upper bounds on Argon2id costs, checked before hashing so that a crafted
+/// hash string cannot make verification allocate gigabytes or run for hours
+const MAX_ARGON2_MEMORY_KIB: u32 = 1024 * 1024;
+const MAX_ARGON2_ITERATIONS: u32 = 64;
+const MAX_ARGON2_PARALLELISM: u32 = 64;
+const MAX_PASSWORD_HASH_LEN: usize = 128;
+const MAX_PASSWORD_SALT_LEN: usize = 128;
+
+/// Argon2id cost parameters for password hashing
+///
+/// The defaults follow the OWASP recommendation (19 MiB of memory, 2
+/// passes, 1 lane). Raise the costs as hardware gets faster; existing hashes
+/// keep verifying because the parameters are stored in the hash string.
+/// Memory is capped at 1 GiB, passes and lanes at 64 each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PasswordHashParams {
+    /// Number of passes over memory (Argon2 `t`)
+    pub iterations: u32,
+    /// Memory size in KiB (Argon2 `m`)
+    pub memory_kib: u32,
+    /// Degree of parallelism (Argon2 `p`)
+    pub parallelism: u32,
+    /// Random salt length in bytes
+    pub salt_len: usize,
+    /// Derived hash length in bytes
+    pub hash_len: usize,
+}
+
+impl Default for PasswordHashParams {
+    fn default() -> Self {
+        Self {
+            iterations: 2,
+            memory_kib: 19 * 1024,
+            parallelism: 1,
+            salt_len: 16,
+            hash_len: 32,
+        }
+    }
+}
+
+impl PasswordHashParams {
+    /// Check the costs against the supported ranges
+    fn validate(&self) -> Result<()> {
+        if !(1..=MAX_ARGON2_ITERATIONS).contains(&self.iterations) {
+            return Err(Error::validation(format!(
+                "Password hash iterations must be between 1 and {MAX_ARGON2_ITERATIONS}"
+            )));
+        }
+        if self.memory_kib > MAX_ARGON2_MEMORY_KIB {
+            return Err(Error::validation(format!(
+                "Password hash memory must be at most {MAX_ARGON2_MEMORY_KIB} KiB"
+            )));
+        }
+        if !(1..=MAX_ARGON2_PARALLELISM).contains(&self.parallelism) {
+            return Err(Error::validation(format!(
+                "Password hash parallelism must be between 1 and {MAX_ARGON2_PARALLELISM}"
+            )));
+        }
+        if !(8..=MAX_PASSWORD_SALT_LEN).contains(&self.salt_len) {
+            return Err(Error::validation(format!(
+                "Password hash salt must be between 8 and {MAX_PASSWORD_SALT_LEN} bytes"
+            )));
+        }
+        if !(16..=MAX_PASSWORD_HASH_LEN).contains(&self.hash_len) {
+            return Err(Error::validation(format!(
+                "Password hash length must be between 16 and {MAX_PASSWORD_HASH_LEN} bytes"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Derive an Argon2id hash of `password` filling `out`
+    fn derive(&self, password: &[u8], salt: &[u8], out: &mut [u8]) -> Result<()> {
+        let params = argon2::Params::new(
+            self.memory_kib,
+            self.iterations,
+            self.parallelism,
+            Some(out.len()),
+        )
+        .map_err(|e| Error::validation(format!("Invalid Argon2 parameters: {e}")))?;
+        argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+            .hash_password_into(password, salt, out)
+            .map_err(|e| Error::validation(format!("Argon2 hashing failed: {e}")))
+    }
+}
+
+/// Character sets for [`SecureUtil::random_string`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Alphabet {
+    /// `A-Z`, `a-z` and `0-9` (62 characters)
+    Alphanumeric,
+    /// Lowercase hexadecimal `0-9a-f` (16 characters)
+    Hex,
+    /// Bitcoin base58: alphanumeric without `0`, `O`, `I` and `l` (58 characters)
+    Base58,
+    /// URL and filename safe base64 characters `A-Za-z0-9-_` (64 characters)
+    UrlSafe,
+    /// Digits `0-9` (10 characters)
+    Numeric,
+    /// Custom character set
+    Custom(String),
+}
+
+impl Alphabet {
+    /// Characters in this alphabet
+    pub fn chars(&self) -> &str {
+        match self {
+            Alphabet::Alphanumeric => {
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789"
+            }
+            Alphabet::Hex => "0123456789abcdef",
+            Alphabet::Base58 => "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+            Alphabet::UrlSafe => "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_",
+            Alphabet::Numeric => "0123456789",
+            Alphabet::Custom(chars) => chars,
+        }
+    }
+
+    /// Number of characters in this alphabet
+    pub fn size(&self) -> usize {
+        self.chars().chars().count()
+    }
+
+    /// Entropy in bits of a random string of `len` characters: `len * log2(size)`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::crypto::Alphabet;
+    ///
+    /// assert_eq!(Alphabet::Hex.entropy_bits(32), 128.0);
+    /// assert_eq!(Alphabet::UrlSafe.entropy_bits(22), 132.0);
+    /// ```
+    pub fn entropy_bits(&self, len: usize) -> f64 {
+        let to_f64 = |n: usize| f64::from(u32::try_from(n).unwrap_or(u32::MAX));
+        match self.size() {
+            0 => 0.0,
+            size => to_f64(len) * to_f64(size).log2(),
+        }
+    }
+}
+
+impl From<&str> for Alphabet {
+    fn from(chars: &str) -> Self {
+        Alphabet::Custom(chars.to_string())
+    }
+}
+
+impl From<&String> for Alphabet {
+    fn from(chars: &String) -> Self {
+        Alphabet::Custom(chars.clone())
+    }
+}
+
+impl From<String> for Alphabet {
+    fn from(chars: String) -> Self {
+        Alphabet::Custom(chars)
+    }
+}
 
 /// Security utility functions
 pub struct SecureUtil;
@@ -30,21 +211,36 @@ impl SecureUtil {
         bytes
     }
 
-    /// Generate secure random string with custom charset
+    /// Generate secure random string from an alphabet
+    ///
+    /// Accepts an [`Alphabet`] preset or any custom charset string. Characters
+    /// are drawn uniformly (no modulo bias) from `rand::thread_rng`, a CSPRNG
+    /// seeded and periodically reseeded from the operating system, so the
+    /// result is suitable for API keys and tokens.
+    ///
+    /// The output carries `len * log2(alphabet_size)` bits of entropy, see
+    /// [`Alphabet::entropy_bits`]; e.g. 22 alphanumeric characters give ~131 bits.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use yimi_rutool::crypto::SecureUtil;
+    /// use yimi_rutool::crypto::{Alphabet, SecureUtil};
     ///
     /// let random_str = SecureUtil::random_string(16, "abcdef0123456789");
     /// assert_eq!(random_str.len(), 16);
+    ///
+    /// let api_key = SecureUtil::random_string(32, Alphabet::Base58);
+    /// assert!(api_key.chars().all(|c| Alphabet::Base58.chars().contains(c)));
     /// ```
-    pub fn random_string(len: usize, charset: &str) -> String {
+    pub fn random_string(len: usize, alphabet: impl Into<Alphabet>) -> String {
         use rand::Rng;
-        let mut rng = thread_rng();
-        let chars: Vec<char> = charset.chars().collect();
+        let alphabet = alphabet.into();
+        let chars: Vec<char> = alphabet.chars().chars().collect();
+        if chars.is_empty() {
+            return String::new();
+        }
 
+        let mut rng = thread_rng();
         (0..len)
             .map(|_| chars[rng.gen_range(0..chars.len())])
             .collect()
@@ -79,7 +275,7 @@ impl SecureUtil {
     /// assert!(random_num.chars().all(|c| c.is_numeric()));
     /// ```
     pub fn random_numeric(len: usize) -> String {
-        Self::random_string(len, "0123456789")
+        Self::random_string(len, Alphabet::Numeric)
     }
 
     /// Generate secure hex string
@@ -94,7 +290,7 @@ impl SecureUtil {
     /// assert!(random_hex.chars().all(|c| c.is_ascii_hexdigit()));
     /// ```
     pub fn random_hex(len: usize) -> String {
-        Self::random_string(len, "0123456789abcdef")
+        Self::random_string(len, Alphabet::Hex)
     }
 
     /// Generate secure password with mixed characters
@@ -139,6 +335,8 @@ impl SecureUtil {
 
     /// Generate UUID v4 (random)
     ///
+    /// Same as [`SecureUtil::uuid_v4`].
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -148,6 +346,23 @@ impl SecureUtil {
     /// assert_eq!(uuid.len(), 36); // Standard UUID format: 8-4-4-4-12
     /// ```
     pub fn generate_uuid() -> String {
+        Self::uuid_v4()
+    }
+
+    /// Generate a random RFC 4122 version 4 UUID in lowercase hyphenated form
+    ///
+    /// 122 of the 128 bits come from the CSPRNG; the rest encode version and variant.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::crypto::SecureUtil;
+    ///
+    /// let uuid = SecureUtil::uuid_v4();
+    /// assert_eq!(uuid.len(), 36);
+    /// assert_eq!(&uuid[14..15], "4");
+    /// ```
+    pub fn uuid_v4() -> String {
         let bytes = Self::random_bytes(16);
 
         // Set version (4) and variant bits according to RFC 4122
@@ -298,6 +513,410 @@ impl SecureUtil {
         let bytes = Self::random_bytes(byte_len);
         base64::engine::general_purpose::STANDARD.encode(&bytes)
     }
+
+    /// Hash a password for storage using the default [`PasswordHashParams`]
+    ///
+    /// Returns a salted PHC-format string such as
+    /// `$argon2id$v=19$m=19456,t=2,p=1$<salt>$<hash>`, which embeds everything
+    /// [`SecureUtil::verify_password`] needs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::crypto::SecureUtil;
+    ///
+    /// let hash = SecureUtil::hash_password("correct horse battery staple").unwrap();
+    /// assert!(hash.starts_with("$argon2id$v=19$m=19456,t=2,p=1$"));
+    /// assert!(SecureUtil::verify_password("correct horse battery staple", &hash).unwrap());
+    /// ```
+    pub fn hash_password(password: &str) -> Result<String> {
+        Self::hash_password_with(password, &PasswordHashParams::default())
+    }
+
+    /// Hash a password with custom cost parameters
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::crypto::{PasswordHashParams, SecureUtil};
+    ///
+    /// let params = PasswordHashParams {
+    ///     iterations: 3,
+    ///     memory_kib: 12 * 1024,
+    ///     ..PasswordHashParams::default()
+    /// };
+    /// let hash = SecureUtil::hash_password_with("s3cret", &params).unwrap();
+    /// assert!(hash.starts_with("$argon2id$v=19$m=12288,t=3,p=1$"));
+    /// ```
+    pub fn hash_password_with(password: &str, params: &PasswordHashParams) -> Result<String> {
+        params.validate()?;
+
+        let salt = Self::generate_salt(params.salt_len);
+        let mut hash = vec![0u8; params.hash_len];
+        params.derive(password.as_bytes(), &salt, &mut hash)?;
+
+        Ok(format!(
+            "$argon2id$v=19$m={},t={},p={}${}${}",
+            params.memory_kib,
+            params.iterations,
+            params.parallelism,
+            PHC_B64.encode(&salt),
+            PHC_B64.encode(&hash)
+        ))
+    }
+
+    /// Verify a password against a hash produced by [`SecureUtil::hash_password`]
+    ///
+    /// Returns `Ok(false)` for a wrong password and an error if `hash` is not
+    /// an Argon2id PHC string or its costs exceed the limits of
+    /// [`PasswordHashParams`]. The final comparison runs in constant time.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::crypto::{PasswordHashParams, SecureUtil};
+    ///
+    /// let params = PasswordHashParams { memory_kib: 1024, ..PasswordHashParams::default() };
+    /// let hash = SecureUtil::hash_password_with("s3cret", &params).unwrap();
+    ///
+    /// assert!(SecureUtil::verify_password("s3cret", &hash).unwrap());
+    /// assert!(!SecureUtil::verify_password("wrong", &hash).unwrap());
+    /// assert!(SecureUtil::verify_password("s3cret", "not a hash").is_err());
+    /// ```
+    pub fn verify_password(password: &str, hash: &str) -> Result<bool> {
+        let invalid = || Error::validation("Invalid password hash format");
+
+        // $argon2id$v=19$m=<memory>,t=<iterations>,p=<lanes>$<salt>$<hash>
+        let mut fields = hash.strip_prefix('$').ok_or_else(invalid)?.split('$');
+        let (Some(id), Some(version), Some(param_str), Some(salt_b64), Some(hash_b64), None) = (
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+        ) else {
+            return Err(invalid());
+        };
+        if id != "argon2id" {
+            return Err(Error::validation(format!(
+                "Unsupported password hash algorithm: {id}"
+            )));
+        }
+        if version != "v=19" {
+            return Err(Error::validation(format!(
+                "Unsupported Argon2 version: {version}"
+            )));
+        }
+
+        let (mut memory_kib, mut iterations, mut parallelism) = (None, None, None);
+        for pair in param_str.split(',') {
+            let (key, value) = pair.split_once('=').ok_or_else(invalid)?;
+            let value = value.parse::<u32>().map_err(|_| invalid())?;
+            let slot = match key {
+                "m" => &mut memory_kib,
+                "t" => &mut iterations,
+                "p" => &mut parallelism,
+                _ => return Err(invalid()),
+            };
+            *slot = Some(value);
+        }
+        let (Some(memory_kib), Some(iterations), Some(parallelism)) =
+            (memory_kib, iterations, parallelism)
+        else {
+            return Err(invalid());
+        };
+
+        let salt = PHC_B64.decode(salt_b64).map_err(|_| invalid())?;
+        let expected = PHC_B64.decode(hash_b64).map_err(|_| invalid())?;
+        let params = PasswordHashParams {
+            iterations,
+            memory_kib,
+            parallelism,
+            salt_len: salt.len(),
+            hash_len: expected.len(),
+        };
+        params.validate()?;
+
+        let mut actual = vec![0u8; expected.len()];
+        params.derive(password.as_bytes(), &salt, &mut actual)?;
+        Ok(Self::constant_time_eq(&actual, &expected))
+    }
+
+    /// Generate an HOTP code (RFC 4226) for a base32 secret and counter
+    ///
+    /// Spaces, `=` padding and lowercase letters in the secret are accepted,
+    /// as secrets are often shown grouped for typing. `digits` must be
+    /// between 6 and 8.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::crypto::SecureUtil;
+    ///
+    /// // RFC 4226 test secret "12345678901234567890"
+    /// let secret = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+    /// assert_eq!(SecureUtil::hotp(secret, 0, 6).unwrap(), "755224");
+    /// assert_eq!(SecureUtil::hotp(secret, 1, 6).unwrap(), "287082");
+    /// ```
+    pub fn hotp(secret_base32: &str, counter: u64, digits: u32) -> Result<String> {
+        if !(6..=8).contains(&digits) {
+            return Err(Error::validation(format!(
+                "OTP length must be between 6 and 8 digits, got {digits}"
+            )));
+        }
+        Self::hotp_with_key(&decode_base32(secret_base32)?, counter, digits)
+    }
+
+    /// Check an HOTP code against a base32 secret and counter
+    ///
+    /// Codes of the wrong length or containing non-digits never match.
+    pub fn verify_hotp(secret_base32: &str, code: &str, counter: u64) -> Result<bool> {
+        Self::verify_otp(secret_base32, code, counter..=counter)
+    }
+
+    /// Generate the TOTP code (RFC 6238) for the current time
+    ///
+    /// Uses HMAC-SHA1 with `period`-second time steps, as authenticator apps
+    /// do by default (6 digits, 30 seconds).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::crypto::SecureUtil;
+    ///
+    /// let secret = "JBSWY3DPEHPK3PXP";
+    /// let code = SecureUtil::totp_now(secret, 6, 30).unwrap();
+    /// assert_eq!(code.len(), 6);
+    /// assert!(SecureUtil::verify_totp(secret, &code, 1).unwrap());
+    /// ```
+    pub fn totp_now(secret_base32: &str, digits: u32, period: u64) -> Result<String> {
+        Self::totp_at(secret_base32, digits, period, unix_now())
+    }
+
+    /// Generate the TOTP code for a Unix timestamp in seconds
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::crypto::SecureUtil;
+    ///
+    /// // RFC 6238 test vector for SHA-1
+    /// let secret = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+    /// assert_eq!(SecureUtil::totp_at(secret, 8, 30, 59).unwrap(), "94287082");
+    /// ```
+    pub fn totp_at(
+        secret_base32: &str,
+        digits: u32,
+        period: u64,
+        unix_time: u64,
+    ) -> Result<String> {
+        Self::hotp(secret_base32, totp_counter(unix_time, period)?, digits)
+    }
+
+    /// Check a TOTP code for the current time, allowing `window` periods of
+    /// clock drift either way
+    ///
+    /// The code length decides the number of digits and periods are 30
+    /// seconds. Every candidate in the window is compared in constant time.
+    /// `window` may be at most 10.
+    pub fn verify_totp(secret_base32: &str, code: &str, window: u64) -> Result<bool> {
+        Self::verify_totp_at(secret_base32, code, window, TOTP_PERIOD, unix_now())
+    }
+
+    /// Check a TOTP code for a Unix timestamp in seconds, allowing `window`
+    /// periods of `period` seconds of clock drift either way
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::crypto::SecureUtil;
+    ///
+    /// let secret = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+    /// // The code for t=59 is still accepted one period later
+    /// assert!(SecureUtil::verify_totp_at(secret, "94287082", 1, 30, 89).unwrap());
+    /// assert!(!SecureUtil::verify_totp_at(secret, "94287082", 0, 30, 89).unwrap());
+    ///
+    /// // Wide windows would accept almost any code
+    /// assert!(SecureUtil::verify_totp_at(secret, "94287082", 1000, 30, 89).is_err());
+    /// ```
+    pub fn verify_totp_at(
+        secret_base32: &str,
+        code: &str,
+        window: u64,
+        period: u64,
+        unix_time: u64,
+    ) -> Result<bool> {
+        if window > MAX_TOTP_WINDOW {
+            return Err(Error::validation(format!(
+                "TOTP window must be at most {MAX_TOTP_WINDOW} periods, got {window}"
+            )));
+        }
+        let counter = totp_counter(unix_time, period)?;
+        Self::verify_otp(
+            secret_base32,
+            code,
+            counter.saturating_sub(window)..=counter.saturating_add(window),
+        )
+    }
+
+    fn verify_otp(
+        secret_base32: &str,
+        code: &str,
+        counters: std::ops::RangeInclusive<u64>,
+    ) -> Result<bool> {
+        let key = decode_base32(secret_base32)?;
+        let Ok(digits) = u32::try_from(code.len()) else {
+            return Ok(false);
+        };
+        if !(6..=8).contains(&digits) || !code.bytes().all(|b| b.is_ascii_digit()) {
+            return Ok(false);
+        }
+
+        // Check every counter so the time taken does not reveal which matched
+        let mut matched = false;
+        for counter in counters {
+            let expected = Self::hotp_with_key(&key, counter, digits)?;
+            matched |= Self::constant_time_eq(expected.as_bytes(), code.as_bytes());
+        }
+        Ok(matched)
+    }
+
+    fn hotp_with_key(key: &[u8], counter: u64, digits: u32) -> Result<String> {
+        let hmac = HmacUtil::hmac_sha1(key, &counter.to_be_bytes())?;
+
+        // Dynamic truncation: 31 bits read at the offset in the last nibble
+        let offset = usize::from(hmac[hmac.len() - 1] & 0x0f);
+        let binary = u32::from_be_bytes([
+            hmac[offset] & 0x7f,
+            hmac[offset + 1],
+            hmac[offset + 2],
+            hmac[offset + 3],
+        ]);
+        let code = binary % 10u32.pow(digits);
+        Ok(format!("{code:0width$}", width = digits as usize))
+    }
+
+    /// Create a signed token carrying `payload` that expires after `ttl`
+    ///
+    /// The token has the form `payload.expiry.signature`, each part in
+    /// URL-safe base64, with an HMAC-SHA256 signature over the first two.
+    /// It suits password-reset and email-confirmation links: no state is
+    /// stored, and the payload is readable by anyone holding the token, so
+    /// it must not contain secrets.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use yimi_rutool::crypto::SecureUtil;
+    ///
+    /// let secret = b"server-side secret";
+    /// let token = SecureUtil::create_signed_token(b"user:42", secret, Duration::from_secs(3600));
+    ///
+    /// assert_eq!(SecureUtil::verify_signed_token(&token, secret).unwrap(), b"user:42");
+    /// assert!(SecureUtil::verify_signed_token(&token, b"other secret").is_err());
+    /// ```
+    pub fn create_signed_token(payload: &[u8], secret: &[u8], ttl: Duration) -> String {
+        Self::create_signed_token_at(payload, secret, unix_now().saturating_add(ttl.as_secs()))
+    }
+
+    /// Verify a token from [`create_signed_token`](Self::create_signed_token)
+    /// and return its payload
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::Validation`] if the token is malformed
+    /// - [`Error::Crypto`] if the signature does not match, i.e. the token
+    ///   was tampered with or signed with another secret
+    /// - [`Error::PermissionDenied`] if the signature is valid but the token
+    ///   has expired
+    pub fn verify_signed_token(token: &str, secret: &[u8]) -> Result<Vec<u8>> {
+        Self::verify_signed_token_at(token, secret, unix_now())
+    }
+
+    fn create_signed_token_at(payload: &[u8], secret: &[u8], expires_at: u64) -> String {
+        let signed = format!(
+            "{}.{}",
+            TOKEN_B64.encode(payload),
+            TOKEN_B64.encode(expires_at.to_be_bytes())
+        );
+        let signature = HmacUtil::hmac_sha256(secret, signed.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        format!("{signed}.{}", TOKEN_B64.encode(signature))
+    }
+
+    fn verify_signed_token_at(token: &str, secret: &[u8], now: u64) -> Result<Vec<u8>> {
+        let malformed = || Error::validation("Malformed signed token");
+
+        let (signed, signature) = token.rsplit_once('.').ok_or_else(malformed)?;
+        let (payload, expiry) = signed.split_once('.').ok_or_else(malformed)?;
+        let signature = TOKEN_B64.decode(signature).map_err(|_| malformed())?;
+
+        // Check the signature first so a tampered expiry reads as tampering
+        if !HmacUtil::verify_hmac_sha256(secret, signed.as_bytes(), &signature)? {
+            return Err(Error::crypto("Invalid signed token signature"));
+        }
+
+        let expiry = TOKEN_B64.decode(expiry).map_err(|_| malformed())?;
+        let expires_at = u64::from_be_bytes(expiry.try_into().map_err(|_| malformed())?);
+        if now >= expires_at {
+            return Err(Error::permission_denied(format!(
+                "Signed token expired at {expires_at}"
+            )));
+        }
+
+        TOKEN_B64.decode(payload).map_err(|_| malformed())
+    }
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+fn totp_counter(unix_time: u64, period: u64) -> Result<u64> {
+    if period == 0 {
+        return Err(Error::validation("TOTP period cannot be zero"));
+    }
+    Ok(unix_time / period)
+}
+
+/// Decode an RFC 4648 base32 string, ignoring case, spaces and padding,
+/// into an OTP secret of at least [`MIN_OTP_SECRET_LEN`] bytes
+fn decode_base32(encoded: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(encoded.len() * 5 / 8);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in encoded.chars().filter(|c| !c.is_whitespace() && *c != '=') {
+        let value = match c.to_ascii_uppercase() {
+            c @ 'A'..='Z' => c as u32 - 'A' as u32,
+            c @ '2'..='7' => c as u32 - '2' as u32 + 26,
+            _ => {
+                return Err(Error::validation(format!(
+                    "Invalid base32 character in secret: {c:?}"
+                )));
+            }
+        };
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+
+    if bytes.is_empty() {
+        return Err(Error::validation("OTP secret cannot be empty"));
+    }
+    if bytes.len() < MIN_OTP_SECRET_LEN {
+        return Err(Error::validation(format!(
+            "OTP secret must be at least {MIN_OTP_SECRET_LEN} bytes, got {}",
+            bytes.len()
+        )));
+    }
+    Ok(bytes)
 }
 
 #[cfg(test)]
@@ -393,6 +1012,188 @@ mod tests {
         assert_ne!(token1, token2);
     }
 
+    /// RFC 4226 / RFC 6238 SHA-1 secret "12345678901234567890" in base32
+    const RFC_SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    #[test]
+    fn test_hotp_rfc4226_vectors() {
+        let expected = [
+            "755224", "287082", "359152", "969429", "338314", "254676", "287922", "162583",
+            "399871", "520489",
+        ];
+        for (counter, code) in expected.iter().enumerate() {
+            let counter = counter as u64;
+            assert_eq!(&SecureUtil::hotp(RFC_SECRET, counter, 6).unwrap(), code);
+            assert!(SecureUtil::verify_hotp(RFC_SECRET, code, counter).unwrap());
+            assert!(!SecureUtil::verify_hotp(RFC_SECRET, code, counter + 1).unwrap());
+        }
+
+        // Secrets are accepted grouped, lowercase and padded
+        assert_eq!(
+            SecureUtil::hotp("gezd gnbv gy3t qojq gezd gnbv gy3t qojq", 0, 6).unwrap(),
+            "755224"
+        );
+        assert_eq!(
+            SecureUtil::hotp("JBSWY3DPEHPK3PXP====", 0, 6)
+                .unwrap()
+                .len(),
+            6
+        );
+
+        assert!(SecureUtil::hotp(RFC_SECRET, 0, 5).is_err());
+        assert!(SecureUtil::hotp(RFC_SECRET, 0, 9).is_err());
+        assert!(SecureUtil::hotp("NOT-BASE32!", 0, 6).is_err());
+    }
+
+    #[test]
+    fn test_otp_rejects_short_secrets() {
+        // 9 bytes is one short of the minimum, 10 bytes is accepted
+        for secret in ["", "   ", "====", "MZXW6===", "GEZDGNBVGY3TQOI"] {
+            assert!(SecureUtil::hotp(secret, 0, 6).is_err(), "{secret:?}");
+            assert!(SecureUtil::verify_hotp(secret, "123456", 0).is_err());
+            assert!(SecureUtil::verify_totp_at(secret, "123456", 1, 30, 59).is_err());
+        }
+        assert!(SecureUtil::hotp("GEZDGNBVGY3TQOJQ", 0, 6).is_ok());
+    }
+
+    #[test]
+    fn test_totp_rfc6238_vectors() {
+        let expected = [
+            (59, "94287082"),
+            (1_111_111_109, "07081804"),
+            (1_111_111_111, "14050471"),
+            (1_234_567_890, "89005924"),
+            (2_000_000_000, "69279037"),
+            (20_000_000_000, "65353130"),
+        ];
+        for (time, code) in expected {
+            assert_eq!(SecureUtil::totp_at(RFC_SECRET, 8, 30, time).unwrap(), code);
+            assert!(SecureUtil::verify_totp_at(RFC_SECRET, code, 0, 30, time).unwrap());
+        }
+
+        // Six digit codes are the last six digits
+        assert_eq!(
+            SecureUtil::totp_at(RFC_SECRET, 6, 30, 1_111_111_109).unwrap(),
+            "081804"
+        );
+        assert!(SecureUtil::totp_at(RFC_SECRET, 6, 0, 59).is_err());
+    }
+
+    #[test]
+    fn test_verify_totp_window() {
+        // 07081804 belongs to the period starting at 1111111080
+        let code = "07081804";
+        assert!(SecureUtil::verify_totp_at(RFC_SECRET, code, 0, 30, 1_111_111_080).unwrap());
+        assert!(!SecureUtil::verify_totp_at(RFC_SECRET, code, 0, 30, 1_111_111_110).unwrap());
+        assert!(SecureUtil::verify_totp_at(RFC_SECRET, code, 1, 30, 1_111_111_110).unwrap());
+        assert!(SecureUtil::verify_totp_at(RFC_SECRET, code, 1, 30, 1_111_111_050).unwrap());
+        assert!(!SecureUtil::verify_totp_at(RFC_SECRET, code, 1, 30, 1_111_111_140).unwrap());
+        assert!(SecureUtil::verify_totp_at(RFC_SECRET, code, 2, 30, 1_111_111_140).unwrap());
+
+        // The window does not wrap below the first period
+        assert!(SecureUtil::verify_totp_at(RFC_SECRET, "94287082", 5, 30, 0).unwrap());
+
+        // Windows are capped so a code cannot be guessed across many periods
+        assert!(SecureUtil::verify_totp_at(RFC_SECRET, code, 10, 30, 1_111_111_080).unwrap());
+        assert!(SecureUtil::verify_totp_at(RFC_SECRET, code, 11, 30, 1_111_111_080).is_err());
+        assert!(SecureUtil::verify_totp(RFC_SECRET, code, u64::MAX).is_err());
+
+        // Malformed codes never match
+        for code in ["", "12345", "123456789", "12a456", "9428708 "] {
+            assert!(!SecureUtil::verify_totp_at(RFC_SECRET, code, 1, 30, 59).unwrap());
+        }
+
+        let now = SecureUtil::totp_now(RFC_SECRET, 6, 30).unwrap();
+        assert!(SecureUtil::verify_totp(RFC_SECRET, &now, 1).unwrap());
+        assert!(SecureUtil::verify_totp(RFC_SECRET, "94287082", 1).is_ok());
+    }
+
+    #[test]
+    fn test_signed_token_round_trip() {
+        let secret = b"server-side secret";
+        let token = SecureUtil::create_signed_token(
+            b"reset:alice@example.com",
+            secret,
+            Duration::from_secs(900),
+        );
+        assert_eq!(token.split('.').count(), 3);
+        assert!(
+            token
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        );
+        assert_eq!(
+            SecureUtil::verify_signed_token(&token, secret).unwrap(),
+            b"reset:alice@example.com"
+        );
+
+        // Payloads may be empty or binary
+        let token = SecureUtil::create_signed_token(&[], secret, Duration::from_secs(60));
+        assert!(
+            SecureUtil::verify_signed_token(&token, secret)
+                .unwrap()
+                .is_empty()
+        );
+        let token = SecureUtil::create_signed_token(&[0, 255, 46], secret, Duration::from_secs(60));
+        assert_eq!(
+            SecureUtil::verify_signed_token(&token, secret).unwrap(),
+            [0, 255, 46]
+        );
+    }
+
+    #[test]
+    fn test_signed_token_tampered() {
+        let secret = b"server-side secret";
+        let token = SecureUtil::create_signed_token_at(b"user:42", secret, 2_000);
+        let parts: Vec<&str> = token.split('.').collect();
+
+        let forged_payload = format!("{}.{}.{}", TOKEN_B64.encode(b"user:1"), parts[1], parts[2]);
+        let extended = format!(
+            "{}.{}.{}",
+            parts[0],
+            TOKEN_B64.encode(u64::MAX.to_be_bytes()),
+            parts[2]
+        );
+        let bad_signature = format!("{}.{}.{}", parts[0], parts[1], TOKEN_B64.encode([0; 32]));
+        for tampered in [forged_payload, extended, bad_signature] {
+            let err = SecureUtil::verify_signed_token_at(&tampered, secret, 1_000).unwrap_err();
+            assert!(matches!(err, Error::Crypto(_)), "{err}");
+        }
+
+        // A different secret reads as tampering too, even once expired
+        let err = SecureUtil::verify_signed_token_at(&token, b"other", 1_000).unwrap_err();
+        assert!(matches!(err, Error::Crypto(_)));
+        let err = SecureUtil::verify_signed_token_at(&token, b"other", 3_000).unwrap_err();
+        assert!(matches!(err, Error::Crypto(_)));
+
+        for malformed in ["", "abc", "a.b", "a.b.!!!", &format!("{token}.x")] {
+            let err = SecureUtil::verify_signed_token_at(malformed, secret, 1_000).unwrap_err();
+            assert!(matches!(err, Error::Validation(_)), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_signed_token_expired() {
+        let secret = b"server-side secret";
+        let token = SecureUtil::create_signed_token_at(b"user:42", secret, 2_000);
+
+        assert_eq!(
+            SecureUtil::verify_signed_token_at(&token, secret, 1_999).unwrap(),
+            b"user:42"
+        );
+        for now in [2_000, 5_000] {
+            let err = SecureUtil::verify_signed_token_at(&token, secret, now).unwrap_err();
+            assert!(matches!(err, Error::PermissionDenied(_)), "{err}");
+        }
+
+        // A zero TTL expires immediately
+        let token = SecureUtil::create_signed_token(b"user:42", secret, Duration::ZERO);
+        assert!(matches!(
+            SecureUtil::verify_signed_token(&token, secret),
+            Err(Error::PermissionDenied(_))
+        ));
+    }
+
     #[test]
     fn test_constant_time_eq() {
         let a = b"secret";
@@ -451,4 +1252,148 @@ mod tests {
         assert!(!api_key2.is_empty());
         assert_ne!(api_key1, api_key2);
     }
+
+    #[test]
+    fn test_random_string_alphabets() {
+        for alphabet in [
+            Alphabet::Alphanumeric,
+            Alphabet::Hex,
+            Alphabet::Base58,
+            Alphabet::UrlSafe,
+            Alphabet::Numeric,
+        ] {
+            let s = SecureUtil::random_string(64, alphabet.clone());
+            assert_eq!(s.len(), 64);
+            assert!(
+                s.chars().all(|c| alphabet.chars().contains(c)),
+                "{alphabet:?}"
+            );
+        }
+
+        assert_eq!(Alphabet::Alphanumeric.size(), 62);
+        assert_eq!(Alphabet::Base58.size(), 58);
+        assert_eq!(Alphabet::UrlSafe.size(), 64);
+        assert!(!Alphabet::Base58.chars().contains(['0', 'O', 'I', 'l']));
+
+        let unicode = SecureUtil::random_string(5, "αβγ");
+        assert_eq!(unicode.chars().count(), 5);
+        assert!(SecureUtil::random_string(5, "").is_empty());
+    }
+
+    #[test]
+    fn test_random_string_and_uuid_uniqueness() {
+        use std::collections::HashSet;
+
+        let tokens: HashSet<String> = (0..10_000)
+            .map(|_| SecureUtil::random_string(16, Alphabet::Alphanumeric))
+            .collect();
+        assert_eq!(tokens.len(), 10_000);
+
+        let uuids: HashSet<String> = (0..10_000).map(|_| SecureUtil::uuid_v4()).collect();
+        assert_eq!(uuids.len(), 10_000);
+        for uuid in &uuids {
+            assert!(SecureUtil::is_valid_uuid(uuid));
+            assert_eq!(&uuid[14..15], "4");
+            assert!(matches!(&uuid[19..20], "8" | "9" | "a" | "b"));
+        }
+    }
+
+    fn fast_params() -> PasswordHashParams {
+        PasswordHashParams {
+            iterations: 1,
+            memory_kib: 256,
+            ..PasswordHashParams::default()
+        }
+    }
+
+    #[test]
+    fn test_hash_and_verify_password() {
+        let hash = SecureUtil::hash_password_with("hunter2", &fast_params()).unwrap();
+        assert!(hash.starts_with("$argon2id$v=19$m=256,t=1,p=1$"));
+
+        assert!(SecureUtil::verify_password("hunter2", &hash).unwrap());
+        assert!(!SecureUtil::verify_password("hunter3", &hash).unwrap());
+        assert!(!SecureUtil::verify_password("", &hash).unwrap());
+
+        let params = PasswordHashParams {
+            parallelism: 2,
+            hash_len: 64,
+            ..fast_params()
+        };
+        let hash = SecureUtil::hash_password_with("hunter2", &params).unwrap();
+        assert!(hash.starts_with("$argon2id$v=19$m=256,t=1,p=2$"));
+        assert!(SecureUtil::verify_password("hunter2", &hash).unwrap());
+    }
+
+    #[test]
+    fn test_password_hashes_are_salted() {
+        let hash1 = SecureUtil::hash_password_with("same password", &fast_params()).unwrap();
+        let hash2 = SecureUtil::hash_password_with("same password", &fast_params()).unwrap();
+        assert_ne!(hash1, hash2);
+        assert!(SecureUtil::verify_password("same password", &hash1).unwrap());
+        assert!(SecureUtil::verify_password("same password", &hash2).unwrap());
+    }
+
+    #[test]
+    fn test_verify_password_known_vector() {
+        // Argon2id reference hash from the argon2 CLI
+        let hash = "$argon2id$v=19$m=65536,t=2,p=1$c29tZXNhbHQ$CTFhFdXPJO1aFaMaO6Mm5c8y7cJHAph8ArZWb2GRPPc";
+        assert!(SecureUtil::verify_password("password", hash).unwrap());
+        assert!(!SecureUtil::verify_password("Password", hash).unwrap());
+    }
+
+    #[test]
+    fn test_verify_password_rejects_malformed_hash() {
+        for hash in [
+            "",
+            "plaintext",
+            "$pbkdf2-sha256$i=1000,l=32$c2FsdA$xeR41ZKIyEGqUw22hFxMjZYok6ABzk4RpJY4c6qYE0o",
+            "$argon2i$v=19$m=65536,t=3,p=4$c2FsdHNhbHQ$aGFzaGhhc2g",
+            "$argon2id$v=16$m=256,t=1,p=1$c2FsdHNhbHQ$aGFzaGhhc2g",
+            "$argon2id$m=256,t=1,p=1$c2FsdHNhbHQ$aGFzaGhhc2g",
+            "$argon2id$v=19$m=256,t=0,p=1$c2FsdHNhbHQ$aGFzaGhhc2g",
+            "$argon2id$v=19$m=256,t=1,p=0$c2FsdHNhbHQ$aGFzaGhhc2g",
+            "$argon2id$v=19$m=256,i=1,p=1$c2FsdHNhbHQ$aGFzaGhhc2g",
+            "$argon2id$v=19$m=256,t=1$c2FsdHNhbHQ$aGFzaGhhc2g",
+            "$argon2id$v=19$m=256,t=1,p=1$!!!$aGFzaGhhc2g",
+            "$argon2id$v=19$m=256,t=1,p=1$c2FsdHNhbHQ$",
+        ] {
+            assert!(SecureUtil::verify_password("pw", hash).is_err(), "{hash}");
+        }
+
+        assert!(
+            SecureUtil::hash_password_with(
+                "pw",
+                &PasswordHashParams {
+                    iterations: 0,
+                    ..PasswordHashParams::default()
+                }
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_verify_password_rejects_excessive_costs() {
+        let salt = "c29tZXNhbHQ";
+        let hash = "Q1RGaEZkWFBKTzFhRmFNYU82TW01Yzh5N2NKSEFwaDg";
+        let huge_hash = PHC_B64.encode([0u8; MAX_PASSWORD_HASH_LEN + 1]);
+        for stored in [
+            format!("$argon2id$v=19$m=4294967295,t=1,p=1${salt}${hash}"),
+            format!("$argon2id$v=19$m=256,t=4294967295,p=1${salt}${hash}"),
+            format!("$argon2id$v=19$m=256,t=1,p=4294967295${salt}${hash}"),
+            format!("$argon2id$v=19$m=256,t=1,p=1${salt}${huge_hash}"),
+        ] {
+            assert!(
+                SecureUtil::verify_password("pw", &stored).is_err(),
+                "{stored}"
+            );
+        }
+
+        let params = PasswordHashParams {
+            memory_kib: MAX_ARGON2_MEMORY_KIB + 1,
+            ..PasswordHashParams::default()
+        };
+        assert!(SecureUtil::hash_password_with("pw", &params).is_err());
+    }
 }
@@ -6,10 +6,29 @@
 use crate::error::{Error, Result};
 use aes_gcm::{
     Aes256Gcm, Key, Nonce,
-    aead::{Aead, KeyInit},
+    aead::{Aead, KeyInit, Payload},
 };
 use base64::Engine;
 use rand::{RngCore, thread_rng};
+use std::io::{Read, Write};
+
+/// Magic bytes at the start of a stream produced by [`AesUtil::encrypt_file`]
+const STREAM_MAGIC: &[u8; 4] = b"RTAE";
+
+/// Stream format version
+const STREAM_VERSION: u8 = 1;
+
+/// Length of the random nonce prefix stored in the stream header
+const STREAM_NONCE_PREFIX_SIZE: usize = 7;
+
+/// Total stream header length: magic + version + chunk size + nonce prefix
+const STREAM_HEADER_SIZE: usize = 4 + 1 + 4 + STREAM_NONCE_PREFIX_SIZE;
+
+/// Upper bound for the chunk size accepted from a stream header
+const STREAM_MAX_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+/// AES-GCM authentication tag size in bytes
+const TAG_SIZE: usize = 16;
 
 /// AES encryption utility
 pub struct AesUtil;
@@ -21,6 +40,9 @@ impl AesUtil {
     /// AES-GCM nonce size in bytes
     pub const NONCE_SIZE: usize = 12;
 
+    /// Default plaintext chunk size used by [`AesUtil::encrypt_file`] (64 KiB)
+    pub const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
     /// Generate a random AES-256 key
     ///
     /// # Examples
@@ -380,6 +402,227 @@ impl AesUtil {
         pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, 100_000, &mut key);
         Ok(key)
     }
+
+    /// Encrypt a stream with AES-256-GCM in fixed-size chunks
+    ///
+    /// Uses [`AesUtil::STREAM_CHUNK_SIZE`] byte chunks, so memory use stays
+    /// constant regardless of input size. See
+    /// [`AesUtil::encrypt_file_with_chunk_size`] for the output layout.
+    /// Returns the number of plaintext bytes processed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::crypto::AesUtil;
+    ///
+    /// let key = AesUtil::generate_key();
+    /// let plaintext = vec![42u8; 200_000];
+    ///
+    /// let mut encrypted = Vec::new();
+    /// AesUtil::encrypt_file(plaintext.as_slice(), &mut encrypted, &key).unwrap();
+    ///
+    /// let mut decrypted = Vec::new();
+    /// AesUtil::decrypt_file(encrypted.as_slice(), &mut decrypted, &key).unwrap();
+    /// assert_eq!(decrypted, plaintext);
+    /// ```
+    pub fn encrypt_file<R: Read, W: Write>(reader: R, writer: W, key: &[u8]) -> Result<u64> {
+        Self::encrypt_file_with_chunk_size(reader, writer, key, Self::STREAM_CHUNK_SIZE)
+    }
+
+    /// Encrypt a stream with AES-256-GCM using a custom chunk size
+    ///
+    /// The output is self-describing, so [`AesUtil::decrypt_file`] only needs
+    /// the key. Layout (all integers big-endian):
+    ///
+    /// ```text
+    /// offset  size  field
+    /// 0       4     magic "RTAE"
+    /// 4       1     format version (1)
+    /// 5       4     chunk size in bytes (u32)
+    /// 9       7     random nonce prefix
+    /// 16      ...   chunks: ciphertext (<= chunk size) followed by a 16-byte GCM tag
+    /// ```
+    ///
+    /// Every chunk except the last holds exactly `chunk size` bytes of
+    /// plaintext; the last one may be shorter or empty. The nonce of chunk `i`
+    /// is `prefix || i (u32) || last flag (1 byte)` and the 16-byte header is
+    /// passed as associated data, so reordering, truncating, appending to or
+    /// editing the header of a stream all fail authentication.
+    ///
+    /// `chunk_size` must be between 1 byte and 16 MiB.
+    pub fn encrypt_file_with_chunk_size<R: Read, W: Write>(
+        mut reader: R,
+        mut writer: W,
+        key: &[u8],
+        chunk_size: usize,
+    ) -> Result<u64> {
+        let cipher = Self::stream_cipher(key)?;
+        Self::check_chunk_size(chunk_size)?;
+
+        let mut header = [0u8; STREAM_HEADER_SIZE];
+        header[..4].copy_from_slice(STREAM_MAGIC);
+        header[4] = STREAM_VERSION;
+        header[5..9].copy_from_slice(&u32::try_from(chunk_size).unwrap_or(u32::MAX).to_be_bytes());
+        thread_rng().fill_bytes(&mut header[9..]);
+        writer.write_all(&header)?;
+
+        let mut current = vec![0u8; chunk_size];
+        let mut next = vec![0u8; chunk_size];
+        let mut current_len = read_full(&mut reader, &mut current)?;
+        let mut counter: u32 = 0;
+        let mut total: u64 = 0;
+
+        loop {
+            // Look one chunk ahead to know whether this one is the last
+            let next_len = if current_len == chunk_size {
+                read_full(&mut reader, &mut next)?
+            } else {
+                0
+            };
+            let last = next_len == 0;
+
+            let nonce = stream_nonce(&header, counter, last);
+            let ciphertext = cipher
+                .encrypt(
+                    Nonce::from_slice(&nonce),
+                    Payload {
+                        msg: &current[..current_len],
+                        aad: &header,
+                    },
+                )
+                .map_err(|e| Error::crypto(format!("Encryption failed: {e}")))?;
+            writer.write_all(&ciphertext)?;
+            total += current_len as u64;
+
+            if last {
+                break;
+            }
+            counter = counter
+                .checked_add(1)
+                .ok_or_else(|| Error::crypto("Stream too long: chunk counter overflow"))?;
+            std::mem::swap(&mut current, &mut next);
+            current_len = next_len;
+        }
+
+        writer.flush()?;
+        Ok(total)
+    }
+
+    /// Decrypt a stream produced by [`AesUtil::encrypt_file`]
+    ///
+    /// The chunk size and nonce are read from the stream header. Plaintext is
+    /// written chunk by chunk as each one is authenticated, so on error the
+    /// writer may already hold a prefix of the data and should be discarded.
+    /// Returns the number of plaintext bytes written.
+    pub fn decrypt_file<R: Read, W: Write>(
+        mut reader: R,
+        mut writer: W,
+        key: &[u8],
+    ) -> Result<u64> {
+        let cipher = Self::stream_cipher(key)?;
+
+        let mut header = [0u8; STREAM_HEADER_SIZE];
+        if read_full(&mut reader, &mut header)? < STREAM_HEADER_SIZE || &header[..4] != STREAM_MAGIC
+        {
+            return Err(Error::crypto("Invalid encrypted stream: missing header"));
+        }
+        if header[4] != STREAM_VERSION {
+            return Err(Error::crypto(format!(
+                "Unsupported encrypted stream version: {}",
+                header[4]
+            )));
+        }
+        let chunk_size = u32::from_be_bytes([header[5], header[6], header[7], header[8]]) as usize;
+        Self::check_chunk_size(chunk_size)?;
+
+        let sealed_size = chunk_size + TAG_SIZE;
+        let mut current = vec![0u8; sealed_size];
+        let mut next = vec![0u8; sealed_size];
+        let mut current_len = read_full(&mut reader, &mut current)?;
+        let mut counter: u32 = 0;
+        let mut total: u64 = 0;
+
+        loop {
+            let next_len = if current_len == sealed_size {
+                read_full(&mut reader, &mut next)?
+            } else {
+                0
+            };
+            let last = next_len == 0;
+
+            let nonce = stream_nonce(&header, counter, last);
+            let plaintext = cipher
+                .decrypt(
+                    Nonce::from_slice(&nonce),
+                    Payload {
+                        msg: &current[..current_len],
+                        aad: &header,
+                    },
+                )
+                .map_err(|_| {
+                    Error::crypto(format!(
+                        "Decryption failed at chunk {counter}: wrong key, or data is corrupted or truncated"
+                    ))
+                })?;
+            writer.write_all(&plaintext)?;
+            total += plaintext.len() as u64;
+
+            if last {
+                break;
+            }
+            counter = counter
+                .checked_add(1)
+                .ok_or_else(|| Error::crypto("Stream too long: chunk counter overflow"))?;
+            std::mem::swap(&mut current, &mut next);
+            current_len = next_len;
+        }
+
+        writer.flush()?;
+        Ok(total)
+    }
+
+    fn stream_cipher(key: &[u8]) -> Result<Aes256Gcm> {
+        if key.len() != Self::KEY_SIZE {
+            return Err(Error::crypto(format!(
+                "Invalid key size: expected {}, got {}",
+                Self::KEY_SIZE,
+                key.len()
+            )));
+        }
+        Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)))
+    }
+
+    fn check_chunk_size(chunk_size: usize) -> Result<()> {
+        if chunk_size == 0 || chunk_size > STREAM_MAX_CHUNK_SIZE {
+            return Err(Error::crypto(format!(
+                "Invalid chunk size: {chunk_size} (must be between 1 and {STREAM_MAX_CHUNK_SIZE})"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Build the per-chunk nonce: header nonce prefix, chunk counter and last-chunk flag
+fn stream_nonce(header: &[u8; STREAM_HEADER_SIZE], counter: u32, last: bool) -> [u8; 12] {
+    let mut nonce = [0u8; 12];
+    nonce[..STREAM_NONCE_PREFIX_SIZE].copy_from_slice(&header[9..]);
+    nonce[7..11].copy_from_slice(&counter.to_be_bytes());
+    nonce[11] = u8::from(last);
+    nonce
+}
+
+/// Read until `buf` is full or the reader is exhausted, returning the bytes read
+fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(filled)
 }
 
 #[cfg(test)]
@@ -523,4 +766,91 @@ mod tests {
         let result = AesUtil::decrypt(&ciphertext, &key2, &nonce);
         assert!(result.is_err()); // Should fail with wrong key
     }
+
+    #[test]
+    fn test_encrypt_decrypt_file_round_trip() {
+        use std::io::{Seek, SeekFrom};
+
+        let key = AesUtil::generate_key();
+        // Larger than several chunks and not a multiple of the chunk size
+        let data: Vec<u8> = (0..AesUtil::STREAM_CHUNK_SIZE * 3 + 1234)
+            .map(|i| u8::try_from(i % 251).unwrap())
+            .collect();
+
+        let mut source = tempfile::tempfile().unwrap();
+        source.write_all(&data).unwrap();
+        source.seek(SeekFrom::Start(0)).unwrap();
+
+        let mut encrypted = tempfile::tempfile().unwrap();
+        let written = AesUtil::encrypt_file(&mut source, &mut encrypted, &key).unwrap();
+        assert_eq!(written, data.len() as u64);
+
+        let expected_len = STREAM_HEADER_SIZE + data.len() + 4 * TAG_SIZE;
+        assert_eq!(encrypted.metadata().unwrap().len(), expected_len as u64);
+
+        encrypted.seek(SeekFrom::Start(0)).unwrap();
+        let mut decrypted = Vec::new();
+        let read = AesUtil::decrypt_file(&mut encrypted, &mut decrypted, &key).unwrap();
+        assert_eq!(read, data.len() as u64);
+        assert_eq!(decrypted, data);
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_file_chunk_boundaries() {
+        let key = AesUtil::generate_key();
+        for len in [0, 1, 15, 16, 17, 32, 33] {
+            let data = vec![7u8; len];
+            let mut encrypted = Vec::new();
+            AesUtil::encrypt_file_with_chunk_size(data.as_slice(), &mut encrypted, &key, 16)
+                .unwrap();
+
+            let mut decrypted = Vec::new();
+            AesUtil::decrypt_file(encrypted.as_slice(), &mut decrypted, &key).unwrap();
+            assert_eq!(decrypted, data, "len {len}");
+        }
+    }
+
+    #[test]
+    fn test_decrypt_file_rejects_tampering() {
+        let key = AesUtil::generate_key();
+        let data = vec![1u8; 64];
+        let mut encrypted = Vec::new();
+        AesUtil::encrypt_file_with_chunk_size(data.as_slice(), &mut encrypted, &key, 16).unwrap();
+        let sealed = 16 + TAG_SIZE;
+
+        let decrypt = |bytes: &[u8], key: &[u8]| {
+            AesUtil::decrypt_file(bytes, &mut Vec::new(), key).map_err(|e| e.to_string())
+        };
+        assert!(decrypt(&encrypted, &key).is_ok());
+
+        // Wrong key
+        assert!(decrypt(&encrypted, &AesUtil::generate_key()).is_err());
+
+        // Truncated at a chunk boundary: chunk 2 was not sealed as the last one
+        let truncated = &encrypted[..STREAM_HEADER_SIZE + 3 * sealed];
+        let err = decrypt(truncated, &key).unwrap_err();
+        assert!(err.contains("chunk 2"), "{err}");
+
+        // Swapped chunks
+        let mut swapped = encrypted.clone();
+        let (a, b) = (STREAM_HEADER_SIZE, STREAM_HEADER_SIZE + sealed);
+        let first: Vec<u8> = swapped[a..b].to_vec();
+        swapped.copy_within(b..b + sealed, a);
+        swapped[b..b + sealed].copy_from_slice(&first);
+        assert!(decrypt(&swapped, &key).is_err());
+
+        // Flipped ciphertext bit
+        let mut flipped = encrypted.clone();
+        flipped[STREAM_HEADER_SIZE + 3] ^= 1;
+        assert!(decrypt(&flipped, &key).is_err());
+
+        // Modified header
+        let mut bad_header = encrypted.clone();
+        bad_header[10] ^= 1;
+        assert!(decrypt(&bad_header, &key).is_err());
+
+        // Not an encrypted stream
+        let err = decrypt(b"plain text", &key).unwrap_err();
+        assert!(err.contains("missing header"), "{err}");
+    }
 }
@@ -3,9 +3,12 @@
 //! This module provides tools for managing database schema changes
 //! through versioned migrations.
 
+use crate::crypto::ShaUtil;
 use crate::db::connection::DatabaseConnection;
 use crate::error::{Error, Result};
+use std::collections::BTreeMap;
 use std::fmt;
+use std::path::Path;
 
 /// Database migration
 #[derive(Debug, Clone)]
@@ -54,10 +57,93 @@ impl Migration {
         }
     }
 
+    /// Load migrations from `*.up.sql` / `*.down.sql` files in a directory
+    ///
+    /// Files are named `YYYYMMDD_HHMMSS_<name>.up.sql` and
+    /// `YYYYMMDD_HHMMSS_<name>.down.sql`. The timestamp becomes the version
+    /// (see [`MigrationTimestamp::parse`]) and each file is split into
+    /// statements on `;`. The down file is optional. Other files are ignored,
+    /// and the result is sorted by version.
+    ///
+    /// Fails with the offending file name when a `.sql` file does not follow
+    /// the naming scheme or a down file has no matching up file.
+    pub fn from_dir(dir: impl AsRef<Path>) -> Result<Vec<Migration>> {
+        let mut migrations: BTreeMap<String, Migration> = BTreeMap::new();
+        let mut down_only: Vec<String> = Vec::new();
+
+        let mut entries: Vec<_> = std::fs::read_dir(dir)?.collect::<std::io::Result<_>>()?;
+        entries.sort_by_key(|entry| entry.file_name());
+
+        for entry in entries {
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            if !entry.file_type()?.is_file() || !file_name.ends_with(".sql") {
+                continue;
+            }
+
+            let (stem, is_up) = if let Some(stem) = file_name.strip_suffix(".up.sql") {
+                (stem, true)
+            } else if let Some(stem) = file_name.strip_suffix(".down.sql") {
+                (stem, false)
+            } else {
+                return Err(Error::validation(format!(
+                    "Migration file must end with .up.sql or .down.sql: {}",
+                    file_name
+                )));
+            };
+
+            let malformed = || {
+                Error::validation(format!(
+                    "Migration file name must look like YYYYMMDD_HHMMSS_name.up.sql: {}",
+                    file_name
+                ))
+            };
+            let (timestamp, name) = stem
+                .get(..15)
+                .zip(stem.get(15..))
+                .and_then(|(timestamp, rest)| Some((timestamp, rest.strip_prefix('_')?)))
+                .filter(|(_, name)| !name.is_empty())
+                .ok_or_else(malformed)?;
+            let version = MigrationTimestamp::parse(timestamp).map_err(|_| malformed())?;
+
+            let statements = split_sql_statements(&std::fs::read_to_string(entry.path())?);
+            let migration = migrations
+                .entry(stem.to_string())
+                .or_insert_with(|| Migration::new(&version, name, Vec::new(), Vec::new()));
+            if is_up {
+                migration.up_sql = statements;
+            } else {
+                migration.down_sql = statements;
+                down_only.push(stem.to_string());
+            }
+        }
+
+        // A down file sorts before its up file, so check pairs once all are read
+        for stem in down_only {
+            if migrations[&stem].up_sql.is_empty() {
+                return Err(Error::validation(format!(
+                    "Migration has a down file but no up file: {}.down.sql",
+                    stem
+                )));
+            }
+        }
+
+        let mut migrations: Vec<Migration> = migrations.into_values().collect();
+        migrations.sort_by(|a, b| a.version.cmp(&b.version));
+        Ok(migrations)
+    }
+
     /// Get the migration's unique identifier
     pub fn id(&self) -> String {
         format!("{}_{}", self.version, self.name)
     }
+
+    /// SHA-256 checksum (hex) of the migration's up SQL
+    ///
+    /// The checksum is recorded when the migration is applied so later edits
+    /// to an applied migration can be detected with [`MigrationRunner::verify`].
+    pub fn checksum(&self) -> String {
+        ShaUtil::sha256_hex(self.up_sql.join("\n").as_bytes())
+    }
 }
 
 impl fmt::Display for Migration {
@@ -98,6 +184,31 @@ impl MigrationRunner {
         }
     }
 
+    /// Create a migration runner with the migrations found in a directory
+    ///
+    /// See [`Migration::from_dir`] for the expected file layout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use yimi_rutool::db::{DatabaseConnection, DatabaseConfig, DatabaseType, MigrationRunner};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let config = DatabaseConfig::new(DatabaseType::SQLite, "app.db");
+    ///     let conn = DatabaseConnection::new(config).await?;
+    ///     let runner = MigrationRunner::from_dir(conn, "migrations")?;
+    ///     runner.init().await?;
+    ///     runner.migrate_up().await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn from_dir(connection: DatabaseConnection, dir: impl AsRef<Path>) -> Result<Self> {
+        let mut runner = Self::new(connection);
+        runner.add_migrations(Migration::from_dir(dir)?);
+        Ok(runner)
+    }
+
     /// Set a custom migrations table name
     pub fn with_migrations_table(mut self, table_name: &str) -> Self {
         self.migrations_table = table_name.to_string();
@@ -134,15 +245,76 @@ impl MigrationRunner {
             r"CREATE TABLE IF NOT EXISTS {} (
                 version TEXT PRIMARY KEY,
                 name TEXT NOT NULL,
+                checksum TEXT,
                 applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
             )",
             self.migrations_table
         );
 
         self.connection.execute(&create_table_sql).await?;
+
+        // Tables created before checksums were tracked lack the column
+        let probe_sql = format!("SELECT checksum FROM {} WHERE 1 = 0", self.migrations_table);
+        if self.connection.fetch_all(&probe_sql).await.is_err() {
+            let alter_sql = format!(
+                "ALTER TABLE {} ADD COLUMN checksum TEXT",
+                self.migrations_table
+            );
+            self.connection.execute(&alter_sql).await?;
+        }
+
         Ok(())
     }
 
+    /// Verify that applied migrations have not been edited since they ran
+    ///
+    /// Compares the checksum recorded for each applied migration with the
+    /// checksum of the registered migration's current SQL, and returns a
+    /// validation error listing every mismatch. Migrations recorded without a
+    /// checksum are skipped. This check also runs before `migrate_up` and
+    /// `migrate_to` apply anything.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::db::{DatabaseConnection, MigrationRunner};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let runner = MigrationRunner::new(DatabaseConnection::Mock);
+    ///     runner.verify().await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn verify(&self) -> Result<()> {
+        self.init().await?;
+
+        let sql = format!(
+            "SELECT version, checksum FROM {} ORDER BY version",
+            self.migrations_table
+        );
+        let rows = self.connection.fetch_all(&sql).await?;
+
+        let mismatched: Vec<String> = rows
+            .iter()
+            .filter_map(|row| {
+                let version = row.get("version")?.as_str()?;
+                let recorded = row.get("checksum")?.as_str()?;
+                let migration = self.migrations.iter().find(|m| m.version == version)?;
+                (migration.checksum() != recorded).then(|| migration.to_string())
+            })
+            .collect();
+
+        if mismatched.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::validation(format!(
+                "Applied migrations have been modified: {}",
+                mismatched.join(", ")
+            )))
+        }
+    }
+
     /// Get list of applied migrations
     pub async fn get_applied_migrations(&self) -> Result<Vec<String>> {
         let sql = format!(
@@ -205,7 +377,7 @@ impl MigrationRunner {
     /// }
     /// ```
     pub async fn migrate_up(&self) -> Result<Vec<MigrationResult>> {
-        self.init().await?;
+        self.verify().await?;
 
         let pending = self.get_pending_migrations().await?;
         let mut results = Vec::new();
@@ -230,7 +402,7 @@ impl MigrationRunner {
 
     /// Run migrations up to a specific version
     pub async fn migrate_to(&self, target_version: &str) -> Result<Vec<MigrationResult>> {
-        self.init().await?;
+        self.verify().await?;
 
         let pending = self.get_pending_migrations().await?;
         let mut results = Vec::new();
@@ -276,6 +448,72 @@ impl MigrationRunner {
         Ok(None)
     }
 
+    /// Rollback the last `steps` applied migrations, newest first
+    ///
+    /// Every migration to be reverted must have `down` SQL; otherwise an error
+    /// is returned before anything is rolled back. Rolling back stops at the
+    /// first failure, which is reported in the returned results.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::db::{DatabaseConnection, Migration, MigrationRunner};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut runner = MigrationRunner::new(DatabaseConnection::Mock);
+    ///     runner.add_migration(Migration::simple(
+    ///         "001",
+    ///         "create_users",
+    ///         "CREATE TABLE users (id INTEGER PRIMARY KEY)",
+    ///         "DROP TABLE users",
+    ///     ));
+    ///
+    ///     let results = runner.rollback_steps(2).await?;
+    ///     println!("Rolled back {} migrations", results.len());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn rollback_steps(&self, steps: usize) -> Result<Vec<MigrationResult>> {
+        let applied = self.get_applied_migrations().await?;
+
+        let mut to_rollback = Vec::new();
+        for version in applied.iter().rev().take(steps) {
+            let migration = self
+                .migrations
+                .iter()
+                .find(|m| &m.version == version)
+                .ok_or_else(|| {
+                    Error::not_found(format!("Applied migration {version} is not registered"))
+                })?;
+
+            if migration.down_sql.is_empty() {
+                return Err(Error::validation(format!(
+                    "Migration {migration} has no down SQL and cannot be rolled back"
+                )));
+            }
+            to_rollback.push(migration);
+        }
+
+        let mut results = Vec::new();
+        for migration in to_rollback {
+            let result = self.rollback_migration(migration).await;
+            let migration_result = MigrationResult {
+                migration: migration.clone(),
+                success: result.is_ok(),
+                error: result.err().map(|e| e.to_string()),
+            };
+
+            results.push(migration_result.clone());
+
+            if !migration_result.success {
+                break;
+            }
+        }
+
+        Ok(results)
+    }
+
     /// Rollback to a specific version
     pub async fn rollback_to(&self, target_version: &str) -> Result<Vec<MigrationResult>> {
         let applied = self.get_applied_migrations().await?;
@@ -327,11 +565,11 @@ impl MigrationRunner {
     /// Apply a single migration
     async fn apply_migration(&self, migration: &Migration) -> Result<()> {
         // Begin transaction
-        let tx = self.connection.begin_transaction().await?;
+        let mut tx = self.connection.begin_transaction().await?;
 
         // Execute up SQL statements
         for sql in &migration.up_sql {
-            if let Err(e) = self.connection.execute(sql).await {
+            if let Err(e) = tx.execute(sql).await {
                 tx.rollback().await?;
                 return Err(Error::database(format!(
                     "Failed to apply migration {}: {}",
@@ -342,11 +580,14 @@ impl MigrationRunner {
 
         // Record migration as applied
         let record_sql = format!(
-            "INSERT INTO {} (version, name) VALUES ('{}', '{}')",
-            self.migrations_table, migration.version, migration.name
+            "INSERT INTO {} (version, name, checksum) VALUES ('{}', '{}', '{}')",
+            self.migrations_table,
+            migration.version,
+            migration.name,
+            migration.checksum()
         );
 
-        if let Err(e) = self.connection.execute(&record_sql).await {
+        if let Err(e) = tx.execute(&record_sql).await {
             tx.rollback().await?;
             return Err(Error::database(format!(
                 "Failed to record migration {}: {}",
@@ -362,11 +603,11 @@ impl MigrationRunner {
     /// Rollback a single migration
     async fn rollback_migration(&self, migration: &Migration) -> Result<()> {
         // Begin transaction
-        let tx = self.connection.begin_transaction().await?;
+        let mut tx = self.connection.begin_transaction().await?;
 
         // Execute down SQL statements
         for sql in &migration.down_sql {
-            if let Err(e) = self.connection.execute(sql).await {
+            if let Err(e) = tx.execute(sql).await {
                 tx.rollback().await?;
                 return Err(Error::database(format!(
                     "Failed to rollback migration {}: {}",
@@ -381,7 +622,7 @@ impl MigrationRunner {
             self.migrations_table, migration.version
         );
 
-        if let Err(e) = self.connection.execute(&remove_sql).await {
+        if let Err(e) = tx.execute(&remove_sql).await {
             tx.rollback().await?;
             return Err(Error::database(format!(
                 "Failed to remove migration record {}: {}",
@@ -588,6 +829,45 @@ impl MigrationTimestamp {
         format!("{}", now.as_secs())
     }
 
+    /// Parse a `YYYYMMDD_HHMMSS` file name timestamp into a `YYYYMMDDHHMMSS` version
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::db::MigrationTimestamp;
+    ///
+    /// assert_eq!(
+    ///     MigrationTimestamp::parse("20240101_120000").unwrap(),
+    ///     "20240101120000"
+    /// );
+    /// assert!(MigrationTimestamp::parse("20241301_120000").is_err());
+    /// ```
+    pub fn parse(timestamp: &str) -> Result<String> {
+        let invalid = || Error::validation(format!("Invalid migration timestamp: {}", timestamp));
+
+        let (date, time) = timestamp.split_once('_').ok_or_else(invalid)?;
+        if date.len() != 8
+            || time.len() != 6
+            || !date.chars().chain(time.chars()).all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+
+        let field = |s: &str, range: std::ops::Range<usize>| s[range].parse::<u32>().unwrap_or(0);
+        let (month, day) = (field(date, 4..6), field(date, 6..8));
+        let (hour, minute, second) = (field(time, 0..2), field(time, 2..4), field(time, 4..6));
+        if !(1..=12).contains(&month)
+            || !(1..=31).contains(&day)
+            || hour > 23
+            || minute > 59
+            || second > 59
+        {
+            return Err(invalid());
+        }
+
+        Ok(format!("{}{}", date, time))
+    }
+
     /// Generate a formatted timestamp (YYYYMMDDHHMMSS)
     pub fn generate_formatted() -> String {
         #[cfg(feature = "chrono")]
@@ -603,6 +883,49 @@ impl MigrationTimestamp {
     }
 }
 
+/// Split a SQL script into statements on `;`, ignoring semicolons inside
+/// quoted strings and `--` comments
+fn split_sql_statements(script: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut chars = script.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"' | '`') => quote = Some(c),
+            (None, '-') if chars.peek() == Some(&'-') => {
+                // Skip the comment up to the end of the line
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        current.push('\n');
+                        break;
+                    }
+                }
+                continue;
+            }
+            (None, ';') => {
+                let statement = current.trim();
+                if !statement.is_empty() {
+                    statements.push(statement.to_string());
+                }
+                current.clear();
+                continue;
+            }
+            (None, _) => {}
+        }
+        current.push(c);
+    }
+
+    let statement = current.trim();
+    if !statement.is_empty() {
+        statements.push(statement.to_string());
+    }
+    statements
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -660,6 +983,129 @@ mod tests {
         assert_eq!(status.total_migrations, 1);
     }
 
+    async fn sqlite_runner() -> MigrationRunner {
+        use crate::db::{DatabaseConfig, DatabaseType};
+
+        let config =
+            DatabaseConfig::new(DatabaseType::SQLite, "sqlite::memory:").with_max_connections(1);
+        let connection = DatabaseConnection::new(config).await.unwrap();
+        let mut runner = MigrationRunner::new(connection);
+        runner.add_migrations(vec![
+            Migration::simple(
+                "001",
+                "create_users",
+                "CREATE TABLE users (id INTEGER PRIMARY KEY)",
+                "DROP TABLE users",
+            ),
+            Migration::simple(
+                "002",
+                "create_posts",
+                "CREATE TABLE posts (id INTEGER PRIMARY KEY)",
+                "DROP TABLE posts",
+            ),
+            Migration::simple(
+                "003",
+                "create_tags",
+                "CREATE TABLE tags (id INTEGER PRIMARY KEY)",
+                "DROP TABLE tags",
+            ),
+        ]);
+        runner
+    }
+
+    async fn table_names(runner: &MigrationRunner) -> Vec<String> {
+        let rows = runner
+            .connection
+            .fetch_all("SELECT name FROM sqlite_master WHERE type = 'table' ORDER BY name")
+            .await
+            .unwrap();
+        rows.iter()
+            .filter_map(|row| row["name"].as_str().map(str::to_string))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_rollback_steps_reverts_newest_first() {
+        let runner = sqlite_runner().await;
+
+        let applied = runner.migrate_up().await.unwrap();
+        assert_eq!(applied.len(), 3);
+        assert!(applied.iter().all(|r| r.success));
+
+        let rolled_back = runner.rollback_steps(2).await.unwrap();
+        let versions: Vec<&str> = rolled_back
+            .iter()
+            .map(|r| r.migration.version.as_str())
+            .collect();
+        assert_eq!(versions, vec!["003", "002"]);
+        assert!(rolled_back.iter().all(|r| r.success));
+
+        assert_eq!(runner.get_applied_migrations().await.unwrap(), vec!["001"]);
+        assert_eq!(
+            table_names(&runner).await,
+            vec!["schema_migrations", "users"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rollback_steps_requires_down_sql() {
+        let mut runner = sqlite_runner().await;
+        runner.add_migration(Migration::new(
+            "004",
+            "irreversible",
+            vec!["CREATE TABLE audit (id INTEGER PRIMARY KEY)".to_string()],
+            vec![],
+        ));
+        runner.migrate_up().await.unwrap();
+
+        let err = runner.rollback_steps(2).await.unwrap_err();
+        assert!(err.to_string().contains("004"));
+
+        // Nothing was rolled back because validation happens up front
+        assert_eq!(runner.get_applied_migrations().await.unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_migration_checksum() {
+        let migration = Migration::simple(
+            "001",
+            "create_users",
+            "CREATE TABLE users (id INTEGER)",
+            "DROP TABLE users",
+        );
+        let mut edited = migration.clone();
+        edited.up_sql = vec!["CREATE TABLE users (id INTEGER, name TEXT)".to_string()];
+
+        assert_eq!(migration.checksum().len(), 64);
+        assert_eq!(migration.checksum(), migration.clone().checksum());
+        assert_ne!(migration.checksum(), edited.checksum());
+    }
+
+    #[tokio::test]
+    async fn test_verify_detects_edited_migration() {
+        let mut runner = sqlite_runner().await;
+        runner.migrate_up().await.unwrap();
+        assert!(runner.verify().await.is_ok());
+
+        runner.migrations[1].up_sql =
+            vec!["CREATE TABLE posts (id INTEGER PRIMARY KEY, title TEXT)".to_string()];
+
+        let err = runner.verify().await.unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+        assert!(err.to_string().contains("002: create_posts"));
+        assert!(!err.to_string().contains("001"));
+
+        // Pending migrations are not applied while drift is detected
+        runner.add_migration(Migration::simple(
+            "004",
+            "create_audit",
+            "CREATE TABLE audit (id INTEGER PRIMARY KEY)",
+            "DROP TABLE audit",
+        ));
+        assert!(runner.migrate_up().await.is_err());
+        assert_eq!(runner.get_applied_migrations().await.unwrap().len(), 3);
+    }
+
     #[test]
     fn test_migration_validation() {
         let mut runner = MigrationRunner::new(DatabaseConnection::Mock);
@@ -714,6 +1160,85 @@ mod tests {
         assert!(version2.parse::<u64>().is_ok());
     }
 
+    #[test]
+    fn test_migration_timestamp_parse() {
+        assert_eq!(
+            MigrationTimestamp::parse("20240101_120000").unwrap(),
+            "20240101120000"
+        );
+        assert!(MigrationTimestamp::parse("20240101120000").is_err());
+        assert!(MigrationTimestamp::parse("2024011_120000").is_err());
+        assert!(MigrationTimestamp::parse("20240132_120000").is_err());
+        assert!(MigrationTimestamp::parse("20240101_246000").is_err());
+        assert!(MigrationTimestamp::parse("2024010a_120000").is_err());
+    }
+
+    #[test]
+    fn test_split_sql_statements() {
+        let statements = split_sql_statements(
+            "-- create; table\nCREATE TABLE t (v TEXT);\nINSERT INTO t VALUES ('a;b');\n\n",
+        );
+        assert_eq!(
+            statements,
+            vec!["CREATE TABLE t (v TEXT)", "INSERT INTO t VALUES ('a;b')"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migrations_from_dir() {
+        use std::fs;
+
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, sql: &str| fs::write(dir.path().join(name), sql).unwrap();
+        write(
+            "20240201_090000_add_email.up.sql",
+            "ALTER TABLE users ADD COLUMN email TEXT;",
+        );
+        write(
+            "20240101_120000_create_users.up.sql",
+            "CREATE TABLE users (id INTEGER PRIMARY KEY);\nCREATE INDEX idx_users_id ON users (id);",
+        );
+        write("20240101_120000_create_users.down.sql", "DROP TABLE users;");
+        write("README.md", "not a migration");
+
+        let migrations = Migration::from_dir(dir.path()).unwrap();
+        assert_eq!(migrations.len(), 2);
+        assert_eq!(migrations[0].version, "20240101120000");
+        assert_eq!(migrations[0].name, "create_users");
+        assert_eq!(migrations[0].up_sql.len(), 2);
+        assert_eq!(migrations[0].down_sql, vec!["DROP TABLE users"]);
+        assert_eq!(migrations[1].version, "20240201090000");
+        assert_eq!(migrations[1].name, "add_email");
+        assert!(migrations[1].down_sql.is_empty());
+
+        let runner = MigrationRunner::from_dir(DatabaseConnection::Mock, dir.path()).unwrap();
+        assert!(
+            runner
+                .validate()
+                .unwrap()
+                .iter()
+                .all(|e| matches!(e.error_type, ValidationErrorType::EmptyDownSql))
+        );
+
+        // Malformed names are reported with the offending file name
+        write(
+            "2024_create_posts.up.sql",
+            "CREATE TABLE posts (id INTEGER);",
+        );
+        let err = Migration::from_dir(dir.path()).unwrap_err();
+        assert!(err.to_string().contains("2024_create_posts.up.sql"));
+        fs::remove_file(dir.path().join("2024_create_posts.up.sql")).unwrap();
+
+        write("20240301_000000_orphan.down.sql", "DROP TABLE orphan;");
+        let err = Migration::from_dir(dir.path()).unwrap_err();
+        assert!(err.to_string().contains("20240301_000000_orphan.down.sql"));
+        fs::remove_file(dir.path().join("20240301_000000_orphan.down.sql")).unwrap();
+
+        write("20240301_000000_notes.sql", "SELECT 1;");
+        let err = Migration::from_dir(dir.path()).unwrap_err();
+        assert!(err.to_string().contains("20240301_000000_notes.sql"));
+    }
+
     #[test]
     fn test_migration_result_display() {
         let migration = Migration::simple(
@@ -12,6 +12,9 @@ pub mod migration;
 pub mod query_builder;
 
 /// Re-export commonly used types for convenience
-pub use connection::{ConnectionPool, DatabaseConfig, DatabaseConnection, DatabaseType};
+pub use connection::{
+    ConnectionPool, DatabaseConfig, DatabaseConnection, DatabaseTransaction, DatabaseType,
+    TransactionFuture,
+};
 pub use migration::{Migration, MigrationRunner, MigrationTimestamp};
-pub use query_builder::QueryBuilder;
+pub use query_builder::{ConflictAction, QueryBuilder, QueryValue, SqlDialect};
@@ -3,6 +3,7 @@
 //! This module provides a fluent interface for building SQL queries
 //! in a database-agnostic way.
 
+use super::connection::DatabaseType;
 use crate::error::{Error, Result};
 use std::collections::HashMap;
 
@@ -11,8 +12,9 @@ use std::collections::HashMap;
 pub struct QueryBuilder {
     query_type: QueryType,
     table: Option<String>,
-    columns: Vec<String>,
+    columns: Vec<Column>,
     values: Vec<QueryValue>,
+    rows: Vec<Vec<QueryValue>>,
     conditions: Vec<Condition>,
     joins: Vec<Join>,
     group_by: Vec<String>,
@@ -20,6 +22,115 @@ pub struct QueryBuilder {
     order_by: Vec<OrderBy>,
     limit: Option<usize>,
     offset: Option<usize>,
+    on_conflict: Option<OnConflict>,
+    dialect: SqlDialect,
+    wrapper: Option<SelectWrapper>,
+}
+
+/// SQL dialect used when rendering identifiers and pagination
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SqlDialect {
+    /// Dialect-neutral output: identifiers unquoted, `LIMIT`/`OFFSET` pagination
+    #[default]
+    Generic,
+    /// `"double-quoted"` identifiers, `LIMIT`/`OFFSET` pagination
+    SQLite,
+    /// `"double-quoted"` identifiers, `LIMIT`/`OFFSET` pagination
+    PostgreSQL,
+    /// `` `backtick` `` identifiers, `LIMIT`/`OFFSET` pagination
+    MySQL,
+    /// `[bracketed]` identifiers, `TOP` or `OFFSET ... FETCH` pagination
+    SqlServer,
+}
+
+impl From<DatabaseType> for SqlDialect {
+    fn from(db_type: DatabaseType) -> Self {
+        match db_type {
+            DatabaseType::SQLite => SqlDialect::SQLite,
+            DatabaseType::PostgreSQL => SqlDialect::PostgreSQL,
+            DatabaseType::MySQL => SqlDialect::MySQL,
+        }
+    }
+}
+
+impl SqlDialect {
+    /// Quote an identifier such as `users`, `u.name` or `users u`
+    ///
+    /// Each dot-separated part is quoted, and a trailing alias (`users u` or
+    /// `users AS u`) is quoted as well. Anything that is not a plain identifier,
+    /// such as `COUNT(*)` or `*`, is returned unchanged.
+    pub fn quote_identifier(self, name: &str) -> String {
+        let (open, close) = match self {
+            SqlDialect::Generic => return name.to_string(),
+            SqlDialect::SQLite | SqlDialect::PostgreSQL => ('"', '"'),
+            SqlDialect::MySQL => ('`', '`'),
+            SqlDialect::SqlServer => ('[', ']'),
+        };
+
+        let parts: Vec<&str> = name.split_whitespace().collect();
+        let (ident, alias) = match parts.as_slice() {
+            [ident] => (*ident, None),
+            [ident, alias] => (*ident, Some(*alias)),
+            [ident, kw, alias] if kw.eq_ignore_ascii_case("AS") => (*ident, Some(*alias)),
+            _ => return name.to_string(),
+        };
+
+        let is_plain = |s: &str| {
+            !s.is_empty()
+                && s.chars().all(|c| c.is_alphanumeric() || c == '_')
+                && !s.starts_with(|c: char| c.is_ascii_digit())
+        };
+        if !ident.split('.').all(is_plain) || !alias.is_none_or(is_plain) {
+            return name.to_string();
+        }
+
+        let quote = |s: &str| format!("{open}{s}{close}");
+        let quoted = ident.split('.').map(quote).collect::<Vec<_>>().join(".");
+        match alias {
+            Some(alias) => format!("{quoted} {}", quote(alias)),
+            None => quoted,
+        }
+    }
+}
+
+/// A column in the select list, INSERT column list or UPDATE SET clause
+#[derive(Debug, Clone)]
+enum Column {
+    /// Identifier quoted according to the dialect
+    Name(String),
+    /// SQL expression emitted verbatim
+    Raw(String),
+    /// Aggregate function over a quoted column, e.g. `SUM(amount) AS total`
+    Aggregate {
+        function: &'static str,
+        column: String,
+        alias: String,
+    },
+}
+
+impl Column {
+    fn render(&self, dialect: SqlDialect) -> String {
+        match self {
+            Column::Name(name) => dialect.quote_identifier(name),
+            Column::Raw(expr) => expr.clone(),
+            Column::Aggregate {
+                function,
+                column,
+                alias,
+            } => format!(
+                "{function}({}) AS {}",
+                dialect.quote_identifier(column),
+                dialect.quote_identifier(alias)
+            ),
+        }
+    }
+}
+
+/// Query a SELECT is turned into by [`QueryBuilder::count`] or [`QueryBuilder::exists`]
+#[derive(Debug, Clone, Copy)]
+enum SelectWrapper {
+    Count,
+    Exists,
 }
 
 #[derive(Debug, Clone)]
@@ -30,7 +141,7 @@ enum QueryType {
     Delete,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 /// Query value types for SQL operations
 pub enum QueryValue {
     /// String value
@@ -50,10 +161,106 @@ pub enum QueryValue {
 pub struct Condition {
     column: String,
     operator: String,
-    value: QueryValue,
+    operand: Operand,
     connector: String, // AND, OR
 }
 
+#[derive(Debug, Clone)]
+enum Operand {
+    None,
+    Single(QueryValue),
+    List(Vec<QueryValue>),
+    Range(QueryValue, QueryValue),
+    Group(Vec<Condition>),
+    Raw(String, Vec<QueryValue>),
+    Subquery(Box<QueryBuilder>),
+}
+
+/// Renders values either inline or as bind placeholders
+struct ValueRenderer {
+    placeholder: Option<DatabaseType>,
+    params: Vec<QueryValue>,
+}
+
+impl ValueRenderer {
+    fn inline() -> Self {
+        Self {
+            placeholder: None,
+            params: Vec::new(),
+        }
+    }
+
+    fn parameterized(db_type: DatabaseType) -> Self {
+        Self {
+            placeholder: Some(db_type),
+            params: Vec::new(),
+        }
+    }
+
+    fn render(&mut self, value: &QueryValue) -> String {
+        match self.placeholder {
+            None => QueryBuilder::format_value(value),
+            Some(db_type) => {
+                self.params.push(value.clone());
+                match db_type {
+                    DatabaseType::PostgreSQL => format!("${}", self.params.len()),
+                    DatabaseType::SQLite | DatabaseType::MySQL => "?".to_string(),
+                }
+            }
+        }
+    }
+
+    /// Render a raw SQL fragment, replacing each `?` outside string literals
+    /// with the next parameter
+    fn render_raw(&mut self, sql: &str, params: &[QueryValue]) -> Result<String> {
+        let mut rendered = String::with_capacity(sql.len());
+        let mut params = params.iter();
+        let mut in_literal = false;
+
+        for c in sql.chars() {
+            match c {
+                '\'' => {
+                    in_literal = !in_literal;
+                    rendered.push(c);
+                }
+                '?' if !in_literal => {
+                    let value = params.next().ok_or_else(|| {
+                        Error::validation(format!("Too few parameters for raw SQL: {}", sql))
+                    })?;
+                    rendered.push_str(&self.render(value));
+                }
+                _ => rendered.push(c),
+            }
+        }
+
+        if params.next().is_some() {
+            return Err(Error::validation(format!(
+                "Too many parameters for raw SQL: {}",
+                sql
+            )));
+        }
+        Ok(rendered)
+    }
+
+    fn render_operand(&mut self, operand: &Operand) -> String {
+        match operand {
+            Operand::None | Operand::Group(_) | Operand::Raw(..) | Operand::Subquery(_) => {
+                String::new()
+            }
+            Operand::Single(value) => self.render(value),
+            Operand::List(values) => {
+                let rendered: Vec<String> = values.iter().map(|v| self.render(v)).collect();
+                format!("({})", rendered.join(", "))
+            }
+            Operand::Range(low, high) => {
+                let low = self.render(low);
+                let high = self.render(high);
+                format!("{low} AND {high}")
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 /// SQL JOIN clause configuration
 pub struct Join {
@@ -69,6 +276,21 @@ pub struct OrderBy {
     direction: String, // ASC, DESC
 }
 
+/// Action taken by an INSERT when a row conflicts with an existing key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConflictAction {
+    /// Keep the existing row and skip the conflicting insert
+    DoNothing,
+    /// Overwrite the listed columns of the existing row with the inserted values
+    Update(Vec<String>),
+}
+
+#[derive(Debug, Clone)]
+struct OnConflict {
+    columns: Vec<String>,
+    action: ConflictAction,
+}
+
 impl QueryBuilder {
     /// Create a new SELECT query builder
     ///
@@ -84,19 +306,7 @@ impl QueryBuilder {
     ///     .build();
     /// ```
     pub fn select() -> Self {
-        Self {
-            query_type: QueryType::Select,
-            table: None,
-            columns: Vec::new(),
-            values: Vec::new(),
-            conditions: Vec::new(),
-            joins: Vec::new(),
-            group_by: Vec::new(),
-            having: Vec::new(),
-            order_by: Vec::new(),
-            limit: None,
-            offset: None,
-        }
+        Self::with_type(QueryType::Select)
     }
 
     /// Create a new INSERT query builder
@@ -113,19 +323,7 @@ impl QueryBuilder {
     ///     .build();
     /// ```
     pub fn insert() -> Self {
-        Self {
-            query_type: QueryType::Insert,
-            table: None,
-            columns: Vec::new(),
-            values: Vec::new(),
-            conditions: Vec::new(),
-            joins: Vec::new(),
-            group_by: Vec::new(),
-            having: Vec::new(),
-            order_by: Vec::new(),
-            limit: None,
-            offset: None,
-        }
+        Self::with_type(QueryType::Insert)
     }
 
     /// Create a new UPDATE query builder
@@ -142,19 +340,7 @@ impl QueryBuilder {
     ///     .build();
     /// ```
     pub fn update() -> Self {
-        Self {
-            query_type: QueryType::Update,
-            table: None,
-            columns: Vec::new(),
-            values: Vec::new(),
-            conditions: Vec::new(),
-            joins: Vec::new(),
-            group_by: Vec::new(),
-            having: Vec::new(),
-            order_by: Vec::new(),
-            limit: None,
-            offset: None,
-        }
+        Self::with_type(QueryType::Update)
     }
 
     /// Create a new DELETE query builder
@@ -170,11 +356,16 @@ impl QueryBuilder {
     ///     .build();
     /// ```
     pub fn delete() -> Self {
+        Self::with_type(QueryType::Delete)
+    }
+
+    fn with_type(query_type: QueryType) -> Self {
         Self {
-            query_type: QueryType::Delete,
+            query_type,
             table: None,
             columns: Vec::new(),
             values: Vec::new(),
+            rows: Vec::new(),
             conditions: Vec::new(),
             joins: Vec::new(),
             group_by: Vec::new(),
@@ -182,9 +373,44 @@ impl QueryBuilder {
             order_by: Vec::new(),
             limit: None,
             offset: None,
+            on_conflict: None,
+            dialect: SqlDialect::Generic,
+            wrapper: None,
         }
     }
 
+    /// Set the SQL dialect used for identifier quoting and pagination
+    ///
+    /// Accepts either a [`SqlDialect`] or a [`DatabaseType`]. The default
+    /// [`SqlDialect::Generic`] leaves identifiers unquoted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::db::{DatabaseType, QueryBuilder, SqlDialect};
+    ///
+    /// let query = QueryBuilder::select()
+    ///     .columns(&["id", "name"])
+    ///     .from("users")
+    ///     .limit(10)
+    ///     .dialect(DatabaseType::MySQL)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(query, "SELECT `id`, `name` FROM `users` LIMIT 10");
+    ///
+    /// let query = QueryBuilder::select()
+    ///     .from("users")
+    ///     .limit(10)
+    ///     .dialect(SqlDialect::SqlServer)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(query, "SELECT TOP 10 * FROM [users]");
+    /// ```
+    pub fn dialect(mut self, dialect: impl Into<SqlDialect>) -> Self {
+        self.dialect = dialect.into();
+        self
+    }
+
     /// Set the table name (for SELECT, UPDATE, DELETE)
     pub fn from(mut self, table: &str) -> Self {
         self.table = Some(table.to_string());
@@ -205,13 +431,150 @@ impl QueryBuilder {
 
     /// Set the columns to select
     pub fn columns(mut self, columns: &[&str]) -> Self {
-        self.columns = columns.iter().map(|s| s.to_string()).collect();
+        self.columns = columns
+            .iter()
+            .map(|s| Column::Name(s.to_string()))
+            .collect();
         self
     }
 
     /// Add a single column to select
     pub fn column(mut self, column: &str) -> Self {
-        self.columns.push(column.to_string());
+        self.columns.push(Column::Name(column.to_string()));
+        self
+    }
+
+    /// Add a computed column to select, emitted without identifier quoting
+    ///
+    /// The expression is trusted and inserted into the SQL as written.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::db::{DatabaseType, QueryBuilder};
+    ///
+    /// let query = QueryBuilder::select()
+    ///     .column("id")
+    ///     .column_raw("CURRENT_TIMESTAMP")
+    ///     .from("users")
+    ///     .dialect(DatabaseType::PostgreSQL)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(query, "SELECT \"id\", CURRENT_TIMESTAMP FROM \"users\"");
+    /// ```
+    pub fn column_raw(mut self, expr: &str) -> Self {
+        self.columns.push(Column::Raw(expr.to_string()));
+        self
+    }
+
+    /// Add `SUM(column) AS alias` to the select list
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::db::{DatabaseType, QueryBuilder};
+    ///
+    /// let query = QueryBuilder::select()
+    ///     .column("customer_id")
+    ///     .sum("amount", "total")
+    ///     .avg("amount", "average")
+    ///     .from("orders")
+    ///     .group_by(&["customer_id"])
+    ///     .dialect(DatabaseType::MySQL)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     query,
+    ///     "SELECT `customer_id`, SUM(`amount`) AS `total`, AVG(`amount`) AS `average` \
+    ///      FROM `orders` GROUP BY `customer_id`"
+    /// );
+    /// ```
+    pub fn sum(self, column: &str, alias: &str) -> Self {
+        self.aggregate("SUM", column, alias)
+    }
+
+    /// Add `AVG(column) AS alias` to the select list
+    pub fn avg(self, column: &str, alias: &str) -> Self {
+        self.aggregate("AVG", column, alias)
+    }
+
+    /// Add `MIN(column) AS alias` to the select list
+    pub fn min(self, column: &str, alias: &str) -> Self {
+        self.aggregate("MIN", column, alias)
+    }
+
+    /// Add `MAX(column) AS alias` to the select list
+    pub fn max(self, column: &str, alias: &str) -> Self {
+        self.aggregate("MAX", column, alias)
+    }
+
+    fn aggregate(mut self, function: &'static str, column: &str, alias: &str) -> Self {
+        self.columns.push(Column::Aggregate {
+            function,
+            column: column.to_string(),
+            alias: alias.to_string(),
+        });
+        self
+    }
+
+    /// Turn this SELECT into a query counting its rows
+    ///
+    /// The table, joins and WHERE conditions are kept, the select list
+    /// becomes `COUNT(*)` and ORDER BY is dropped. Grouped or paginated
+    /// queries are counted as a derived table instead, so the result is the
+    /// number of groups or of rows on the page.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::db::QueryBuilder;
+    ///
+    /// let users = QueryBuilder::select()
+    ///     .columns(&["id", "name"])
+    ///     .from("users")
+    ///     .where_eq("active", true)
+    ///     .order_by_asc("name");
+    ///
+    /// assert_eq!(
+    ///     users.clone().count().build().unwrap(),
+    ///     "SELECT COUNT(*) FROM users WHERE active = TRUE"
+    /// );
+    /// assert_eq!(
+    ///     users.limit(20).count().build().unwrap(),
+    ///     "SELECT COUNT(*) FROM (SELECT id, name FROM users WHERE active = TRUE \
+    ///      ORDER BY name ASC LIMIT 20) counted"
+    /// );
+    /// ```
+    pub fn count(mut self) -> Self {
+        self.wrapper = Some(SelectWrapper::Count);
+        self
+    }
+
+    /// Turn this SELECT into `SELECT EXISTS(...)`, true when it matches any row
+    ///
+    /// The table, joins and WHERE conditions are kept; the inner select list
+    /// becomes `1`. SQL Server, which has no boolean values, gets
+    /// `SELECT CASE WHEN EXISTS (...) THEN 1 ELSE 0 END` instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::db::{DatabaseType, QueryBuilder};
+    ///
+    /// let (sql, params) = QueryBuilder::select()
+    ///     .from("users")
+    ///     .where_eq("email", "alice@example.com")
+    ///     .exists()
+    ///     .build_parameterized(DatabaseType::PostgreSQL)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(sql, "SELECT EXISTS(SELECT 1 FROM users WHERE email = $1)");
+    /// assert_eq!(params.len(), 1);
+    /// ```
+    pub fn exists(mut self) -> Self {
+        self.wrapper = Some(SelectWrapper::Exists);
         self
     }
 
@@ -231,30 +594,113 @@ impl QueryBuilder {
         self
     }
 
+    /// Set values of mixed types for INSERT
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::db::{QueryBuilder, QueryValue};
+    ///
+    /// let query = QueryBuilder::insert()
+    ///     .into("users")
+    ///     .columns(&["name", "age", "active"])
+    ///     .values_typed(&["Alice".into(), 30.into(), true.into()])
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(query, "INSERT INTO users (name, age, active) VALUES ('Alice', 30, TRUE)");
+    /// ```
+    pub fn values_typed(mut self, values: &[QueryValue]) -> Self {
+        self.values.extend_from_slice(values);
+        self
+    }
+
+    /// Add a row for a multi-row INSERT
+    ///
+    /// Rows are emitted after any values set with `values`, `int_values` or
+    /// `values_typed`. Every row must have one value per column.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::db::{QueryBuilder, QueryValue};
+    ///
+    /// let query = QueryBuilder::insert()
+    ///     .into("users")
+    ///     .columns(&["name", "age"])
+    ///     .add_row(&["Alice".into(), 30.into()])
+    ///     .add_row(&["Bob".into(), QueryValue::Null])
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(query, "INSERT INTO users (name, age) VALUES ('Alice', 30), ('Bob', NULL)");
+    /// ```
+    pub fn add_row(mut self, row: &[QueryValue]) -> Self {
+        self.rows.push(row.to_vec());
+        self
+    }
+
+    /// Turn an INSERT into an upsert that handles key conflicts with `action`
+    ///
+    /// PostgreSQL, SQLite and the generic dialect render
+    /// `ON CONFLICT (columns) DO NOTHING` or `ON CONFLICT (columns) DO UPDATE
+    /// SET col = excluded.col`. MySQL renders `ON DUPLICATE KEY UPDATE
+    /// col = VALUES(col)`; it has no conflict target, so `columns` is only
+    /// used to build a no-op assignment for [`ConflictAction::DoNothing`].
+    /// SQL Server has no upsert clause and fails to build.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::db::{ConflictAction, DatabaseType, QueryBuilder};
+    ///
+    /// let query = QueryBuilder::insert()
+    ///     .into("users")
+    ///     .columns(&["email", "name"])
+    ///     .values(&["alice@example.com", "Alice"])
+    ///     .on_conflict(&["email"], ConflictAction::Update(vec!["name".to_string()]))
+    ///     .dialect(DatabaseType::PostgreSQL)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     query,
+    ///     "INSERT INTO \"users\" (\"email\", \"name\") VALUES ('alice@example.com', 'Alice') \
+    ///      ON CONFLICT (\"email\") DO UPDATE SET \"name\" = excluded.\"name\""
+    /// );
+    /// ```
+    pub fn on_conflict(mut self, columns: &[&str], action: ConflictAction) -> Self {
+        self.on_conflict = Some(OnConflict {
+            columns: columns.iter().map(|s| s.to_string()).collect(),
+            action,
+        });
+        self
+    }
+
     /// Set a single value for UPDATE
     pub fn set(mut self, column: &str, value: &str) -> Self {
-        self.columns.push(column.to_string());
+        self.columns.push(Column::Name(column.to_string()));
         self.values.push(QueryValue::String(value.to_string()));
         self
     }
 
     /// Set an integer value for UPDATE
     pub fn set_int(mut self, column: &str, value: i64) -> Self {
-        self.columns.push(column.to_string());
+        self.columns.push(Column::Name(column.to_string()));
         self.values.push(QueryValue::Integer(value));
         self
     }
 
     /// Set a float value for UPDATE
     pub fn set_float(mut self, column: &str, value: f64) -> Self {
-        self.columns.push(column.to_string());
+        self.columns.push(Column::Name(column.to_string()));
         self.values.push(QueryValue::Float(value));
         self
     }
 
     /// Set a boolean value for UPDATE
     pub fn set_bool(mut self, column: &str, value: bool) -> Self {
-        self.columns.push(column.to_string());
+        self.columns.push(Column::Name(column.to_string()));
         self.values.push(QueryValue::Boolean(value));
         self
     }
@@ -264,7 +710,7 @@ impl QueryBuilder {
         self.conditions.push(Condition {
             column: column.to_string(),
             operator: "=".to_string(),
-            value: value.into(),
+            operand: Operand::Single(value.into()),
             connector: "AND".to_string(),
         });
         self
@@ -275,7 +721,7 @@ impl QueryBuilder {
         self.conditions.push(Condition {
             column: column.to_string(),
             operator: "!=".to_string(),
-            value: value.into(),
+            operand: Operand::Single(value.into()),
             connector: "AND".to_string(),
         });
         self
@@ -286,7 +732,7 @@ impl QueryBuilder {
         self.conditions.push(Condition {
             column: column.to_string(),
             operator: ">".to_string(),
-            value: value.into(),
+            operand: Operand::Single(value.into()),
             connector: "AND".to_string(),
         });
         self
@@ -297,7 +743,7 @@ impl QueryBuilder {
         self.conditions.push(Condition {
             column: column.to_string(),
             operator: "<".to_string(),
-            value: value.into(),
+            operand: Operand::Single(value.into()),
             connector: "AND".to_string(),
         });
         self
@@ -308,7 +754,7 @@ impl QueryBuilder {
         self.conditions.push(Condition {
             column: column.to_string(),
             operator: "LIKE".to_string(),
-            value: QueryValue::String(pattern.to_string()),
+            operand: Operand::Single(QueryValue::String(pattern.to_string())),
             connector: "AND".to_string(),
         });
         self
@@ -316,38 +762,205 @@ impl QueryBuilder {
 
     /// Add a WHERE condition with IN
     pub fn where_in(mut self, column: &str, values: &[&str]) -> Self {
-        let values_str = values
-            .iter()
-            .map(|v| format!("'{}'", v.replace('\'', "''")))
-            .collect::<Vec<_>>()
-            .join(", ");
-
         self.conditions.push(Condition {
             column: column.to_string(),
             operator: "IN".to_string(),
-            value: QueryValue::String(format!("({})", values_str)),
+            operand: Operand::List(
+                values
+                    .iter()
+                    .map(|v| QueryValue::String((*v).to_string()))
+                    .collect(),
+            ),
             connector: "AND".to_string(),
         });
         self
     }
 
-    /// Add an OR WHERE condition with equality
-    pub fn or_where_eq(mut self, column: &str, value: impl Into<QueryValue>) -> Self {
+    /// Add a WHERE condition `column IN (subquery)`
+    ///
+    /// The subquery is rendered with this builder's dialect, and its values
+    /// are bound in the position where it appears.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::db::{DatabaseType, QueryBuilder};
+    ///
+    /// let big_spenders = QueryBuilder::select()
+    ///     .column("user_id")
+    ///     .from("orders")
+    ///     .where_gt("total", 100);
+    ///
+    /// let (sql, params) = QueryBuilder::select()
+    ///     .from("users")
+    ///     .where_eq("active", true)
+    ///     .where_in_subquery("id", big_spenders)
+    ///     .build_parameterized(DatabaseType::PostgreSQL)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     sql,
+    ///     "SELECT * FROM users WHERE active = $1 AND id IN (SELECT user_id FROM orders WHERE total > $2)"
+    /// );
+    /// assert_eq!(params.len(), 2);
+    /// ```
+    pub fn where_in_subquery(mut self, column: &str, subquery: QueryBuilder) -> Self {
         self.conditions.push(Condition {
             column: column.to_string(),
-            operator: "=".to_string(),
-            value: value.into(),
-            connector: "OR".to_string(),
+            operator: "IN".to_string(),
+            operand: Operand::Subquery(Box::new(subquery)),
+            connector: "AND".to_string(),
         });
         self
     }
 
-    /// Add an INNER JOIN
-    pub fn inner_join(mut self, table: &str, on: &str) -> Self {
-        self.joins.push(Join {
-            join_type: "INNER".to_string(),
-            table: table.to_string(),
-            on_condition: on.to_string(),
+    /// Add a raw SQL WHERE condition joined with AND
+    ///
+    /// Each `?` in `sql` (outside single-quoted literals) is replaced by the
+    /// next value from `params`, either inlined as an escaped literal or as a
+    /// bind placeholder by [`QueryBuilder::build_parameterized`], so the
+    /// values stay in order with the rest of the query. Use `?` for every
+    /// backend; it becomes `$n` for PostgreSQL. The fragment is wrapped in
+    /// parentheses to keep its precedence.
+    ///
+    /// The SQL itself is trusted and inserted as written, so it must never
+    /// be built from user input; the params are escaped or bound like any
+    /// other value. Building fails if the number of `?` placeholders does
+    /// not match the number of params.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::db::{DatabaseType, QueryBuilder};
+    ///
+    /// let (sql, params) = QueryBuilder::select()
+    ///     .from("events")
+    ///     .where_eq("kind", "login")
+    ///     .where_raw("created_at > NOW() - ? * INTERVAL '1 day'", &[7.into()])
+    ///     .build_parameterized(DatabaseType::PostgreSQL)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     sql,
+    ///     "SELECT * FROM events WHERE kind = $1 AND (created_at > NOW() - $2 * INTERVAL '1 day')"
+    /// );
+    /// assert_eq!(params.len(), 2);
+    /// ```
+    pub fn where_raw(mut self, sql: &str, params: &[QueryValue]) -> Self {
+        self.conditions.push(Condition {
+            column: String::new(),
+            operator: String::new(),
+            operand: Operand::Raw(sql.to_string(), params.to_vec()),
+            connector: "AND".to_string(),
+        });
+        self
+    }
+
+    /// Add a WHERE condition with BETWEEN (inclusive on both ends)
+    pub fn where_between(
+        mut self,
+        column: &str,
+        low: impl Into<QueryValue>,
+        high: impl Into<QueryValue>,
+    ) -> Self {
+        self.conditions.push(Condition {
+            column: column.to_string(),
+            operator: "BETWEEN".to_string(),
+            operand: Operand::Range(low.into(), high.into()),
+            connector: "AND".to_string(),
+        });
+        self
+    }
+
+    /// Add a WHERE condition with IS NULL
+    pub fn where_null(mut self, column: &str) -> Self {
+        self.conditions.push(Condition {
+            column: column.to_string(),
+            operator: "IS NULL".to_string(),
+            operand: Operand::None,
+            connector: "AND".to_string(),
+        });
+        self
+    }
+
+    /// Add a WHERE condition with IS NOT NULL
+    pub fn where_not_null(mut self, column: &str) -> Self {
+        self.conditions.push(Condition {
+            column: column.to_string(),
+            operator: "IS NOT NULL".to_string(),
+            operand: Operand::None,
+            connector: "AND".to_string(),
+        });
+        self
+    }
+
+    /// Add an OR WHERE condition with equality
+    pub fn or_where_eq(mut self, column: &str, value: impl Into<QueryValue>) -> Self {
+        self.conditions.push(Condition {
+            column: column.to_string(),
+            operator: "=".to_string(),
+            operand: Operand::Single(value.into()),
+            connector: "OR".to_string(),
+        });
+        self
+    }
+
+    /// Add a parenthesized group of conditions joined with AND
+    ///
+    /// The closure receives an empty builder; the conditions added to it are
+    /// wrapped in parentheses so their precedence is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::db::QueryBuilder;
+    ///
+    /// let query = QueryBuilder::select()
+    ///     .from("users")
+    ///     .where_group(|q| q.where_eq("a", 1).or_where_eq("b", 2))
+    ///     .where_eq("c", 3)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(query, "SELECT * FROM users WHERE (a = 1 OR b = 2) AND c = 3");
+    /// ```
+    pub fn where_group<F>(self, f: F) -> Self
+    where
+        F: FnOnce(QueryBuilder) -> QueryBuilder,
+    {
+        self.push_group("AND", f)
+    }
+
+    /// Add a parenthesized group of conditions joined with OR
+    pub fn or_where_group<F>(self, f: F) -> Self
+    where
+        F: FnOnce(QueryBuilder) -> QueryBuilder,
+    {
+        self.push_group("OR", f)
+    }
+
+    fn push_group<F>(mut self, connector: &str, f: F) -> Self
+    where
+        F: FnOnce(QueryBuilder) -> QueryBuilder,
+    {
+        let group = f(QueryBuilder::select()).conditions;
+        if !group.is_empty() {
+            self.conditions.push(Condition {
+                column: String::new(),
+                operator: String::new(),
+                operand: Operand::Group(group),
+                connector: connector.to_string(),
+            });
+        }
+        self
+    }
+
+    /// Add an INNER JOIN
+    pub fn inner_join(mut self, table: &str, on: &str) -> Self {
+        self.joins.push(Join {
+            join_type: "INNER".to_string(),
+            table: table.to_string(),
+            on_condition: on.to_string(),
         });
         self
     }
@@ -383,7 +996,7 @@ impl QueryBuilder {
         self.having.push(Condition {
             column: column.to_string(),
             operator: "=".to_string(),
-            value: value.into(),
+            operand: Operand::Single(value.into()),
             connector: "AND".to_string(),
         });
         self
@@ -420,29 +1033,117 @@ impl QueryBuilder {
     }
 
     /// Build the SQL query string
+    ///
+    /// Values are inlined into the SQL as escaped literals. Prefer
+    /// [`QueryBuilder::build_parameterized`] when the values come from user input.
     pub fn build(self) -> Result<String> {
-        match self.query_type {
-            QueryType::Select => self.build_select(),
-            QueryType::Insert => self.build_insert(),
-            QueryType::Update => self.build_update(),
-            QueryType::Delete => self.build_delete(),
+        let mut renderer = ValueRenderer::inline();
+        self.build_with(&mut renderer)
+    }
+
+    /// Build the SQL query with bind placeholders instead of inlined values
+    ///
+    /// Returns the SQL together with the values to bind, in placeholder order.
+    /// Postgres uses numbered `$1, $2, ...` placeholders while the other
+    /// backends use `?`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::db::{DatabaseType, QueryBuilder, QueryValue};
+    ///
+    /// let (sql, params) = QueryBuilder::select()
+    ///     .from("users")
+    ///     .where_eq("name", "O'Brien")
+    ///     .where_gt("age", 18)
+    ///     .build_parameterized(DatabaseType::PostgreSQL)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(sql, "SELECT * FROM users WHERE name = $1 AND age > $2");
+    /// assert_eq!(
+    ///     params,
+    ///     vec![QueryValue::String("O'Brien".to_string()), QueryValue::Integer(18)]
+    /// );
+    /// ```
+    pub fn build_parameterized(self, db_type: DatabaseType) -> Result<(String, Vec<QueryValue>)> {
+        let mut renderer = ValueRenderer::parameterized(db_type);
+        let sql = self.build_with(&mut renderer)?;
+        Ok((sql, renderer.params))
+    }
+
+    fn build_with(&self, renderer: &mut ValueRenderer) -> Result<String> {
+        match (&self.query_type, self.wrapper) {
+            (QueryType::Select, None) => self.build_select(renderer),
+            (QueryType::Select, Some(SelectWrapper::Count)) => self.build_count(renderer),
+            (QueryType::Select, Some(SelectWrapper::Exists)) => self.build_exists(renderer),
+            (_, Some(_)) => Err(Error::validation(
+                "COUNT and EXISTS queries can only be built from a SELECT".to_string(),
+            )),
+            (QueryType::Insert, None) => self.build_insert(renderer),
+            (QueryType::Update, None) => self.build_update(renderer),
+            (QueryType::Delete, None) => self.build_delete(renderer),
         }
     }
 
-    fn build_select(&self) -> Result<String> {
+    /// The plain SELECT a count or exists query is built around
+    ///
+    /// ORDER BY only matters when it decides which rows a page holds.
+    fn inner_select(&self) -> Self {
+        let mut inner = self.clone();
+        inner.wrapper = None;
+        if self.limit.is_none() && self.offset.is_none() {
+            inner.order_by.clear();
+        }
+        inner
+    }
+
+    fn build_count(&self, renderer: &mut ValueRenderer) -> Result<String> {
+        let mut inner = self.inner_select();
+        if self.group_by.is_empty() && self.limit.is_none() && self.offset.is_none() {
+            inner.columns = vec![Column::Raw("COUNT(*)".to_string())];
+            return inner.build_select(renderer);
+        }
+
+        Ok(format!(
+            "SELECT COUNT(*) FROM ({}) {}",
+            inner.build_select(renderer)?,
+            self.quote("counted")
+        ))
+    }
+
+    fn build_exists(&self, renderer: &mut ValueRenderer) -> Result<String> {
+        let mut inner = self.inner_select();
+        inner.columns = vec![Column::Raw("1".to_string())];
+        let inner = inner.build_select(renderer)?;
+
+        Ok(match self.dialect {
+            SqlDialect::SqlServer => format!("SELECT CASE WHEN EXISTS ({inner}) THEN 1 ELSE 0 END"),
+            _ => format!("SELECT EXISTS({inner})"),
+        })
+    }
+
+    fn build_select(&self, renderer: &mut ValueRenderer) -> Result<String> {
         let mut query = String::new();
 
         // SELECT clause
         query.push_str("SELECT ");
+        if self.dialect == SqlDialect::SqlServer
+            && self.offset.is_none()
+            && let Some(limit) = self.limit
+        {
+            query.push_str("TOP ");
+            query.push_str(&limit.to_string());
+            query.push(' ');
+        }
         if self.columns.is_empty() {
             query.push('*');
         } else {
-            query.push_str(&self.columns.join(", "));
+            query.push_str(&self.render_columns());
         }
 
         // FROM clause
         if let Some(table) = &self.table {
-            query.push_str(&format!(" FROM {}", table));
+            query.push_str(&format!(" FROM {}", self.quote(table)));
         } else {
             return Err(Error::validation(
                 "Table name is required for SELECT query".to_string(),
@@ -453,45 +1154,27 @@ impl QueryBuilder {
         for join in &self.joins {
             query.push_str(&format!(
                 " {} JOIN {} ON {}",
-                join.join_type, join.table, join.on_condition
+                join.join_type,
+                self.quote(&join.table),
+                join.on_condition
             ));
         }
 
         // WHERE clause
         if !self.conditions.is_empty() {
             query.push_str(" WHERE ");
-            for (i, condition) in self.conditions.iter().enumerate() {
-                if i > 0 {
-                    query.push_str(&format!(" {} ", condition.connector));
-                }
-                query.push_str(&format!(
-                    "{} {} {}",
-                    condition.column,
-                    condition.operator,
-                    self.format_value(&condition.value)
-                ));
-            }
+            query.push_str(&self.render_conditions(&self.conditions, renderer)?);
         }
 
         // GROUP BY clause
         if !self.group_by.is_empty() {
-            query.push_str(&format!(" GROUP BY {}", self.group_by.join(", ")));
+            query.push_str(&format!(" GROUP BY {}", self.quote_all(&self.group_by)));
         }
 
         // HAVING clause
         if !self.having.is_empty() {
             query.push_str(" HAVING ");
-            for (i, condition) in self.having.iter().enumerate() {
-                if i > 0 {
-                    query.push_str(&format!(" {} ", condition.connector));
-                }
-                query.push_str(&format!(
-                    "{} {} {}",
-                    condition.column,
-                    condition.operator,
-                    self.format_value(&condition.value)
-                ));
-            }
+            query.push_str(&self.render_conditions(&self.having, renderer)?);
         }
 
         // ORDER BY clause
@@ -500,25 +1183,70 @@ impl QueryBuilder {
             let order_parts: Vec<String> = self
                 .order_by
                 .iter()
-                .map(|order| format!("{} {}", order.column, order.direction))
+                .map(|order| format!("{} {}", self.quote(&order.column), order.direction))
                 .collect();
             query.push_str(&order_parts.join(", "));
         }
 
-        // LIMIT clause
-        if let Some(limit) = self.limit {
-            query.push_str(&format!(" LIMIT {}", limit));
-        }
+        // LIMIT / OFFSET clauses
+        query.push_str(&self.render_pagination());
+
+        Ok(query)
+    }
 
-        // OFFSET clause
-        if let Some(offset) = self.offset {
-            query.push_str(&format!(" OFFSET {}", offset));
+    fn render_pagination(&self) -> String {
+        let limit_clause = |limit: Option<usize>| {
+            limit
+                .map(|limit| format!(" LIMIT {limit}"))
+                .unwrap_or_default()
+        };
+
+        match (self.dialect, self.limit, self.offset) {
+            // TOP is rendered in the SELECT clause
+            (_, None, None) | (SqlDialect::SqlServer, Some(_), None) => String::new(),
+            (SqlDialect::SqlServer, limit, Some(offset)) => {
+                // OFFSET ... FETCH requires an ORDER BY clause
+                let order = if self.order_by.is_empty() {
+                    " ORDER BY (SELECT NULL)"
+                } else {
+                    ""
+                };
+                let fetch = limit
+                    .map(|limit| format!(" FETCH NEXT {limit} ROWS ONLY"))
+                    .unwrap_or_default();
+                format!("{order} OFFSET {offset} ROWS{fetch}")
+            }
+            // SQLite and MySQL reject OFFSET without LIMIT
+            (SqlDialect::SQLite, None, Some(offset)) => format!(" LIMIT -1 OFFSET {offset}"),
+            (SqlDialect::MySQL, None, Some(offset)) => {
+                format!(" LIMIT {} OFFSET {offset}", u64::MAX)
+            }
+            (_, limit, Some(offset)) => format!("{} OFFSET {offset}", limit_clause(limit)),
+            (_, limit, None) => limit_clause(limit),
         }
+    }
 
-        Ok(query)
+    fn quote(&self, name: &str) -> String {
+        self.dialect.quote_identifier(name)
+    }
+
+    fn render_columns(&self) -> String {
+        self.columns
+            .iter()
+            .map(|column| column.render(self.dialect))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn quote_all(&self, names: &[String]) -> String {
+        names
+            .iter()
+            .map(|name| self.quote(name))
+            .collect::<Vec<_>>()
+            .join(", ")
     }
 
-    fn build_insert(&self) -> Result<String> {
+    fn build_insert(&self, renderer: &mut ValueRenderer) -> Result<String> {
         let table = self.table.as_ref().ok_or_else(|| {
             Error::validation("Table name is required for INSERT query".to_string())
         })?;
@@ -529,26 +1257,111 @@ impl QueryBuilder {
             ));
         }
 
-        if self.values.len() != self.columns.len() {
-            return Err(Error::validation(
-                "Number of values must match number of columns".to_string(),
-            ));
+        let mut rows: Vec<&[QueryValue]> = Vec::with_capacity(self.rows.len() + 1);
+        if !self.values.is_empty() || self.rows.is_empty() {
+            rows.push(&self.values);
+        }
+        rows.extend(self.rows.iter().map(Vec::as_slice));
+
+        for (i, row) in rows.iter().enumerate() {
+            if row.len() != self.columns.len() {
+                return Err(Error::validation(format!(
+                    "Number of values must match number of columns (row {}: expected {}, got {})",
+                    i + 1,
+                    self.columns.len(),
+                    row.len()
+                )));
+            }
         }
 
-        let mut query = format!("INSERT INTO {} ({})", table, self.columns.join(", "));
+        let mut query = format!(
+            "INSERT INTO {} ({})",
+            self.quote(table),
+            self.render_columns()
+        );
 
-        let values_str: Vec<String> = self
-            .values
+        let groups: Vec<String> = rows
             .iter()
-            .map(|value| self.format_value(value))
+            .map(|row| {
+                let values_str: Vec<String> =
+                    row.iter().map(|value| renderer.render(value)).collect();
+                format!("({})", values_str.join(", "))
+            })
             .collect();
 
-        query.push_str(&format!(" VALUES ({})", values_str.join(", ")));
+        query.push_str(" VALUES ");
+        query.push_str(&groups.join(", "));
+
+        if let Some(on_conflict) = &self.on_conflict {
+            query.push_str(&self.render_on_conflict(on_conflict)?);
+        }
 
         Ok(query)
     }
 
-    fn build_update(&self) -> Result<String> {
+    fn render_on_conflict(&self, on_conflict: &OnConflict) -> Result<String> {
+        match (self.dialect, &on_conflict.action) {
+            (SqlDialect::SqlServer, _) => Err(Error::validation(
+                "ON CONFLICT is not supported for SQL Server".to_string(),
+            )),
+            (_, ConflictAction::Update(columns)) if columns.is_empty() => Err(Error::validation(
+                "ON CONFLICT update requires at least one column".to_string(),
+            )),
+            (SqlDialect::MySQL, action) => {
+                let assignments: Vec<String> = match action {
+                    // MySQL has no DO NOTHING; assigning a key column to itself is a no-op
+                    ConflictAction::DoNothing => {
+                        let column = on_conflict.columns.first().ok_or_else(|| {
+                            Error::validation(
+                                "ON CONFLICT DO NOTHING requires a conflict column for MySQL"
+                                    .to_string(),
+                            )
+                        })?;
+                        let column = self.quote(column);
+                        vec![format!("{column} = {column}")]
+                    }
+                    ConflictAction::Update(columns) => columns
+                        .iter()
+                        .map(|column| {
+                            let column = self.quote(column);
+                            format!("{column} = VALUES({column})")
+                        })
+                        .collect(),
+                };
+                Ok(format!(
+                    " ON DUPLICATE KEY UPDATE {}",
+                    assignments.join(", ")
+                ))
+            }
+            (_, action) => {
+                let mut clause = String::from(" ON CONFLICT");
+                if !on_conflict.columns.is_empty() {
+                    clause.push_str(&format!(" ({})", self.quote_all(&on_conflict.columns)));
+                } else if matches!(action, ConflictAction::Update(_)) {
+                    return Err(Error::validation(
+                        "ON CONFLICT update requires conflict target columns".to_string(),
+                    ));
+                }
+                match action {
+                    ConflictAction::DoNothing => clause.push_str(" DO NOTHING"),
+                    ConflictAction::Update(columns) => {
+                        let assignments: Vec<String> = columns
+                            .iter()
+                            .map(|column| {
+                                let column = self.quote(column);
+                                format!("{column} = excluded.{column}")
+                            })
+                            .collect();
+                        clause.push_str(" DO UPDATE SET ");
+                        clause.push_str(&assignments.join(", "));
+                    }
+                }
+                Ok(clause)
+            }
+        }
+    }
+
+    fn build_update(&self, renderer: &mut ValueRenderer) -> Result<String> {
         let table = self.table.as_ref().ok_or_else(|| {
             Error::validation("Table name is required for UPDATE query".to_string())
         })?;
@@ -565,7 +1378,7 @@ impl QueryBuilder {
             ));
         }
 
-        let mut query = format!("UPDATE {}", table);
+        let mut query = format!("UPDATE {}", self.quote(table));
 
         // SET clause
         query.push_str(" SET ");
@@ -573,65 +1386,88 @@ impl QueryBuilder {
             .columns
             .iter()
             .zip(self.values.iter())
-            .map(|(col, val)| format!("{} = {}", col, self.format_value(val)))
+            .map(|(col, val)| format!("{} = {}", col.render(self.dialect), renderer.render(val)))
             .collect();
         query.push_str(&set_parts.join(", "));
 
         // WHERE clause
         if !self.conditions.is_empty() {
             query.push_str(" WHERE ");
-            for (i, condition) in self.conditions.iter().enumerate() {
-                if i > 0 {
-                    query.push_str(&format!(" {} ", condition.connector));
-                }
-                query.push_str(&format!(
-                    "{} {} {}",
-                    condition.column,
-                    condition.operator,
-                    self.format_value(&condition.value)
-                ));
-            }
+            query.push_str(&self.render_conditions(&self.conditions, renderer)?);
         }
 
         Ok(query)
     }
 
-    fn build_delete(&self) -> Result<String> {
+    fn build_delete(&self, renderer: &mut ValueRenderer) -> Result<String> {
         let table = self.table.as_ref().ok_or_else(|| {
             Error::validation("Table name is required for DELETE query".to_string())
         })?;
 
-        let mut query = format!("DELETE FROM {}", table);
+        let mut query = format!("DELETE FROM {}", self.quote(table));
 
         // WHERE clause
         if !self.conditions.is_empty() {
             query.push_str(" WHERE ");
-            for (i, condition) in self.conditions.iter().enumerate() {
-                if i > 0 {
-                    query.push_str(&format!(" {} ", condition.connector));
-                }
-                query.push_str(&format!(
-                    "{} {} {}",
-                    condition.column,
-                    condition.operator,
-                    self.format_value(&condition.value)
-                ));
-            }
+            query.push_str(&self.render_conditions(&self.conditions, renderer)?);
         }
 
         Ok(query)
     }
 
-    fn format_value(&self, value: &QueryValue) -> String {
-        match value {
-            QueryValue::String(s) => {
-                if s.starts_with('(') && s.ends_with(')') {
-                    // For IN clauses, don't add extra quotes
-                    s.clone()
-                } else {
-                    format!("'{}'", s.replace('\'', "''"))
+    fn render_conditions(
+        &self,
+        conditions: &[Condition],
+        renderer: &mut ValueRenderer,
+    ) -> Result<String> {
+        let mut clause = String::new();
+        for (i, condition) in conditions.iter().enumerate() {
+            if i > 0 {
+                clause.push(' ');
+                clause.push_str(&condition.connector);
+                clause.push(' ');
+            }
+            match &condition.operand {
+                Operand::Group(group) => {
+                    clause.push('(');
+                    clause.push_str(&self.render_conditions(group, renderer)?);
+                    clause.push(')');
+                }
+                Operand::Raw(sql, params) => {
+                    clause.push('(');
+                    clause.push_str(&renderer.render_raw(sql, params)?);
+                    clause.push(')');
+                }
+                Operand::Subquery(subquery) => {
+                    let mut subquery = (**subquery).clone();
+                    subquery.dialect = self.dialect;
+                    clause.push_str(&self.quote(&condition.column));
+                    clause.push(' ');
+                    clause.push_str(&condition.operator);
+                    clause.push_str(" (");
+                    clause.push_str(&subquery.build_with(renderer)?);
+                    clause.push(')');
+                }
+                Operand::None => {
+                    clause.push_str(&self.quote(&condition.column));
+                    clause.push(' ');
+                    clause.push_str(&condition.operator);
+                }
+                operand => {
+                    clause.push_str(&self.quote(&condition.column));
+                    clause.push(' ');
+                    clause.push_str(&condition.operator);
+                    clause.push(' ');
+                    clause.push_str(&renderer.render_operand(operand));
                 }
             }
+        }
+        Ok(clause)
+    }
+
+    fn format_value(value: &QueryValue) -> String {
+        match value {
+            QueryValue::String(s) => format!("'{}'", s.replace('\'', "''")),
             QueryValue::Integer(i) => i.to_string(),
             QueryValue::Float(f) => f.to_string(),
             QueryValue::Boolean(b) => if *b { "TRUE" } else { "FALSE" }.to_string(),
@@ -793,6 +1629,87 @@ impl QueryExecutor {
 
         Ok(result)
     }
+
+    /// Convert query result to a GitHub-flavored Markdown table
+    ///
+    /// Pipe characters in cell values are escaped as `\|` and line breaks are
+    /// rendered as `<br>` so that each row stays on a single line.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use yimi_rutool::db::query_builder::QueryExecutor;
+    ///
+    /// let mut row = HashMap::new();
+    /// row.insert("expr".to_string(), serde_json::json!("a|b"));
+    ///
+    /// let table = QueryExecutor::to_markdown(vec![row]).unwrap();
+    /// assert_eq!(table, "| expr |\n| --- |\n| a\\|b |\n");
+    /// ```
+    pub fn to_markdown(rows: Vec<HashMap<String, serde_json::Value>>) -> Result<String> {
+        if rows.is_empty() {
+            return Ok(String::new());
+        }
+
+        let escape = |s: &str| {
+            s.replace('\\', "\\\\")
+                .replace('|', "\\|")
+                .replace("\r\n", "<br>")
+                .replace('\n', "<br>")
+        };
+
+        let mut result = String::new();
+
+        // Get column names from first row
+        let columns: Vec<String> = rows[0].keys().cloned().collect();
+
+        // Write header and separator
+        let header: Vec<String> = columns.iter().map(|col| escape(col)).collect();
+        result.push_str(&format!("| {} |\n", header.join(" | ")));
+        result.push_str(&format!("|{}\n", " --- |".repeat(columns.len())));
+
+        // Write data rows
+        for row in &rows {
+            let values: Vec<String> = columns
+                .iter()
+                .map(|col| {
+                    let value = row.get(col).unwrap_or(&serde_json::Value::Null);
+                    match value {
+                        serde_json::Value::String(s) => escape(s),
+                        serde_json::Value::Null => "NULL".to_string(),
+                        _ => escape(&value.to_string()),
+                    }
+                })
+                .collect();
+
+            result.push_str(&format!("| {} |\n", values.join(" | ")));
+        }
+
+        Ok(result)
+    }
+
+    /// Convert query result to a JSON array with one object per row
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use yimi_rutool::db::query_builder::QueryExecutor;
+    ///
+    /// let mut row = HashMap::new();
+    /// row.insert("id".to_string(), serde_json::json!(1));
+    ///
+    /// let json = QueryExecutor::to_json(vec![row]);
+    /// assert_eq!(json, serde_json::json!([{ "id": 1 }]));
+    /// ```
+    pub fn to_json(rows: Vec<HashMap<String, serde_json::Value>>) -> serde_json::Value {
+        serde_json::Value::Array(
+            rows.into_iter()
+                .map(|row| serde_json::Value::Object(row.into_iter().collect()))
+                .collect(),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -828,6 +1745,56 @@ mod tests {
         assert_eq!(query, expected);
     }
 
+    #[test]
+    fn test_multi_row_insert_with_mixed_types() {
+        let query = QueryBuilder::insert()
+            .into("products")
+            .columns(&["name", "price", "stock", "active"])
+            .add_row(&["Pen".into(), 1.5.into(), 100.into(), true.into()])
+            .add_row(&[
+                "O'Neil Notebook".into(),
+                3.25.into(),
+                0.into(),
+                false.into(),
+            ])
+            .add_row(&["Eraser".into(), QueryValue::Null, 42.into(), true.into()])
+            .build()
+            .unwrap();
+
+        let expected = "INSERT INTO products (name, price, stock, active) VALUES \
+                        ('Pen', 1.5, 100, TRUE), \
+                        ('O''Neil Notebook', 3.25, 0, FALSE), \
+                        ('Eraser', NULL, 42, TRUE)";
+        assert_eq!(query, expected);
+
+        let (sql, params) = QueryBuilder::insert()
+            .into("products")
+            .columns(&["name", "stock"])
+            .values_typed(&["Pen".into(), 100.into()])
+            .add_row(&["Ink".into(), 7.into()])
+            .build_parameterized(DatabaseType::PostgreSQL)
+            .unwrap();
+        assert_eq!(
+            sql,
+            "INSERT INTO products (name, stock) VALUES ($1, $2), ($3, $4)"
+        );
+        assert_eq!(params.len(), 4);
+        assert_eq!(params[3], QueryValue::Integer(7));
+    }
+
+    #[test]
+    fn test_multi_row_insert_row_length_validation() {
+        let result = QueryBuilder::insert()
+            .into("products")
+            .columns(&["name", "stock"])
+            .add_row(&["Pen".into(), 100.into()])
+            .add_row(&["Ink".into()])
+            .build();
+
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("row 2"));
+    }
+
     #[test]
     fn test_update_query_builder() {
         let query = QueryBuilder::update()
@@ -881,6 +1848,426 @@ mod tests {
         assert_eq!(query, expected);
     }
 
+    #[test]
+    fn test_where_in_escapes_values() {
+        let query = QueryBuilder::select()
+            .from("users")
+            .where_in("name", &["O'Brien", "(x)"])
+            .build()
+            .unwrap();
+
+        let expected = "SELECT * FROM users WHERE name IN ('O''Brien', '(x)')";
+        assert_eq!(query, expected);
+    }
+
+    #[test]
+    fn test_build_parameterized_placeholders() {
+        let builder = QueryBuilder::select()
+            .from("users")
+            .where_eq("name", "Alice")
+            .where_in("status", &["active", "pending"])
+            .where_gt("age", 18);
+
+        let (sql, params) = builder
+            .clone()
+            .build_parameterized(DatabaseType::SQLite)
+            .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT * FROM users WHERE name = ? AND status IN (?, ?) AND age > ?"
+        );
+        assert_eq!(
+            params,
+            vec![
+                QueryValue::String("Alice".to_string()),
+                QueryValue::String("active".to_string()),
+                QueryValue::String("pending".to_string()),
+                QueryValue::Integer(18),
+            ]
+        );
+
+        let (sql, params) = builder
+            .build_parameterized(DatabaseType::PostgreSQL)
+            .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT * FROM users WHERE name = $1 AND status IN ($2, $3) AND age > $4"
+        );
+        assert_eq!(params.len(), 4);
+    }
+
+    #[test]
+    fn test_build_parameterized_insert_update_delete() {
+        let (sql, params) = QueryBuilder::insert()
+            .into("users")
+            .columns(&["name", "email"])
+            .values(&["Alice", "alice@example.com"])
+            .build_parameterized(DatabaseType::MySQL)
+            .unwrap();
+        assert_eq!(sql, "INSERT INTO users (name, email) VALUES (?, ?)");
+        assert_eq!(params.len(), 2);
+
+        let (sql, params) = QueryBuilder::update()
+            .table("users")
+            .set("name", "Bob")
+            .where_eq("id", 1)
+            .build_parameterized(DatabaseType::PostgreSQL)
+            .unwrap();
+        assert_eq!(sql, "UPDATE users SET name = $1 WHERE id = $2");
+        assert_eq!(
+            params,
+            vec![
+                QueryValue::String("Bob".to_string()),
+                QueryValue::Integer(1)
+            ]
+        );
+
+        let (sql, params) = QueryBuilder::delete()
+            .from("users")
+            .where_eq("active", false)
+            .build_parameterized(DatabaseType::SQLite)
+            .unwrap();
+        assert_eq!(sql, "DELETE FROM users WHERE active = ?");
+        assert_eq!(params, vec![QueryValue::Boolean(false)]);
+    }
+
+    #[test]
+    fn test_where_between_and_null_checks() {
+        let query = QueryBuilder::select()
+            .from("orders")
+            .where_between("total", 10, 100)
+            .where_null("deleted_at")
+            .where_not_null("shipped_at")
+            .build()
+            .unwrap();
+
+        let expected = "SELECT * FROM orders WHERE total BETWEEN 10 AND 100 AND deleted_at IS NULL AND shipped_at IS NOT NULL";
+        assert_eq!(query, expected);
+
+        let (sql, params) = QueryBuilder::select()
+            .from("orders")
+            .where_between("created_at", "2024-01-01", "2024-12-31")
+            .where_null("deleted_at")
+            .build_parameterized(DatabaseType::PostgreSQL)
+            .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT * FROM orders WHERE created_at BETWEEN $1 AND $2 AND deleted_at IS NULL"
+        );
+        assert_eq!(params.len(), 2);
+    }
+
+    #[test]
+    fn test_where_group_parenthesization() {
+        let query = QueryBuilder::select()
+            .from("users")
+            .where_group(|q| q.where_eq("a", 1).or_where_eq("b", 2))
+            .where_eq("c", 3)
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            "SELECT * FROM users WHERE (a = 1 OR b = 2) AND c = 3"
+        );
+
+        let query = QueryBuilder::select()
+            .from("users")
+            .where_eq("role", "admin")
+            .or_where_group(|q| {
+                q.where_eq("role", "editor").where_group(|inner| {
+                    inner.where_null("banned_at").or_where_eq("verified", true)
+                })
+            })
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            "SELECT * FROM users WHERE role = 'admin' OR (role = 'editor' AND (banned_at IS NULL OR verified = TRUE))"
+        );
+    }
+
+    #[test]
+    fn test_where_group_parameterized_order_and_empty_group() {
+        let (sql, params) = QueryBuilder::delete()
+            .from("sessions")
+            .where_group(|q| q.where_lt("expires_at", 100).or_where_eq("revoked", true))
+            .where_group(|q| q)
+            .where_eq("user_id", 7)
+            .build_parameterized(DatabaseType::PostgreSQL)
+            .unwrap();
+
+        assert_eq!(
+            sql,
+            "DELETE FROM sessions WHERE (expires_at < $1 OR revoked = $2) AND user_id = $3"
+        );
+        assert_eq!(
+            params,
+            vec![
+                QueryValue::Integer(100),
+                QueryValue::Boolean(true),
+                QueryValue::Integer(7)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_subquery_with_raw_fragments() {
+        let premium_products = QueryBuilder::select()
+            .column("id")
+            .from("products")
+            .where_eq("tier", "premium")
+            .where_raw("price > ? * 2", &[QueryValue::Float(9.5)]);
+        let buyers = QueryBuilder::select()
+            .column("user_id")
+            .from("orders")
+            .where_in_subquery("product_id", premium_products)
+            .where_gt("quantity", 1);
+        let builder = QueryBuilder::select()
+            .column("name")
+            .column_raw("LOWER(email) AS email_key")
+            .from("users")
+            .where_eq("active", true)
+            .where_in_subquery("id", buyers)
+            .where_raw("name <> '?' OR name LIKE ?", &["A%".into()]);
+
+        let (sql, params) = builder
+            .clone()
+            .build_parameterized(DatabaseType::PostgreSQL)
+            .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT name, LOWER(email) AS email_key FROM users WHERE active = $1 \
+             AND id IN (SELECT user_id FROM orders WHERE product_id IN \
+             (SELECT id FROM products WHERE tier = $2 AND (price > $3 * 2)) AND quantity > $4) \
+             AND (name <> '?' OR name LIKE $5)"
+        );
+        assert_eq!(
+            params,
+            vec![
+                QueryValue::Boolean(true),
+                QueryValue::String("premium".to_string()),
+                QueryValue::Float(9.5),
+                QueryValue::Integer(1),
+                QueryValue::String("A%".to_string()),
+            ]
+        );
+
+        // Inline build and dialect quoting reach into the subqueries
+        let query = builder.dialect(DatabaseType::MySQL).build().unwrap();
+        assert_eq!(
+            query,
+            "SELECT `name`, LOWER(email) AS email_key FROM `users` WHERE `active` = TRUE \
+             AND `id` IN (SELECT `user_id` FROM `orders` WHERE `product_id` IN \
+             (SELECT `id` FROM `products` WHERE `tier` = 'premium' AND (price > 9.5 * 2)) AND `quantity` > 1) \
+             AND (name <> '?' OR name LIKE 'A%')"
+        );
+    }
+
+    #[test]
+    fn test_raw_fragment_parameter_count() {
+        let too_few = QueryBuilder::select()
+            .from("users")
+            .where_raw("a = ? AND b = ?", &[1.into()])
+            .build();
+        assert!(too_few.unwrap_err().to_string().contains("Too few"));
+
+        let too_many = QueryBuilder::delete()
+            .from("users")
+            .where_raw("a = ?", &[1.into(), 2.into()])
+            .build_parameterized(DatabaseType::SQLite);
+        assert!(too_many.unwrap_err().to_string().contains("Too many"));
+
+        let subquery_error = QueryBuilder::select()
+            .from("users")
+            .where_in_subquery("id", QueryBuilder::select())
+            .build();
+        assert!(subquery_error.is_err());
+    }
+
+    #[test]
+    fn test_on_conflict_dialects() {
+        let upsert = |action: ConflictAction| {
+            QueryBuilder::insert()
+                .into("users")
+                .columns(&["email", "name", "age"])
+                .values_typed(&["a@example.com".into(), "Alice".into(), 30.into()])
+                .on_conflict(&["email"], action)
+        };
+        let update = || ConflictAction::Update(vec!["name".to_string(), "age".to_string()]);
+
+        let query = upsert(update())
+            .dialect(DatabaseType::PostgreSQL)
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            "INSERT INTO \"users\" (\"email\", \"name\", \"age\") VALUES ('a@example.com', 'Alice', 30) \
+             ON CONFLICT (\"email\") DO UPDATE SET \"name\" = excluded.\"name\", \"age\" = excluded.\"age\""
+        );
+
+        let query = upsert(ConflictAction::DoNothing)
+            .dialect(DatabaseType::SQLite)
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            "INSERT INTO \"users\" (\"email\", \"name\", \"age\") VALUES ('a@example.com', 'Alice', 30) \
+             ON CONFLICT (\"email\") DO NOTHING"
+        );
+
+        let query = upsert(update())
+            .dialect(DatabaseType::MySQL)
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            "INSERT INTO `users` (`email`, `name`, `age`) VALUES ('a@example.com', 'Alice', 30) \
+             ON DUPLICATE KEY UPDATE `name` = VALUES(`name`), `age` = VALUES(`age`)"
+        );
+
+        let query = upsert(ConflictAction::DoNothing)
+            .dialect(DatabaseType::MySQL)
+            .build()
+            .unwrap();
+        assert!(query.ends_with(" ON DUPLICATE KEY UPDATE `email` = `email`"));
+
+        // Binds stay in VALUES order; the conflict clause adds none
+        let (sql, params) = upsert(update())
+            .build_parameterized(DatabaseType::PostgreSQL)
+            .unwrap();
+        assert_eq!(
+            sql,
+            "INSERT INTO users (email, name, age) VALUES ($1, $2, $3) \
+             ON CONFLICT (email) DO UPDATE SET name = excluded.name, age = excluded.age"
+        );
+        assert_eq!(params.len(), 3);
+
+        assert!(
+            upsert(update())
+                .dialect(SqlDialect::SqlServer)
+                .build()
+                .is_err()
+        );
+        assert!(
+            upsert(ConflictAction::Update(Vec::new()))
+                .dialect(DatabaseType::MySQL)
+                .build()
+                .is_err()
+        );
+        let no_target = QueryBuilder::insert()
+            .into("users")
+            .columns(&["email"])
+            .values(&["a@example.com"])
+            .on_conflict(&[], update())
+            .dialect(DatabaseType::PostgreSQL)
+            .build();
+        assert!(no_target.is_err());
+    }
+
+    #[test]
+    fn test_generic_dialect_is_default() {
+        let query = QueryBuilder::select()
+            .columns(&["id", "name"])
+            .from("users")
+            .limit(10)
+            .offset(20)
+            .build()
+            .unwrap();
+        assert_eq!(query, "SELECT id, name FROM users LIMIT 10 OFFSET 20");
+    }
+
+    #[test]
+    fn test_dialect_identifier_quoting() {
+        let builder = QueryBuilder::select()
+            .columns(&["u.name", "p.title", "COUNT(*)"])
+            .from("users u")
+            .inner_join("posts AS p", "u.id = p.user_id")
+            .where_eq("u.active", true)
+            .group_by(&["u.name", "p.title"])
+            .order_by_desc("p.title");
+
+        let query = builder
+            .clone()
+            .dialect(DatabaseType::MySQL)
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            "SELECT `u`.`name`, `p`.`title`, COUNT(*) FROM `users` `u` INNER JOIN `posts` `p` ON u.id = p.user_id WHERE `u`.`active` = TRUE GROUP BY `u`.`name`, `p`.`title` ORDER BY `p`.`title` DESC"
+        );
+
+        let query = builder.dialect(DatabaseType::PostgreSQL).build().unwrap();
+        assert_eq!(
+            query,
+            "SELECT \"u\".\"name\", \"p\".\"title\", COUNT(*) FROM \"users\" \"u\" INNER JOIN \"posts\" \"p\" ON u.id = p.user_id WHERE \"u\".\"active\" = TRUE GROUP BY \"u\".\"name\", \"p\".\"title\" ORDER BY \"p\".\"title\" DESC"
+        );
+
+        let query = QueryBuilder::insert()
+            .into("users")
+            .columns(&["name", "order"])
+            .values(&["Alice", "1"])
+            .dialect(SqlDialect::SqlServer)
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            "INSERT INTO [users] ([name], [order]) VALUES ('Alice', '1')"
+        );
+    }
+
+    #[test]
+    fn test_dialect_pagination() {
+        let paged = || QueryBuilder::select().from("users").limit(10).offset(20);
+
+        let query = paged().dialect(DatabaseType::PostgreSQL).build().unwrap();
+        assert_eq!(query, "SELECT * FROM \"users\" LIMIT 10 OFFSET 20");
+
+        let query = paged().dialect(SqlDialect::SqlServer).build().unwrap();
+        assert_eq!(
+            query,
+            "SELECT * FROM [users] ORDER BY (SELECT NULL) OFFSET 20 ROWS FETCH NEXT 10 ROWS ONLY"
+        );
+
+        let query = QueryBuilder::select()
+            .from("users")
+            .order_by_asc("id")
+            .offset(5)
+            .dialect(SqlDialect::SqlServer)
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            "SELECT * FROM [users] ORDER BY [id] ASC OFFSET 5 ROWS"
+        );
+
+        let query = QueryBuilder::select()
+            .from("users")
+            .limit(3)
+            .dialect(SqlDialect::SqlServer)
+            .build()
+            .unwrap();
+        assert_eq!(query, "SELECT TOP 3 * FROM [users]");
+
+        let query = QueryBuilder::select()
+            .from("users")
+            .offset(5)
+            .dialect(DatabaseType::SQLite)
+            .build()
+            .unwrap();
+        assert_eq!(query, "SELECT * FROM \"users\" LIMIT -1 OFFSET 5");
+
+        let query = QueryBuilder::select()
+            .from("users")
+            .offset(5)
+            .dialect(DatabaseType::MySQL)
+            .build()
+            .unwrap();
+        assert_eq!(
+            query,
+            "SELECT * FROM `users` LIMIT 18446744073709551615 OFFSET 5"
+        );
+    }
+
     #[test]
     fn test_group_by_having() {
         let query = QueryBuilder::select()
@@ -895,6 +2282,141 @@ mod tests {
         assert_eq!(query, expected);
     }
 
+    #[test]
+    fn test_count_preserves_conditions() {
+        let query = QueryBuilder::select()
+            .columns(&["u.id", "u.name"])
+            .from("users u")
+            .left_join("orders o", "o.user_id = u.id")
+            .where_eq("u.active", true)
+            .where_group(|g| g.where_gt("o.total", 100).or_where_eq("u.vip", true))
+            .where_not_null("u.email")
+            .order_by_desc("u.id");
+
+        assert_eq!(
+            query.clone().count().build().unwrap(),
+            "SELECT COUNT(*) FROM users u LEFT JOIN orders o ON o.user_id = u.id \
+             WHERE u.active = TRUE AND (o.total > 100 OR u.vip = TRUE) AND u.email IS NOT NULL"
+        );
+
+        let (sql, params) = query
+            .count()
+            .dialect(DatabaseType::PostgreSQL)
+            .build_parameterized(DatabaseType::PostgreSQL)
+            .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT COUNT(*) FROM \"users\" \"u\" LEFT JOIN \"orders\" \"o\" ON o.user_id = u.id \
+             WHERE \"u\".\"active\" = $1 AND (\"o\".\"total\" > $2 OR \"u\".\"vip\" = $3) \
+             AND \"u\".\"email\" IS NOT NULL"
+        );
+        assert_eq!(
+            params,
+            vec![
+                QueryValue::Boolean(true),
+                QueryValue::Integer(100),
+                QueryValue::Boolean(true)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_count_grouped_and_paginated() {
+        let grouped = QueryBuilder::select()
+            .column("status")
+            .from("orders")
+            .where_gt("total", 0)
+            .group_by(&["status"])
+            .having_eq("status", "open")
+            .order_by_asc("status")
+            .count()
+            .build()
+            .unwrap();
+        assert_eq!(
+            grouped,
+            "SELECT COUNT(*) FROM (SELECT status FROM orders WHERE total > 0 \
+             GROUP BY status HAVING status = 'open') counted"
+        );
+
+        let paged = QueryBuilder::select()
+            .from("orders")
+            .where_eq("status", "open")
+            .order_by_asc("id")
+            .limit(10)
+            .offset(20)
+            .dialect(SqlDialect::SqlServer)
+            .count()
+            .build()
+            .unwrap();
+        assert_eq!(
+            paged,
+            "SELECT COUNT(*) FROM (SELECT * FROM [orders] WHERE [status] = 'open' \
+             ORDER BY [id] ASC OFFSET 20 ROWS FETCH NEXT 10 ROWS ONLY) [counted]"
+        );
+    }
+
+    #[test]
+    fn test_exists() {
+        let query = QueryBuilder::select()
+            .columns(&["id"])
+            .from("users")
+            .inner_join("roles", "roles.user_id = users.id")
+            .where_eq("roles.name", "admin")
+            .order_by_asc("id");
+
+        assert_eq!(
+            query.clone().exists().build().unwrap(),
+            "SELECT EXISTS(SELECT 1 FROM users INNER JOIN roles ON roles.user_id = users.id \
+             WHERE roles.name = 'admin')"
+        );
+        assert_eq!(
+            query
+                .dialect(SqlDialect::SqlServer)
+                .exists()
+                .build()
+                .unwrap(),
+            "SELECT CASE WHEN EXISTS (SELECT 1 FROM [users] INNER JOIN [roles] \
+             ON roles.user_id = users.id WHERE [roles].[name] = 'admin') THEN 1 ELSE 0 END"
+        );
+
+        // Only SELECT queries can be wrapped
+        assert!(
+            QueryBuilder::delete()
+                .from("users")
+                .count()
+                .build()
+                .is_err()
+        );
+        assert!(QueryBuilder::select().exists().build().is_err());
+    }
+
+    #[test]
+    fn test_aggregate_columns() {
+        let query = QueryBuilder::select()
+            .column("region")
+            .sum("sales.amount", "total")
+            .avg("sales.amount", "average")
+            .min("sold_at", "first_sale")
+            .max("sold_at", "last_sale")
+            .from("sales")
+            .where_eq("year", 2024)
+            .group_by(&["region"]);
+
+        assert_eq!(
+            query.clone().build().unwrap(),
+            "SELECT region, SUM(sales.amount) AS total, AVG(sales.amount) AS average, \
+             MIN(sold_at) AS first_sale, MAX(sold_at) AS last_sale \
+             FROM sales WHERE year = 2024 GROUP BY region"
+        );
+        assert_eq!(
+            query.dialect(DatabaseType::PostgreSQL).build().unwrap(),
+            "SELECT \"region\", SUM(\"sales\".\"amount\") AS \"total\", \
+             AVG(\"sales\".\"amount\") AS \"average\", MIN(\"sold_at\") AS \"first_sale\", \
+             MAX(\"sold_at\") AS \"last_sale\" FROM \"sales\" WHERE \"year\" = 2024 \
+             GROUP BY \"region\""
+        );
+    }
+
     #[test]
     fn test_query_value_conversions() {
         let str_val: QueryValue = "test".into();
@@ -964,6 +2486,44 @@ mod tests {
         assert!(csv.contains("Alice"));
     }
 
+    #[test]
+    fn test_query_executor_markdown() {
+        let mut row1 = HashMap::new();
+        row1.insert(
+            "name".to_string(),
+            serde_json::Value::String("a|b".to_string()),
+        );
+        let mut row2 = HashMap::new();
+        row2.insert(
+            "name".to_string(),
+            serde_json::Value::String("line1\nline2".to_string()),
+        );
+        let mut row3 = HashMap::new();
+        row3.insert("name".to_string(), serde_json::Value::Null);
+
+        let markdown = QueryExecutor::to_markdown(vec![row1, row2, row3]).unwrap();
+        assert_eq!(
+            markdown,
+            "| name |\n| --- |\n| a\\|b |\n| line1<br>line2 |\n| NULL |\n"
+        );
+        assert!(QueryExecutor::to_markdown(Vec::new()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_query_executor_json() {
+        let mut row = HashMap::new();
+        row.insert("id".to_string(), serde_json::json!(1));
+        row.insert("name".to_string(), serde_json::json!("Alice"));
+        row.insert("email".to_string(), serde_json::Value::Null);
+
+        let json = QueryExecutor::to_json(vec![row]);
+        assert_eq!(
+            json,
+            serde_json::json!([{ "id": 1, "name": "Alice", "email": null }])
+        );
+        assert_eq!(QueryExecutor::to_json(Vec::new()), serde_json::json!([]));
+    }
+
     #[test]
     fn test_error_cases() {
         // Missing table name
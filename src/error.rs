@@ -28,6 +28,18 @@ pub enum Error {
     #[error("HTTP error: {0}")]
     Http(#[from] reqwest::Error),
 
+    /// HTTP responses with an unsuccessful status code
+    #[cfg(feature = "http")]
+    #[error("HTTP status {status} from {url}: {body}")]
+    HttpStatus {
+        /// Numeric status code, e.g. 404
+        status: u16,
+        /// URL of the response
+        url: String,
+        /// Beginning of the response body, possibly truncated
+        body: String,
+    },
+
     /// Cryptography errors
     #[cfg(feature = "crypto")]
     #[error("Crypto error: {0}")]
@@ -78,6 +90,55 @@ pub enum Error {
     /// Concurrency errors (lock poisoning, etc.)
     #[error("Concurrency error: {0}")]
     Concurrency(String),
+
+    /// Another error with a human-readable explanation attached
+    #[error("{context}: {source}")]
+    Context {
+        /// What was being done when the error occurred
+        context: String,
+        /// The underlying error
+        source: Box<Error>,
+    },
+}
+
+/// Category of an [`Error`], for branching without matching on messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// IO operation failed
+    Io,
+    /// Invalid UTF-8 data
+    Utf8,
+    /// JSON parsing or serialization failed
+    Json,
+    /// HTTP request failed before a response was received
+    Http,
+    /// HTTP response had an unsuccessful status code
+    HttpStatus,
+    /// Cryptographic operation failed
+    Crypto,
+    /// Database operation failed
+    Database,
+    /// Date/time parsing failed
+    DateTime,
+    /// Regex compilation failed
+    Regex,
+    /// Generic error
+    Custom,
+    /// Value conversion failed
+    Conversion,
+    /// Input failed validation
+    Validation,
+    /// Something was not found
+    NotFound,
+    /// Permission denied
+    PermissionDenied,
+    /// Operation timed out
+    Timeout,
+    /// Invalid configuration
+    Config,
+    /// Lock poisoning or other concurrency failure
+    Concurrency,
 }
 
 impl Error {
@@ -138,6 +199,123 @@ impl Error {
     pub fn concurrency<S: Into<String>>(message: S) -> Self {
         Self::Concurrency(message.into())
     }
+
+    /// Wrap this error with a description of what was being done
+    ///
+    /// The wrapped error keeps its [`kind`](Self::kind) and retryability.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::error::{Error, ErrorKind};
+    ///
+    /// let err = Error::not_found("config.toml").with_context("Loading settings");
+    /// assert_eq!(err.to_string(), "Loading settings: Not found: config.toml");
+    /// assert_eq!(err.kind(), ErrorKind::NotFound);
+    /// ```
+    #[must_use]
+    pub fn with_context<S: Into<String>>(self, context: S) -> Self {
+        Self::Context {
+            context: context.into(),
+            source: Box::new(self),
+        }
+    }
+
+    /// Category of this error, looking through any attached context
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Io(_) => ErrorKind::Io,
+            Self::Utf8(_) | Self::FromUtf8(_) => ErrorKind::Utf8,
+            #[cfg(feature = "json")]
+            Self::Json(_) => ErrorKind::Json,
+            #[cfg(feature = "http")]
+            Self::Http(_) => ErrorKind::Http,
+            #[cfg(feature = "http")]
+            Self::HttpStatus { .. } => ErrorKind::HttpStatus,
+            #[cfg(feature = "crypto")]
+            Self::Crypto(_) => ErrorKind::Crypto,
+            #[cfg(feature = "db")]
+            Self::Database(_) => ErrorKind::Database,
+            #[cfg(feature = "core")]
+            Self::DateTime(_) => ErrorKind::DateTime,
+            Self::Regex(_) => ErrorKind::Regex,
+            Self::Custom(_) => ErrorKind::Custom,
+            Self::Conversion(_) => ErrorKind::Conversion,
+            Self::Validation(_) => ErrorKind::Validation,
+            Self::NotFound(_) => ErrorKind::NotFound,
+            Self::PermissionDenied(_) => ErrorKind::PermissionDenied,
+            Self::Timeout(_) => ErrorKind::Timeout,
+            Self::Config(_) => ErrorKind::Config,
+            Self::Concurrency(_) => ErrorKind::Concurrency,
+            Self::Context { source, .. } => source.kind(),
+        }
+    }
+
+    /// Whether the operation may succeed if simply tried again
+    ///
+    /// True for timeouts, transient IO failures such as refused or reset
+    /// connections, HTTP connection failures, and HTTP responses with
+    /// status 408, 429, 500, 502, 503 or 504.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::error::Error;
+    ///
+    /// assert!(Error::timeout("no reply after 30s").is_retryable());
+    /// assert!(!Error::validation("bad input").is_retryable());
+    /// ```
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Io(e) => matches!(
+                e.kind(),
+                std::io::ErrorKind::TimedOut
+                    | std::io::ErrorKind::Interrupted
+                    | std::io::ErrorKind::WouldBlock
+                    | std::io::ErrorKind::ConnectionRefused
+                    | std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::BrokenPipe
+                    | std::io::ErrorKind::UnexpectedEof
+            ),
+            #[cfg(feature = "http")]
+            Self::Http(e) => {
+                e.is_timeout()
+                    || e.is_connect()
+                    || e.status()
+                        .is_some_and(|status| is_retryable_status(status.as_u16()))
+            }
+            #[cfg(feature = "http")]
+            Self::HttpStatus { status, .. } => is_retryable_status(*status),
+            Self::Timeout(_) => true,
+            Self::Context { source, .. } => source.is_retryable(),
+            _ => false,
+        }
+    }
+}
+
+impl From<std::num::ParseIntError> for Error {
+    fn from(err: std::num::ParseIntError) -> Self {
+        Self::Conversion(format!("Invalid integer: {err}"))
+    }
+}
+
+impl From<std::num::ParseFloatError> for Error {
+    fn from(err: std::num::ParseFloatError) -> Self {
+        Self::Conversion(format!("Invalid float: {err}"))
+    }
+}
+
+impl From<std::fmt::Error> for Error {
+    fn from(_: std::fmt::Error) -> Self {
+        Self::Conversion("Formatting failed".to_string())
+    }
+}
+
+/// HTTP status codes that indicate a transient server-side condition
+#[cfg(feature = "http")]
+fn is_retryable_status(status: u16) -> bool {
+    matches!(status, 408 | 429 | 500 | 502 | 503 | 504)
 }
 
 #[cfg(test)]
@@ -157,4 +335,108 @@ mod tests {
         assert!(msg.contains("Validation error"));
         assert!(msg.contains("invalid input"));
     }
+
+    #[test]
+    fn test_error_kind() {
+        assert_eq!(Error::custom("x").kind(), ErrorKind::Custom);
+        assert_eq!(Error::timeout("x").kind(), ErrorKind::Timeout);
+        let io = Error::from(std::io::Error::from(std::io::ErrorKind::NotFound));
+        assert_eq!(io.kind(), ErrorKind::Io);
+        let utf8 = Error::from(String::from_utf8(vec![0xff]).unwrap_err());
+        assert_eq!(utf8.kind(), ErrorKind::Utf8);
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(Error::timeout("slow").is_retryable());
+        assert!(
+            Error::from(std::io::Error::from(std::io::ErrorKind::ConnectionReset)).is_retryable()
+        );
+        assert!(!Error::from(std::io::Error::from(std::io::ErrorKind::NotFound)).is_retryable());
+        assert!(!Error::validation("bad").is_retryable());
+        assert!(!Error::not_found("gone").is_retryable());
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_http_status_retryable() {
+        let status = |status| Error::HttpStatus {
+            status,
+            url: "http://example.com/".to_string(),
+            body: String::new(),
+        };
+        assert!(status(503).is_retryable());
+        assert!(status(429).is_retryable());
+        assert!(!status(404).is_retryable());
+        assert!(!status(501).is_retryable());
+    }
+
+    fn parse_pair(input: &str) -> Result<(i32, f64)> {
+        let (left, right) = input
+            .split_once(',')
+            .ok_or_else(|| Error::validation("missing comma"))?;
+        Ok((left.trim().parse()?, right.trim().parse()?))
+    }
+
+    #[test]
+    fn test_from_parse_errors() {
+        assert_eq!(parse_pair("3, 1.5").unwrap(), (3, 1.5));
+
+        let err = parse_pair("x, 1.5").unwrap_err();
+        assert!(matches!(err, Error::Conversion(_)));
+        assert_eq!(
+            err.to_string(),
+            "Conversion error: Invalid integer: invalid digit found in string"
+        );
+
+        let err = parse_pair("3, y").unwrap_err();
+        assert!(matches!(err, Error::Conversion(_)));
+        assert!(err.to_string().contains("Invalid float"));
+    }
+
+    fn decode(bytes: &[u8]) -> Result<&str> {
+        Ok(std::str::from_utf8(bytes)?)
+    }
+
+    fn render(out: &mut dyn std::fmt::Write) -> Result<()> {
+        write!(out, "value")?;
+        Ok(())
+    }
+
+    /// A writer that always fails
+    struct Full;
+
+    impl std::fmt::Write for Full {
+        fn write_str(&mut self, _: &str) -> std::fmt::Result {
+            Err(std::fmt::Error)
+        }
+    }
+
+    #[test]
+    fn test_from_utf8_and_fmt_errors() {
+        assert!(matches!(decode(&[0xff, 0xfe]), Err(Error::Utf8(_))));
+
+        assert!(matches!(render(&mut Full), Err(Error::Conversion(_))));
+        let mut out = String::new();
+        render(&mut out).unwrap();
+        assert_eq!(out, "value");
+    }
+
+    #[test]
+    fn test_with_context() {
+        let err = Error::timeout("no reply").with_context("Fetching feed");
+        assert_eq!(err.to_string(), "Fetching feed: Timeout error: no reply");
+        assert_eq!(err.kind(), ErrorKind::Timeout);
+        assert!(err.is_retryable());
+
+        let nested = Error::validation("empty")
+            .with_context("Parsing row 3")
+            .with_context("Importing users.csv");
+        assert_eq!(
+            nested.to_string(),
+            "Importing users.csv: Parsing row 3: Validation error: empty"
+        );
+        assert_eq!(nested.kind(), ErrorKind::Validation);
+        assert!(std::error::Error::source(&nested).is_some());
+    }
 }
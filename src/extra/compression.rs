@@ -6,10 +6,18 @@
 use crate::error::{Error, Result};
 use std::fs::{File, create_dir_all};
 use std::io::{Read, Write};
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 
 #[cfg(feature = "zip")]
-use zip::{CompressionMethod, ZipArchive, ZipWriter, write::FileOptions};
+use std::collections::HashMap;
+#[cfg(feature = "zip")]
+use std::io::Cursor;
+#[cfg(feature = "zip")]
+use tar::{Archive, Builder, EntryType, Header};
+#[cfg(feature = "zip")]
+use zip::{
+    AesMode, CompressionMethod, ZipArchive, ZipWriter, result::ZipError, write::FileOptions,
+};
 
 /// Supported compression formats
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -22,6 +30,10 @@ pub enum CompressionFormat {
     Tar,
     /// TAR.GZ format (compressed archive)
     TarGz,
+    /// Zstandard format (single file)
+    Zstd,
+    /// Brotli format (single file)
+    Brotli,
 }
 
 impl CompressionFormat {
@@ -32,6 +44,8 @@ impl CompressionFormat {
             Self::Gzip => "gz",
             Self::Tar => "tar",
             Self::TarGz => "tar.gz",
+            Self::Zstd => "zst",
+            Self::Brotli => "br",
         }
     }
 
@@ -42,6 +56,8 @@ impl CompressionFormat {
             "gz" | "gzip" => Some(Self::Gzip),
             "tar" => Some(Self::Tar),
             "tar.gz" | "tgz" => Some(Self::TarGz),
+            "zst" | "zstd" => Some(Self::Zstd),
+            "br" => Some(Self::Brotli),
             _ => None,
         }
     }
@@ -84,6 +100,17 @@ impl CompressionLevel {
             Self::Best => 9,
         }
     }
+
+    /// ZIP entry options for this level; `None` stores entries uncompressed
+    #[cfg(feature = "zip")]
+    fn to_zip_options(self) -> FileOptions<'static, ()> {
+        match self {
+            Self::None => FileOptions::default().compression_method(CompressionMethod::Stored),
+            _ => FileOptions::default()
+                .compression_method(CompressionMethod::Deflated)
+                .compression_level(Some(i64::from(self.to_zip_level()))),
+        }
+    }
 }
 
 /// Compression statistics
@@ -177,9 +204,88 @@ impl CompressionUtil {
         destination: Q,
         level: CompressionLevel,
     ) -> Result<CompressionStats> {
-        let source_path = source.as_ref();
-        let destination_path = destination.as_ref();
+        Self::compress_zip_with_password(source, destination, level, None)
+    }
+
+    /// Compress a file or directory to ZIP format, optionally encrypting
+    /// every file with a password
+    ///
+    /// Encrypted entries use the AES-256 extension (AE-2) introduced by
+    /// `WinZip`. 7-Zip, `WinZip`, `WinRAR`, Bandizip and libraries built on the
+    /// `zip` crate can open them. The built-in Windows and macOS archive
+    /// tools, Info-ZIP `unzip` and Python's `zipfile` module can't. Directory
+    /// entries and file names are not encrypted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::extra::{CompressionUtil, CompressionLevel};
+    ///
+    /// // let stats = CompressionUtil::compress_zip_with_password(
+    /// //     "sensitive_directory",
+    /// //     "bundle.zip",
+    /// //     CompressionLevel::Balanced,
+    /// //     Some("correct horse battery staple"),
+    /// // ).unwrap();
+    /// ```
+    #[cfg(feature = "zip")]
+    pub fn compress_zip_with_password<P: AsRef<Path>, Q: AsRef<Path>>(
+        source: P,
+        destination: Q,
+        level: CompressionLevel,
+        password: Option<&str>,
+    ) -> Result<CompressionStats> {
+        Self::write_zip(
+            source.as_ref(),
+            destination.as_ref(),
+            level,
+            password,
+            &mut |_| {},
+        )
+    }
+
+    /// Compress a file or directory to ZIP format, reporting progress
+    ///
+    /// `on_progress` is called synchronously when each file starts and after
+    /// every chunk written, between I/O operations and without any lock held,
+    /// so it can render a progress bar directly.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::extra::{CompressionUtil, CompressionLevel};
+    ///
+    /// // CompressionUtil::compress_zip_with_progress(
+    /// //     "source_directory",
+    /// //     "output.zip",
+    /// //     CompressionLevel::Balanced,
+    /// //     |event| println!("{:.0}% {}", event.percentage(), event.current_file),
+    /// // ).unwrap();
+    /// ```
+    #[cfg(feature = "zip")]
+    pub fn compress_zip_with_progress<P: AsRef<Path>, Q: AsRef<Path>>(
+        source: P,
+        destination: Q,
+        level: CompressionLevel,
+        mut on_progress: impl FnMut(ProgressEvent),
+    ) -> Result<CompressionStats> {
+        Self::write_zip(
+            source.as_ref(),
+            destination.as_ref(),
+            level,
+            None,
+            &mut on_progress,
+        )
+    }
 
+    #[cfg(feature = "zip")]
+    fn write_zip(
+        source_path: &Path,
+        destination_path: &Path,
+        level: CompressionLevel,
+        password: Option<&str>,
+        on_progress: &mut dyn FnMut(ProgressEvent),
+    ) -> Result<CompressionStats> {
         if !source_path.exists() {
             return Err(Error::not_found(format!(
                 "Source path does not exist: {:?}",
@@ -187,50 +293,59 @@ impl CompressionUtil {
             )));
         }
 
-        let file = File::create(destination_path)
-            .map_err(|e| Error::validation(format!("Failed to create ZIP file: {e}")))?;
-
-        let mut zip = ZipWriter::new(file);
-        let options = FileOptions::<()>::default()
-            .compression_method(CompressionMethod::Deflated)
-            .compression_level(Some(level.to_zip_level() as i64));
-
-        let mut original_size = 0u64;
-        let mut file_count = 0usize;
+        let mut options = level.to_zip_options();
+        if let Some(password) = password {
+            if password.is_empty() {
+                return Err(Error::validation(
+                    "ZIP password must not be empty".to_string(),
+                ));
+            }
+            options = options.with_aes_encryption(AesMode::Aes256, password);
+        }
 
+        let mut entries = Vec::new();
         if source_path.is_file() {
-            // Compress single file
             let file_name = source_path
                 .file_name()
                 .ok_or_else(|| Error::validation("Invalid file name".to_string()))?
-                .to_string_lossy();
+                .to_string_lossy()
+                .into_owned();
+            entries.push(ArchiveSource::file(source_path, file_name)?);
+        } else if source_path.is_dir() {
+            Self::collect_archive_sources(source_path, source_path, &mut entries)?;
+        }
 
-            zip.start_file(&file_name, options)
-                .map_err(|e| Error::validation(format!("Failed to start ZIP file entry: {e}")))?;
+        let file = File::create(destination_path)
+            .map_err(|e| Error::validation(format!("Failed to create ZIP file: {e}")))?;
+        let mut zip = ZipWriter::new(file);
 
-            let mut source_file = File::open(source_path)
-                .map_err(|e| Error::validation(format!("Failed to open source file: {e}")))?;
+        let mut progress = ProgressEvent::new(
+            entries.iter().filter(|entry| !entry.is_dir).count(),
+            entries.iter().map(|entry| entry.size).sum(),
+        );
 
-            let mut buffer = Vec::new();
-            source_file
-                .read_to_end(&mut buffer)
-                .map_err(|e| Error::validation(format!("Failed to read source file: {e}")))?;
+        for entry in &entries {
+            if entry.is_dir {
+                zip.add_directory(format!("{}/", entry.name), options)
+                    .map_err(|e| {
+                        Error::validation(format!("Failed to add directory to ZIP: {e}"))
+                    })?;
+                continue;
+            }
 
-            original_size += buffer.len() as u64;
-            file_count += 1;
+            zip.start_file(&entry.name, options)
+                .map_err(|e| Error::validation(format!("Failed to start ZIP file entry: {e}")))?;
 
-            zip.write_all(&buffer)
-                .map_err(|e| Error::validation(format!("Failed to write to ZIP: {e}")))?;
-        } else if source_path.is_dir() {
-            // Compress directory
-            Self::compress_directory_to_zip(
-                &mut zip,
-                source_path,
-                source_path,
-                options,
-                &mut original_size,
-                &mut file_count,
-            )?;
+            let mut source_file = File::open(&entry.path)
+                .map_err(|e| Error::validation(format!("Failed to open source file: {e}")))?;
+
+            progress.start_file(&entry.name);
+            on_progress(progress.clone());
+            copy_with_progress(&mut source_file, &mut zip, &mut |bytes| {
+                progress.bytes_processed += bytes;
+                on_progress(progress.clone());
+            })
+            .map_err(|e| Error::validation(format!("Failed to write to ZIP: {e}")))?;
         }
 
         let zip_file = zip
@@ -243,21 +358,68 @@ impl CompressionUtil {
             .len();
 
         Ok(CompressionStats::new(
-            original_size,
+            progress.bytes_processed,
             compressed_size,
-            file_count,
+            progress.total_files,
         ))
     }
 
     /// Decompress a ZIP file
+    ///
+    /// Every entry must resolve inside `destination`: entry names with
+    /// absolute paths or `..` components are rejected before anything is
+    /// written, and output paths are checked again after resolving symlinks
+    /// in the destination tree.
     #[cfg(feature = "zip")]
     pub fn decompress_zip<P: AsRef<Path>, Q: AsRef<Path>>(
         source: P,
         destination: Q,
     ) -> Result<CompressionStats> {
-        let source_path = source.as_ref();
-        let destination_path = destination.as_ref();
+        Self::decompress_zip_with_password(source, destination, None)
+    }
+
+    /// Decompress a ZIP file whose entries may be password protected
+    ///
+    /// Both AES and legacy `ZipCrypto` entries are supported. Encrypted entries
+    /// fail with an error when no password or a wrong one is given; a file
+    /// whose data fails authentication is removed rather than left with
+    /// garbage contents. Unencrypted entries are extracted normally. The path
+    /// checks of [`CompressionUtil::decompress_zip`] apply.
+    #[cfg(feature = "zip")]
+    pub fn decompress_zip_with_password<P: AsRef<Path>, Q: AsRef<Path>>(
+        source: P,
+        destination: Q,
+        password: Option<&str>,
+    ) -> Result<CompressionStats> {
+        Self::extract_zip(source.as_ref(), destination.as_ref(), password, &mut |_| {})
+    }
+
+    /// Decompress a ZIP file, reporting progress
+    ///
+    /// `on_progress` is called the same way as for
+    /// [`CompressionUtil::compress_zip_with_progress`], with byte counts of
+    /// uncompressed data.
+    #[cfg(feature = "zip")]
+    pub fn decompress_zip_with_progress<P: AsRef<Path>, Q: AsRef<Path>>(
+        source: P,
+        destination: Q,
+        mut on_progress: impl FnMut(ProgressEvent),
+    ) -> Result<CompressionStats> {
+        Self::extract_zip(
+            source.as_ref(),
+            destination.as_ref(),
+            None,
+            &mut on_progress,
+        )
+    }
 
+    #[cfg(feature = "zip")]
+    fn extract_zip(
+        source_path: &Path,
+        destination_path: &Path,
+        password: Option<&str>,
+        on_progress: &mut dyn FnMut(ProgressEvent),
+    ) -> Result<CompressionStats> {
         if !source_path.exists() {
             return Err(Error::not_found(format!(
                 "ZIP file does not exist: {:?}",
@@ -271,42 +433,86 @@ impl CompressionUtil {
         let mut archive = ZipArchive::new(file)
             .map_err(|e| Error::validation(format!("Failed to read ZIP archive: {e}")))?;
 
-        let mut original_size = 0u64;
         let mut compressed_size = 0u64;
         let file_count = archive.len();
 
+        // Check every entry name and password requirement before writing anything
+        let mut encrypted = Vec::with_capacity(archive.len());
+        let mut total_files = 0;
+        let mut total_bytes = 0;
         for i in 0..archive.len() {
-            let mut file = archive
-                .by_index(i)
+            let file = archive
+                .by_index_raw(i)
                 .map_err(|e| Error::validation(format!("Failed to read ZIP entry {i}: {e}")))?;
+            entry_output_path(destination_path, file.name())?;
+            if file.encrypted() && password.is_none() {
+                return Err(Error::validation(format!(
+                    "ZIP entry '{}' is encrypted and requires a password",
+                    file.name()
+                )));
+            }
+            encrypted.push(file.encrypted());
+            if !file.name().ends_with('/') {
+                total_files += 1;
+                total_bytes += file.size();
+            }
+        }
+
+        let root = extraction_root(destination_path)?;
+        let mut progress = ProgressEvent::new(total_files, total_bytes);
+
+        for (i, &is_encrypted) in encrypted.iter().enumerate() {
+            let entry = match password {
+                Some(password) if is_encrypted => archive.by_index_decrypt(i, password.as_bytes()),
+                _ => archive.by_index(i),
+            };
+            let mut file = entry.map_err(|e| match e {
+                ZipError::InvalidPassword => {
+                    Error::validation(format!("Incorrect password for ZIP entry {i}"))
+                }
+                e => Error::validation(format!("Failed to read ZIP entry {i}: {e}")),
+            })?;
 
-            let outpath = destination_path.join(file.name());
+            let outpath = entry_output_path(&root, file.name())?;
 
             compressed_size += file.compressed_size();
-            original_size += file.size();
 
             if file.name().ends_with('/') {
                 // Directory
-                create_dir_all(&outpath)
-                    .map_err(|e| Error::validation(format!("Failed to create directory: {e}")))?;
+                create_dir_within(&root, &outpath, file.name())?;
             } else {
                 // File
                 if let Some(parent) = outpath.parent() {
-                    create_dir_all(parent).map_err(|e| {
-                        Error::validation(format!("Failed to create parent directory: {e}"))
-                    })?;
+                    create_dir_within(&root, parent, file.name())?;
                 }
+                verify_within(&root, &outpath, file.name())?;
 
                 let mut outfile = File::create(&outpath)
                     .map_err(|e| Error::validation(format!("Failed to create output file: {e}")))?;
 
-                std::io::copy(&mut file, &mut outfile)
-                    .map_err(|e| Error::validation(format!("Failed to extract file: {e}")))?;
+                let name = file.name().to_string();
+                progress.start_file(&name);
+                on_progress(progress.clone());
+                let copied = copy_with_progress(&mut file, &mut outfile, &mut |bytes| {
+                    progress.bytes_processed += bytes;
+                    on_progress(progress.clone());
+                });
+                if let Err(e) = copied {
+                    drop(outfile);
+                    let _ = std::fs::remove_file(&outpath);
+                    return Err(if is_encrypted {
+                        Error::validation(format!(
+                            "Failed to extract encrypted file '{name}' (incorrect password or corrupted data): {e}"
+                        ))
+                    } else {
+                        Error::validation(format!("Failed to extract file: {e}"))
+                    });
+                }
             }
         }
 
         Ok(CompressionStats::new(
-            original_size,
+            progress.bytes_processed,
             compressed_size,
             file_count,
         ))
@@ -341,6 +547,97 @@ impl CompressionUtil {
         Ok(decompressed)
     }
 
+    /// Compress data to Zstandard format
+    ///
+    /// `level` ranges from 1 (fastest) to 22 (smallest output); 3 is the
+    /// zstd default. Zstandard usually compresses text such as logs both
+    /// smaller and faster than GZIP.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error` if `level` is outside 1..=22.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::extra::CompressionUtil;
+    ///
+    /// let data = b"GET /health 200\n".repeat(1000);
+    /// let compressed = CompressionUtil::compress_zstd(&data, 3).unwrap();
+    /// assert!(compressed.len() < data.len() / 10);
+    /// assert_eq!(CompressionUtil::decompress_zstd(&compressed).unwrap(), data);
+    /// ```
+    #[cfg(feature = "zstd")]
+    pub fn compress_zstd(data: &[u8], level: i32) -> Result<Vec<u8>> {
+        if !(1..=22).contains(&level) {
+            return Err(Error::validation(format!(
+                "Zstandard level must be between 1 and 22, got {level}"
+            )));
+        }
+
+        zstd::encode_all(data, level)
+            .map_err(|e| Error::validation(format!("Failed to compress with Zstandard: {e}")))
+    }
+
+    /// Decompress Zstandard data
+    #[cfg(feature = "zstd")]
+    pub fn decompress_zstd(data: &[u8]) -> Result<Vec<u8>> {
+        zstd::decode_all(data)
+            .map_err(|e| Error::validation(format!("Failed to decompress Zstandard: {e}")))
+    }
+
+    /// Compress data to Brotli format
+    ///
+    /// `quality` ranges from 0 (fastest) to 11 (smallest output). Brotli is
+    /// widely supported by browsers as a `Content-Encoding`, which makes it a
+    /// good fit for precompressed web assets.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error` if `quality` is above 11.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::extra::CompressionUtil;
+    ///
+    /// let data = b"<li>item</li>".repeat(500);
+    /// let compressed = CompressionUtil::compress_brotli(&data, 9).unwrap();
+    /// assert_eq!(CompressionUtil::decompress_brotli(&compressed).unwrap(), data);
+    /// ```
+    #[cfg(feature = "brotli")]
+    pub fn compress_brotli(data: &[u8], quality: u32) -> Result<Vec<u8>> {
+        // Window size as log2 bytes; 22 (4 MiB) is the Brotli default
+        const LG_WINDOW_SIZE: u32 = 22;
+
+        if quality > 11 {
+            return Err(Error::validation(format!(
+                "Brotli quality must be between 0 and 11, got {quality}"
+            )));
+        }
+
+        let mut encoder = brotli::CompressorWriter::new(Vec::new(), 4096, quality, LG_WINDOW_SIZE);
+        encoder
+            .write_all(data)
+            .map_err(|e| Error::validation(format!("Failed to compress with Brotli: {e}")))?;
+        encoder
+            .flush()
+            .map_err(|e| Error::validation(format!("Failed to finish Brotli compression: {e}")))?;
+        Ok(encoder.into_inner())
+    }
+
+    /// Decompress Brotli data
+    #[cfg(feature = "brotli")]
+    pub fn decompress_brotli(data: &[u8]) -> Result<Vec<u8>> {
+        let mut decoder = brotli::Decompressor::new(data, 4096);
+        let mut decompressed = Vec::new();
+        decoder
+            .read_to_end(&mut decompressed)
+            .map_err(|e| Error::validation(format!("Failed to decompress Brotli: {e}")))?;
+
+        Ok(decompressed)
+    }
+
     /// Compress a file with GZIP
     #[cfg(feature = "flate2")]
     pub fn compress_file_gzip<P: AsRef<Path>, Q: AsRef<Path>>(
@@ -416,24 +713,185 @@ impl CompressionUtil {
         Ok(CompressionStats::new(original_size, compressed_size, 1))
     }
 
-    /// Recursively compress a directory to ZIP
-    #[cfg(feature = "zip")]
-    fn compress_directory_to_zip(
-        zip: &mut ZipWriter<File>,
+    /// Archive a file or directory to TAR format
+    ///
+    /// Directory contents are stored with paths relative to `source`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::extra::CompressionUtil;
+    ///
+    /// // let stats = CompressionUtil::compress_tar("source_directory", "output.tar").unwrap();
+    /// ```
+    pub fn compress_tar<P: AsRef<Path>, Q: AsRef<Path>>(
+        source: P,
+        destination: Q,
+    ) -> Result<CompressionStats> {
+        let destination_path = destination.as_ref();
+        let file = File::create(destination_path)
+            .map_err(|e| Error::validation(format!("Failed to create TAR file: {e}")))?;
+
+        let (_, original_size, file_count) = Self::write_tar(source.as_ref(), file)?;
+
+        let compressed_size = std::fs::metadata(destination_path)
+            .map_err(|e| Error::validation(format!("Failed to get TAR file metadata: {e}")))?
+            .len();
+
+        Ok(CompressionStats::new(
+            original_size,
+            compressed_size,
+            file_count,
+        ))
+    }
+
+    /// Archive a file or directory to TAR format compressed with GZIP
+    #[cfg(feature = "flate2")]
+    pub fn compress_tar_gz<P: AsRef<Path>, Q: AsRef<Path>>(
+        source: P,
+        destination: Q,
+        level: CompressionLevel,
+    ) -> Result<CompressionStats> {
+        use flate2::{Compression, write::GzEncoder};
+
+        let destination_path = destination.as_ref();
+        let file = File::create(destination_path)
+            .map_err(|e| Error::validation(format!("Failed to create TAR.GZ file: {e}")))?;
+
+        let encoder = GzEncoder::new(file, Compression::new(level.to_zip_level().unsigned_abs()));
+        let (encoder, original_size, file_count) = Self::write_tar(source.as_ref(), encoder)?;
+        encoder
+            .finish()
+            .map_err(|e| Error::validation(format!("Failed to finish GZIP compression: {e}")))?;
+
+        let compressed_size = std::fs::metadata(destination_path)
+            .map_err(|e| Error::validation(format!("Failed to get TAR.GZ file metadata: {e}")))?
+            .len();
+
+        Ok(CompressionStats::new(
+            original_size,
+            compressed_size,
+            file_count,
+        ))
+    }
+
+    /// Extract a TAR archive into a directory
+    ///
+    /// Entries with absolute paths or `..` components are rejected with an
+    /// error, so an archive can't write outside `destination`. Entries
+    /// extracted before the offending one are left in place. Links and other
+    /// special entries are skipped.
+    pub fn decompress_tar<P: AsRef<Path>, Q: AsRef<Path>>(
+        source: P,
+        destination: Q,
+    ) -> Result<CompressionStats> {
+        let source_path = source.as_ref();
+        if !source_path.exists() {
+            return Err(Error::not_found(format!(
+                "TAR file does not exist: {source_path:?}"
+            )));
+        }
+
+        let file = File::open(source_path)
+            .map_err(|e| Error::validation(format!("Failed to open TAR file: {e}")))?;
+        let (original_size, file_count) =
+            Self::extract_tar(std::io::BufReader::new(file), destination.as_ref())?;
+
+        let compressed_size = std::fs::metadata(source_path)
+            .map_err(|e| Error::validation(format!("Failed to get source file size: {e}")))?
+            .len();
+
+        Ok(CompressionStats::new(
+            original_size,
+            compressed_size,
+            file_count,
+        ))
+    }
+
+    /// Extract a GZIP-compressed TAR archive into a directory
+    ///
+    /// Applies the same path checks as [`CompressionUtil::decompress_tar`].
+    #[cfg(feature = "flate2")]
+    pub fn decompress_tar_gz<P: AsRef<Path>, Q: AsRef<Path>>(
+        source: P,
+        destination: Q,
+    ) -> Result<CompressionStats> {
+        use flate2::read::GzDecoder;
+
+        let source_path = source.as_ref();
+        if !source_path.exists() {
+            return Err(Error::not_found(format!(
+                "TAR.GZ file does not exist: {source_path:?}"
+            )));
+        }
+
+        let file = File::open(source_path)
+            .map_err(|e| Error::validation(format!("Failed to open TAR.GZ file: {e}")))?;
+        let decoder = GzDecoder::new(std::io::BufReader::new(file));
+        let (original_size, file_count) = Self::extract_tar(decoder, destination.as_ref())?;
+
+        let compressed_size = std::fs::metadata(source_path)
+            .map_err(|e| Error::validation(format!("Failed to get source file size: {e}")))?
+            .len();
+
+        Ok(CompressionStats::new(
+            original_size,
+            compressed_size,
+            file_count,
+        ))
+    }
+
+    /// Write a file or directory tree as a TAR stream
+    fn write_tar<W: Write>(source_path: &Path, writer: W) -> Result<(W, u64, usize)> {
+        if !source_path.exists() {
+            return Err(Error::not_found(format!(
+                "Source path does not exist: {source_path:?}"
+            )));
+        }
+
+        let mut tar = Builder::new(writer);
+        let mut original_size = 0u64;
+        let mut file_count = 0usize;
+
+        if source_path.is_file() {
+            let file_name = source_path
+                .file_name()
+                .ok_or_else(|| Error::validation("Invalid file name".to_string()))?
+                .to_string_lossy();
+            original_size += Self::append_tar_file(&mut tar, source_path, &file_name)?;
+            file_count += 1;
+        } else if source_path.is_dir() {
+            Self::compress_directory_to_tar(
+                &mut tar,
+                source_path,
+                source_path,
+                &mut original_size,
+                &mut file_count,
+            )?;
+        }
+
+        let writer = tar
+            .into_inner()
+            .map_err(|e| Error::validation(format!("Failed to finish TAR archive: {e}")))?;
+        Ok((writer, original_size, file_count))
+    }
+
+    /// Recursively add a directory to a TAR stream
+    fn compress_directory_to_tar<W: Write>(
+        tar: &mut Builder<W>,
         base_path: &Path,
         current_path: &Path,
-        options: FileOptions<()>,
         total_size: &mut u64,
         file_count: &mut usize,
     ) -> Result<()> {
-        let entries = std::fs::read_dir(current_path)
-            .map_err(|e| Error::validation(format!("Failed to read directory: {e}")))?;
-
-        for entry in entries {
-            let entry = entry
-                .map_err(|e| Error::validation(format!("Failed to read directory entry: {e}")))?;
-            let path = entry.path();
-
+        let mut paths = std::fs::read_dir(current_path)
+            .map_err(|e| Error::validation(format!("Failed to read directory: {e}")))?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<_>>>()
+            .map_err(|e| Error::validation(format!("Failed to read directory entry: {e}")))?;
+        paths.sort();
+
+        for path in paths {
             let relative_path = path
                 .strip_prefix(base_path)
                 .map_err(|e| Error::validation(format!("Failed to create relative path: {e}")))?;
@@ -441,33 +899,116 @@ impl CompressionUtil {
             let name = relative_path.to_string_lossy().replace('\\', "/");
 
             if path.is_file() {
-                zip.start_file(&name, options).map_err(|e| {
-                    Error::validation(format!("Failed to start ZIP file entry: {e}"))
-                })?;
-
-                let mut file = File::open(&path)
-                    .map_err(|e| Error::validation(format!("Failed to open file: {e}")))?;
-
-                let mut buffer = Vec::new();
-                file.read_to_end(&mut buffer)
-                    .map_err(|e| Error::validation(format!("Failed to read file: {e}")))?;
-
-                *total_size += buffer.len() as u64;
+                *total_size += Self::append_tar_file(tar, &path, &name)?;
                 *file_count += 1;
-
-                zip.write_all(&buffer)
-                    .map_err(|e| Error::validation(format!("Failed to write to ZIP: {e}")))?;
             } else if path.is_dir() {
-                // Add directory entry
-                zip.add_directory(format!("{name}/"), options)
+                let metadata = std::fs::metadata(&path)
+                    .map_err(|e| Error::validation(format!("Failed to read metadata: {e}")))?;
+                let mut header = tar_header(EntryType::Directory, 0, &metadata, 0o755);
+                tar.append_data(&mut header, format!("{name}/"), std::io::empty())
                     .map_err(|e| {
-                        Error::validation(format!("Failed to add directory to ZIP: {e}"))
+                        Error::validation(format!("Failed to write TAR entry {name}: {e}"))
                     })?;
 
-                // Recursively process subdirectory
-                Self::compress_directory_to_zip(
-                    zip, base_path, &path, options, total_size, file_count,
-                )?;
+                Self::compress_directory_to_tar(tar, base_path, &path, total_size, file_count)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Add one file to a TAR stream, returning its size
+    fn append_tar_file<W: Write>(tar: &mut Builder<W>, path: &Path, name: &str) -> Result<u64> {
+        let file =
+            File::open(path).map_err(|e| Error::validation(format!("Failed to open file: {e}")))?;
+        let metadata = file
+            .metadata()
+            .map_err(|e| Error::validation(format!("Failed to read metadata: {e}")))?;
+
+        let mut header = tar_header(EntryType::Regular, metadata.len(), &metadata, 0o644);
+        let data = ExactSizeReader {
+            inner: file,
+            remaining: metadata.len(),
+        };
+        tar.append_data(&mut header, name, data)
+            .map_err(|e| Error::validation(format!("Failed to write TAR entry {name}: {e}")))?;
+        Ok(metadata.len())
+    }
+
+    /// Extract a TAR stream, returning the extracted size and file count
+    fn extract_tar<R: Read>(reader: R, destination: &Path) -> Result<(u64, usize)> {
+        let mut archive = Archive::new(reader);
+        let mut original_size = 0u64;
+        let mut file_count = 0usize;
+
+        let root = extraction_root(destination)?;
+
+        let entries = archive
+            .entries()
+            .map_err(|e| Error::validation(format!("Failed to read TAR archive: {e}")))?;
+        for entry in entries {
+            let mut entry =
+                entry.map_err(|e| Error::validation(format!("Failed to read TAR entry: {e}")))?;
+            let name = String::from_utf8_lossy(&entry.path_bytes()).into_owned();
+            let entry_type = entry.header().entry_type();
+            let is_dir = entry_type.is_dir() || (entry_type.is_file() && name.ends_with('/'));
+            // Links and special files are skipped
+            if !is_dir && !entry_type.is_file() && entry_type != EntryType::Continuous {
+                continue;
+            }
+            let outpath = entry_output_path(&root, &name)?;
+
+            if is_dir {
+                create_dir_within(&root, &outpath, &name)?;
+                continue;
+            }
+
+            if let Some(parent) = outpath.parent() {
+                create_dir_within(&root, parent, &name)?;
+            }
+            verify_within(&root, &outpath, &name)?;
+
+            let mut outfile = File::create(&outpath)
+                .map_err(|e| Error::validation(format!("Failed to create output file: {e}")))?;
+            original_size += std::io::copy(&mut entry, &mut outfile)
+                .map_err(|e| Error::validation(format!("Failed to read TAR entry data: {e}")))?;
+            file_count += 1;
+        }
+
+        Ok((original_size, file_count))
+    }
+
+    /// Recursively list the files and directories to archive, sorted by path
+    #[cfg(feature = "zip")]
+    fn collect_archive_sources(
+        base_path: &Path,
+        current_path: &Path,
+        sources: &mut Vec<ArchiveSource>,
+    ) -> Result<()> {
+        let mut paths = std::fs::read_dir(current_path)
+            .map_err(|e| Error::validation(format!("Failed to read directory: {e}")))?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<_>>>()
+            .map_err(|e| Error::validation(format!("Failed to read directory entry: {e}")))?;
+        paths.sort();
+
+        for path in paths {
+            let relative_path = path
+                .strip_prefix(base_path)
+                .map_err(|e| Error::validation(format!("Failed to create relative path: {e}")))?;
+
+            let name = relative_path.to_string_lossy().replace('\\', "/");
+
+            if path.is_file() {
+                sources.push(ArchiveSource::file(&path, name)?);
+            } else if path.is_dir() {
+                sources.push(ArchiveSource {
+                    path: path.clone(),
+                    name,
+                    is_dir: true,
+                    size: 0,
+                });
+                Self::collect_archive_sources(base_path, &path, sources)?;
             }
         }
 
@@ -575,6 +1116,93 @@ impl CompressionUtil {
         Ok(())
     }
 
+    /// Create a ZIP archive in memory from `(name, contents)` pairs
+    ///
+    /// Names may contain `/` to place files in folders; entries are written
+    /// in the given order. Nothing touches the filesystem, which makes this
+    /// suitable for building downloads in a request handler.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error` if an entry name is empty or appears twice.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::extra::{CompressionLevel, CompressionUtil};
+    ///
+    /// let entries = vec![
+    ///     ("report.csv".to_string(), b"id,total\n1,42\n".to_vec()),
+    ///     ("logs/app.log".to_string(), b"started".to_vec()),
+    /// ];
+    /// let bytes = CompressionUtil::create_zip_in_memory(&entries, CompressionLevel::Balanced).unwrap();
+    ///
+    /// let files = CompressionUtil::read_zip_from_memory(&bytes).unwrap();
+    /// assert_eq!(files["logs/app.log"], b"started");
+    /// ```
+    #[cfg(feature = "zip")]
+    pub fn create_zip_in_memory(
+        entries: &[(String, Vec<u8>)],
+        level: CompressionLevel,
+    ) -> Result<Vec<u8>> {
+        let options = level.to_zip_options();
+
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, data) in entries {
+            if name.is_empty() {
+                return Err(Error::validation(
+                    "ZIP entry name must not be empty".to_string(),
+                ));
+            }
+            zip.start_file(name.as_str(), options).map_err(|e| {
+                Error::validation(format!("Failed to start ZIP file entry '{name}': {e}"))
+            })?;
+            zip.write_all(data)
+                .map_err(|e| Error::validation(format!("Failed to write to ZIP: {e}")))?;
+        }
+
+        let cursor = zip
+            .finish()
+            .map_err(|e| Error::validation(format!("Failed to finish ZIP file: {e}")))?;
+        Ok(cursor.into_inner())
+    }
+
+    /// Read every file of an in-memory ZIP archive into a map of name to contents
+    ///
+    /// Directory entries are skipped. The archive is read entirely into
+    /// memory, so only use this for archives of trusted or bounded size.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error` if the data is not a valid ZIP archive or contains
+    /// encrypted entries.
+    #[cfg(feature = "zip")]
+    pub fn read_zip_from_memory(data: &[u8]) -> Result<HashMap<String, Vec<u8>>> {
+        let mut archive = ZipArchive::new(Cursor::new(data))
+            .map_err(|e| Error::validation(format!("Failed to read ZIP archive: {e}")))?;
+
+        let mut files = HashMap::with_capacity(archive.len());
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i).map_err(|e| match e {
+                ZipError::UnsupportedArchive(ZipError::PASSWORD_REQUIRED) => Error::validation(
+                    format!("ZIP entry {i} is encrypted and requires a password"),
+                ),
+                e => Error::validation(format!("Failed to read ZIP entry {i}: {e}")),
+            })?;
+            if file.is_dir() {
+                continue;
+            }
+
+            let mut contents = Vec::with_capacity(usize::try_from(file.size()).unwrap_or(0));
+            file.read_to_end(&mut contents).map_err(|e| {
+                Error::validation(format!("Failed to read ZIP entry '{}': {e}", file.name()))
+            })?;
+            files.insert(file.name().to_string(), contents);
+        }
+
+        Ok(files)
+    }
+
     /// Calculate compression ratio for a file
     pub fn calculate_compression_ratio(original_size: u64, compressed_size: u64) -> f64 {
         if original_size > 0 {
@@ -605,6 +1233,243 @@ impl CompressionUtil {
     }
 }
 
+/// Progress of a long-running archive operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgressEvent {
+    /// Bytes of file data processed so far, across all files
+    pub bytes_processed: u64,
+    /// Total bytes of file data in the operation
+    pub total_bytes: u64,
+    /// Path of the file being processed, relative to the archive root
+    pub current_file: String,
+    /// Zero-based index of the file being processed
+    pub file_index: usize,
+    /// Number of files in the operation
+    pub total_files: usize,
+}
+
+impl ProgressEvent {
+    fn new(total_files: usize, total_bytes: u64) -> Self {
+        Self {
+            bytes_processed: 0,
+            total_bytes,
+            current_file: String::new(),
+            file_index: 0,
+            total_files,
+        }
+    }
+
+    /// Move on to the next file; the first call selects index 0
+    fn start_file(&mut self, name: &str) {
+        if !self.current_file.is_empty() {
+            self.file_index += 1;
+        }
+        self.current_file = name.to_string();
+    }
+
+    /// Completed share of the bytes, from 0 to 100
+    #[allow(clippy::cast_precision_loss)]
+    pub fn percentage(&self) -> f64 {
+        if self.total_bytes == 0 {
+            100.0
+        } else {
+            (self.bytes_processed as f64 / self.total_bytes as f64) * 100.0
+        }
+    }
+}
+
+/// A file or directory queued for archiving
+struct ArchiveSource {
+    path: PathBuf,
+    /// Entry name with `/` separators
+    name: String,
+    is_dir: bool,
+    size: u64,
+}
+
+impl ArchiveSource {
+    fn file(path: &Path, name: String) -> Result<Self> {
+        let size = std::fs::metadata(path)
+            .map_err(|e| Error::validation(format!("Failed to read metadata: {e}")))?
+            .len();
+        Ok(Self {
+            path: path.to_path_buf(),
+            name,
+            is_dir: false,
+            size,
+        })
+    }
+}
+
+/// Copy in chunks, calling `on_chunk` with the size of each chunk written
+fn copy_with_progress<R: Read + ?Sized, W: Write + ?Sized>(
+    reader: &mut R,
+    writer: &mut W,
+    on_chunk: &mut dyn FnMut(u64),
+) -> std::io::Result<u64> {
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut total = 0u64;
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => return Ok(total),
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        writer.write_all(&buffer[..read])?;
+        total += read as u64;
+        on_chunk(read as u64);
+    }
+}
+
+/// Resolve an archive entry name under `destination`
+///
+/// Rejects absolute paths and `..` components, which would let a crafted
+/// archive write outside the destination directory.
+fn entry_output_path(destination: &Path, name: &str) -> Result<PathBuf> {
+    let mut path = destination.to_path_buf();
+    for component in Path::new(name).components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return Err(outside_destination(name));
+            }
+        }
+    }
+    Ok(path)
+}
+
+/// Create the destination directory and return its canonical path
+fn extraction_root(destination: &Path) -> Result<PathBuf> {
+    create_dir_all(destination)
+        .map_err(|e| Error::validation(format!("Failed to create directory: {e}")))?;
+    destination
+        .canonicalize()
+        .map_err(|e| Error::validation(format!("Failed to resolve destination directory: {e}")))
+}
+
+/// Create `dir` inside the canonical destination `root` one component at a
+/// time, checking after each step that the directory so far still resolves
+/// inside `root`
+///
+/// `create_dir_all` would follow a symlinked directory already present in
+/// the destination and create the rest of the path wherever it points.
+fn create_dir_within(root: &Path, dir: &Path, name: &str) -> Result<()> {
+    let relative = dir
+        .strip_prefix(root)
+        .map_err(|_| outside_destination(name))?;
+    let mut current = root.to_path_buf();
+    for component in relative.components() {
+        current.push(component);
+        match std::fs::create_dir(&current) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+            Err(e) => {
+                return Err(Error::validation(format!(
+                    "Failed to create directory: {e}"
+                )));
+            }
+        }
+        let resolved = current
+            .canonicalize()
+            .map_err(|e| Error::validation(format!("Failed to resolve output path: {e}")))?;
+        if !resolved.starts_with(root) {
+            return Err(outside_destination(name));
+        }
+    }
+    Ok(())
+}
+
+/// Check that an output path stays inside the canonical destination `root`
+/// once symlinks in its existing parent directories are resolved
+fn verify_within(root: &Path, outpath: &Path, name: &str) -> Result<()> {
+    // Writing through an existing symlink could reach any file
+    if std::fs::symlink_metadata(outpath).is_ok_and(|m| m.file_type().is_symlink()) {
+        return Err(outside_destination(name));
+    }
+
+    let parent = outpath.parent().unwrap_or(outpath);
+    let resolved = parent
+        .canonicalize()
+        .map_err(|e| Error::validation(format!("Failed to resolve output path: {e}")))?;
+    if resolved.starts_with(root) {
+        Ok(())
+    } else {
+        Err(outside_destination(name))
+    }
+}
+
+fn outside_destination(name: &str) -> Error {
+    Error::validation(format!(
+        "Archive entry '{name}' would be extracted outside the destination directory"
+    ))
+}
+
+/// Permission bits to store for a TAR entry
+fn tar_mode(metadata: &std::fs::Metadata, default: u32) -> u32 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = default;
+        metadata.permissions().mode() & 0o7777
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        default
+    }
+}
+
+/// Modification time to store for a TAR entry, in seconds since the epoch
+fn tar_mtime(metadata: &std::fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |duration| duration.as_secs())
+}
+
+/// Header for a TAR entry with the permissions and modification time of
+/// `metadata`; owner fields are left at zero so archives are reproducible
+fn tar_header(
+    entry_type: EntryType,
+    size: u64,
+    metadata: &std::fs::Metadata,
+    default_mode: u32,
+) -> Header {
+    let mut header = Header::new_gnu();
+    header.set_entry_type(entry_type);
+    header.set_size(size);
+    header.set_mode(tar_mode(metadata, default_mode));
+    header.set_mtime(tar_mtime(metadata));
+    header
+}
+
+/// Reader that yields exactly `remaining` bytes, failing if the file
+/// shrinks while it is archived and ignoring anything appended to it
+struct ExactSizeReader<R> {
+    inner: R,
+    remaining: u64,
+}
+
+impl<R: Read> Read for ExactSizeReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.remaining == 0 || buf.is_empty() {
+            return Ok(0);
+        }
+        let read = (&mut self.inner).take(self.remaining).read(buf)?;
+        if read == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "file changed size while being archived",
+            ));
+        }
+        self.remaining -= read as u64;
+        Ok(read)
+    }
+}
+
 /// Information about a ZIP archive
 #[derive(Debug, Clone)]
 pub struct ZipInfo {
@@ -684,6 +1549,84 @@ mod tests {
         assert_eq!(CompressionFormat::from_extension("unknown"), None);
     }
 
+    #[test]
+    fn test_zstd_and_brotli_extensions() {
+        assert_eq!(CompressionFormat::Zstd.extension(), "zst");
+        assert_eq!(CompressionFormat::Brotli.extension(), "br");
+        assert_eq!(
+            CompressionFormat::from_extension("ZST"),
+            Some(CompressionFormat::Zstd)
+        );
+        assert_eq!(
+            CompressionFormat::from_path("app.log.zst"),
+            Some(CompressionFormat::Zstd)
+        );
+        assert_eq!(
+            CompressionFormat::from_path("bundle.js.br"),
+            Some(CompressionFormat::Brotli)
+        );
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_round_trip() {
+        for data in [Vec::new(), b"x".to_vec(), sample_log()] {
+            let compressed = CompressionUtil::compress_zstd(&data, 3).unwrap();
+            assert_eq!(CompressionUtil::decompress_zstd(&compressed).unwrap(), data);
+        }
+
+        assert!(CompressionUtil::compress_zstd(b"data", 0).is_err());
+        assert!(CompressionUtil::compress_zstd(b"data", 23).is_err());
+        assert!(CompressionUtil::decompress_zstd(b"not zstd").is_err());
+    }
+
+    #[cfg(feature = "brotli")]
+    #[test]
+    fn test_brotli_round_trip() {
+        for data in [Vec::new(), b"x".to_vec(), sample_log()] {
+            let compressed = CompressionUtil::compress_brotli(&data, 5).unwrap();
+            assert_eq!(
+                CompressionUtil::decompress_brotli(&compressed).unwrap(),
+                data
+            );
+        }
+
+        assert!(CompressionUtil::compress_brotli(b"data", 12).is_err());
+        assert!(CompressionUtil::decompress_brotli(b"not brotli at all").is_err());
+    }
+
+    #[cfg(all(feature = "zstd", feature = "brotli", feature = "flate2"))]
+    #[test]
+    fn test_zstd_and_brotli_beat_gzip_on_logs() {
+        let data = sample_log();
+        let gzip = CompressionUtil::compress_gzip(&data).unwrap().len();
+        let zstd = CompressionUtil::compress_zstd(&data, 19).unwrap().len();
+        let brotli = CompressionUtil::compress_brotli(&data, 11).unwrap().len();
+
+        assert!(zstd < gzip, "zstd {} vs gzip {}", zstd, gzip);
+        assert!(brotli < gzip, "brotli {} vs gzip {}", brotli, gzip);
+    }
+
+    /// Repetitive log lines with varying fields
+    #[cfg(any(feature = "zstd", feature = "brotli"))]
+    fn sample_log() -> Vec<u8> {
+        (0..5000)
+            .map(|i| {
+                format!(
+                    "2024-05-{:02}T12:{:02}:{:02}Z INFO request_id={} path=/api/v1/items/{} status={} latency_ms={}\n",
+                    i % 28 + 1,
+                    i % 60,
+                    (i * 7) % 60,
+                    i * 7919 % 100_000,
+                    i % 250,
+                    if i % 13 == 0 { 404 } else { 200 },
+                    i * 31 % 900
+                )
+            })
+            .collect::<String>()
+            .into_bytes()
+    }
+
     #[test]
     fn test_compression_format_from_path() {
         assert_eq!(
@@ -722,6 +1665,70 @@ mod tests {
         assert!(display.contains("40.0%"));
     }
 
+    #[cfg(feature = "zip")]
+    #[test]
+    fn test_zip_in_memory_round_trip() {
+        let binary: Vec<u8> = (0..=255).cycle().take(10_000).collect();
+        let entries = vec![
+            ("readme.txt".to_string(), b"hello zip".to_vec()),
+            ("empty.txt".to_string(), Vec::new()),
+            ("nested/dir/data.bin".to_string(), binary.clone()),
+            ("unicode-名前.txt".to_string(), "内容".as_bytes().to_vec()),
+        ];
+
+        for level in [CompressionLevel::None, CompressionLevel::Best] {
+            let bytes = CompressionUtil::create_zip_in_memory(&entries, level).unwrap();
+            let files = CompressionUtil::read_zip_from_memory(&bytes).unwrap();
+
+            assert_eq!(files.len(), entries.len());
+            for (name, data) in &entries {
+                assert_eq!(&files[name], data, "{}", name);
+            }
+        }
+
+        let empty = CompressionUtil::create_zip_in_memory(&[], CompressionLevel::Balanced).unwrap();
+        assert!(
+            CompressionUtil::read_zip_from_memory(&empty)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[cfg(feature = "zip")]
+    #[test]
+    fn test_zip_in_memory_errors() {
+        let duplicate = vec![
+            ("a.txt".to_string(), b"1".to_vec()),
+            ("a.txt".to_string(), b"2".to_vec()),
+        ];
+        assert!(
+            CompressionUtil::create_zip_in_memory(&duplicate, CompressionLevel::Balanced).is_err()
+        );
+
+        let unnamed = vec![(String::new(), b"1".to_vec())];
+        assert!(
+            CompressionUtil::create_zip_in_memory(&unnamed, CompressionLevel::Balanced).is_err()
+        );
+
+        assert!(CompressionUtil::read_zip_from_memory(b"not a zip").is_err());
+
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("secret.txt");
+        std::fs::write(&source, "classified").unwrap();
+        let archive = temp.path().join("secret.zip");
+        CompressionUtil::compress_zip_with_password(
+            &source,
+            &archive,
+            CompressionLevel::Balanced,
+            Some("s3cret"),
+        )
+        .unwrap();
+        let err = CompressionUtil::read_zip_from_memory(&std::fs::read(&archive).unwrap())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("requires a password"), "{}", err);
+    }
+
     #[test]
     fn test_calculate_compression_ratio() {
         assert_eq!(CompressionUtil::calculate_compression_ratio(1000, 600), 0.6);
@@ -751,7 +1758,320 @@ mod tests {
         assert_eq!(decompressed, data);
     }
 
-    // Note: ZIP tests would require creating temporary files and directories
-    // These are more complex integration tests that would be better suited
-    // for a separate test module with proper setup and teardown
+    fn write_sample_tree(root: &Path) {
+        std::fs::create_dir_all(root.join("nested/deeper")).unwrap();
+        std::fs::create_dir_all(root.join("empty")).unwrap();
+        std::fs::write(root.join("top.txt"), b"top level").unwrap();
+        std::fs::write(root.join("nested/data.bin"), vec![42u8; 5000]).unwrap();
+        std::fs::write(root.join("nested/deeper/note.md"), b"# Note").unwrap();
+        let long_name = format!("{}.txt", "long".repeat(40));
+        std::fs::write(root.join("nested").join(long_name), b"long name").unwrap();
+    }
+
+    fn assert_same_tree(expected: &Path, actual: &Path) {
+        let mut entries: Vec<_> = std::fs::read_dir(expected)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        entries.sort();
+        for path in entries {
+            let other = actual.join(path.file_name().unwrap());
+            if path.is_dir() {
+                assert!(other.is_dir(), "missing directory {other:?}");
+                assert_same_tree(&path, &other);
+            } else {
+                assert_eq!(
+                    std::fs::read(&path).unwrap(),
+                    std::fs::read(&other).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_tar_directory_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("source");
+        write_sample_tree(&source);
+
+        let archive = temp.path().join("out.tar");
+        let stats = CompressionUtil::compress_tar(&source, &archive).unwrap();
+        assert_eq!(stats.file_count, 4);
+        assert_eq!(stats.original_size, 9 + 5000 + 6 + 9);
+
+        let output = temp.path().join("output");
+        let stats = CompressionUtil::decompress_tar(&archive, &output).unwrap();
+        assert_eq!(stats.file_count, 4);
+        assert!(output.join("empty").is_dir());
+        assert_same_tree(&source, &output);
+    }
+
+    #[test]
+    fn test_tar_rejects_corrupt_archives() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("a.txt");
+        std::fs::write(&source, b"a").unwrap();
+        let archive = temp.path().join("a.tar");
+        CompressionUtil::compress_tar(&source, &archive).unwrap();
+
+        let mut bytes = std::fs::read(&archive).unwrap();
+        bytes[0] = b'b';
+        std::fs::write(&archive, &bytes).unwrap();
+        let output = temp.path().join("output");
+        assert!(CompressionUtil::decompress_tar(&archive, &output).is_err());
+
+        bytes[0] = b'a';
+        std::fs::write(&archive, &bytes[..300]).unwrap();
+        assert!(CompressionUtil::decompress_tar(&archive, &output).is_err());
+    }
+
+    #[cfg(feature = "flate2")]
+    #[test]
+    fn test_tar_gz_directory_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("source");
+        write_sample_tree(&source);
+
+        let archive = temp.path().join("out.tar.gz");
+        let stats =
+            CompressionUtil::compress_tar_gz(&source, &archive, CompressionLevel::Best).unwrap();
+        assert!(stats.compressed_size < stats.original_size);
+
+        let output = temp.path().join("output");
+        CompressionUtil::decompress_tar_gz(&archive, &output).unwrap();
+        assert_same_tree(&source, &output);
+
+        // A single file is stored under its own name
+        let single = temp.path().join("single.tar.gz");
+        CompressionUtil::compress_tar_gz(
+            source.join("top.txt"),
+            &single,
+            CompressionLevel::Fastest,
+        )
+        .unwrap();
+        let output = temp.path().join("single");
+        CompressionUtil::decompress_tar_gz(&single, &output).unwrap();
+        assert_eq!(std::fs::read(output.join("top.txt")).unwrap(), b"top level");
+    }
+
+    #[test]
+    fn test_tar_rejects_path_traversal() {
+        let temp = tempfile::tempdir().unwrap();
+        for name in [
+            "../escaped.txt",
+            "safe/../../escaped.txt",
+            "/tmp/absolute.txt",
+        ] {
+            let mut builder = Builder::new(Vec::new());
+            let mut header = Header::new_gnu();
+            header.set_size(2);
+            builder
+                .append_data(&mut header, "ok.txt", &b"ok"[..])
+                .unwrap();
+            // The builder refuses unsafe paths, so write the name directly
+            let mut header = Header::new_gnu();
+            header.as_old_mut().name[..name.len()].copy_from_slice(name.as_bytes());
+            header.set_size(4);
+            header.set_cksum();
+            builder.append(&header, &b"evil"[..]).unwrap();
+            let archive = temp.path().join("malicious.tar");
+            std::fs::write(&archive, builder.into_inner().unwrap()).unwrap();
+
+            let output = temp.path().join("output");
+            let result = CompressionUtil::decompress_tar(&archive, &output);
+            assert!(result.is_err(), "entry {name} was accepted");
+            assert!(!temp.path().join("escaped.txt").exists());
+        }
+    }
+
+    #[test]
+    fn test_zip_rejects_path_traversal() {
+        let temp = tempfile::tempdir().unwrap();
+        for name in [
+            "../escaped.txt",
+            "nested/../../escaped.txt",
+            "/tmp/absolute.txt",
+        ] {
+            let archive = temp.path().join("malicious.zip");
+            let mut zip = ZipWriter::new(File::create(&archive).unwrap());
+            zip.start_file("ok.txt", FileOptions::<()>::default())
+                .unwrap();
+            zip.write_all(b"ok").unwrap();
+            zip.start_file(name, FileOptions::<()>::default()).unwrap();
+            zip.write_all(b"evil").unwrap();
+            zip.finish().unwrap();
+
+            let output = temp.path().join("output");
+            let result = CompressionUtil::decompress_zip(&archive, &output);
+            assert!(result.is_err(), "entry {name} was accepted");
+            assert!(!temp.path().join("escaped.txt").exists());
+            // Nothing is extracted from a rejected archive
+            assert!(!output.join("ok.txt").exists());
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_extraction_refuses_symlinked_directories() {
+        let temp = tempfile::tempdir().unwrap();
+        let outside = temp.path().join("outside");
+        std::fs::create_dir_all(&outside).unwrap();
+        let output = temp.path().join("output");
+        std::fs::create_dir_all(&output).unwrap();
+        std::os::unix::fs::symlink(&outside, output.join("link")).unwrap();
+
+        let archive = temp.path().join("link.zip");
+        let mut zip = ZipWriter::new(File::create(&archive).unwrap());
+        zip.start_file("link/escaped.txt", FileOptions::<()>::default())
+            .unwrap();
+        zip.write_all(b"evil").unwrap();
+        zip.finish().unwrap();
+
+        assert!(CompressionUtil::decompress_zip(&archive, &output).is_err());
+        assert!(!outside.join("escaped.txt").exists());
+
+        // No directories are created through the link either
+        let archive = temp.path().join("link.tar");
+        let mut builder = Builder::new(Vec::new());
+        let mut header = Header::new_gnu();
+        header.set_entry_type(EntryType::Directory);
+        header.set_size(0);
+        builder
+            .append_data(&mut header, "link/made/", std::io::empty())
+            .unwrap();
+        let mut header = Header::new_gnu();
+        header.set_size(4);
+        builder
+            .append_data(&mut header, "link/deep/escaped.txt", &b"evil"[..])
+            .unwrap();
+        std::fs::write(&archive, builder.into_inner().unwrap()).unwrap();
+
+        assert!(CompressionUtil::decompress_tar(&archive, &output).is_err());
+        assert!(!outside.join("made").exists());
+        assert!(!outside.join("deep").exists());
+    }
+
+    #[test]
+    fn test_zip_password_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("source");
+        write_sample_tree(&source);
+
+        let archive = temp.path().join("secret.zip");
+        CompressionUtil::compress_zip_with_password(
+            &source,
+            &archive,
+            CompressionLevel::Balanced,
+            Some("s3cret"),
+        )
+        .unwrap();
+
+        // File contents are not stored in the clear
+        let raw = std::fs::read(&archive).unwrap();
+        assert!(!raw.windows(9).any(|w| w == b"top level"));
+
+        let output = temp.path().join("output");
+        CompressionUtil::decompress_zip_with_password(&archive, &output, Some("s3cret")).unwrap();
+        assert_same_tree(&source, &output);
+    }
+
+    #[test]
+    fn test_zip_wrong_or_missing_password() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("data.txt");
+        std::fs::write(&source, b"confidential contents".repeat(50)).unwrap();
+        let archive = temp.path().join("secret.zip");
+        CompressionUtil::compress_zip_with_password(
+            &source,
+            &archive,
+            CompressionLevel::Fastest,
+            Some("right"),
+        )
+        .unwrap();
+
+        let output = temp.path().join("wrong");
+        let result =
+            CompressionUtil::decompress_zip_with_password(&archive, &output, Some("wrong"));
+        assert!(result.is_err());
+        assert!(!output.join("data.txt").exists());
+
+        let output = temp.path().join("missing");
+        let error = CompressionUtil::decompress_zip(&archive, &output).unwrap_err();
+        assert!(error.to_string().contains("requires a password"));
+        assert!(!output.join("data.txt").exists());
+
+        assert!(
+            CompressionUtil::compress_zip_with_password(
+                &source,
+                temp.path().join("empty.zip"),
+                CompressionLevel::Fastest,
+                Some("")
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_zip_progress_events() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("source");
+        write_sample_tree(&source);
+        std::fs::write(source.join("large.bin"), vec![1u8; 200 * 1024]).unwrap();
+
+        let archive = temp.path().join("progress.zip");
+        let mut events = Vec::new();
+        let stats = CompressionUtil::compress_zip_with_progress(
+            &source,
+            &archive,
+            CompressionLevel::Fastest,
+            |event| events.push(event),
+        )
+        .unwrap();
+
+        let last = events.last().unwrap();
+        assert_eq!(last.total_files, 5);
+        assert_eq!(last.file_index, 4);
+        assert_eq!(last.bytes_processed, last.total_bytes);
+        assert_eq!(last.bytes_processed, stats.original_size);
+        assert!((last.percentage() - 100.0).abs() < f64::EPSILON);
+        assert!(
+            events
+                .windows(2)
+                .all(|pair| pair[0].bytes_processed <= pair[1].bytes_processed
+                    && pair[0].file_index <= pair[1].file_index)
+        );
+        // The large file is reported in several chunks
+        assert!(
+            events
+                .iter()
+                .filter(|e| e.current_file == "large.bin")
+                .count()
+                > 3
+        );
+
+        let mut events = Vec::new();
+        let output = temp.path().join("output");
+        CompressionUtil::decompress_zip_with_progress(&archive, &output, |event| {
+            events.push(event);
+        })
+        .unwrap();
+        let last = events.last().unwrap();
+        assert_eq!((last.file_index, last.total_files), (4, 5));
+        assert_eq!(last.bytes_processed, last.total_bytes);
+        assert_same_tree(&source, &output);
+    }
+
+    #[test]
+    fn test_zip_directory_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("source");
+        write_sample_tree(&source);
+
+        let archive = temp.path().join("out.zip");
+        CompressionUtil::compress_zip(&source, &archive, CompressionLevel::Balanced).unwrap();
+
+        let output = temp.path().join("output");
+        CompressionUtil::decompress_zip(&archive, &output).unwrap();
+        assert_same_tree(&source, &output);
+    }
 }
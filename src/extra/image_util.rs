@@ -6,9 +6,14 @@
 use crate::error::{Error, Result};
 use std::path::Path;
 
+#[cfg(feature = "image")]
+use std::collections::HashMap;
+
 #[cfg(feature = "image")]
 use image::{
     ColorType, DynamicImage, GenericImageView, ImageBuffer, ImageFormat as ImgFormat, Rgb, Rgba,
+    codecs::jpeg::JpegEncoder,
+    codecs::png::{CompressionType, FilterType as PngFilterType, PngEncoder},
     imageops::FilterType,
 };
 
@@ -103,6 +108,61 @@ impl ResizeFilter {
     }
 }
 
+/// PNG compression level used by [`ImageUtil::save_png`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PngCompression {
+    /// Fast, minimal compression (the encoder default)
+    #[default]
+    Fast,
+    /// Balanced compression level
+    Default,
+    /// Smallest output, slowest encoding
+    Best,
+}
+
+#[cfg(feature = "image")]
+impl PngCompression {
+    fn to_compression_type(self) -> CompressionType {
+        match self {
+            Self::Fast => CompressionType::Fast,
+            Self::Default => CompressionType::Default,
+            Self::Best => CompressionType::Best,
+        }
+    }
+}
+
+/// PNG scanline filter used by [`ImageUtil::save_png`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PngFilter {
+    /// No filtering, best for low bit depth or few colors
+    NoFilter,
+    /// Difference to the pixel on the left
+    Sub,
+    /// Difference to the pixel above
+    Up,
+    /// Difference to the average of the left and upper pixels
+    Avg,
+    /// Paeth predictor from the left, upper and upper-left pixels
+    Paeth,
+    /// Choose a filter per scanline (the encoder default)
+    #[default]
+    Adaptive,
+}
+
+#[cfg(feature = "image")]
+impl PngFilter {
+    fn to_png_filter_type(self) -> PngFilterType {
+        match self {
+            Self::NoFilter => PngFilterType::NoFilter,
+            Self::Sub => PngFilterType::Sub,
+            Self::Up => PngFilterType::Up,
+            Self::Avg => PngFilterType::Avg,
+            Self::Paeth => PngFilterType::Paeth,
+            Self::Adaptive => PngFilterType::Adaptive,
+        }
+    }
+}
+
 /// Image rotation angle
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RotationAngle {
@@ -114,6 +174,77 @@ pub enum RotationAngle {
     Rotate270,
 }
 
+/// Placement of an image that is smaller than its slot when combining
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alignment {
+    /// Align to the top or left edge
+    #[default]
+    Start,
+    /// Center in the slot
+    Center,
+    /// Align to the bottom or right edge
+    End,
+}
+
+impl Alignment {
+    fn offset(self, slot: u32, size: u32) -> u32 {
+        let free = slot.saturating_sub(size);
+        match self {
+            Self::Start => 0,
+            Self::Center => free / 2,
+            Self::End => free,
+        }
+    }
+}
+
+/// Total length of consecutive sizes separated by `spacing`, if it fits in u32
+fn span(sizes: &[u32], spacing: u32) -> Option<u32> {
+    let gaps = u32::try_from(sizes.len().saturating_sub(1)).ok()?;
+    sizes
+        .iter()
+        .try_fold(0u32, |total, &size| total.checked_add(size))?
+        .checked_add(spacing.checked_mul(gaps)?)
+}
+
+/// Options for [`ImageUtil::combine_grid_with`] and related functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CombineOptions {
+    /// RGBA fill for areas not covered by an image
+    pub background: [u8; 4],
+    /// Placement of smaller images within their slot
+    pub alignment: Alignment,
+    /// Background pixels between neighbouring images
+    pub spacing: u32,
+}
+
+impl Default for CombineOptions {
+    fn default() -> Self {
+        Self {
+            background: [255, 255, 255, 255],
+            alignment: Alignment::Start,
+            spacing: 0,
+        }
+    }
+}
+
+/// Placement of a watermark on the base image
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Corner {
+    /// Top-left corner
+    TopLeft,
+    /// Top-right corner
+    TopRight,
+    /// Bottom-left corner
+    BottomLeft,
+    /// Bottom-right corner
+    #[default]
+    BottomRight,
+    /// Centered on the image
+    Center,
+    /// Repeated across the whole image, starting at the top-left corner
+    Tiled,
+}
+
 /// Image information
 #[derive(Debug, Clone)]
 pub struct ImageInfo {
@@ -190,6 +321,73 @@ impl ImageUtil {
         Ok(bytes)
     }
 
+    /// Encode an image as JPEG with the given quality
+    ///
+    /// `quality` ranges from 1 (smallest) to 100 (best); [`ImageUtil::save_to_bytes`]
+    /// uses the encoder default of 75. JPEG has no alpha channel, so images
+    /// with transparency are flattened to RGB first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `quality` is outside 1 to 100 or encoding fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::extra::ImageUtil;
+    ///
+    /// let image = ImageUtil::create_solid_color(16, 16, 200, 100, 50);
+    /// let jpeg = ImageUtil::save_jpeg(&image, 90).unwrap();
+    /// assert_eq!(&jpeg[..2], &[0xFF, 0xD8]);
+    /// ```
+    #[cfg(feature = "image")]
+    pub fn save_jpeg(image: &DynamicImage, quality: u8) -> Result<Vec<u8>> {
+        if !(1..=100).contains(&quality) {
+            return Err(Error::validation(format!(
+                "JPEG quality must be between 1 and 100, got {quality}"
+            )));
+        }
+
+        let mut bytes = Vec::new();
+        let rgb = match image {
+            DynamicImage::ImageRgb8(_) | DynamicImage::ImageLuma8(_) => image.clone(),
+            _ => DynamicImage::ImageRgb8(image.to_rgb8()),
+        };
+        JpegEncoder::new_with_quality(&mut bytes, quality)
+            .encode_image(&rgb)
+            .map_err(|e| Error::validation(format!("Failed to encode JPEG: {}", e)))?;
+
+        Ok(bytes)
+    }
+
+    /// Encode an image as PNG with explicit compression and filter settings
+    ///
+    /// PNG is lossless, so these settings only trade encoding time for size.
+    /// [`ImageUtil::save_to_bytes`] uses [`PngCompression::Fast`] with
+    /// [`PngFilter::Adaptive`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if encoding fails.
+    #[cfg(feature = "image")]
+    pub fn save_png(
+        image: &DynamicImage,
+        compression: PngCompression,
+        filter: PngFilter,
+    ) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        let encoder = PngEncoder::new_with_quality(
+            &mut bytes,
+            compression.to_compression_type(),
+            filter.to_png_filter_type(),
+        );
+        image
+            .write_with_encoder(encoder)
+            .map_err(|e| Error::validation(format!("Failed to encode PNG: {}", e)))?;
+
+        Ok(bytes)
+    }
+
     /// Get image information
     #[cfg(feature = "image")]
     pub fn get_info<P: AsRef<Path>>(path: P) -> Result<ImageInfo> {
@@ -393,43 +591,200 @@ impl ImageUtil {
     }
 
     /// Combine two images side by side
+    ///
+    /// The canvas is as tall as the taller image; the shorter one is aligned
+    /// to the top on a white background. See
+    /// [`ImageUtil::combine_horizontal_with`] for other fills and alignments.
     #[cfg(feature = "image")]
     pub fn combine_horizontal(left: &DynamicImage, right: &DynamicImage) -> Result<DynamicImage> {
-        let (left_width, left_height) = left.dimensions();
-        let (right_width, right_height) = right.dimensions();
-
-        let max_height = left_height.max(right_height);
-        let total_width = left_width + right_width;
-
-        let mut combined = Self::create_solid_color(total_width, max_height, 255, 255, 255);
-
-        // Overlay left image
-        image::imageops::overlay(&mut combined, left, 0i64, 0i64);
-
-        // Overlay right image
-        image::imageops::overlay(&mut combined, right, left_width as i64, 0i64);
+        Self::combine_horizontal_with(left, right, &CombineOptions::default())
+    }
 
-        Ok(combined)
+    /// Combine two images side by side with a custom background and alignment
+    ///
+    /// `options.spacing` pixels of background separate the images.
+    #[cfg(feature = "image")]
+    pub fn combine_horizontal_with(
+        left: &DynamicImage,
+        right: &DynamicImage,
+        options: &CombineOptions,
+    ) -> Result<DynamicImage> {
+        Self::combine_linear(&[left, right], true, options)
     }
 
     /// Combine two images vertically
+    ///
+    /// The canvas is as wide as the wider image; the narrower one is aligned
+    /// to the left on a white background. See
+    /// [`ImageUtil::combine_vertical_with`] for other fills and alignments.
     #[cfg(feature = "image")]
     pub fn combine_vertical(top: &DynamicImage, bottom: &DynamicImage) -> Result<DynamicImage> {
-        let (top_width, top_height) = top.dimensions();
-        let (bottom_width, bottom_height) = bottom.dimensions();
+        Self::combine_vertical_with(top, bottom, &CombineOptions::default())
+    }
 
-        let max_width = top_width.max(bottom_width);
-        let total_height = top_height + bottom_height;
+    /// Combine two images vertically with a custom background and alignment
+    #[cfg(feature = "image")]
+    pub fn combine_vertical_with(
+        top: &DynamicImage,
+        bottom: &DynamicImage,
+        options: &CombineOptions,
+    ) -> Result<DynamicImage> {
+        Self::combine_linear(&[top, bottom], false, options)
+    }
+
+    /// Arrange images in a grid with `cols` columns, e.g. for contact sheets
+    ///
+    /// Every cell is as large as the largest image, and images fill the grid
+    /// row by row on a white background.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::extra::ImageUtil;
+    ///
+    /// let images: Vec<_> = (0..5)
+    ///     .map(|_| ImageUtil::create_solid_color(40, 30, 200, 0, 0))
+    ///     .collect();
+    /// let sheet = ImageUtil::combine_grid(&images, 3).unwrap();
+    /// assert_eq!((sheet.width(), sheet.height()), (120, 60));
+    /// ```
+    #[cfg(feature = "image")]
+    pub fn combine_grid(images: &[DynamicImage], cols: usize) -> Result<DynamicImage> {
+        Self::combine_grid_with(images, cols, &CombineOptions::default())
+    }
+
+    /// Arrange images in a grid with a custom background, alignment within
+    /// each cell and spacing between cells
+    ///
+    /// The result has an RGB color type when the background is opaque and
+    /// RGBA otherwise.
+    #[cfg(feature = "image")]
+    pub fn combine_grid_with(
+        images: &[DynamicImage],
+        cols: usize,
+        options: &CombineOptions,
+    ) -> Result<DynamicImage> {
+        if images.is_empty() {
+            return Err(Error::validation(
+                "At least one image is required to combine".to_string(),
+            ));
+        }
+        if cols == 0 {
+            return Err(Error::validation(
+                "Grid must have at least one column".to_string(),
+            ));
+        }
 
-        let mut combined = Self::create_solid_color(max_width, total_height, 255, 255, 255);
+        let cols = cols.min(images.len());
+        let rows = images.len().div_ceil(cols);
+        let cell_width = images
+            .iter()
+            .map(GenericImageView::width)
+            .max()
+            .unwrap_or(0);
+        let cell_height = images
+            .iter()
+            .map(GenericImageView::height)
+            .max()
+            .unwrap_or(0);
+
+        let (Some(width), Some(height)) = (
+            span(&vec![cell_width; cols], options.spacing),
+            span(&vec![cell_height; rows], options.spacing),
+        ) else {
+            return Err(Error::validation(
+                "Combined image dimensions are too large".to_string(),
+            ));
+        };
+
+        let placements = images.iter().enumerate().map(|(index, image)| {
+            // Both indices are below `images.len()`, and the canvas size fits in u32
+            #[allow(clippy::cast_possible_truncation)]
+            let (col, row) = ((index % cols) as u32, (index / cols) as u32);
+            let x = col * (cell_width + options.spacing)
+                + options.alignment.offset(cell_width, image.width());
+            let y = row * (cell_height + options.spacing)
+                + options.alignment.offset(cell_height, image.height());
+            (image, x, y)
+        });
+        Ok(Self::compose(width, height, placements, options))
+    }
 
-        // Overlay top image
-        image::imageops::overlay(&mut combined, top, 0i64, 0i64);
+    /// Place images one after another along one axis
+    #[cfg(feature = "image")]
+    fn combine_linear(
+        images: &[&DynamicImage],
+        horizontal: bool,
+        options: &CombineOptions,
+    ) -> Result<DynamicImage> {
+        let along: Vec<u32> = images
+            .iter()
+            .map(|image| {
+                if horizontal {
+                    image.width()
+                } else {
+                    image.height()
+                }
+            })
+            .collect();
+        let across = images
+            .iter()
+            .map(|image| {
+                if horizontal {
+                    image.height()
+                } else {
+                    image.width()
+                }
+            })
+            .max()
+            .unwrap_or(0);
+        let length = span(&along, options.spacing).ok_or_else(|| {
+            Error::validation("Combined image dimensions are too large".to_string())
+        })?;
+
+        let mut position = 0;
+        let placements = images.iter().zip(&along).map(|(image, &size)| {
+            let start = position;
+            position += size + options.spacing;
+            let cross_size = if horizontal {
+                image.height()
+            } else {
+                image.width()
+            };
+            let offset = options.alignment.offset(across, cross_size);
+            if horizontal {
+                (*image, start, offset)
+            } else {
+                (*image, offset, start)
+            }
+        });
+
+        let (width, height) = if horizontal {
+            (length, across)
+        } else {
+            (across, length)
+        };
+        Ok(Self::compose(width, height, placements, options))
+    }
 
-        // Overlay bottom image
-        image::imageops::overlay(&mut combined, bottom, 0i64, top_height as i64);
+    /// Draw images at the given positions onto a background-filled canvas
+    #[cfg(feature = "image")]
+    fn compose<'a>(
+        width: u32,
+        height: u32,
+        placements: impl Iterator<Item = (&'a DynamicImage, u32, u32)>,
+        options: &CombineOptions,
+    ) -> DynamicImage {
+        let mut canvas = ImageBuffer::from_pixel(width, height, Rgba(options.background));
+        for (image, x, y) in placements {
+            image::imageops::overlay(&mut canvas, &image.to_rgba8(), i64::from(x), i64::from(y));
+        }
 
-        Ok(combined)
+        if options.background[3] == u8::MAX {
+            DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(canvas).to_rgb8())
+        } else {
+            DynamicImage::ImageRgba8(canvas)
+        }
     }
 
     /// Add watermark to image
@@ -455,6 +810,195 @@ impl ImageUtil {
         Ok(result)
     }
 
+    /// Blend a watermark onto an image at a corner, the center or tiled
+    ///
+    /// `opacity` (0.0 to 1.0) scales the watermark's own alpha channel. Corner
+    /// placements keep a margin of 2% of the shorter image side, and parts of
+    /// the watermark falling outside the image are clipped, so the result
+    /// always has the dimensions of `image`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `opacity` is outside 0.0 to 1.0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::extra::{Corner, ImageUtil};
+    /// use image::GenericImageView;
+    ///
+    /// let photo = ImageUtil::create_solid_color(200, 100, 255, 255, 255);
+    /// let logo = ImageUtil::create_solid_color(20, 10, 255, 0, 0);
+    /// let marked = ImageUtil::watermark(&photo, &logo, Corner::BottomRight, 0.5).unwrap();
+    /// assert_eq!(marked.dimensions(), (200, 100));
+    /// ```
+    #[cfg(feature = "image")]
+    pub fn watermark(
+        image: &DynamicImage,
+        overlay: &DynamicImage,
+        position: Corner,
+        opacity: f32,
+    ) -> Result<DynamicImage> {
+        if !(0.0..=1.0).contains(&opacity) {
+            return Err(Error::validation(format!(
+                "Watermark opacity must be between 0.0 and 1.0, got {opacity}"
+            )));
+        }
+
+        let mut mark = overlay.to_rgba8();
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        for pixel in mark.pixels_mut() {
+            pixel[3] = (f32::from(pixel[3]) * opacity).round() as u8;
+        }
+
+        let mut canvas = image.to_rgba8();
+        let (width, height) = (i64::from(image.width()), i64::from(image.height()));
+        let (mark_width, mark_height) = (i64::from(mark.width()), i64::from(mark.height()));
+        if mark_width == 0 || mark_height == 0 {
+            return Ok(image.clone());
+        }
+        let margin = width.min(height) / 50;
+        let right = width - mark_width - margin;
+        let bottom = height - mark_height - margin;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let positions: Vec<(i64, i64)> = match position {
+            Corner::TopLeft => vec![(margin, margin)],
+            Corner::TopRight => vec![(right, margin)],
+            Corner::BottomLeft => vec![(margin, bottom)],
+            Corner::BottomRight => vec![(right, bottom)],
+            Corner::Center => vec![((width - mark_width) / 2, (height - mark_height) / 2)],
+            Corner::Tiled => (0..height)
+                .step_by(mark_height as usize)
+                .flat_map(|y| (0..width).step_by(mark_width as usize).map(move |x| (x, y)))
+                .collect(),
+        };
+        for (x, y) in positions {
+            image::imageops::overlay(&mut canvas, &mark, x, y);
+        }
+
+        Ok(Self::restore_color(image, canvas))
+    }
+
+    /// Draw a line or lines of text with a TrueType or OpenType font
+    ///
+    /// `(x, y)` is the top-left corner of the first line, `size` is the em
+    /// size in pixels and `color` is RGBA. Lines are separated by `\n` and
+    /// characters missing from the font are drawn as the font's missing glyph.
+    /// Fonts are parsed and rasterized by `ab_glyph`; pair kerning is applied,
+    /// complex script shaping is not.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `font_bytes` is not a valid font or `size` is not
+    /// a positive number.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use yimi_rutool::extra::ImageUtil;
+    ///
+    /// let font = std::fs::read("DejaVuSans.ttf").unwrap();
+    /// let image = ImageUtil::create_solid_color(200, 50, 255, 255, 255);
+    /// let labelled =
+    ///     ImageUtil::draw_text(&image, "Hello", 10, 10, &font, 24.0, [0, 0, 0, 255]).unwrap();
+    /// ```
+    #[cfg(feature = "image")]
+    pub fn draw_text(
+        image: &DynamicImage,
+        text: &str,
+        x: i32,
+        y: i32,
+        font_bytes: &[u8],
+        size: f32,
+        color: [u8; 4],
+    ) -> Result<DynamicImage> {
+        use ab_glyph::{Font, FontRef, PxScale, ScaleFont, point};
+
+        if !(size.is_finite() && size > 0.0) {
+            return Err(Error::validation(format!(
+                "Font size must be a positive number, got {size}"
+            )));
+        }
+        let font = FontRef::try_from_slice(font_bytes)
+            .map_err(|e| Error::validation(format!("Invalid font: {e}")))?;
+        // ab_glyph scales by the ascent-to-descent height, not the em size
+        let units_per_em = font
+            .units_per_em()
+            .ok_or_else(|| Error::validation("Invalid font: bad units per em"))?;
+        let scale = PxScale::from(size * font.height_unscaled() / units_per_em);
+        let scaled = font.as_scaled(scale);
+        let line_height = scaled.height() + scaled.line_gap();
+
+        let mut canvas = image.to_rgba8();
+        #[allow(clippy::cast_precision_loss)]
+        for (line_index, line) in text.lines().enumerate() {
+            let baseline = y as f32 + scaled.ascent() + line_index as f32 * line_height;
+            let mut pen = x as f32;
+            let mut previous = None;
+            for ch in line.chars() {
+                let glyph_id = scaled.glyph_id(ch);
+                if let Some(previous) = previous {
+                    pen += scaled.kern(previous, glyph_id);
+                }
+                let glyph = glyph_id.with_scale_and_position(scale, point(pen, baseline));
+                if let Some(outlined) = font.outline_glyph(glyph) {
+                    Self::fill_glyph(&mut canvas, &outlined, color);
+                }
+                pen += scaled.h_advance(glyph_id);
+                previous = Some(glyph_id);
+            }
+        }
+
+        Ok(Self::restore_color(image, canvas))
+    }
+
+    /// Blend a rasterized glyph into the canvas, clipping at the edges
+    #[cfg(feature = "image")]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_possible_wrap
+    )]
+    fn fill_glyph(canvas: &mut image::RgbaImage, glyph: &ab_glyph::OutlinedGlyph, color: [u8; 4]) {
+        let bounds = glyph.px_bounds();
+        let (left, top) = (bounds.min.x as i64, bounds.min.y as i64);
+        let (width, height) = (i64::from(canvas.width()), i64::from(canvas.height()));
+        let alpha = f32::from(color[3]) / 255.0;
+
+        glyph.draw(|gx, gy, coverage| {
+            let (px, py) = (left + i64::from(gx), top + i64::from(gy));
+            if coverage <= 0.0 || px < 0 || py < 0 || px >= width || py >= height {
+                return;
+            }
+            let pixel = canvas.get_pixel_mut(px as u32, py as u32);
+            let source = coverage.min(1.0) * alpha;
+            let below = f32::from(pixel[3]) / 255.0 * (1.0 - source);
+            let total = source + below;
+            if total <= 0.0 {
+                return;
+            }
+            for channel in 0..3 {
+                let blended = (f32::from(color[channel]) * source
+                    + f32::from(pixel[channel]) * below)
+                    / total;
+                pixel[channel] = blended.round() as u8;
+            }
+            pixel[3] = (total * 255.0).round() as u8;
+        });
+    }
+
+    /// Convert a drawing canvas back to RGB if the original had no alpha
+    #[cfg(feature = "image")]
+    fn restore_color(original: &DynamicImage, canvas: image::RgbaImage) -> DynamicImage {
+        let canvas = DynamicImage::ImageRgba8(canvas);
+        if original.color().has_alpha() {
+            canvas
+        } else {
+            DynamicImage::ImageRgb8(canvas.to_rgb8())
+        }
+    }
+
     /// Get image histogram
     #[cfg(feature = "image")]
     pub fn histogram(image: &DynamicImage) -> ImageHistogram {
@@ -493,6 +1037,300 @@ impl ImageUtil {
             is_mostly_bright: average_brightness > 170,
         }
     }
+
+    /// Get the average color of an image
+    ///
+    /// Pixels are weighted by their alpha, so fully transparent pixels are
+    /// ignored. A fully transparent image averages to black.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use image::Rgb;
+    /// use yimi_rutool::extra::ImageUtil;
+    ///
+    /// let image = ImageUtil::create_solid_color(10, 10, 30, 60, 90);
+    /// assert_eq!(ImageUtil::average_color(&image), Rgb([30, 60, 90]));
+    /// ```
+    #[cfg(feature = "image")]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn average_color(image: &DynamicImage) -> Rgb<u8> {
+        let mut sums = [0u64; 3];
+        let mut total_alpha = 0u64;
+        for pixel in image.to_rgba8().pixels() {
+            let alpha = u64::from(pixel[3]);
+            for (sum, &channel) in sums.iter_mut().zip(&pixel.0[..3]) {
+                *sum += u64::from(channel) * alpha;
+            }
+            total_alpha += alpha;
+        }
+
+        if total_alpha == 0 {
+            return Rgb([0, 0, 0]);
+        }
+        Rgb(sums.map(|sum| ((sum + total_alpha / 2) / total_alpha) as u8))
+    }
+
+    /// Extract the `k` most dominant colors of an image
+    ///
+    /// The image is first downsampled to at most 64×64 pixels, so the cost
+    /// does not depend on the image size. The sampled pixels are then grouped
+    /// with k-means clustering, and the cluster centers are returned ordered
+    /// from the largest cluster to the smallest. Fewer than `k` colors are
+    /// returned when the image has fewer distinct colors. Pixels that are
+    /// mostly transparent are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use image::Rgb;
+    /// use yimi_rutool::extra::ImageUtil;
+    ///
+    /// let image = ImageUtil::create_solid_color(500, 300, 200, 40, 40);
+    /// assert_eq!(ImageUtil::dominant_colors(&image, 3), vec![Rgb([200, 40, 40])]);
+    /// ```
+    #[cfg(feature = "image")]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn dominant_colors(image: &DynamicImage, k: usize) -> Vec<Rgb<u8>> {
+        const SAMPLE_SIZE: u32 = 64;
+        const MAX_ITERATIONS: usize = 20;
+
+        if k == 0 || image.width() == 0 || image.height() == 0 {
+            return Vec::new();
+        }
+
+        // Nearest-neighbour sampling keeps the original colors instead of
+        // blending neighbours into new ones
+        let sample = if image.width() > SAMPLE_SIZE || image.height() > SAMPLE_SIZE {
+            image.resize(SAMPLE_SIZE, SAMPLE_SIZE, FilterType::Nearest)
+        } else {
+            image.clone()
+        };
+
+        // Cluster distinct colors weighted by how often they occur
+        let mut counts: HashMap<[u8; 3], u32> = HashMap::new();
+        for pixel in sample.to_rgba8().pixels() {
+            if pixel[3] >= 128 {
+                *counts.entry([pixel[0], pixel[1], pixel[2]]).or_default() += 1;
+            }
+        }
+        // HashMap order is random; sort so the result is deterministic
+        let mut counts: Vec<([u8; 3], u32)> = counts.into_iter().collect();
+        counts.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        let colors: Vec<([f32; 3], f32)> = counts
+            .into_iter()
+            .map(|(rgb, count)| (rgb.map(f32::from), count as f32))
+            .collect();
+        if colors.is_empty() {
+            return Vec::new();
+        }
+
+        let centers = Self::kmeans_init(&colors, k);
+        let (centers, sizes) = Self::kmeans(&colors, centers, MAX_ITERATIONS);
+
+        let mut clusters: Vec<(Rgb<u8>, f32)> = centers
+            .into_iter()
+            .zip(sizes)
+            .filter(|&(_, size)| size > 0.0)
+            .map(|(center, size)| (Rgb(center.map(|c| c.round().clamp(0.0, 255.0) as u8)), size))
+            .collect();
+        clusters.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let mut palette: Vec<Rgb<u8>> = Vec::with_capacity(clusters.len());
+        for (color, _) in clusters {
+            if !palette.contains(&color) {
+                palette.push(color);
+            }
+        }
+        palette
+    }
+
+    /// Pick initial cluster centers: the most common color, then repeatedly
+    /// the color farthest from all centers chosen so far
+    #[cfg(feature = "image")]
+    fn kmeans_init(colors: &[([f32; 3], f32)], k: usize) -> Vec<[f32; 3]> {
+        let mut centers = vec![colors[0].0];
+        let mut nearest: Vec<f32> = colors
+            .iter()
+            .map(|(color, _)| Self::color_distance(color, &centers[0]))
+            .collect();
+
+        while centers.len() < k {
+            let Some((index, &distance)) =
+                nearest.iter().enumerate().max_by(|a, b| a.1.total_cmp(b.1))
+            else {
+                break;
+            };
+            if distance == 0.0 {
+                break; // Fewer distinct colors than k
+            }
+
+            let center = colors[index].0;
+            for (dist, (color, _)) in nearest.iter_mut().zip(colors) {
+                *dist = dist.min(Self::color_distance(color, &center));
+            }
+            centers.push(center);
+        }
+        centers
+    }
+
+    /// Run weighted k-means, returning the centers and the weight of each cluster
+    #[cfg(feature = "image")]
+    fn kmeans(
+        colors: &[([f32; 3], f32)],
+        mut centers: Vec<[f32; 3]>,
+        max_iterations: usize,
+    ) -> (Vec<[f32; 3]>, Vec<f32>) {
+        let mut sizes = vec![0.0; centers.len()];
+        for _ in 0..max_iterations {
+            let mut sums = vec![[0.0f32; 3]; centers.len()];
+            sizes.fill(0.0);
+
+            for (color, weight) in colors {
+                let cluster = centers
+                    .iter()
+                    .map(|center| Self::color_distance(color, center))
+                    .enumerate()
+                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                    .map_or(0, |(index, _)| index);
+                for (sum, channel) in sums[cluster].iter_mut().zip(color) {
+                    *sum += channel * weight;
+                }
+                sizes[cluster] += weight;
+            }
+
+            let mut moved = false;
+            for ((center, sum), &size) in centers.iter_mut().zip(&sums).zip(&sizes) {
+                if size > 0.0 {
+                    let updated = sum.map(|s| s / size);
+                    moved |= Self::color_distance(center, &updated) > 0.25;
+                    *center = updated;
+                }
+            }
+            if !moved {
+                break;
+            }
+        }
+        (centers, sizes)
+    }
+
+    /// Compute the average hash (aHash) of an image
+    ///
+    /// The image is shrunk to 8×8 grayscale and each bit records whether a
+    /// pixel is brighter than the mean. Compare hashes with
+    /// [`hamming_distance`](Self::hamming_distance); see
+    /// [`perceptual_hash`](Self::perceptual_hash) for what the hash is stable
+    /// against. aHash is cheaper than pHash but more sensitive to brightness
+    /// and contrast changes.
+    #[cfg(feature = "image")]
+    pub fn average_hash(image: &DynamicImage) -> u64 {
+        let small = image.resize_exact(8, 8, FilterType::Triangle).to_luma8();
+        let pixels: Vec<u32> = small.pixels().map(|p| u32::from(p[0])).collect();
+        let mean = pixels.iter().sum::<u32>() / 64;
+
+        pixels
+            .iter()
+            .enumerate()
+            .filter(|&(_, &value)| value > mean)
+            .fold(0u64, |hash, (bit, _)| hash | (1 << bit))
+    }
+
+    /// Compute the perceptual hash (pHash) of an image
+    ///
+    /// The image is shrunk to 32×32 grayscale and transformed with a 2D DCT;
+    /// each bit of the hash records whether one of the 8×8 lowest-frequency
+    /// coefficients is above their median. Near-duplicate images have hashes
+    /// a small [`hamming_distance`](Self::hamming_distance) apart: around 10
+    /// or less out of 64 usually means the same picture.
+    ///
+    /// The hash is stable against resizing (including aspect ratio changes),
+    /// re-encoding, mild blur or noise and uniform brightness or contrast
+    /// changes. It is not stable against rotation, flipping or significant
+    /// cropping, which move the image content and produce unrelated hashes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use image::{DynamicImage, ImageBuffer, Rgb};
+    /// use yimi_rutool::extra::{ImageUtil, ResizeFilter};
+    ///
+    /// let image = DynamicImage::ImageRgb8(ImageBuffer::from_fn(200, 200, |x, y| {
+    ///     Rgb([(x + y) as u8, x as u8, y as u8])
+    /// }));
+    /// let thumbnail = ImageUtil::resize(&image, 64, 64, ResizeFilter::Triangle);
+    ///
+    /// let distance = ImageUtil::hamming_distance(
+    ///     ImageUtil::perceptual_hash(&image),
+    ///     ImageUtil::perceptual_hash(&thumbnail),
+    /// );
+    /// assert!(distance <= 10);
+    /// ```
+    #[cfg(feature = "image")]
+    pub fn perceptual_hash(image: &DynamicImage) -> u64 {
+        const SIZE: usize = 32;
+        const LOW: usize = 8;
+
+        let small = image
+            .resize_exact(SIZE as u32, SIZE as u32, FilterType::Triangle)
+            .to_luma8();
+        let pixels: Vec<f64> = small.pixels().map(|p| f64::from(p[0])).collect();
+
+        // DCT-II basis: cos((2x + 1) * u * pi / 2N) for the lowest frequencies
+        let basis: Vec<[f64; SIZE]> = (0..LOW)
+            .map(|u| {
+                let mut row = [0.0; SIZE];
+                for (x, value) in row.iter_mut().enumerate() {
+                    *value =
+                        (((2 * x + 1) * u) as f64 * std::f64::consts::PI / (2 * SIZE) as f64).cos();
+                }
+                row
+            })
+            .collect();
+
+        // Transform rows, then columns, keeping only the low frequencies
+        let rows: Vec<[f64; LOW]> = pixels
+            .chunks_exact(SIZE)
+            .map(|row| {
+                let mut out = [0.0; LOW];
+                for (u, value) in out.iter_mut().enumerate() {
+                    *value = row.iter().zip(&basis[u]).map(|(p, c)| p * c).sum();
+                }
+                out
+            })
+            .collect();
+        let mut coefficients = [0.0f64; LOW * LOW];
+        for v in 0..LOW {
+            for u in 0..LOW {
+                coefficients[v * LOW + u] =
+                    rows.iter().zip(&basis[v]).map(|(row, c)| row[u] * c).sum();
+            }
+        }
+
+        // The DC term only reflects overall brightness, so leave it out of
+        // the median
+        let mut sorted = coefficients[1..].to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let median = sorted[sorted.len() / 2];
+
+        coefficients
+            .iter()
+            .enumerate()
+            .filter(|&(_, &value)| value > median)
+            .fold(0u64, |hash, (bit, _)| hash | (1 << bit))
+    }
+
+    /// Count the bits that differ between two image hashes
+    ///
+    /// 0 means identical hashes and 64 completely different ones.
+    pub fn hamming_distance(a: u64, b: u64) -> u32 {
+        (a ^ b).count_ones()
+    }
+
+    /// Squared euclidean distance between two colors in RGB space
+    #[cfg(feature = "image")]
+    fn color_distance(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+        a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+    }
 }
 
 /// Image histogram data
@@ -655,6 +1493,69 @@ mod tests {
         assert_eq!(vertical.dimensions(), (50, 200));
     }
 
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_combine_mismatched_sizes() {
+        let small = ImageUtil::create_solid_color(30, 20, 255, 0, 0);
+        let large = ImageUtil::create_solid_color(60, 80, 0, 0, 255);
+
+        let horizontal = ImageUtil::combine_horizontal(&small, &large).unwrap();
+        assert_eq!(horizontal.dimensions(), (90, 80));
+        // Default fill is white below the shorter, top-aligned image
+        assert_eq!(horizontal.get_pixel(10, 50), Rgba([255, 255, 255, 255]));
+        assert_eq!(horizontal.get_pixel(10, 10), Rgba([255, 0, 0, 255]));
+
+        let vertical = ImageUtil::combine_vertical(&small, &large).unwrap();
+        assert_eq!(vertical.dimensions(), (60, 100));
+
+        let options = CombineOptions {
+            background: [0, 0, 0, 0],
+            alignment: Alignment::Center,
+            spacing: 4,
+        };
+        let centered = ImageUtil::combine_horizontal_with(&small, &large, &options).unwrap();
+        assert_eq!(centered.dimensions(), (94, 80));
+        assert_eq!(centered.color(), ColorType::Rgba8);
+        // The small image is vertically centered, with spacing before the large one
+        assert_eq!(centered.get_pixel(10, 10), Rgba([0, 0, 0, 0]));
+        assert_eq!(centered.get_pixel(10, 40), Rgba([255, 0, 0, 255]));
+        assert_eq!(centered.get_pixel(32, 40), Rgba([0, 0, 0, 0]));
+        assert_eq!(centered.get_pixel(34, 40), Rgba([0, 0, 255, 255]));
+
+        let bottom = CombineOptions {
+            alignment: Alignment::End,
+            ..CombineOptions::default()
+        };
+        let vertical = ImageUtil::combine_vertical_with(&small, &large, &bottom).unwrap();
+        assert_eq!(vertical.get_pixel(59, 5), Rgba([255, 0, 0, 255]));
+        assert_eq!(vertical.get_pixel(0, 5), Rgba([255, 255, 255, 255]));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_combine_grid() {
+        let images: Vec<_> = (0u8..7)
+            .map(|i| ImageUtil::create_solid_color(10 + u32::from(i) * 5, 20, i * 30, 0, 0))
+            .collect();
+
+        let grid = ImageUtil::combine_grid(&images, 3).unwrap();
+        assert_eq!(grid.dimensions(), (3 * 40, 3 * 20));
+
+        let spaced = CombineOptions {
+            spacing: 2,
+            ..CombineOptions::default()
+        };
+        let grid = ImageUtil::combine_grid_with(&images, 3, &spaced).unwrap();
+        assert_eq!(grid.dimensions(), (3 * 40 + 4, 3 * 20 + 4));
+
+        // More columns than images collapses to a single row
+        let row = ImageUtil::combine_grid(&images[..2], 5).unwrap();
+        assert_eq!(row.dimensions(), (2 * 15, 20));
+
+        assert!(ImageUtil::combine_grid(&images, 0).is_err());
+        assert!(ImageUtil::combine_grid(&[], 2).is_err());
+    }
+
     #[cfg(feature = "image")]
     #[test]
     fn test_watermark() {
@@ -671,6 +1572,214 @@ mod tests {
         assert!(invalid.is_err());
     }
 
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_watermark_positions() {
+        let base = ImageUtil::create_solid_color(200, 100, 255, 255, 255);
+        let mark = ImageUtil::create_solid_color(20, 10, 255, 0, 0);
+
+        for position in [
+            Corner::TopLeft,
+            Corner::TopRight,
+            Corner::BottomLeft,
+            Corner::BottomRight,
+            Corner::Center,
+            Corner::Tiled,
+        ] {
+            let marked = ImageUtil::watermark(&base, &mark, position, 0.5).unwrap();
+            assert_eq!(marked.dimensions(), base.dimensions());
+            assert_eq!(marked.color(), base.color());
+        }
+
+        // Margin is 2% of the shorter side
+        let marked = ImageUtil::watermark(&base, &mark, Corner::TopLeft, 0.5).unwrap();
+        let blended = marked.get_pixel(2, 2);
+        assert_eq!(blended[0], 255);
+        assert!((126..=129).contains(&blended[1]));
+        assert_eq!(marked.get_pixel(1, 1), Rgba([255, 255, 255, 255]));
+
+        let marked = ImageUtil::watermark(&base, &mark, Corner::BottomRight, 1.0).unwrap();
+        assert_eq!(marked.get_pixel(197, 97), Rgba([255, 0, 0, 255]));
+        assert_eq!(marked.get_pixel(199, 99), Rgba([255, 255, 255, 255]));
+
+        let tiled = ImageUtil::watermark(&base, &mark, Corner::Tiled, 1.0).unwrap();
+        assert_eq!(tiled.get_pixel(0, 0), Rgba([255, 0, 0, 255]));
+        assert_eq!(tiled.get_pixel(199, 99), Rgba([255, 0, 0, 255]));
+
+        // Oversized watermarks are clipped rather than growing the image
+        let big = ImageUtil::create_solid_color(500, 500, 0, 0, 255);
+        let clipped = ImageUtil::watermark(&base, &big, Corner::Center, 1.0).unwrap();
+        assert_eq!(clipped.dimensions(), (200, 100));
+
+        let transparent = ImageUtil::watermark(&base, &mark, Corner::Center, 0.0).unwrap();
+        assert_eq!(transparent.to_rgb8(), base.to_rgb8());
+
+        assert!(ImageUtil::watermark(&base, &mark, Corner::TopLeft, 1.5).is_err());
+        assert!(ImageUtil::watermark(&base, &mark, Corner::TopLeft, f32::NAN).is_err());
+    }
+
+    /// Build a minimal TrueType font with `.notdef`, a space and a square
+    /// glyph mapped to 'A'; units per em is 1000
+    #[cfg(feature = "image")]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn test_font() -> Vec<u8> {
+        fn u16s(values: &[u16]) -> Vec<u8> {
+            values.iter().flat_map(|v| v.to_be_bytes()).collect()
+        }
+
+        // Glyph 2: a 100..700 square with a quadratic bulge on the right
+        let mut glyf = u16s(&[1, 100, 0, 800, 700, 4]);
+        glyf.extend(u16s(&[0])); // no instructions
+        glyf.extend([0x01, 0x01, 0x00, 0x01, 0x01]); // on, on, off, on, on
+        // x: 100, 700, 800, 700, 100 as int16 deltas
+        glyf.extend(u16s(&[100, 600, 100, (-100i16) as u16, (-600i16) as u16]));
+        // y: 0, 0, 350, 700, 700
+        glyf.extend(u16s(&[0, 0, 350, 350, 0]));
+        glyf.resize(glyf.len().div_ceil(2) * 2, 0);
+        let loca = u16s(&[0, 0, 0, (glyf.len() / 2) as u16]);
+
+        let mut head = vec![0u8; 54];
+        head[0..4].copy_from_slice(&0x0001_0000u32.to_be_bytes());
+        head[12..16].copy_from_slice(&0x5F0F_3CF5u32.to_be_bytes());
+        head[18..20].copy_from_slice(&1000u16.to_be_bytes());
+        let mut hhea = vec![0u8; 36];
+        hhea[0..4].copy_from_slice(&0x0001_0000u32.to_be_bytes());
+        hhea[4..6].copy_from_slice(&800u16.to_be_bytes());
+        hhea[6..8].copy_from_slice(&(-200i16).to_be_bytes());
+        hhea[34..36].copy_from_slice(&3u16.to_be_bytes());
+        let mut maxp = 0x0000_5000u32.to_be_bytes().to_vec();
+        maxp.extend(u16s(&[3]));
+        let hmtx = u16s(&[500, 0, 250, 0, 900, 100]);
+
+        // Format 4 with segments ' ' -> 1, 'A' -> 2 and the final 0xFFFF
+        let mut cmap = u16s(&[0, 1, 3, 1, 0, 12]);
+        cmap.extend(u16s(&[4, 40, 0, 6, 4, 1, 2]));
+        cmap.extend(u16s(&[0x20, 0x41, 0xFFFF, 0])); // end codes, pad
+        cmap.extend(u16s(&[0x20, 0x41, 0xFFFF])); // start codes
+        cmap.extend(u16s(&[(1i16 - 0x20) as u16, (2i16 - 0x41) as u16, 1])); // deltas
+        cmap.extend(u16s(&[0, 0, 0])); // range offsets
+
+        let tables: [(&[u8; 4], Vec<u8>); 7] = [
+            (b"cmap", cmap),
+            (b"glyf", glyf),
+            (b"head", head),
+            (b"hhea", hhea),
+            (b"hmtx", hmtx),
+            (b"loca", loca),
+            (b"maxp", maxp),
+        ];
+        let mut font = u16s(&[1, 0, tables.len() as u16, 0, 0, 0]);
+        let mut offset = 12 + tables.len() * 16;
+        let mut body = Vec::new();
+        for (tag, data) in &tables {
+            font.extend_from_slice(&tag[..]);
+            font.extend(0u32.to_be_bytes());
+            font.extend((offset as u32).to_be_bytes());
+            font.extend((data.len() as u32).to_be_bytes());
+            let mut padded = data.clone();
+            padded.resize(data.len().div_ceil(4) * 4, 0);
+            offset += padded.len();
+            body.extend(padded);
+        }
+        font.extend(body);
+        font
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_draw_text() {
+        let font = test_font();
+        let image = ImageUtil::create_solid_color(60, 40, 255, 255, 255);
+
+        // The test glyph for 'A' covers x 2..16 and y 2..16 at 20px
+        let drawn = ImageUtil::draw_text(&image, "A", 0, 0, &font, 20.0, [0, 0, 0, 255]).unwrap();
+        assert_eq!(drawn.dimensions(), image.dimensions());
+        assert_eq!(drawn.get_pixel(8, 10), Rgba([0, 0, 0, 255]));
+        assert_eq!(drawn.get_pixel(30, 30), Rgba([255, 255, 255, 255]));
+
+        // The second line starts one line height (20px) lower
+        let lines =
+            ImageUtil::draw_text(&image, " \nA", 0, 0, &font, 20.0, [0, 0, 0, 255]).unwrap();
+        assert_eq!(lines.get_pixel(8, 10), Rgba([255, 255, 255, 255]));
+        assert_eq!(lines.get_pixel(8, 30), Rgba([0, 0, 0, 255]));
+
+        // Text running off the edge, or starting before it, is clipped
+        let clipped = ImageUtil::draw_text(&image, "AAAAA", 40, 30, &font, 20.0, [255, 0, 0, 128]);
+        assert_eq!(clipped.unwrap().dimensions(), image.dimensions());
+        let clipped = ImageUtil::draw_text(&image, "AA", -30, -10, &font, 20.0, [255, 0, 0, 255]);
+        assert_eq!(clipped.unwrap().dimensions(), image.dimensions());
+
+        assert!(
+            ImageUtil::draw_text(&image, "A", 0, 0, b"not a font", 20.0, [0, 0, 0, 255]).is_err()
+        );
+        assert!(ImageUtil::draw_text(&image, "A", 0, 0, &font, 0.0, [0, 0, 0, 255]).is_err());
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_draw_text_with_corrupted_fonts() {
+        let image = ImageUtil::create_solid_color(40, 40, 255, 255, 255);
+        let font = test_font();
+
+        // Truncated and bit-flipped fonts must fail cleanly or draw, never panic
+        for length in (0..font.len()).step_by(7) {
+            let _ =
+                ImageUtil::draw_text(&image, "A A", 0, 0, &font[..length], 20.0, [0, 0, 0, 255]);
+        }
+        let mut state: u32 = 12345;
+        for _ in 0..200 {
+            let mut corrupted = font.clone();
+            for _ in 0..4 {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                let index = (state >> 8) as usize % corrupted.len();
+                corrupted[index] ^= (state >> 24) as u8 | 1;
+            }
+            let _ = ImageUtil::draw_text(&image, "A A", 0, 0, &corrupted, 20.0, [0, 0, 0, 255]);
+        }
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    #[allow(clippy::cast_possible_truncation)]
+    fn test_save_jpeg_quality() {
+        // A gradient with noise so quality makes a visible size difference
+        let image = DynamicImage::ImageRgb8(ImageBuffer::from_fn(64, 64, |x, y| {
+            let noise = ((x * 31 + y * 17) % 23) as u8 * 5;
+            Rgb([(x * 4) as u8, (y * 4) as u8, noise])
+        }));
+
+        let low = ImageUtil::save_jpeg(&image, 10).unwrap();
+        let high = ImageUtil::save_jpeg(&image, 95).unwrap();
+        assert!(low.len() < high.len());
+        assert_eq!(
+            ImageUtil::load_from_bytes(&low).unwrap().dimensions(),
+            (64, 64)
+        );
+
+        let transparent = ImageUtil::create_solid_color_rgba(8, 8, 0, 0, 0, 0);
+        assert!(ImageUtil::save_jpeg(&transparent, 80).is_ok());
+
+        assert!(ImageUtil::save_jpeg(&image, 0).is_err());
+        assert!(ImageUtil::save_jpeg(&image, 101).is_err());
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    #[allow(clippy::cast_possible_truncation)]
+    fn test_save_png_options() {
+        let image = DynamicImage::ImageRgb8(ImageBuffer::from_fn(64, 64, |x, y| {
+            Rgb([(x * 4) as u8, (y * 4) as u8, ((x + y) * 2) as u8])
+        }));
+
+        let fast = ImageUtil::save_png(&image, PngCompression::Fast, PngFilter::NoFilter).unwrap();
+        let best = ImageUtil::save_png(&image, PngCompression::Best, PngFilter::Adaptive).unwrap();
+        assert!(best.len() <= fast.len());
+
+        // Lossless regardless of settings
+        let decoded = ImageUtil::load_from_bytes(&best).unwrap();
+        assert_eq!(decoded.to_rgb8(), image.to_rgb8());
+    }
+
     #[cfg(feature = "image")]
     #[test]
     fn test_histogram() {
@@ -703,6 +1812,104 @@ mod tests {
         assert!(bright_analysis.is_mostly_bright);
     }
 
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_palette_of_solid_color() {
+        let image = ImageUtil::create_solid_color(1000, 800, 12, 150, 230);
+        assert_eq!(
+            ImageUtil::dominant_colors(&image, 1),
+            vec![Rgb([12, 150, 230])]
+        );
+        assert_eq!(
+            ImageUtil::dominant_colors(&image, 5),
+            vec![Rgb([12, 150, 230])]
+        );
+        assert_eq!(ImageUtil::average_color(&image), Rgb([12, 150, 230]));
+        assert!(ImageUtil::dominant_colors(&image, 0).is_empty());
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_dominant_colors() {
+        // Left three quarters red, right quarter blue, with slight noise
+        let image = DynamicImage::ImageRgb8(ImageBuffer::from_fn(200, 100, |x, y| {
+            let noise = ((x + y) % 3) as u8;
+            if x < 150 {
+                Rgb([220 + noise, 10, 10])
+            } else {
+                Rgb([10, 10, 200 + noise])
+            }
+        }));
+
+        let palette = ImageUtil::dominant_colors(&image, 2);
+        assert_eq!(palette.len(), 2);
+        assert!(palette[0][0] >= 219 && palette[0][2] == 10, "{:?}", palette);
+        assert!(palette[1][2] >= 199 && palette[1][0] == 10, "{:?}", palette);
+
+        let average = ImageUtil::average_color(&image);
+        assert!((166..=168).contains(&average[0]), "{:?}", average);
+        assert!((58..=60).contains(&average[2]), "{:?}", average);
+
+        // Transparent pixels are ignored
+        let mut rgba = image.to_rgba8();
+        for x in 0..150 {
+            for y in 0..100 {
+                rgba.get_pixel_mut(x, y)[3] = 0;
+            }
+        }
+        let rgba = DynamicImage::ImageRgba8(rgba);
+        assert_eq!(ImageUtil::dominant_colors(&rgba, 1)[0][0], 10);
+        assert_eq!(ImageUtil::average_color(&rgba)[0], 10);
+    }
+
+    #[cfg(feature = "image")]
+    #[allow(clippy::cast_possible_truncation)]
+    fn hash_test_image(seed: u32) -> DynamicImage {
+        // Overlapping blobs and stripes, varied by seed
+        DynamicImage::ImageRgb8(ImageBuffer::from_fn(400, 300, |x, y| {
+            let cx = 100 + seed * 37 % 200;
+            let cy = 80 + seed * 53 % 140;
+            let dx = x.abs_diff(cx);
+            let dy = y.abs_diff(cy);
+            let blob = if dx * dx + dy * dy < 70 * 70 { 180 } else { 0 };
+            let stripe = if (x / (20 + seed * 7 % 30)) % 2 == 0 {
+                60
+            } else {
+                0
+            };
+            let shade = (y * 60 / 300) as u8;
+            Rgb([
+                blob as u8 + shade,
+                stripe as u8 + shade,
+                (blob / 2 + stripe) as u8,
+            ])
+        }))
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_image_hashes() {
+        let original = hash_test_image(1);
+        let resized = ImageUtil::resize(&original, 150, 120, ResizeFilter::Lanczos3);
+        let brighter = ImageUtil::adjust_brightness(&original, 20);
+        let other = hash_test_image(4);
+
+        let phash = ImageUtil::perceptual_hash(&original);
+        let ahash = ImageUtil::average_hash(&original);
+        assert_eq!(ImageUtil::perceptual_hash(&original), phash);
+
+        let near = |a: u64, b: u64| ImageUtil::hamming_distance(a, b);
+        assert!(near(phash, ImageUtil::perceptual_hash(&resized)) <= 4);
+        assert!(near(ahash, ImageUtil::average_hash(&resized)) <= 4);
+        assert!(near(phash, ImageUtil::perceptual_hash(&brighter)) <= 4);
+
+        assert!(near(phash, ImageUtil::perceptual_hash(&other)) > 12);
+        assert!(near(ahash, ImageUtil::average_hash(&other)) > 12);
+
+        assert_eq!(ImageUtil::hamming_distance(0, u64::MAX), 64);
+        assert_eq!(ImageUtil::hamming_distance(0b1010, 0b0110), 2);
+    }
+
     #[cfg(feature = "image")]
     #[test]
     fn test_image_effects() {
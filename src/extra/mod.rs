@@ -18,10 +18,13 @@ pub mod compression;
 
 /// Re-export commonly used types for convenience
 #[cfg(feature = "qrcode")]
-pub use qr_code::{ErrorCorrectionLevel, QrCode, QrCodeBuilder, QrCodeUtil};
+pub use qr_code::{ErrorCorrectionLevel, QrCode, QrCodeBuilder, QrCodeCapacity, QrCodeUtil};
 
 #[cfg(feature = "image")]
-pub use image_util::{ImageFormat, ImageUtil, ResizeFilter};
+pub use image_util::{
+    Alignment, CombineOptions, Corner, ImageFormat, ImageUtil, PngCompression, PngFilter,
+    ResizeFilter,
+};
 
 #[cfg(feature = "zip")]
-pub use compression::{CompressionFormat, CompressionLevel, CompressionUtil};
+pub use compression::{CompressionFormat, CompressionLevel, CompressionUtil, ProgressEvent};
@@ -8,7 +8,7 @@ use std::fmt;
 use std::path::Path;
 
 #[cfg(feature = "qrcode")]
-use qrcode::{EcLevel, QrCode as LibQrCode, Version};
+use qrcode::{Color, EcLevel, QrCode as LibQrCode, Version};
 
 #[cfg(feature = "image")]
 use image::{DynamicImage, ImageFormat as ImgFormat, Luma};
@@ -45,6 +45,38 @@ impl Default for ErrorCorrectionLevel {
     }
 }
 
+impl ErrorCorrectionLevel {
+    /// Convert to the level type of the `qrcode` crate
+    #[cfg(feature = "qrcode")]
+    fn to_ec_level(self) -> EcLevel {
+        match self {
+            Self::Low => EcLevel::L,
+            Self::Medium => EcLevel::M,
+            Self::Quartile => EcLevel::Q,
+            Self::High => EcLevel::H,
+        }
+    }
+
+    /// Approximate fraction of codewords that can be restored
+    pub fn recovery_capacity(&self) -> f32 {
+        match self {
+            Self::Low => 0.07,
+            Self::Medium => 0.15,
+            Self::Quartile => 0.25,
+            Self::High => 0.30,
+        }
+    }
+
+    /// Largest logo width, as a fraction of the image width, that
+    /// `QrCode::to_image_with_logo` accepts for this level
+    ///
+    /// The logo may cover at most 40% of the recovery capacity by area,
+    /// leaving the rest for print defects and scanning noise.
+    pub fn max_logo_fraction(&self) -> f32 {
+        (self.recovery_capacity() * 0.4).sqrt()
+    }
+}
+
 /// QR code output format
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum QrCodeFormat {
@@ -229,24 +261,22 @@ impl QrCode {
     }
 
     /// Generate QR code as JPEG image bytes
+    ///
+    /// `quality` ranges from 1 to 100. QR codes are high-contrast, so even
+    /// moderate qualities such as 80 stay scannable; PNG is preferable when
+    /// size is not a concern.
     #[cfg(all(feature = "qrcode", feature = "image"))]
-    pub fn to_jpeg(&self, size: u32, _quality: u8) -> Result<Vec<u8>> {
+    pub fn to_jpeg(&self, size: u32, quality: u8) -> Result<Vec<u8>> {
         let image = self.to_image(size)?;
-        let mut bytes = Vec::new();
-        let mut cursor = std::io::Cursor::new(&mut bytes);
-
-        // Convert to RGB for JPEG
-        let rgb_image = image.to_rgb8();
-        let dynamic = DynamicImage::ImageRgb8(rgb_image);
-
-        dynamic
-            .write_to(&mut cursor, ImgFormat::Jpeg)
-            .map_err(|e| Error::validation(format!("Failed to encode JPEG: {}", e)))?;
-
-        Ok(bytes)
+        crate::extra::ImageUtil::save_jpeg(&image, quality)
     }
 
     /// Generate QR code as image
+    ///
+    /// The image is exactly `size`×`size` pixels. Modules are drawn at the
+    /// largest integer scale that leaves room for the standard 4-module quiet
+    /// zone, and the remaining pixels are split evenly into a white border.
+    /// Sizes smaller than the module count are rejected.
     #[cfg(all(feature = "qrcode", feature = "image"))]
     pub fn to_image(&self, size: u32) -> Result<DynamicImage> {
         if size == 0 {
@@ -255,25 +285,85 @@ impl QrCode {
             ));
         }
 
-        let qr_size = self.qr_code.width();
-        let scale = size / qr_size as u32;
-        if scale == 0 {
+        let qr_size = self.qr_code.width() as u32;
+        if size < qr_size {
             return Err(Error::validation(format!(
                 "Image size {} is too small for QR code size {}",
                 size, qr_size
             )));
         }
 
-        let image = self
-            .qr_code
-            .render::<Luma<u8>>()
-            .min_dimensions(size, size)
-            .max_dimensions(size, size)
-            .build();
+        let scale = (size / (qr_size + 8)).max(1);
+        let offset = (size - qr_size * scale) / 2;
+        let colors = self.qr_code.to_colors();
+
+        let image = image::GrayImage::from_fn(size, size, |x, y| {
+            let (x, y) = (
+                x.wrapping_sub(offset) / scale,
+                y.wrapping_sub(offset) / scale,
+            );
+            if x < qr_size && y < qr_size && colors[(y * qr_size + x) as usize] == Color::Dark {
+                Luma([0])
+            } else {
+                Luma([255])
+            }
+        });
 
         Ok(DynamicImage::ImageLuma8(image))
     }
 
+    /// Generate QR code as image with a logo centered over it
+    ///
+    /// `logo_fraction` is the logo width relative to the image width. The
+    /// logo is scaled to fit, keeping its aspect ratio, and placed on a white
+    /// background. Fractions above
+    /// [`ErrorCorrectionLevel::max_logo_fraction`] are rejected because the
+    /// covered modules could no longer be recovered; use
+    /// [`ErrorCorrectionLevel::High`] for the largest logos.
+    #[cfg(all(feature = "qrcode", feature = "image"))]
+    pub fn to_image_with_logo(
+        &self,
+        size: u32,
+        logo: &DynamicImage,
+        logo_fraction: f32,
+    ) -> Result<DynamicImage> {
+        let max_fraction = self.error_correction.max_logo_fraction();
+        if !(logo_fraction > 0.0 && logo_fraction <= max_fraction) {
+            return Err(Error::validation(format!(
+                "Logo fraction {} must be between 0 and {:.2} for {} error correction",
+                logo_fraction, max_fraction, self.error_correction
+            )));
+        }
+        if logo.width() == 0 || logo.height() == 0 {
+            return Err(Error::validation("Logo image is empty".to_string()));
+        }
+
+        let mut image = self.to_image(size)?.to_rgba8();
+        let (width, height) = image.dimensions();
+
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_precision_loss,
+            clippy::cast_sign_loss
+        )]
+        let box_size = ((width.min(height) as f32 * logo_fraction).round() as u32).max(1);
+        let logo = logo.resize(box_size, box_size, image::imageops::FilterType::Lanczos3);
+
+        let background =
+            image::RgbaImage::from_pixel(box_size, box_size, image::Rgba([255, 255, 255, 255]));
+        let left = i64::from((width - box_size) / 2);
+        let top = i64::from((height - box_size) / 2);
+        image::imageops::overlay(&mut image, &background, left, top);
+        image::imageops::overlay(
+            &mut image,
+            &logo.to_rgba8(),
+            left + i64::from((box_size - logo.width()) / 2),
+            top + i64::from((box_size - logo.height()) / 2),
+        );
+
+        Ok(DynamicImage::ImageRgba8(image))
+    }
+
     /// Save QR code as image file
     #[cfg(all(feature = "qrcode", feature = "image"))]
     pub fn save_image<P: AsRef<Path>>(&self, path: P, size: u32) -> Result<()> {
@@ -284,6 +374,53 @@ impl QrCode {
         Ok(())
     }
 
+    /// Decode the text of a QR code in an image file
+    ///
+    /// The code may be rotated, scaled or slightly noisy. Returns a not found
+    /// error if the image contains no recognizable QR code.
+    #[cfg(all(feature = "qrcode", feature = "image"))]
+    pub fn decode_image<P: AsRef<Path>>(path: P) -> Result<String> {
+        let image = image::open(path)
+            .map_err(|e| Error::validation(format!("Failed to open image: {}", e)))?;
+        Self::decode_from_image(&image)
+    }
+
+    /// Decode the text of a QR code in encoded image bytes (PNG, JPEG, ...)
+    #[cfg(all(feature = "qrcode", feature = "image"))]
+    pub fn decode_bytes(bytes: &[u8]) -> Result<String> {
+        let image = image::load_from_memory(bytes)
+            .map_err(|e| Error::validation(format!("Failed to load image: {}", e)))?;
+        Self::decode_from_image(&image)
+    }
+
+    /// Decode the text of a QR code in an in-memory image
+    ///
+    /// Detection and decoding are done by the `rqrr` crate. If the image
+    /// contains several codes, the first one that decodes is returned.
+    #[cfg(all(feature = "qrcode", feature = "image"))]
+    pub fn decode_from_image(image: &DynamicImage) -> Result<String> {
+        let luma = image.to_luma8();
+        // Coordinates are below the image dimensions, so they fit in a u32
+        #[allow(clippy::cast_possible_truncation)]
+        let mut prepared = rqrr::PreparedImage::prepare_from_greyscale(
+            luma.width() as usize,
+            luma.height() as usize,
+            |x, y| luma.get_pixel(x as u32, y as u32).0[0],
+        );
+
+        let mut last_error = None;
+        for grid in prepared.detect_grids() {
+            match grid.decode() {
+                Ok((_, text)) => return Ok(text),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(match last_error {
+            Some(e) => Error::validation(format!("Failed to decode QR code: {e}")),
+            None => Error::not_found("No QR code found in image"),
+        })
+    }
+
     /// Generate QR code as SVG string
     #[cfg(feature = "qrcode")]
     pub fn to_svg(&self, size: u32) -> String {
@@ -334,52 +471,13 @@ impl QrCode {
     }
 
     /// Get QR code capacity information
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the code's version has no capacity table entry.
     #[cfg(feature = "qrcode")]
-    pub fn capacity_info(&self) -> QrCodeCapacity {
-        let version = self.actual_version();
-        let ec_level = self.error_correction;
-
-        // These are approximate capacities based on QR code specifications
-        let (numeric, alphanumeric, binary) = match (version, ec_level) {
-            (1, ErrorCorrectionLevel::Low) => (41, 25, 17),
-            (1, ErrorCorrectionLevel::Medium) => (34, 20, 14),
-            (1, ErrorCorrectionLevel::Quartile) => (27, 16, 11),
-            (1, ErrorCorrectionLevel::High) => (17, 10, 7),
-            (10, ErrorCorrectionLevel::Low) => (652, 395, 271),
-            (10, ErrorCorrectionLevel::Medium) => (513, 311, 213),
-            (10, ErrorCorrectionLevel::Quartile) => (364, 221, 151),
-            (10, ErrorCorrectionLevel::High) => (288, 174, 119),
-            (20, ErrorCorrectionLevel::Low) => (1852, 1122, 771),
-            (20, ErrorCorrectionLevel::Medium) => (1429, 866, 595),
-            (20, ErrorCorrectionLevel::Quartile) => (1057, 641, 441),
-            (20, ErrorCorrectionLevel::High) => (808, 493, 339),
-            (40, ErrorCorrectionLevel::Low) => (7089, 4296, 2953),
-            (40, ErrorCorrectionLevel::Medium) => (5596, 3391, 2331),
-            (40, ErrorCorrectionLevel::Quartile) => (3993, 2420, 1663),
-            (40, ErrorCorrectionLevel::High) => (3057, 1852, 1273),
-            _ => {
-                // Rough estimation for other versions
-                let base = version as usize * version as usize;
-                let factor = match ec_level {
-                    ErrorCorrectionLevel::Low => 1.0,
-                    ErrorCorrectionLevel::Medium => 0.8,
-                    ErrorCorrectionLevel::Quartile => 0.6,
-                    ErrorCorrectionLevel::High => 0.4,
-                };
-                let numeric = (base as f64 * factor * 0.3) as usize;
-                let alphanumeric = (numeric as f64 * 0.6) as usize;
-                let binary = (alphanumeric as f64 * 0.7) as usize;
-                (numeric, alphanumeric, binary)
-            }
-        };
-
-        QrCodeCapacity {
-            version,
-            error_correction: ec_level,
-            numeric_capacity: numeric,
-            alphanumeric_capacity: alphanumeric,
-            binary_capacity: binary,
-        }
+    pub fn capacity_info(&self) -> Result<QrCodeCapacity> {
+        QrCodeCapacity::for_version(self.actual_version(), self.error_correction)
     }
 }
 
@@ -398,6 +496,69 @@ pub struct QrCodeCapacity {
     pub binary_capacity: usize,
 }
 
+impl QrCodeCapacity {
+    /// Compute the capacity of a version (1-40) and error correction level
+    ///
+    /// Capacities follow the QR code specification: the data bits of the
+    /// symbol, minus the mode indicator and character count, divided into
+    /// characters of a single encoding mode.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::extra::{ErrorCorrectionLevel, QrCodeCapacity};
+    ///
+    /// let capacity = QrCodeCapacity::for_version(1, ErrorCorrectionLevel::Low).unwrap();
+    /// assert_eq!(capacity.numeric_capacity, 41);
+    /// assert_eq!(capacity.alphanumeric_capacity, 25);
+    /// assert_eq!(capacity.binary_capacity, 17);
+    /// ```
+    #[cfg(feature = "qrcode")]
+    pub fn for_version(version: u8, error_correction: ErrorCorrectionLevel) -> Result<Self> {
+        if !(1..=40).contains(&version) {
+            return Err(Error::validation(format!(
+                "QR code version must be between 1 and 40, got {}",
+                version
+            )));
+        }
+
+        let data_bits = qrcode::bits::Bits::new(Version::Normal(i16::from(version)))
+            .max_len(error_correction.to_ec_level())
+            .map_err(|e| Error::validation(format!("Invalid QR code version: {}", e)))?;
+
+        // Character count indicator widths for versions 1-9, 10-26 and 27-40
+        let (numeric_count, alphanumeric_count, byte_count) = match version {
+            1..=9 => (10, 9, 8),
+            10..=26 => (12, 11, 16),
+            _ => (14, 13, 16),
+        };
+        let available = |count_bits: usize| data_bits.saturating_sub(4 + count_bits);
+
+        // Numeric packs 3 digits in 10 bits, with 2 digits in 7 and 1 in 4
+        let bits = available(numeric_count);
+        let numeric = bits / 10 * 3
+            + match bits % 10 {
+                7..=9 => 2,
+                4..=6 => 1,
+                _ => 0,
+            };
+
+        // Alphanumeric packs 2 characters in 11 bits, with 1 in 6
+        let bits = available(alphanumeric_count);
+        let alphanumeric = bits / 11 * 2 + usize::from(bits % 11 >= 6);
+
+        let binary = available(byte_count) / 8;
+
+        Ok(Self {
+            version,
+            error_correction,
+            numeric_capacity: numeric,
+            alphanumeric_capacity: alphanumeric,
+            binary_capacity: binary,
+        })
+    }
+}
+
 impl fmt::Display for QrCodeCapacity {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(
@@ -549,34 +710,44 @@ impl QrCodeUtil {
         QrCode::new(&geo_string)
     }
 
+    /// Create QR codes for many inputs, keeping one result per input
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::extra::QrCodeUtil;
+    ///
+    /// let codes = QrCodeUtil::batch(&["first", "second"]);
+    /// assert_eq!(codes.len(), 2);
+    /// assert!(codes.iter().all(|qr| qr.is_ok()));
+    /// ```
+    pub fn batch(data: &[&str]) -> Vec<Result<QrCode>> {
+        data.iter().map(|item| QrCode::new(item)).collect()
+    }
+
     /// Get optimal QR code version for given data
+    ///
+    /// Returns the smallest version that holds `data` at the given error
+    /// correction level, using the same segment optimization as
+    /// [`QrCode::with_error_correction`]. Data too long for any version
+    /// returns 40.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::extra::{ErrorCorrectionLevel, QrCodeUtil};
+    ///
+    /// assert_eq!(QrCodeUtil::optimal_version("Short", ErrorCorrectionLevel::Low), 1);
+    /// ```
     pub fn optimal_version(data: &str, error_correction: ErrorCorrectionLevel) -> u8 {
-        let data_len = data.len();
-
-        // Simple heuristic based on data length and error correction
-        let base_version = match data_len {
-            0..=25 => 1,
-            26..=47 => 2,
-            48..=77 => 3,
-            78..=114 => 4,
-            115..=154 => 5,
-            155..=195 => 6,
-            196..=224 => 7,
-            225..=279 => 8,
-            280..=335 => 9,
-            336..=395 => 10,
-            _ => ((data_len as f64 / 40.0).ceil() as u8).min(40),
-        };
-
-        // Adjust for error correction level
-        let adjustment = match error_correction {
-            ErrorCorrectionLevel::Low => 0,
-            ErrorCorrectionLevel::Medium => 1,
-            ErrorCorrectionLevel::Quartile => 2,
-            ErrorCorrectionLevel::High => 3,
-        };
-
-        (base_version + adjustment).min(40).max(1)
+        let ec_level = error_correction.to_ec_level();
+        (1..=40u8)
+            .find(|&version| {
+                let mut bits = qrcode::bits::Bits::new(Version::Normal(i16::from(version)));
+                bits.push_optimal_data(data.as_bytes()).is_ok()
+                    && bits.push_terminator(ec_level).is_ok()
+            })
+            .unwrap_or(40)
     }
 }
 
@@ -744,19 +915,83 @@ mod tests {
         let long_text = "A".repeat(200);
         let version = QrCodeUtil::optimal_version(&long_text, ErrorCorrectionLevel::High);
         assert!(version > 5);
+
+        // Boundaries from the specification's byte mode capacities
+        assert_eq!(
+            QrCodeUtil::optimal_version(&"a".repeat(17), ErrorCorrectionLevel::Low),
+            1
+        );
+        assert_eq!(
+            QrCodeUtil::optimal_version(&"a".repeat(18), ErrorCorrectionLevel::Low),
+            2
+        );
+        assert_eq!(
+            QrCodeUtil::optimal_version(&"a".repeat(64), ErrorCorrectionLevel::High),
+            7
+        );
+        assert_eq!(
+            QrCodeUtil::optimal_version(&"a".repeat(65), ErrorCorrectionLevel::High),
+            8
+        );
+
+        // Agrees with the version chosen when encoding
+        for len in [1, 30, 100, 400, 800] {
+            let data = "x1".repeat(len);
+            let qr = QrCode::with_error_correction(&data, ErrorCorrectionLevel::Quartile).unwrap();
+            assert_eq!(
+                QrCodeUtil::optimal_version(&data, ErrorCorrectionLevel::Quartile),
+                qr.actual_version()
+            );
+        }
     }
 
     #[cfg(feature = "qrcode")]
     #[test]
     fn test_capacity_info() {
         let qr = QrCode::with_version("Test", 5).unwrap();
-        let capacity = qr.capacity_info();
+        let capacity = qr.capacity_info().unwrap();
         assert_eq!(capacity.version, 5);
         assert!(capacity.numeric_capacity > 0);
         assert!(capacity.alphanumeric_capacity > 0);
         assert!(capacity.binary_capacity > 0);
     }
 
+    #[cfg(feature = "qrcode")]
+    #[test]
+    fn test_capacity_spec_values() {
+        use ErrorCorrectionLevel::{High, Low, Medium, Quartile};
+
+        // (version, level, numeric, alphanumeric, byte) from ISO/IEC 18004 table 7
+        let expected = [
+            (1, Low, 41, 25, 17),
+            (1, High, 17, 10, 7),
+            (7, Low, 370, 224, 154),
+            (7, Medium, 293, 178, 122),
+            (7, Quartile, 207, 125, 86),
+            (7, High, 154, 93, 64),
+            (10, Medium, 513, 311, 213),
+            (15, Low, 1250, 758, 520),
+            (15, High, 530, 321, 220),
+            (40, Low, 7089, 4296, 2953),
+            (40, High, 3057, 1852, 1273),
+        ];
+        for (version, level, numeric, alphanumeric, binary) in expected {
+            let capacity = QrCodeCapacity::for_version(version, level).unwrap();
+            assert_eq!(
+                (
+                    capacity.numeric_capacity,
+                    capacity.alphanumeric_capacity,
+                    capacity.binary_capacity
+                ),
+                (numeric, alphanumeric, binary),
+                "version {version} {level}"
+            );
+        }
+
+        assert!(QrCodeCapacity::for_version(0, Low).is_err());
+        assert!(QrCodeCapacity::for_version(41, Low).is_err());
+    }
+
     #[test]
     fn test_error_correction_display() {
         assert_eq!(ErrorCorrectionLevel::Low.to_string(), "Low");
@@ -777,17 +1012,38 @@ mod tests {
     #[cfg(all(feature = "qrcode", feature = "image"))]
     #[test]
     fn test_image_generation() {
+        use image::GenericImageView;
+
         let qr = QrCode::new("Image test").unwrap();
         let image = qr.to_image(200);
         assert!(image.is_ok());
 
         let image = image.unwrap();
-        // The actual image size depends on QR code size and scaling
-        // Just check that we got a reasonable size
-        assert!(image.width() > 0);
-        assert!(image.height() > 0);
-        assert!(image.width() <= 200);
-        assert!(image.height() <= 200);
+        assert_eq!(image.dimensions(), (200, 200));
+    }
+
+    #[cfg(all(feature = "qrcode", feature = "image"))]
+    #[test]
+    fn test_image_exact_dimensions() {
+        use image::GenericImageView;
+
+        let qr = QrCode::new("Exact size").unwrap();
+        let width = qr.qr_code.width() as u32;
+        for size in [width, width + 3, 57, 100, 128, 333, 1000] {
+            let image = qr.to_image(size).unwrap();
+            assert_eq!(image.dimensions(), (size, size));
+            // Without room for a quiet zone the code is not decodable
+            if size > width {
+                assert_eq!(QrCode::decode_from_image(&image).unwrap(), "Exact size");
+            }
+        }
+
+        // Corners stay light and the quiet zone surrounds the code
+        let image = qr.to_image(300).unwrap().to_luma8();
+        assert_eq!(image.get_pixel(0, 0).0, [255]);
+        assert_eq!(image.get_pixel(299, 299).0, [255]);
+
+        assert!(qr.to_image(width - 1).is_err());
     }
 
     #[cfg(all(feature = "qrcode", feature = "image"))]
@@ -803,6 +1059,106 @@ mod tests {
         assert_eq!(&bytes[0..8], &[137, 80, 78, 71, 13, 10, 26, 10]);
     }
 
+    #[cfg(all(feature = "qrcode", feature = "image"))]
+    #[test]
+    fn test_jpeg_quality() {
+        let qr = QrCode::new("JPEG quality test").unwrap();
+        let low = qr.to_jpeg(200, 20).unwrap();
+        let high = qr.to_jpeg(200, 95).unwrap();
+        assert_eq!(&low[..2], &[0xFF, 0xD8]);
+        assert!(low.len() < high.len());
+        assert!(qr.to_jpeg(200, 0).is_err());
+    }
+
+    #[cfg(all(feature = "qrcode", feature = "image"))]
+    #[test]
+    fn test_decode_png_round_trip() {
+        let qr = QrCode::new("https://example.com/decode?id=42").unwrap();
+        let png = qr.to_png(300).unwrap();
+        assert_eq!(
+            QrCode::decode_bytes(&png).unwrap(),
+            "https://example.com/decode?id=42"
+        );
+    }
+
+    #[cfg(all(feature = "qrcode", feature = "image"))]
+    #[test]
+    fn test_decode_image_file() {
+        let path = std::env::temp_dir().join("rutool_qr_decode_test.png");
+        let qr = QrCode::with_error_correction("File decode", ErrorCorrectionLevel::High).unwrap();
+        qr.save_image(&path, 250).unwrap();
+
+        let decoded = QrCode::decode_image(&path);
+        std::fs::remove_file(&path).ok();
+        assert_eq!(decoded.unwrap(), "File decode");
+    }
+
+    #[cfg(all(feature = "qrcode", feature = "image"))]
+    #[test]
+    fn test_decode_rotated_and_unicode() {
+        let qr = QrCode::new("你好，世界 – héllo").unwrap();
+        let image = qr.to_image(300).unwrap();
+        for rotated in [image.rotate90(), image.rotate180(), image.rotate270()] {
+            assert_eq!(
+                QrCode::decode_from_image(&rotated).unwrap(),
+                "你好，世界 – héllo"
+            );
+        }
+    }
+
+    #[cfg(all(feature = "qrcode", feature = "image"))]
+    #[test]
+    fn test_decode_without_qr_code() {
+        let blank = DynamicImage::new_luma8(200, 200);
+        assert!(QrCode::decode_from_image(&blank).is_err());
+        assert!(QrCode::decode_bytes(b"not an image").is_err());
+    }
+
+    #[cfg(all(feature = "qrcode", feature = "image"))]
+    #[test]
+    #[allow(clippy::cast_possible_truncation)]
+    fn test_image_with_logo_still_decodes() {
+        let logo = DynamicImage::ImageRgb8(image::RgbImage::from_fn(60, 40, |x, y| {
+            image::Rgb([(x * 4) as u8, (y * 6) as u8, 128])
+        }));
+        let qr = QrCode::with_error_correction(
+            "https://example.com/campaign",
+            ErrorCorrectionLevel::High,
+        )
+        .unwrap();
+
+        let image = qr.to_image_with_logo(300, &logo, 0.3).unwrap();
+        let base = qr.to_image(300).unwrap();
+        assert_eq!(image.width(), base.width());
+        assert_ne!(image.to_luma8(), base.to_luma8());
+        assert_eq!(
+            QrCode::decode_from_image(&image).unwrap(),
+            "https://example.com/campaign"
+        );
+    }
+
+    #[cfg(all(feature = "qrcode", feature = "image"))]
+    #[test]
+    fn test_image_with_logo_too_large() {
+        let logo = DynamicImage::new_rgb8(10, 10);
+        let qr = QrCode::with_error_correction("Logo", ErrorCorrectionLevel::Low).unwrap();
+        assert!(qr.to_image_with_logo(200, &logo, 0.3).is_err());
+        assert!(qr.to_image_with_logo(200, &logo, 0.0).is_err());
+        assert!(qr.to_image_with_logo(200, &logo, 0.15).is_ok());
+
+        let qr = QrCode::with_error_correction("Logo", ErrorCorrectionLevel::High).unwrap();
+        assert!(qr.to_image_with_logo(200, &logo, 0.3).is_ok());
+        assert!(qr.to_image_with_logo(200, &logo, 0.5).is_err());
+    }
+
+    #[test]
+    fn test_qr_util_batch() {
+        let codes = QrCodeUtil::batch(&["one", "two", "three"]);
+        assert_eq!(codes.len(), 3);
+        assert_eq!(codes[1].as_ref().unwrap().data(), "two");
+        assert!(QrCodeUtil::batch(&[]).is_empty());
+    }
+
     #[test]
     fn test_invalid_image_size() {
         let qr = QrCode::new("Size test").unwrap();
@@ -0,0 +1,871 @@
+//! Reusable, configurable HTTP client
+//!
+//! [`HttpClient`] wraps a single `reqwest::Client` so that connections are
+//! pooled across requests. It is configured once through
+//! [`HttpClientBuilder`] (timeouts, default headers, base URL, proxy,
+//! redirects, TLS options, cookies and rate limits) and then used through
+//! instance methods.
+//! The static helpers on [`HttpUtil`](crate::http::HttpUtil) delegate to a
+//! shared default instance.
+
+use super::cookie::CookieJar;
+use super::rate_limit::RateLimiter;
+use super::response::ResponseExt;
+use crate::error::{Error, Result};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Client, Method, RequestBuilder, Response, Url, redirect};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+/// Default request timeout
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Default `User-Agent` header
+pub const DEFAULT_USER_AGENT: &str = "rutool/0.1.0";
+
+/// Connect and per-read timeouts of the shared streaming client
+const STREAMING_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+const STREAMING_READ_TIMEOUT: Duration = Duration::from_secs(60);
+
+static SHARED_CLIENT: LazyLock<HttpClient> = LazyLock::new(|| {
+    HttpClient::builder()
+        .build()
+        .expect("default HTTP client configuration is valid")
+});
+
+static STREAMING_CLIENT: LazyLock<HttpClient> = LazyLock::new(|| {
+    HttpClient::builder()
+        .no_timeout()
+        .connect_timeout(STREAMING_CONNECT_TIMEOUT)
+        .read_timeout(STREAMING_READ_TIMEOUT)
+        .build()
+        .expect("streaming HTTP client configuration is valid")
+});
+
+/// HTTP client holding a pooled connection set and shared configuration
+///
+/// Cloning is cheap and clones share the same connection pool.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use yimi_rutool::http::HttpClient;
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = HttpClient::builder()
+///         .base_url("https://httpbin.org")
+///         .timeout(Duration::from_secs(10))
+///         .header("Accept", "application/json")
+///         .build()?;
+///
+///     let body = client.get_text("get").await?;
+///     println!("{}", body);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    client: Client,
+    base_url: Option<Url>,
+    cookie_jar: Option<Arc<CookieJar>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+}
+
+impl HttpClient {
+    /// Start configuring a new client
+    pub fn builder() -> HttpClientBuilder {
+        HttpClientBuilder::new()
+    }
+
+    /// Shared client with the default configuration
+    ///
+    /// The instance is created on first use and reused afterwards, so
+    /// requests made through it share one connection pool.
+    pub fn shared() -> &'static HttpClient {
+        &SHARED_CLIENT
+    }
+
+    /// Shared client for long transfers such as downloads
+    ///
+    /// It has no total request timeout; a connection that goes idle for 60
+    /// seconds is dropped instead.
+    pub(crate) fn shared_streaming() -> &'static HttpClient {
+        &STREAMING_CLIENT
+    }
+
+    /// Underlying `reqwest` client
+    pub fn inner(&self) -> &Client {
+        &self.client
+    }
+
+    /// Base URL that relative request paths are resolved against
+    pub fn base_url(&self) -> Option<&Url> {
+        self.base_url.as_ref()
+    }
+
+    /// Cookie jar used by this client, if cookies are enabled
+    pub fn cookie_jar(&self) -> Option<&Arc<CookieJar>> {
+        self.cookie_jar.as_ref()
+    }
+
+    /// Rate limiter throttling this client's requests, if any
+    pub fn rate_limiter(&self) -> Option<&Arc<RateLimiter>> {
+        self.rate_limiter.as_ref()
+    }
+
+    /// Resolve a request target to an absolute URL
+    ///
+    /// Absolute URLs are used as they are. Anything else is appended to the
+    /// base URL, so with a base of `https://api.example.com/v1` the path
+    /// `users/1` (or `/users/1`) resolves to `https://api.example.com/v1/users/1`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target is relative and no base URL is
+    /// configured, or if the result is not a valid URL.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::http::HttpClient;
+    ///
+    /// let client = HttpClient::builder()
+    ///     .base_url("https://api.example.com/v1")
+    ///     .build()
+    ///     .unwrap();
+    /// let url = client.url("/users/1").unwrap();
+    /// assert_eq!(url.as_str(), "https://api.example.com/v1/users/1");
+    /// ```
+    pub fn url(&self, target: &str) -> Result<Url> {
+        match Url::parse(target) {
+            Ok(url) => Ok(url),
+            Err(url::ParseError::RelativeUrlWithoutBase) => {
+                let base = self.base_url.as_ref().ok_or_else(|| {
+                    Error::validation(format!(
+                        "Relative URL '{}' requires a base URL to be configured",
+                        target
+                    ))
+                })?;
+                base.join(target.trim_start_matches('/'))
+                    .map_err(|e| Error::validation(format!("Invalid URL '{}': {}", target, e)))
+            }
+            Err(e) => Err(Error::validation(format!(
+                "Invalid URL '{}': {}",
+                target, e
+            ))),
+        }
+    }
+
+    /// Create a request builder for the given method and URL or path
+    pub fn request(&self, method: Method, target: &str) -> Result<RequestBuilder> {
+        Ok(self.client.request(method, self.url(target)?))
+    }
+
+    /// Send a prepared request
+    ///
+    /// With a rate limiter configured, the request waits for a token for its
+    /// host before it is sent.
+    pub async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let Some(limiter) = &self.rate_limiter else {
+            return request.send().await.map_err(Error::Http);
+        };
+
+        let request = request.build().map_err(Error::Http)?;
+        limiter
+            .acquire(request.url().host_str().unwrap_or_default())
+            .await;
+        self.client.execute(request).await.map_err(Error::Http)
+    }
+
+    /// Perform a GET request
+    pub async fn get(&self, target: &str) -> Result<Response> {
+        self.send(self.request(Method::GET, target)?).await
+    }
+
+    /// Perform a GET request and return the body as text
+    pub async fn get_text(&self, target: &str) -> Result<String> {
+        let response = self.get(target).await?;
+        response.text().await.map_err(Error::Http)
+    }
+
+    /// Perform a GET request and deserialize the JSON body
+    ///
+    /// Non-2xx responses are returned as [`Error::HttpStatus`] instead of
+    /// being deserialized.
+    pub async fn get_json<T: DeserializeOwned>(&self, target: &str) -> Result<T> {
+        self.get(target).await?.json_checked().await
+    }
+
+    /// Perform a POST request with a JSON body
+    pub async fn post_json<T: Serialize>(&self, target: &str, json: &T) -> Result<Response> {
+        self.send(self.request(Method::POST, target)?.json(json))
+            .await
+    }
+
+    /// Perform a POST request with form data
+    pub async fn post_form(&self, target: &str, form: &HashMap<&str, &str>) -> Result<Response> {
+        self.send(self.request(Method::POST, target)?.form(form))
+            .await
+    }
+
+    /// Perform a POST request with a plain text body
+    pub async fn post_text(&self, target: &str, text: &str) -> Result<Response> {
+        let request = self
+            .request(Method::POST, target)?
+            .body(text.to_string())
+            .header("Content-Type", "text/plain");
+        self.send(request).await
+    }
+
+    /// Perform a PUT request with a JSON body
+    pub async fn put_json<T: Serialize>(&self, target: &str, json: &T) -> Result<Response> {
+        self.send(self.request(Method::PUT, target)?.json(json))
+            .await
+    }
+
+    /// Perform a PATCH request with a JSON body
+    pub async fn patch_json<T: Serialize>(&self, target: &str, json: &T) -> Result<Response> {
+        self.send(self.request(Method::PATCH, target)?.json(json))
+            .await
+    }
+
+    /// Perform a DELETE request
+    pub async fn delete(&self, target: &str) -> Result<Response> {
+        self.send(self.request(Method::DELETE, target)?).await
+    }
+
+    /// Perform a HEAD request
+    pub async fn head(&self, target: &str) -> Result<Response> {
+        self.send(self.request(Method::HEAD, target)?).await
+    }
+
+    /// Send a GraphQL query and deserialize the `data` field of the response
+    ///
+    /// The query is posted as the standard `{"query": ..., "variables": ...}`
+    /// JSON envelope. A non-empty `errors` array in the response is turned
+    /// into an error listing each message with its path, even when partial
+    /// `data` was returned. Non-2xx responses fail with
+    /// [`Error::HttpStatus`].
+    pub async fn graphql<T: DeserializeOwned>(
+        &self,
+        target: &str,
+        query: &str,
+        variables: &serde_json::Value,
+        headers: Option<&HashMap<&str, &str>>,
+    ) -> Result<T> {
+        let envelope = serde_json::json!({ "query": query, "variables": variables });
+        let mut request = self.request(Method::POST, target)?.json(&envelope);
+        if let Some(headers) = headers {
+            for (key, value) in headers {
+                request = request.header(*key, *value);
+            }
+        }
+
+        let mut body: serde_json::Value = self.send(request).await?.json_checked().await?;
+        if let Some(errors) = body.get("errors").and_then(|e| e.as_array())
+            && !errors.is_empty()
+        {
+            return Err(Error::custom(format!(
+                "GraphQL request to {} failed: {}",
+                target,
+                describe_graphql_errors(errors)
+            )));
+        }
+
+        match body.get_mut("data").map(serde_json::Value::take) {
+            Some(data) if !data.is_null() => Ok(serde_json::from_value(data)?),
+            _ => Err(Error::custom(format!(
+                "GraphQL response from {} has no data",
+                target
+            ))),
+        }
+    }
+}
+
+/// Join GraphQL error messages, each followed by its path if present
+fn describe_graphql_errors(errors: &[serde_json::Value]) -> String {
+    errors
+        .iter()
+        .map(|error| {
+            let message = error
+                .get("message")
+                .and_then(|m| m.as_str())
+                .map(str::to_string)
+                .unwrap_or_else(|| error.to_string());
+            let path = error.get("path").and_then(|p| p.as_array()).map(|path| {
+                path.iter()
+                    .map(|segment| match segment {
+                        serde_json::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(".")
+            });
+            match path {
+                Some(path) if !path.is_empty() => format!("{} (at {})", message, path),
+                _ => message,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Builder for [`HttpClient`]
+///
+/// Invalid settings such as malformed header values or proxy URLs are
+/// reported by [`HttpClientBuilder::build`].
+#[derive(Debug, Clone)]
+pub struct HttpClientBuilder {
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    user_agent: String,
+    headers: Vec<(String, String)>,
+    base_url: Option<String>,
+    proxy: Option<String>,
+    max_redirects: Option<usize>,
+    accept_invalid_certs: bool,
+    https_only: bool,
+    root_certificates: Vec<Vec<u8>>,
+    cookie_jar: Option<Arc<CookieJar>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+}
+
+impl Default for HttpClientBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HttpClientBuilder {
+    /// Create a builder with the default 60 second timeout and user agent
+    pub fn new() -> Self {
+        Self {
+            timeout: Some(DEFAULT_TIMEOUT),
+            connect_timeout: None,
+            read_timeout: None,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            headers: Vec::new(),
+            base_url: None,
+            proxy: None,
+            max_redirects: None,
+            accept_invalid_certs: false,
+            https_only: false,
+            root_certificates: Vec::new(),
+            cookie_jar: None,
+            rate_limiter: None,
+        }
+    }
+
+    /// Total time allowed for each request
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Disable the request timeout
+    pub fn no_timeout(mut self) -> Self {
+        self.timeout = None;
+        self
+    }
+
+    /// Time allowed to establish a connection
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Time allowed between two reads of the response body
+    ///
+    /// Unlike [`timeout`](Self::timeout) this resets whenever data arrives,
+    /// so it suits long downloads and streams.
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// `User-Agent` sent with every request
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = user_agent.to_string();
+        self
+    }
+
+    /// Header sent with every request; repeated names add multiple values
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Base URL for resolving relative request paths (see [`HttpClient::url`])
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = Some(base_url.to_string());
+        self
+    }
+
+    /// Route all requests through a proxy, e.g. `http://127.0.0.1:8080`
+    pub fn proxy(mut self, proxy_url: &str) -> Self {
+        self.proxy = Some(proxy_url.to_string());
+        self
+    }
+
+    /// Follow at most `max` redirects (reqwest follows up to 10 by default)
+    pub fn max_redirects(mut self, max: usize) -> Self {
+        self.max_redirects = Some(max);
+        self
+    }
+
+    /// Never follow redirects; 3xx responses are returned as they are
+    pub fn no_redirects(self) -> Self {
+        self.max_redirects(0)
+    }
+
+    /// Accept invalid TLS certificates
+    ///
+    /// This disables certificate validation entirely and should only be used
+    /// for testing against self-signed servers.
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.accept_invalid_certs = accept;
+        self
+    }
+
+    /// Refuse plain `http://` URLs
+    pub fn https_only(mut self, https_only: bool) -> Self {
+        self.https_only = https_only;
+        self
+    }
+
+    /// Trust an additional root certificate in PEM format
+    pub fn add_root_certificate_pem(mut self, pem: &[u8]) -> Self {
+        self.root_certificates.push(pem.to_vec());
+        self
+    }
+
+    /// Keep cookies between requests in a new, empty [`CookieJar`]
+    ///
+    /// Matching cookies are added to every request without its own `Cookie`
+    /// header, and `Set-Cookie` headers are stored from every response,
+    /// including intermediate redirects. Passing `false` disables cookie
+    /// handling, which is the default.
+    pub fn cookie_store(mut self, enabled: bool) -> Self {
+        self.cookie_jar = enabled.then(|| Arc::new(CookieJar::new()));
+        self
+    }
+
+    /// Keep cookies in an existing jar, e.g. one seeded with a session or
+    /// shared between several clients
+    pub fn cookie_jar(mut self, jar: Arc<CookieJar>) -> Self {
+        self.cookie_jar = Some(jar);
+        self
+    }
+
+    /// Throttle requests with a per-host [`RateLimiter`]
+    ///
+    /// Every request waits for a token for its host before being sent. The
+    /// limiter may be shared between clients so that they draw from the same
+    /// buckets.
+    pub fn rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Build the client
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a header, the base URL, the proxy URL or a
+    /// certificate is invalid, or if the TLS backend fails to initialize.
+    pub fn build(self) -> Result<HttpClient> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| Error::validation(format!("Invalid header name '{}': {}", name, e)))?;
+            let value = HeaderValue::from_str(value).map_err(|e| {
+                Error::validation(format!("Invalid value for header '{}': {}", name, e))
+            })?;
+            headers.append(name, value);
+        }
+
+        let base_url = self
+            .base_url
+            .map(|base| {
+                let mut url = Url::parse(&base).map_err(|e| {
+                    Error::validation(format!("Invalid base URL '{}': {}", base, e))
+                })?;
+                if url.cannot_be_a_base() {
+                    return Err(Error::validation(format!(
+                        "Invalid base URL '{}': cannot have relative paths",
+                        base
+                    )));
+                }
+                // Treat the base as a directory so joined paths extend it
+                if !url.path().ends_with('/') {
+                    url.set_path(&format!("{}/", url.path()));
+                }
+                Ok(url)
+            })
+            .transpose()?;
+
+        let mut builder = Client::builder()
+            .user_agent(self.user_agent)
+            .default_headers(headers)
+            .danger_accept_invalid_certs(self.accept_invalid_certs)
+            .https_only(self.https_only);
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.read_timeout {
+            builder = builder.read_timeout(timeout);
+        }
+        if let Some(max) = self.max_redirects {
+            builder = builder.redirect(if max == 0 {
+                redirect::Policy::none()
+            } else {
+                redirect::Policy::limited(max)
+            });
+        }
+        if let Some(proxy) = &self.proxy {
+            let proxy = reqwest::Proxy::all(proxy)
+                .map_err(|e| Error::validation(format!("Invalid proxy URL '{}': {}", proxy, e)))?;
+            builder = builder.proxy(proxy);
+        }
+        if let Some(jar) = &self.cookie_jar {
+            builder = builder.cookie_provider(Arc::clone(jar));
+        }
+        for pem in &self.root_certificates {
+            let certificate = reqwest::Certificate::from_pem(pem)
+                .map_err(|e| Error::validation(format!("Invalid root certificate: {}", e)))?;
+            builder = builder.add_root_certificate(certificate);
+        }
+
+        Ok(HttpClient {
+            client: builder.build().map_err(Error::Http)?,
+            base_url,
+            cookie_jar: self.cookie_jar,
+            rate_limiter: self.rate_limiter,
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+
+    /// Serve canned HTTP responses on a local port, one per connection
+    ///
+    /// Returns the server's base URL and a handle yielding the raw requests.
+    pub(crate) fn serve(responses: Vec<String>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                requests.push(read_request(&mut stream));
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+        (base, handle)
+    }
+
+    fn read_request(stream: &mut std::net::TcpStream) -> String {
+        let mut data = Vec::new();
+        let mut buffer = [0u8; 1024];
+        loop {
+            let read = stream.read(&mut buffer).unwrap();
+            data.extend_from_slice(&buffer[..read]);
+            let text = String::from_utf8_lossy(&data);
+            if let Some(end) = text.find("\r\n\r\n") {
+                let length = text[..end]
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("content-length")
+                            .then(|| value.trim().parse::<usize>().ok())?
+                    })
+                    .unwrap_or(0);
+                if data.len() >= end + 4 + length || read == 0 {
+                    return String::from_utf8_lossy(&data).into_owned();
+                }
+            } else if read == 0 {
+                return text.into_owned();
+            }
+        }
+    }
+
+    /// A `200 OK` response closing the connection after `body`
+    pub(crate) fn ok_response(content_type: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            content_type,
+            body.len(),
+            body
+        )
+    }
+
+    #[test]
+    fn test_url_resolution() {
+        let client = HttpClient::builder()
+            .base_url("https://api.example.com/v1")
+            .build()
+            .unwrap();
+        assert_eq!(
+            client.url("users/1").unwrap().as_str(),
+            "https://api.example.com/v1/users/1"
+        );
+        assert_eq!(
+            client.url("/users?page=2").unwrap().as_str(),
+            "https://api.example.com/v1/users?page=2"
+        );
+        assert_eq!(
+            client.url("http://other.example.com/x").unwrap().as_str(),
+            "http://other.example.com/x"
+        );
+
+        let no_base = HttpClient::builder().build().unwrap();
+        assert!(no_base.base_url().is_none());
+        assert!(no_base.url("users/1").is_err());
+        assert!(no_base.url("https://example.com").is_ok());
+    }
+
+    #[test]
+    fn test_invalid_configuration() {
+        assert!(
+            HttpClient::builder()
+                .header("bad name", "x")
+                .build()
+                .is_err()
+        );
+        assert!(
+            HttpClient::builder()
+                .header("X-Ok", "bad\nvalue")
+                .build()
+                .is_err()
+        );
+        assert!(HttpClient::builder().base_url("not a url").build().is_err());
+        assert!(
+            HttpClient::builder()
+                .base_url("mailto:me@example.com")
+                .build()
+                .is_err()
+        );
+        assert!(
+            HttpClient::builder()
+                .proxy("::not a proxy::")
+                .build()
+                .is_err()
+        );
+        assert!(
+            HttpClient::builder()
+                .add_root_certificate_pem(b"not a certificate")
+                .build()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_shared_client_is_reused() {
+        assert!(std::ptr::eq(HttpClient::shared(), HttpClient::shared()));
+    }
+
+    #[tokio::test]
+    async fn test_default_headers_and_base_url() {
+        let (base, server) = serve(vec![ok_response("application/json", r#"{"ok":true}"#)]);
+        let client = HttpClient::builder()
+            .base_url(&format!("{}/api", base))
+            .header("X-Api-Key", "secret")
+            .user_agent("rutool-test")
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+
+        let body: serde_json::Value = client.get_json("/status").await.unwrap();
+        assert_eq!(body["ok"], true);
+
+        let request = server.join().unwrap().remove(0);
+        assert!(request.starts_with("GET /api/status HTTP/1.1"));
+        assert!(request.to_ascii_lowercase().contains("x-api-key: secret"));
+        assert!(
+            request
+                .to_ascii_lowercase()
+                .contains("user-agent: rutool-test")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_redirects() {
+        let (base, server) = serve(vec![
+            "HTTP/1.1 302 Found\r\nLocation: /elsewhere\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                .to_string(),
+        ]);
+        let client = HttpClient::builder()
+            .base_url(&base)
+            .no_redirects()
+            .build()
+            .unwrap();
+
+        let response = client.get("start").await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::FOUND);
+        assert_eq!(server.join().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cookie_session_flow() {
+        let (base, server) = serve(vec![
+            "HTTP/1.1 200 OK\r\nSet-Cookie: session=s3cr3t; Path=/; HttpOnly\r\nSet-Cookie: theme=dark\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok"
+                .to_string(),
+            ok_response("application/json", r#"{"user":"alice"}"#),
+            ok_response("application/json", r#"{"user":"alice"}"#),
+        ]);
+        let client = HttpClient::builder()
+            .base_url(&base)
+            .cookie_store(true)
+            .build()
+            .unwrap();
+
+        let mut form = HashMap::new();
+        form.insert("user", "alice");
+        client.post_form("login", &form).await.unwrap();
+
+        let jar = client.cookie_jar().unwrap();
+        let login_url = client.url("login").unwrap();
+        assert_eq!(jar.get(&login_url, "session").as_deref(), Some("s3cr3t"));
+        assert_eq!(jar.cookies().len(), 2);
+
+        let profile: serde_json::Value = client.get_json("me").await.unwrap();
+        assert_eq!(profile["user"], "alice");
+
+        // Manually seeded cookies are sent as well
+        jar.add(&base, "extra=1").unwrap();
+        client.get("me").await.unwrap();
+
+        let requests = server.join().unwrap();
+        assert!(!requests[0].to_ascii_lowercase().contains("\r\ncookie:"));
+        assert!(requests[1].contains("session=s3cr3t"));
+        assert!(requests[1].contains("theme=dark"));
+        assert!(requests[2].contains("extra=1"));
+    }
+
+    #[tokio::test]
+    async fn test_cookies_from_redirect_hops() {
+        let (base, server) = serve(vec![
+            "HTTP/1.1 302 Found\r\nLocation: /home\r\nSet-Cookie: session=s3cr3t; Path=/\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                .to_string(),
+            ok_response("text/plain", "home"),
+        ]);
+        let client = HttpClient::builder()
+            .base_url(&base)
+            .cookie_store(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(client.get_text("login").await.unwrap(), "home");
+        let jar = client.cookie_jar().unwrap();
+        assert_eq!(
+            jar.get(&client.url("home").unwrap(), "session").as_deref(),
+            Some("s3cr3t")
+        );
+
+        // The cookie from the redirect is already sent to its target
+        let requests = server.join().unwrap();
+        assert!(requests[1].starts_with("GET /home HTTP/1.1"));
+        assert!(requests[1].contains("session=s3cr3t"));
+    }
+
+    #[tokio::test]
+    async fn test_graphql_returns_data() {
+        let (base, server) = serve(vec![ok_response(
+            "application/json",
+            r#"{"data":{"user":{"name":"Alice"}}}"#,
+        )]);
+        let client = HttpClient::builder().base_url(&base).build().unwrap();
+        let mut headers = HashMap::new();
+        headers.insert("Authorization", "Bearer token");
+
+        let data: serde_json::Value = client
+            .graphql(
+                "graphql",
+                "query($id: ID!) { user(id: $id) { name } }",
+                &serde_json::json!({ "id": "1" }),
+                Some(&headers),
+            )
+            .await
+            .unwrap();
+        assert_eq!(data["user"]["name"], "Alice");
+
+        let request = server.join().unwrap().remove(0);
+        assert!(request.starts_with("POST /graphql HTTP/1.1"));
+        assert!(
+            request
+                .to_ascii_lowercase()
+                .contains("authorization: bearer token")
+        );
+        let body = &request[request.find("\r\n\r\n").unwrap() + 4..];
+        let envelope: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(
+            envelope["query"],
+            "query($id: ID!) { user(id: $id) { name } }"
+        );
+        assert_eq!(envelope["variables"]["id"], "1");
+    }
+
+    #[tokio::test]
+    async fn test_graphql_errors_are_surfaced() {
+        let (base, server) = serve(vec![
+            ok_response(
+                "application/json",
+                r#"{"data":{"user":null},"errors":[
+                    {"message":"User not found","path":["user",0,"name"]},
+                    {"message":"Rate limited"}
+                ]}"#,
+            ),
+            ok_response("application/json", r#"{"data":null}"#),
+        ]);
+        let client = HttpClient::builder().base_url(&base).build().unwrap();
+        let variables = serde_json::json!({});
+
+        let err = client
+            .graphql::<serde_json::Value>("graphql", "{ user { name } }", &variables, None)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Rutool error: GraphQL request to graphql failed: \
+             User not found (at user.0.name); Rate limited"
+        );
+
+        let err = client
+            .graphql::<serde_json::Value>("graphql", "{ user { name } }", &variables, None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("has no data"));
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cookies_disabled_by_default() {
+        let (base, server) = serve(vec![
+            "HTTP/1.1 200 OK\r\nSet-Cookie: session=1\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                .to_string(),
+            ok_response("text/plain", "ok"),
+        ]);
+        let client = HttpClient::builder().base_url(&base).build().unwrap();
+        assert!(client.cookie_jar().is_none());
+
+        client.get("a").await.unwrap();
+        client.get("b").await.unwrap();
+        let requests = server.join().unwrap();
+        assert!(!requests[1].contains("session=1"));
+    }
+}
@@ -0,0 +1,350 @@
+//! Cookie jar for session-based HTTP APIs
+//!
+//! [`CookieJar`] wraps a [`cookie_store::CookieStore`], which implements the
+//! domain, path, `Secure` and expiry rules of RFC 6265, and plugs into
+//! reqwest's own cookie handling. Because reqwest stores cookies for every
+//! response it receives, cookies set on redirect hops are kept as well. A
+//! `Domain` attribute naming a public suffix such as `com` or `co.uk` is
+//! rejected, so one site cannot set cookies for all others.
+//!
+//! A jar is attached to an [`HttpClient`](crate::http::HttpClient) through
+//! [`HttpClientBuilder::cookie_store`](crate::http::HttpClientBuilder::cookie_store).
+
+use crate::error::{Error, Result};
+use cookie_store::{CookieDomain, CookieExpiration, CookieStore};
+use reqwest::Url;
+use reqwest::header::HeaderValue;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::SystemTime;
+
+/// A stored cookie
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cookie {
+    /// Cookie name
+    pub name: String,
+    /// Cookie value
+    pub value: String,
+    /// Domain the cookie is sent to (without a leading dot)
+    pub domain: String,
+    /// Only sent to exactly `domain`, not its subdomains
+    pub host_only: bool,
+    /// Path prefix the cookie is sent to
+    pub path: String,
+    /// Only sent over HTTPS
+    pub secure: bool,
+    /// Expiry time; `None` for session cookies
+    pub expires: Option<SystemTime>,
+}
+
+impl From<&cookie_store::Cookie<'_>> for Cookie {
+    fn from(cookie: &cookie_store::Cookie<'_>) -> Self {
+        Cookie {
+            name: cookie.name().to_string(),
+            value: cookie.value().to_string(),
+            domain: String::from(&cookie.domain),
+            host_only: matches!(cookie.domain, CookieDomain::HostOnly(_)),
+            path: String::from(&cookie.path),
+            secure: cookie.secure().unwrap_or(false),
+            expires: match cookie.expires {
+                CookieExpiration::AtUtc(at) => Some(at.into()),
+                CookieExpiration::SessionEnd => None,
+            },
+        }
+    }
+}
+
+/// Thread-safe cookie storage shared by the requests of a client
+///
+/// # Examples
+///
+/// ```rust
+/// use yimi_rutool::http::CookieJar;
+///
+/// let jar = CookieJar::new();
+/// jar.add("https://example.com/", "session=abc123; Path=/").unwrap();
+/// assert_eq!(
+///     jar.cookie_header("https://example.com/account").unwrap(),
+///     Some("session=abc123".to_string())
+/// );
+///
+/// // A public suffix cannot be used as the cookie domain
+/// assert!(jar.add("https://example.com/", "a=1; Domain=com").is_err());
+/// ```
+#[derive(Debug, Default)]
+pub struct CookieJar {
+    store: Mutex<CookieStore>,
+}
+
+impl CookieJar {
+    /// Create an empty jar
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Seed the jar with a cookie as if `url` had sent `set_cookie`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `url` is not a valid URL or the cookie is
+    /// malformed or not allowed for that URL's host.
+    pub fn add(&self, url: &str, set_cookie: &str) -> Result<()> {
+        let url = parse_url(url)?;
+        if self.store(set_cookie, &url) {
+            Ok(())
+        } else {
+            Err(Error::validation(format!(
+                "Invalid cookie '{}' for {}",
+                set_cookie, url
+            )))
+        }
+    }
+
+    /// Store a `Set-Cookie` header value received from `url`
+    ///
+    /// Cookies replace earlier ones with the same name, domain and path;
+    /// an already expired cookie removes its earlier version. Returns
+    /// `false` if the cookie was rejected, e.g. because its `Domain`
+    /// attribute does not cover the host of `url` or is a public suffix.
+    pub fn store(&self, set_cookie: &str, url: &Url) -> bool {
+        let Ok(mut cookie) = cookie_store::Cookie::parse(set_cookie, url) else {
+            return false;
+        };
+
+        if let CookieDomain::Suffix(domain) = &cookie.domain
+            && is_public_suffix(domain)
+        {
+            // RFC 6265 section 5.3 step 5: a public suffix is only accepted
+            // as a host-only cookie for that exact host
+            if !url
+                .host_str()
+                .is_some_and(|host| host.eq_ignore_ascii_case(domain))
+            {
+                return false;
+            }
+            match CookieDomain::host_only(url) {
+                Ok(host_only) => cookie.domain = host_only,
+                Err(_) => return false,
+            }
+        }
+
+        self.lock().insert(cookie.into_owned(), url).is_ok()
+    }
+
+    /// Cookies that would be sent with a request to `url`
+    ///
+    /// More specific paths come first, as RFC 6265 recommends.
+    pub fn cookies_for(&self, url: &Url) -> Vec<Cookie> {
+        let mut matching: Vec<Cookie> = self
+            .lock()
+            .matches(url)
+            .into_iter()
+            .map(Cookie::from)
+            .collect();
+        matching.sort_by_key(|cookie| std::cmp::Reverse(cookie.path.len()));
+        matching
+    }
+
+    /// Value of the `Cookie` header for a request to `url`, if any apply
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `url` is not a valid URL.
+    pub fn cookie_header(&self, url: &str) -> Result<Option<String>> {
+        Ok(self.header_for(&parse_url(url)?))
+    }
+
+    fn header_for(&self, url: &Url) -> Option<String> {
+        let cookies = self.cookies_for(url);
+        if cookies.is_empty() {
+            return None;
+        }
+        Some(
+            cookies
+                .iter()
+                .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+                .collect::<Vec<_>>()
+                .join("; "),
+        )
+    }
+
+    /// Value of the cookie called `name` that applies to `url`
+    pub fn get(&self, url: &Url, name: &str) -> Option<String> {
+        self.cookies_for(url)
+            .into_iter()
+            .find(|cookie| cookie.name == name)
+            .map(|cookie| cookie.value)
+    }
+
+    /// All unexpired cookies in the jar
+    pub fn cookies(&self) -> Vec<Cookie> {
+        self.lock().iter_unexpired().map(Cookie::from).collect()
+    }
+
+    /// Remove every cookie
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CookieStore> {
+        // A panic while holding the lock cannot leave the store inconsistent
+        self.store.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl reqwest::cookie::CookieStore for CookieJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        for set_cookie in cookie_headers {
+            if let Ok(set_cookie) = set_cookie.to_str() {
+                self.store(set_cookie, url);
+            }
+        }
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        self.header_for(url)
+            .and_then(|header| HeaderValue::from_str(&header).ok())
+    }
+}
+
+fn parse_url(url: &str) -> Result<Url> {
+    Url::parse(url).map_err(|e| Error::validation(format!("Invalid URL '{}': {}", url, e)))
+}
+
+/// Whether `domain` is a public suffix such as `com` or `co.uk`
+///
+/// Single-label domains are always treated as public suffixes.
+fn is_public_suffix(domain: &str) -> bool {
+    let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+    !domain.contains('.') || psl::suffix_str(&domain) == Some(domain.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(value: &str) -> Url {
+        Url::parse(value).unwrap()
+    }
+
+    #[test]
+    fn test_parse_attributes() {
+        let jar = CookieJar::new();
+        jar.add(
+            "https://www.example.com/app/login",
+            "id=42; Domain=.Example.com; Path=/app; Secure; HttpOnly",
+        )
+        .unwrap();
+        let cookie = jar.cookies().remove(0);
+        assert_eq!(cookie.name, "id");
+        assert_eq!(cookie.value, "42");
+        assert_eq!(cookie.domain, "example.com");
+        assert!(!cookie.host_only);
+        assert_eq!(cookie.path, "/app");
+        assert!(cookie.secure);
+
+        jar.clear();
+        jar.add("http://example.com/docs/page", "a=b").unwrap();
+        let default = jar.cookies().remove(0);
+        assert!(default.host_only);
+        assert_eq!(default.path, "/docs");
+
+        // Another site's domain and nameless cookies are rejected
+        assert!(
+            jar.add("http://example.com/", "a=b; Domain=evil.com")
+                .is_err()
+        );
+        assert!(jar.add("http://example.com/", "=b").is_err());
+        assert!(jar.add("http://example.com/", "novalue").is_err());
+    }
+
+    #[test]
+    fn test_public_suffix_domains_are_rejected() {
+        let jar = CookieJar::new();
+        assert!(jar.add("http://example.com/", "a=1; Domain=com").is_err());
+        assert!(jar.add("http://example.com/", "a=1; Domain=.com").is_err());
+        assert!(
+            jar.add("http://shop.example.co.uk/", "a=1; Domain=co.uk")
+                .is_err()
+        );
+        assert!(jar.cookies().is_empty());
+        assert!(jar.cookies_for(&url("http://other.com/")).is_empty());
+
+        // A registrable domain is still allowed
+        jar.add("http://shop.example.co.uk/", "b=2; Domain=example.co.uk")
+            .unwrap();
+        assert_eq!(
+            jar.get(&url("http://www.example.co.uk/"), "b").as_deref(),
+            Some("2")
+        );
+
+        // A single-label host may only set a host-only cookie for itself
+        jar.add("http://localhost/", "c=3; Domain=localhost")
+            .unwrap();
+        let cookie = jar.get(&url("http://localhost/"), "c");
+        assert_eq!(cookie.as_deref(), Some("3"));
+        assert!(
+            jar.cookies()
+                .iter()
+                .any(|cookie| cookie.name == "c" && cookie.host_only)
+        );
+
+        assert!(is_public_suffix("com"));
+        assert!(is_public_suffix("co.uk"));
+        assert!(is_public_suffix("intranet"));
+        assert!(!is_public_suffix("example.com"));
+    }
+
+    #[test]
+    fn test_matching_rules() {
+        let jar = CookieJar::new();
+        jar.add("http://example.com/", "host=1").unwrap();
+        jar.add("http://example.com/", "wide=2; Domain=example.com")
+            .unwrap();
+        jar.add("http://example.com/", "api=3; Path=/api").unwrap();
+        jar.add("https://example.com/", "secure=4; Secure").unwrap();
+
+        let names = |target: &str| -> Vec<String> {
+            let mut cookies = jar.cookies_for(&url(target));
+            // Cookies with equally long paths have no defined order
+            cookies.sort_by(|a, b| b.path.len().cmp(&a.path.len()).then(a.name.cmp(&b.name)));
+            cookies.into_iter().map(|cookie| cookie.name).collect()
+        };
+        assert_eq!(
+            names("http://example.com/api/users"),
+            ["api", "host", "wide"]
+        );
+        assert_eq!(names("http://sub.example.com/"), ["wide"]);
+        assert_eq!(names("http://example.com/apix"), ["host", "wide"]);
+        assert!(names("https://example.com/").contains(&"secure".to_string()));
+        assert!(jar.cookies_for(&url("http://other.com/")).is_empty());
+    }
+
+    #[test]
+    fn test_replace_and_expire() {
+        let jar = CookieJar::new();
+        jar.add("http://example.com/", "token=old").unwrap();
+        jar.add("http://example.com/", "token=new").unwrap();
+        assert_eq!(
+            jar.get(&url("http://example.com/"), "token").as_deref(),
+            Some("new")
+        );
+        assert_eq!(jar.cookies().len(), 1);
+
+        jar.add("http://example.com/", "token=gone; Max-Age=0")
+            .unwrap();
+        assert!(jar.cookies().is_empty());
+
+        // An already expired cookie is not stored
+        jar.add(
+            "http://example.com/",
+            "old=1; Expires=Thu, 01 Jan 1970 00:00:10 GMT",
+        )
+        .ok();
+        assert!(jar.cookies().is_empty());
+
+        jar.add("http://example.com/", "a=1").unwrap();
+        jar.clear();
+        assert!(jar.cookie_header("http://example.com/").unwrap().is_none());
+        assert!(jar.add("not a url", "a=1").is_err());
+    }
+}
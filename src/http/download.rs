@@ -0,0 +1,471 @@
+//! Streaming file downloads with resume and integrity checks
+//!
+//! Downloads are written to `<path>.part` while in progress and renamed to
+//! the final path once complete (and verified, when a checksum is given).
+//! An interrupted download leaves the `.part` file behind so the next
+//! attempt can resume it with an HTTP `Range` request.
+
+use super::client::HttpClient;
+use crate::crypto::Sha256Hasher;
+use crate::error::{Error, Result};
+use reqwest::{Method, StatusCode, header};
+use std::path::{Path, PathBuf};
+use tokio::fs::{self, File, OpenOptions};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Progress of a running download
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadProgress {
+    /// Bytes of the file present on disk so far, including resumed bytes
+    pub downloaded: u64,
+    /// Full file size, if the server reported it
+    pub total: Option<u64>,
+}
+
+impl DownloadProgress {
+    /// Completed percentage from 0.0 to 100.0, if the total size is known
+    pub fn percentage(&self) -> Option<f64> {
+        #[allow(clippy::cast_precision_loss)]
+        self.total.map(|total| {
+            if total == 0 {
+                100.0
+            } else {
+                self.downloaded as f64 / total as f64 * 100.0
+            }
+        })
+    }
+}
+
+/// Options for [`HttpClient::download`]
+///
+/// # Examples
+///
+/// ```rust
+/// use yimi_rutool::http::DownloadOptions;
+///
+/// let options = DownloadOptions::new()
+///     .expected_sha256("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+/// assert!(options.resume);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadOptions {
+    /// Continue from an existing `.part` file instead of starting over
+    pub resume: bool,
+    /// Hex-encoded SHA-256 the finished file must match
+    pub expected_sha256: Option<String>,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DownloadOptions {
+    /// Options with resuming enabled and no checksum
+    pub fn new() -> Self {
+        Self {
+            resume: true,
+            expected_sha256: None,
+        }
+    }
+
+    /// Set whether an existing `.part` file is resumed
+    pub fn resume(mut self, resume: bool) -> Self {
+        self.resume = resume;
+        self
+    }
+
+    /// Require the finished file to have this hex-encoded SHA-256
+    pub fn expected_sha256(mut self, sha256: &str) -> Self {
+        self.expected_sha256 = Some(sha256.to_string());
+        self
+    }
+}
+
+/// Path of the in-progress file for a download target
+pub fn partial_path(path: &Path) -> PathBuf {
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".part");
+    PathBuf::from(partial)
+}
+
+impl HttpClient {
+    /// Download a URL to a file, streaming the body to disk
+    ///
+    /// The body is written to `<path>.part` chunk by chunk and `progress` is
+    /// called after every chunk. When `options.resume` is set and a `.part`
+    /// file exists, only the remaining bytes are requested. The `.part` file
+    /// is discarded and the download restarted when the server ignores the
+    /// `Range` header, answers with a range starting elsewhere, or reports a
+    /// file size that differs from the `.part` file. If the transfer fails
+    /// midway the `.part` file is kept for a later resume.
+    ///
+    /// With `options.expected_sha256` the completed file is hashed before it
+    /// is moved into place; on a mismatch the `.part` file is deleted, since
+    /// resuming corrupt data would not help.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails, the server responds with an
+    /// error status, writing the file fails or the checksum does not match.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use yimi_rutool::http::{DownloadOptions, HttpClient};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = HttpClient::builder().build()?;
+    ///     client
+    ///         .download(
+    ///             "https://example.com/large.iso",
+    ///             "large.iso",
+    ///             &DownloadOptions::new(),
+    ///             |progress| println!("{:?}%", progress.percentage()),
+    ///         )
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn download<P: AsRef<Path>>(
+        &self,
+        target: &str,
+        path: P,
+        options: &DownloadOptions,
+        mut progress: impl FnMut(DownloadProgress),
+    ) -> Result<()> {
+        let path = path.as_ref();
+        let partial = partial_path(path);
+
+        let mut existing = 0;
+        if options.resume
+            && let Ok(metadata) = fs::metadata(&partial).await
+        {
+            existing = metadata.len();
+        }
+
+        let mut response = self.download_request(target, existing).await?;
+        let mut complete = false;
+        if existing > 0 {
+            match response.status() {
+                StatusCode::PARTIAL_CONTENT if content_range_start(&response) == Some(existing) => {
+                }
+                StatusCode::RANGE_NOT_SATISFIABLE
+                    if unsatisfied_range_total(&response) == Some(existing) =>
+                {
+                    complete = true;
+                }
+                StatusCode::PARTIAL_CONTENT | StatusCode::RANGE_NOT_SATISFIABLE => {
+                    // The server's copy does not line up with the .part file
+                    let _ = fs::remove_file(&partial).await;
+                    existing = 0;
+                    response = self.download_request(target, 0).await?;
+                }
+                _ => {}
+            }
+        }
+
+        let resumed = existing > 0 && response.status() == StatusCode::PARTIAL_CONTENT;
+        if !complete {
+            response = response.error_for_status().map_err(Error::Http)?;
+            let start = if resumed { existing } else { 0 };
+            let total = if resumed {
+                content_range_total(&response).or_else(|| {
+                    response
+                        .content_length()
+                        .map(|remaining| existing + remaining)
+                })
+            } else {
+                response.content_length()
+            };
+
+            let mut file = if resumed {
+                OpenOptions::new().append(true).open(&partial).await?
+            } else {
+                File::create(&partial).await?
+            };
+
+            let mut downloaded = start;
+            progress(DownloadProgress { downloaded, total });
+            while let Some(chunk) = response.chunk().await.map_err(Error::Http)? {
+                file.write_all(&chunk).await?;
+                downloaded += chunk.len() as u64;
+                progress(DownloadProgress { downloaded, total });
+            }
+            file.flush().await?;
+
+            if let Some(total) = total
+                && downloaded != total
+            {
+                return Err(Error::validation(format!(
+                    "Download incomplete: received {} of {} bytes",
+                    downloaded, total
+                )));
+            }
+        }
+
+        if let Some(expected) = &options.expected_sha256 {
+            let actual = sha256_file(&partial).await?;
+            if !actual.eq_ignore_ascii_case(expected.trim()) {
+                let _ = fs::remove_file(&partial).await;
+                return Err(Error::validation(format!(
+                    "SHA-256 mismatch for {}: expected {}, got {}",
+                    path.display(),
+                    expected,
+                    actual
+                )));
+            }
+        }
+
+        fs::rename(&partial, path).await?;
+        Ok(())
+    }
+
+    /// Send the GET for a download, asking for bytes from `offset` onwards
+    async fn download_request(&self, target: &str, offset: u64) -> Result<reqwest::Response> {
+        let mut request = self.request(Method::GET, target)?;
+        if offset > 0 {
+            request = request.header(header::RANGE, format!("bytes={}-", offset));
+        }
+        self.send(request).await
+    }
+}
+
+/// First byte from a `Content-Range: bytes start-end/total` header
+fn content_range_start(response: &reqwest::Response) -> Option<u64> {
+    let value = response
+        .headers()
+        .get(header::CONTENT_RANGE)?
+        .to_str()
+        .ok()?;
+    let range = value.trim().strip_prefix("bytes ")?;
+    range.split_once('-')?.0.trim().parse().ok()
+}
+
+/// Total size from a `Content-Range: bytes */total` header sent with a 416
+fn unsatisfied_range_total(response: &reqwest::Response) -> Option<u64> {
+    let value = response
+        .headers()
+        .get(header::CONTENT_RANGE)?
+        .to_str()
+        .ok()?;
+    value.trim().strip_prefix("bytes */")?.trim().parse().ok()
+}
+
+/// Total size from a `Content-Range: bytes start-end/total` header
+fn content_range_total(response: &reqwest::Response) -> Option<u64> {
+    let value = response
+        .headers()
+        .get(header::CONTENT_RANGE)?
+        .to_str()
+        .ok()?;
+    value.rsplit_once('/')?.1.trim().parse().ok()
+}
+
+async fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = File::open(path).await?;
+    let mut hasher = Sha256Hasher::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer).await?;
+        if read == 0 {
+            return Ok(hasher.finalize_hex());
+        }
+        hasher.update(&buffer[..read]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::ShaUtil;
+    use crate::http::client::tests::{ok_response, serve};
+
+    fn fixture() -> String {
+        (0..40_000)
+            .map(|i| format!("{i:08}\n"))
+            .collect::<Vec<_>>()
+            .concat()
+    }
+
+    fn temp_target(name: &str) -> (tempfile::TempDir, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(name);
+        (dir, path)
+    }
+
+    #[tokio::test]
+    async fn test_download_with_progress_and_checksum() {
+        let body = fixture();
+        let (base, server) = serve(vec![ok_response("application/octet-stream", &body)]);
+        let client = HttpClient::builder().base_url(&base).build().unwrap();
+        let (_dir, target) = temp_target("fixture.txt");
+
+        let mut updates = Vec::new();
+        let options = DownloadOptions::new().expected_sha256(&ShaUtil::sha256_hex(body.as_bytes()));
+        client
+            .download("fixture.txt", &target, &options, |p| updates.push(p))
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), body);
+        assert!(!partial_path(&target).exists());
+        let last = updates.last().unwrap();
+        assert_eq!(last.downloaded, body.len() as u64);
+        assert_eq!(last.total, Some(body.len() as u64));
+        assert!((last.percentage().unwrap() - 100.0).abs() < f64::EPSILON);
+        assert!(
+            updates
+                .windows(2)
+                .all(|w| w[0].downloaded <= w[1].downloaded)
+        );
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_download_resumes_partial_file() {
+        let body = fixture();
+        let (head, tail) = body.split_at(150_000);
+        let response = format!(
+            "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes {}-{}/{}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            head.len(),
+            body.len() - 1,
+            body.len(),
+            tail.len(),
+            tail
+        );
+        let (base, server) = serve(vec![response]);
+        let client = HttpClient::builder().base_url(&base).build().unwrap();
+        let (_dir, target) = temp_target("resumed.txt");
+        std::fs::write(partial_path(&target), head).unwrap();
+
+        let mut first = None;
+        client
+            .download("resumed.txt", &target, &DownloadOptions::new(), |p| {
+                first.get_or_insert(p);
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), body);
+        assert_eq!(first.unwrap().downloaded, head.len() as u64);
+        let request = server.join().unwrap().remove(0).to_ascii_lowercase();
+        assert!(request.contains(&format!("range: bytes={}-", head.len())));
+    }
+
+    #[tokio::test]
+    async fn test_unsatisfiable_range_completes_matching_partial_file() {
+        let body = fixture();
+        let response = format!(
+            "HTTP/1.1 416 Range Not Satisfiable\r\nContent-Range: bytes */{}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            body.len()
+        );
+        let (base, server) = serve(vec![response]);
+        let client = HttpClient::builder().base_url(&base).build().unwrap();
+        let (_dir, target) = temp_target("done.txt");
+        std::fs::write(partial_path(&target), &body).unwrap();
+
+        client
+            .download("done.txt", &target, &DownloadOptions::new(), |_| {})
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), body);
+        assert!(!partial_path(&target).exists());
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unsatisfiable_range_with_other_size_restarts() {
+        let body = fixture();
+        let mismatch = format!(
+            "HTTP/1.1 416 Range Not Satisfiable\r\nContent-Range: bytes */{}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            body.len() / 2
+        );
+        let (base, server) = serve(vec![
+            mismatch,
+            ok_response("application/octet-stream", &body),
+        ]);
+        let client = HttpClient::builder().base_url(&base).build().unwrap();
+        let (_dir, target) = temp_target("changed.txt");
+        std::fs::write(partial_path(&target), "stale bytes from an older version").unwrap();
+
+        client
+            .download("changed.txt", &target, &DownloadOptions::new(), |_| {})
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), body);
+        let requests = server.join().unwrap();
+        assert!(requests[0].to_ascii_lowercase().contains("range: bytes="));
+        assert!(!requests[1].to_ascii_lowercase().contains("range:"));
+    }
+
+    #[tokio::test]
+    async fn test_partial_content_at_wrong_offset_restarts() {
+        let body = fixture();
+        let (head, _) = body.split_at(150_000);
+        let wrong = &body[100_000..100_010];
+        let mismatch = format!(
+            "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes 100000-100009/{}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            wrong.len(),
+            wrong
+        );
+        let (base, server) = serve(vec![
+            mismatch,
+            ok_response("application/octet-stream", &body),
+        ]);
+        let client = HttpClient::builder().base_url(&base).build().unwrap();
+        let (_dir, target) = temp_target("offset.txt");
+        std::fs::write(partial_path(&target), head).unwrap();
+
+        client
+            .download("offset.txt", &target, &DownloadOptions::new(), |_| {})
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), body);
+        let requests = server.join().unwrap();
+        assert!(!requests[1].to_ascii_lowercase().contains("range:"));
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_download_keeps_partial_file() {
+        // Promise more bytes than are sent, then close the connection
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: 1000\r\nConnection: close\r\n\r\n{}",
+            "x".repeat(400)
+        );
+        let (base, server) = serve(vec![response]);
+        let client = HttpClient::builder().base_url(&base).build().unwrap();
+        let (_dir, target) = temp_target("broken.bin");
+
+        let result = client
+            .download("broken.bin", &target, &DownloadOptions::new(), |_| {})
+            .await;
+        assert!(result.is_err());
+        assert!(!target.exists());
+        assert!(partial_path(&target).exists());
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_checksum_mismatch() {
+        let (base, server) = serve(vec![ok_response("text/plain", "tampered")]);
+        let client = HttpClient::builder().base_url(&base).build().unwrap();
+        let (_dir, target) = temp_target("checked.txt");
+
+        let options = DownloadOptions::new().expected_sha256(&ShaUtil::sha256_hex(b"original"));
+        let error = client
+            .download("checked.txt", &target, &options, |_| {})
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("SHA-256 mismatch"));
+        assert!(!target.exists());
+        assert!(!partial_path(&target).exists());
+        server.join().unwrap();
+    }
+}
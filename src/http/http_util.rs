@@ -3,19 +3,40 @@
 //! This module provides comprehensive HTTP client utilities,
 //! inspired by Hutool's HttpUtil.
 
+use super::client::{DEFAULT_TIMEOUT, DEFAULT_USER_AGENT, HttpClient};
+use super::download::{DownloadOptions, DownloadProgress};
+use super::sse::SseEvent;
+#[cfg(feature = "websocket")]
+use super::websocket::WebSocketConn;
 use crate::error::{Error, Result};
+use futures::Stream;
 use reqwest::{Client, Method, Response, StatusCode, Url};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::LazyLock;
 use std::time::Duration;
-use tokio::fs::File;
-use tokio::io::AsyncWriteExt;
+
+static BLOCKING_CLIENT: LazyLock<reqwest::blocking::Client> = LazyLock::new(|| {
+    reqwest::blocking::Client::builder()
+        .timeout(DEFAULT_TIMEOUT)
+        .user_agent(DEFAULT_USER_AGENT)
+        .build()
+        .expect("default blocking HTTP client configuration is valid")
+});
 
 /// HTTP utility functions
+///
+/// The async helpers share one pooled [`HttpClient`] with the default
+/// configuration; build your own [`HttpClient`] for custom timeouts,
+/// headers, base URLs or proxies.
 pub struct HttpUtil;
 
 impl HttpUtil {
-    /// Create a new HTTP client with default configuration
+    /// Get an HTTP client with default configuration
+    ///
+    /// The returned client shares its connection pool with the other
+    /// `HttpUtil` helpers.
     ///
     /// # Examples
     ///
@@ -29,11 +50,7 @@ impl HttpUtil {
     /// }
     /// ```
     pub fn client() -> Client {
-        Client::builder()
-            .timeout(Duration::from_secs(60)) // Increased timeout for network reliability
-            .user_agent("rutool/0.1.0")
-            .build()
-            .unwrap()
+        HttpClient::shared().inner().clone()
     }
 
     /// Create a new HTTP client with custom timeout
@@ -51,11 +68,12 @@ impl HttpUtil {
     /// }
     /// ```
     pub fn client_with_timeout(timeout: Duration) -> Client {
-        Client::builder()
+        HttpClient::builder()
             .timeout(timeout)
-            .user_agent("rutool/0.1.0")
             .build()
             .unwrap()
+            .inner()
+            .clone()
     }
 
     /// Perform a simple GET request
@@ -74,8 +92,7 @@ impl HttpUtil {
     /// }
     /// ```
     pub async fn get(url: &str) -> Result<Response> {
-        let client = Self::client();
-        client.get(url).send().await.map_err(|e| Error::Http(e))
+        HttpClient::shared().get(url).await
     }
 
     /// Perform a GET request and return response as text
@@ -93,12 +110,15 @@ impl HttpUtil {
     /// }
     /// ```
     pub async fn get_text(url: &str) -> Result<String> {
-        let response = Self::get(url).await?;
-        response.text().await.map_err(|e| Error::Http(e))
+        HttpClient::shared().get_text(url).await
     }
 
     /// Perform a GET request and return response as JSON
     ///
+    /// Responses with a non-2xx status fail with
+    /// [`Error::HttpStatus`](crate::Error::HttpStatus) rather than attempting
+    /// to deserialize the error body.
+    ///
     /// # Examples
     ///
     /// ```rust,no_run
@@ -119,8 +139,7 @@ impl HttpUtil {
     /// }
     /// ```
     pub async fn get_json<T: for<'de> Deserialize<'de>>(url: &str) -> Result<T> {
-        let response = Self::get(url).await?;
-        response.json().await.map_err(|e| Error::Http(e))
+        HttpClient::shared().get_json(url).await
     }
 
     /// Perform a simple POST request with JSON body
@@ -140,13 +159,7 @@ impl HttpUtil {
     /// }
     /// ```
     pub async fn post_json<T: Serialize>(url: &str, json: &T) -> Result<Response> {
-        let client = Self::client();
-        client
-            .post(url)
-            .json(json)
-            .send()
-            .await
-            .map_err(|e| Error::Http(e))
+        HttpClient::shared().post_json(url, json).await
     }
 
     /// Perform a POST request with form data
@@ -167,13 +180,7 @@ impl HttpUtil {
     /// }
     /// ```
     pub async fn post_form(url: &str, form: &HashMap<&str, &str>) -> Result<Response> {
-        let client = Self::client();
-        client
-            .post(url)
-            .form(form)
-            .send()
-            .await
-            .map_err(|e| Error::Http(e))
+        HttpClient::shared().post_form(url, form).await
     }
 
     /// Perform a POST request with text body
@@ -191,14 +198,7 @@ impl HttpUtil {
     /// }
     /// ```
     pub async fn post_text(url: &str, text: &str) -> Result<Response> {
-        let client = Self::client();
-        client
-            .post(url)
-            .body(text.to_string())
-            .header("Content-Type", "text/plain")
-            .send()
-            .await
-            .map_err(|e| Error::Http(e))
+        HttpClient::shared().post_text(url, text).await
     }
 
     /// Perform a PUT request with JSON body
@@ -218,13 +218,7 @@ impl HttpUtil {
     /// }
     /// ```
     pub async fn put_json<T: Serialize>(url: &str, json: &T) -> Result<Response> {
-        let client = Self::client();
-        client
-            .put(url)
-            .json(json)
-            .send()
-            .await
-            .map_err(|e| Error::Http(e))
+        HttpClient::shared().put_json(url, json).await
     }
 
     /// Perform a DELETE request
@@ -242,8 +236,7 @@ impl HttpUtil {
     /// }
     /// ```
     pub async fn delete(url: &str) -> Result<Response> {
-        let client = Self::client();
-        client.delete(url).send().await.map_err(|e| Error::Http(e))
+        HttpClient::shared().delete(url).await
     }
 
     /// Perform a PATCH request with JSON body
@@ -263,13 +256,7 @@ impl HttpUtil {
     /// }
     /// ```
     pub async fn patch_json<T: Serialize>(url: &str, json: &T) -> Result<Response> {
-        let client = Self::client();
-        client
-            .patch(url)
-            .json(json)
-            .send()
-            .await
-            .map_err(|e| Error::Http(e))
+        HttpClient::shared().patch_json(url, json).await
     }
 
     /// Perform a HEAD request
@@ -288,8 +275,7 @@ impl HttpUtil {
     /// }
     /// ```
     pub async fn head(url: &str) -> Result<Response> {
-        let client = Self::client();
-        client.head(url).send().await.map_err(|e| Error::Http(e))
+        HttpClient::shared().head(url).await
     }
 
     /// Perform a request with custom method and headers
@@ -323,8 +309,8 @@ impl HttpUtil {
         headers: Option<&HashMap<&str, &str>>,
         body: Option<&T>,
     ) -> Result<Response> {
-        let client = Self::client();
-        let mut request = client.request(method, url);
+        let client = HttpClient::shared();
+        let mut request = client.request(method, url)?;
 
         if let Some(headers) = headers {
             for (key, value) in headers {
@@ -336,11 +322,120 @@ impl HttpUtil {
             request = request.json(body);
         }
 
-        request.send().await.map_err(|e| Error::Http(e))
+        client.send(request).await
+    }
+
+    /// Send a GraphQL query and return the `data` field of the response
+    ///
+    /// An `errors` array in the response becomes an error describing each
+    /// message. See [`HttpClient::graphql`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use yimi_rutool::http::HttpUtil;
+    /// use serde::Deserialize;
+    /// use serde_json::json;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Data {
+    ///     country: Country,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Country {
+    ///     name: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let data: Data = HttpUtil::graphql(
+    ///         "https://countries.trevorblades.com/",
+    ///         "query($code: ID!) { country(code: $code) { name } }",
+    ///         &json!({ "code": "FR" }),
+    ///         None,
+    ///     )
+    ///     .await?;
+    ///     println!("{}", data.country.name);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn graphql<T: DeserializeOwned>(
+        url: &str,
+        query: &str,
+        variables: &serde_json::Value,
+        headers: Option<&HashMap<&str, &str>>,
+    ) -> Result<T> {
+        HttpClient::shared()
+            .graphql(url, query, variables, headers)
+            .await
+    }
+
+    /// Open a server-sent events stream
+    ///
+    /// See [`HttpClient::sse_stream`] for details. The stream is opened on a
+    /// shared client without a total request timeout; it ends with an error
+    /// only if the server sends nothing for 60 seconds. Use an
+    /// [`HttpClient`] built with
+    /// [`read_timeout`](crate::http::HttpClientBuilder::read_timeout) to pick
+    /// a different idle limit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use yimi_rutool::http::HttpUtil;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut events = Box::pin(HttpUtil::sse_stream("https://example.com/events", None));
+    ///     while let Some(event) = events.next().await {
+    ///         println!("{}", event?.data);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn sse_stream(
+        url: &str,
+        headers: Option<&HashMap<&str, &str>>,
+    ) -> impl Stream<Item = Result<SseEvent>> + Send + 'static {
+        HttpClient::shared_streaming().sse_stream(url, headers)
+    }
+
+    /// Open a WebSocket connection
+    ///
+    /// `headers` are sent with the opening handshake. See [`WebSocketConn`]
+    /// for sending and receiving messages and for keepalive behaviour.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use yimi_rutool::http::HttpUtil;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut conn = HttpUtil::websocket_connect("wss://example.com/feed", None).await?;
+    ///     conn.send_text(r#"{"subscribe": "prices"}"#).await?;
+    ///     while let Some(message) = conn.recv().await? {
+    ///         println!("{message:?}");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "websocket")]
+    pub async fn websocket_connect(
+        url: &str,
+        headers: Option<&HashMap<&str, &str>>,
+    ) -> Result<WebSocketConn> {
+        WebSocketConn::connect(url, headers).await
     }
 
     /// Download a file from URL to local path
     ///
+    /// The body is streamed to disk rather than buffered in memory. See
+    /// [`HttpUtil::download_file_with_progress`] for progress reporting,
+    /// resuming and checksum verification.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -357,13 +452,47 @@ impl HttpUtil {
     /// }
     /// ```
     pub async fn download_file(url: &str, path: &str) -> Result<()> {
-        let response = Self::get(url).await?;
-        let bytes = response.bytes().await.map_err(|e| Error::Http(e))?;
-
-        let mut file = File::create(path).await.map_err(|e| Error::Io(e))?;
-        file.write_all(&bytes).await.map_err(|e| Error::Io(e))?;
+        let options = DownloadOptions::new().resume(false);
+        HttpClient::shared_streaming()
+            .download(url, path, &options, |_| {})
+            .await
+    }
 
-        Ok(())
+    /// Download a file with progress reporting, resume and integrity check
+    ///
+    /// Delegates to [`HttpClient::download`] on a shared client that has no
+    /// total timeout, so large files are not cut off; a transfer that stalls
+    /// for 60 seconds fails instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use yimi_rutool::http::{DownloadOptions, HttpUtil};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let options = DownloadOptions::new().expected_sha256(
+    ///         "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+    ///     );
+    ///     HttpUtil::download_file_with_progress(
+    ///         "https://example.com/archive.tar.gz",
+    ///         "/tmp/archive.tar.gz",
+    ///         &options,
+    ///         |progress| println!("{} bytes", progress.downloaded),
+    ///     )
+    ///     .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn download_file_with_progress(
+        url: &str,
+        path: &str,
+        options: &DownloadOptions,
+        progress: impl FnMut(DownloadProgress),
+    ) -> Result<()> {
+        HttpClient::shared_streaming()
+            .download(url, path, options, progress)
+            .await
     }
 
     /// Check if a URL is reachable (returns 2xx status code)
@@ -455,14 +584,14 @@ impl HttpUtil {
     /// }
     /// ```
     pub async fn get_multiple(urls: &[&str]) -> Result<Vec<Response>> {
-        let client = Self::client();
-        let futures: Vec<_> = urls.iter().map(|url| client.get(*url).send()).collect();
+        let client = HttpClient::shared();
+        let futures: Vec<_> = urls.iter().map(|url| client.get(url)).collect();
 
         let results = futures::future::join_all(futures).await;
         let mut responses = Vec::new();
 
         for result in results {
-            responses.push(result.map_err(|e| Error::Http(e))?);
+            responses.push(result?);
         }
 
         Ok(responses)
@@ -596,13 +725,7 @@ impl HttpUtil {
     /// }
     /// ```
     pub fn get_blocking(url: &str) -> Result<reqwest::blocking::Response> {
-        let client = reqwest::blocking::Client::builder()
-            .timeout(Duration::from_secs(60)) // Increased timeout for network reliability
-            .user_agent("rutool/0.1.0")
-            .build()
-            .map_err(|e| Error::Http(e))?;
-
-        client.get(url).send().map_err(|e| Error::Http(e))
+        BLOCKING_CLIENT.get(url).send().map_err(|e| Error::Http(e))
     }
 
     /// Perform a blocking GET request and return response as text
@@ -642,13 +765,7 @@ impl HttpUtil {
         url: &str,
         json: &T,
     ) -> Result<reqwest::blocking::Response> {
-        let client = reqwest::blocking::Client::builder()
-            .timeout(Duration::from_secs(60)) // Increased timeout for network reliability
-            .user_agent("rutool/0.1.0")
-            .build()
-            .map_err(|e| Error::Http(e))?;
-
-        client
+        BLOCKING_CLIENT
             .post(url)
             .json(json)
             .send()
@@ -3,11 +3,31 @@
 //! This module provides comprehensive HTTP client functionality including:
 //! - Simple HTTP requests (GET, POST, PUT, DELETE, etc.)
 //! - Async and blocking HTTP clients
-//! - Request/response handling with headers and cookies
-//! - File upload and download
-//! - JSON and form data support
+//! - Reusable, configurable clients with connection pooling
+//! - Request/response handling with headers and persistent cookies
+//! - File upload and resumable, checksum-verified downloads
+//! - JSON, form data and GraphQL support
+//! - Server-sent event streams
+//! - Per-host rate limiting of outbound requests
+//! - WebSocket client connections (`websocket` feature)
 
+pub mod client;
+pub mod cookie;
+pub mod download;
 pub mod http_util;
+pub mod rate_limit;
+pub mod response;
+pub mod sse;
+#[cfg(feature = "websocket")]
+pub mod websocket;
 
 /// Re-export commonly used types for convenience
+pub use client::{HttpClient, HttpClientBuilder};
+pub use cookie::{Cookie, CookieJar};
+pub use download::{DownloadOptions, DownloadProgress};
 pub use http_util::HttpUtil;
+pub use rate_limit::RateLimiter;
+pub use response::ResponseExt;
+pub use sse::{SseEvent, SseParser};
+#[cfg(feature = "websocket")]
+pub use websocket::{WebSocketConn, WsMessage};
@@ -0,0 +1,237 @@
+//! Token-bucket rate limiting for outbound requests
+//!
+//! A [`RateLimiter`] keeps one token bucket per host. Each request takes a
+//! token; tokens refill at a steady rate up to a burst capacity, so a host
+//! sees at most `burst` requests at once and `rate` requests per second on
+//! average. It is attached to an [`HttpClient`](crate::http::HttpClient)
+//! through
+//! [`HttpClientBuilder::rate_limiter`](crate::http::HttpClientBuilder::rate_limiter).
+
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Steady request rate and burst capacity of a bucket
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Limit {
+    per_second: f64,
+    burst: u32,
+}
+
+impl Limit {
+    fn new(per_second: f64, burst: u32) -> Self {
+        assert!(
+            per_second.is_finite() && per_second > 0.0,
+            "rate limit must be a positive number of requests per second"
+        );
+        Self {
+            per_second,
+            burst: burst.max(1),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    limit: Limit,
+    /// Available tokens; negative when requests are queued for future tokens
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn new(limit: Limit, now: Instant) -> Self {
+        Self {
+            limit,
+            tokens: f64::from(limit.burst),
+            updated: now,
+        }
+    }
+
+    /// Take a token and return how long the caller must wait for it
+    fn reserve(&mut self, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * self.limit.per_second).min(f64::from(self.limit.burst));
+        self.updated = now;
+
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.limit.per_second)
+        }
+    }
+}
+
+/// Per-host token-bucket rate limiter
+///
+/// Hosts without their own limit use the default limit, or are not limited
+/// at all when no default is set. Callers waiting for a token are served in
+/// the order they arrived.
+///
+/// # Examples
+///
+/// ```rust
+/// use yimi_rutool::http::RateLimiter;
+///
+/// // 5 requests per second with bursts of 10 to any host, but only
+/// // 1 per second to the search API
+/// let limiter = RateLimiter::per_second(5.0, 10).with_host_limit("search.example.com", 1.0, 1);
+/// assert!(limiter.is_limited("example.com"));
+/// assert!(!RateLimiter::new().is_limited("example.com"));
+/// ```
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    default_limit: Option<Limit>,
+    host_limits: HashMap<String, Limit>,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl RateLimiter {
+    /// Create a limiter that does not limit any host yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a limiter allowing `per_second` requests with bursts of `burst`
+    /// to every host
+    ///
+    /// # Panics
+    ///
+    /// Panics if `per_second` is not a positive, finite number.
+    pub fn per_second(per_second: f64, burst: u32) -> Self {
+        Self::new().with_default_limit(per_second, burst)
+    }
+
+    /// Set the limit for hosts without a host-specific limit
+    ///
+    /// A `burst` of 0 is treated as 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `per_second` is not a positive, finite number.
+    pub fn with_default_limit(mut self, per_second: f64, burst: u32) -> Self {
+        self.default_limit = Some(Limit::new(per_second, burst));
+        self
+    }
+
+    /// Set the limit for one host, matched case-insensitively and without port
+    ///
+    /// # Panics
+    ///
+    /// Panics if `per_second` is not a positive, finite number.
+    pub fn with_host_limit(mut self, host: &str, per_second: f64, burst: u32) -> Self {
+        self.host_limits
+            .insert(host.to_ascii_lowercase(), Limit::new(per_second, burst));
+        self
+    }
+
+    /// Whether requests to `host` are rate limited
+    pub fn is_limited(&self, host: &str) -> bool {
+        self.limit_for(&host.to_ascii_lowercase()).is_some()
+    }
+
+    /// Wait until a request to `host` may be sent
+    pub async fn acquire(&self, host: &str) {
+        let wait = self.reserve(host, Instant::now());
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    fn limit_for(&self, host: &str) -> Option<Limit> {
+        self.host_limits.get(host).copied().or(self.default_limit)
+    }
+
+    fn reserve(&self, host: &str, now: Instant) -> Duration {
+        let host = host.to_ascii_lowercase();
+        let Some(limit) = self.limit_for(&host) else {
+            return Duration::ZERO;
+        };
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+        buckets
+            .entry(host)
+            .or_insert_with(|| Bucket::new(limit, now))
+            .reserve(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::HttpClient;
+    use crate::http::client::tests::{ok_response, serve};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_bucket_refill_and_burst() {
+        let limiter = RateLimiter::per_second(2.0, 3);
+        let start = Instant::now();
+
+        // The burst is available immediately, then tokens arrive every 500ms
+        for _ in 0..3 {
+            assert_eq!(limiter.reserve("a.example.com", start), Duration::ZERO);
+        }
+        assert_eq!(
+            limiter.reserve("a.example.com", start),
+            Duration::from_millis(500)
+        );
+        assert_eq!(
+            limiter.reserve("a.example.com", start),
+            Duration::from_millis(1000)
+        );
+
+        // Each host has its own bucket
+        assert_eq!(limiter.reserve("b.example.com", start), Duration::ZERO);
+
+        // Tokens never accumulate beyond the burst
+        let later = start + Duration::from_secs(60);
+        for _ in 0..3 {
+            assert_eq!(limiter.reserve("A.example.com", later), Duration::ZERO);
+        }
+        assert!(limiter.reserve("a.example.com", later) > Duration::ZERO);
+    }
+
+    #[test]
+    fn test_host_limits() {
+        let limiter = RateLimiter::new().with_host_limit("API.example.com", 1.0, 1);
+        assert!(limiter.is_limited("api.example.com"));
+        assert!(!limiter.is_limited("other.example.com"));
+
+        let start = Instant::now();
+        assert_eq!(limiter.reserve("api.example.com", start), Duration::ZERO);
+        assert_eq!(
+            limiter.reserve("api.example.com", start),
+            Duration::from_secs(1)
+        );
+        assert_eq!(limiter.reserve("other.example.com", start), Duration::ZERO);
+        assert_eq!(limiter.reserve("other.example.com", start), Duration::ZERO);
+    }
+
+    #[test]
+    #[should_panic(expected = "positive")]
+    fn test_invalid_rate_panics() {
+        let _ = RateLimiter::per_second(0.0, 1);
+    }
+
+    #[tokio::test]
+    async fn test_client_requests_are_throttled() {
+        const REQUESTS: usize = 5;
+        let (base, server) = serve(vec![ok_response("text/plain", "ok"); REQUESTS]);
+        let client = HttpClient::builder()
+            .base_url(&base)
+            .rate_limiter(Arc::new(RateLimiter::per_second(10.0, 2)))
+            .build()
+            .unwrap();
+
+        // 2 requests use the burst, the other 3 wait 100ms each
+        let start = Instant::now();
+        for _ in 0..REQUESTS {
+            client.get("ping").await.unwrap();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(300));
+        assert_eq!(server.join().unwrap().len(), REQUESTS);
+    }
+}
@@ -0,0 +1,171 @@
+//! Status-aware response helpers
+//!
+//! reqwest's own `error_for_status` drops the response body, which usually
+//! holds the only useful explanation of a failure. [`ResponseExt`] keeps a
+//! truncated copy of it in [`Error::HttpStatus`].
+
+use crate::error::{Error, Result};
+use reqwest::Response;
+use serde::de::DeserializeOwned;
+use std::future::Future;
+
+/// Maximum number of body bytes kept in an [`Error::HttpStatus`]
+pub const ERROR_BODY_LIMIT: usize = 512;
+
+/// Extension methods for `reqwest::Response`
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use yimi_rutool::http::{HttpUtil, ResponseExt};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let response = HttpUtil::get("https://api.example.com/users/1").await?;
+///     // A 404 becomes an error carrying the status and body instead of a
+///     // confusing deserialization failure
+///     let user: User = response.json_checked().await?;
+///     println!("{}", user.name);
+///     Ok(())
+/// }
+/// ```
+pub trait ResponseExt: Sized {
+    /// Return the response unchanged if its status is 2xx
+    ///
+    /// Otherwise the body is read and an [`Error::HttpStatus`] holding the
+    /// status, URL and the first [`ERROR_BODY_LIMIT`] bytes of the body is
+    /// returned.
+    fn check_status(self) -> impl Future<Output = Result<Self>> + Send;
+
+    /// Check the status, then deserialize the JSON body
+    fn json_checked<T: DeserializeOwned>(self) -> impl Future<Output = Result<T>> + Send;
+
+    /// Check the status, then return the body as text
+    fn text_checked(self) -> impl Future<Output = Result<String>> + Send;
+}
+
+impl ResponseExt for Response {
+    async fn check_status(self) -> Result<Self> {
+        let status = self.status();
+        if status.is_success() {
+            return Ok(self);
+        }
+
+        let url = self.url().to_string();
+        let body = self.text().await.unwrap_or_default();
+        Err(Error::HttpStatus {
+            status: status.as_u16(),
+            url,
+            body: truncate_body(&body, ERROR_BODY_LIMIT),
+        })
+    }
+
+    async fn json_checked<T: DeserializeOwned>(self) -> Result<T> {
+        let response = self.check_status().await?;
+        response.json().await.map_err(Error::Http)
+    }
+
+    async fn text_checked(self) -> Result<String> {
+        let response = self.check_status().await?;
+        response.text().await.map_err(Error::Http)
+    }
+}
+
+/// Cut a body to at most `limit` bytes on a character boundary
+pub(super) fn truncate_body(body: &str, limit: usize) -> String {
+    let body = body.trim();
+    if body.len() <= limit {
+        return body.to_string();
+    }
+    let mut end = limit;
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}... ({} bytes total)", &body[..end], body.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::HttpClient;
+    use crate::http::client::tests::{ok_response, serve};
+
+    fn error_response(status: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+    }
+
+    #[test]
+    fn test_truncate_body() {
+        assert_eq!(truncate_body("  short  ", 10), "short");
+        let long = "é".repeat(10);
+        let truncated = truncate_body(&long, 5);
+        assert!(truncated.starts_with("éé..."));
+        assert!(truncated.ends_with("(20 bytes total)"));
+    }
+
+    #[tokio::test]
+    async fn test_json_checked_reports_status_and_body() {
+        let (base, server) = serve(vec![
+            error_response("404 Not Found", r#"{"error":"no such user"}"#),
+            ok_response("application/json", r#"{"id":7}"#),
+        ]);
+        let client = HttpClient::builder().base_url(&base).build().unwrap();
+
+        let response = client.get("users/1").await.unwrap();
+        let error = response
+            .json_checked::<serde_json::Value>()
+            .await
+            .unwrap_err();
+        match &error {
+            Error::HttpStatus { status, url, body } => {
+                assert_eq!(*status, 404);
+                assert!(url.ends_with("/users/1"));
+                assert!(body.contains("no such user"));
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+        assert!(error.to_string().contains("404"));
+
+        let value: serde_json::Value = client
+            .get("users/7")
+            .await
+            .unwrap()
+            .json_checked()
+            .await
+            .unwrap();
+        assert_eq!(value["id"], 7);
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_json_checks_status() {
+        let body = "x".repeat(2000);
+        let (base, server) = serve(vec![error_response("500 Internal Server Error", &body)]);
+        let client = HttpClient::builder().base_url(&base).build().unwrap();
+
+        let error = client
+            .get_json::<serde_json::Value>("boom")
+            .await
+            .unwrap_err();
+        match error {
+            Error::HttpStatus { status, body, .. } => {
+                assert_eq!(status, 500);
+                assert!(body.len() < 600);
+                assert!(body.ends_with("(2000 bytes total)"));
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,336 @@
+//! Server-sent events (SSE) streaming
+//!
+//! [`HttpClient::sse_stream`] opens a `text/event-stream` response and yields
+//! each event as it arrives. Parsing follows the WHATWG event-stream format:
+//! multi-line `data:` fields are joined with `\n`, comment lines starting
+//! with `:` are skipped, and the last seen `id:` carries over to later
+//! events.
+//!
+//! The stream ends when the server closes the connection. Reconnecting with a
+//! `Last-Event-ID` header and honouring `retry:` is left to the caller, using
+//! the [`SseEvent::id`] of the last event received.
+
+use super::client::HttpClient;
+use super::response::ResponseExt;
+use crate::error::{Error, Result};
+use futures::Stream;
+use reqwest::{Method, RequestBuilder, Response, header};
+use std::collections::{HashMap, VecDeque};
+
+/// A single server-sent event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseEvent {
+    /// Event type from the `event:` field, `"message"` when not given
+    pub event: String,
+    /// Event payload; multiple `data:` lines are joined with `\n`
+    pub data: String,
+    /// Last event ID seen on the stream, if any
+    pub id: Option<String>,
+}
+
+/// Incremental parser turning `text/event-stream` bytes into [`SseEvent`]s
+///
+/// Bytes may be fed in arbitrary chunks; lines split across chunks are
+/// buffered until complete.
+///
+/// # Examples
+///
+/// ```rust
+/// use yimi_rutool::http::SseParser;
+///
+/// let mut parser = SseParser::new();
+/// assert!(parser.feed(b"event: update\ndata: hel").is_empty());
+///
+/// let events = parser.feed(b"lo\n\n");
+/// assert_eq!(events[0].event, "update");
+/// assert_eq!(events[0].data, "hello");
+/// ```
+#[derive(Debug, Default)]
+pub struct SseParser {
+    buffer: Vec<u8>,
+    skip_newline: bool,
+    started: bool,
+    event: Option<String>,
+    data: String,
+    has_data: bool,
+    last_id: Option<String>,
+}
+
+impl SseParser {
+    /// Create a parser at the start of a stream
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next chunk of the stream and return the events it completes
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        let mut events = Vec::new();
+        for &byte in chunk {
+            // A `\r\n` pair split across chunks ends a single line
+            if std::mem::take(&mut self.skip_newline) && byte == b'\n' {
+                continue;
+            }
+            match byte {
+                b'\r' | b'\n' => {
+                    self.skip_newline = byte == b'\r';
+                    let line = std::mem::take(&mut self.buffer);
+                    if let Some(event) = self.process_line(&line) {
+                        events.push(event);
+                    }
+                }
+                _ => self.buffer.push(byte),
+            }
+        }
+        events
+    }
+
+    fn process_line(&mut self, line: &[u8]) -> Option<SseEvent> {
+        let mut line = String::from_utf8_lossy(line).into_owned();
+        if !self.started {
+            self.started = true;
+            if let Some(stripped) = line.strip_prefix('\u{feff}') {
+                line = stripped.to_string();
+            }
+        }
+
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line.as_str(), ""),
+        };
+        match field {
+            "event" => self.event = Some(value.to_string()),
+            "data" => {
+                if self.has_data {
+                    self.data.push('\n');
+                }
+                self.data.push_str(value);
+                self.has_data = true;
+            }
+            "id" if !value.contains('\0') => self.last_id = Some(value.to_string()),
+            _ => {}
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event = self.event.take();
+        if !std::mem::take(&mut self.has_data) {
+            return None;
+        }
+        Some(SseEvent {
+            event: event
+                .filter(|event| !event.is_empty())
+                .unwrap_or_else(|| "message".to_string()),
+            data: std::mem::take(&mut self.data),
+            id: self.last_id.clone(),
+        })
+    }
+}
+
+struct SseState {
+    client: HttpClient,
+    request: Option<Result<RequestBuilder>>,
+    response: Option<Response>,
+    parser: SseParser,
+    pending: VecDeque<SseEvent>,
+    done: bool,
+}
+
+impl SseState {
+    async fn next(&mut self) -> Option<Result<SseEvent>> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(Ok(event));
+            }
+            if self.done {
+                return None;
+            }
+
+            let response = match self.response.as_mut() {
+                Some(response) => response,
+                None => match self.connect().await {
+                    Ok(response) => self.response.insert(response),
+                    Err(err) => {
+                        self.done = true;
+                        return Some(Err(err));
+                    }
+                },
+            };
+
+            match response.chunk().await {
+                Ok(Some(chunk)) => self.pending.extend(self.parser.feed(&chunk)),
+                Ok(None) => self.done = true,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(Error::Http(err)));
+                }
+            }
+        }
+    }
+
+    async fn connect(&mut self) -> Result<Response> {
+        let request = self
+            .request
+            .take()
+            .unwrap_or_else(|| Err(Error::custom("SSE stream already connected")))?;
+        self.client.send(request).await?.check_status().await
+    }
+}
+
+impl HttpClient {
+    /// Open a server-sent events stream
+    ///
+    /// The request is sent with `Accept: text/event-stream` when the stream
+    /// is first polled. Connection failures and non-2xx responses are
+    /// yielded as the stream's only item; after that, events are yielded as
+    /// the server sends them until it closes the connection.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use yimi_rutool::http::HttpClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = HttpClient::builder().no_timeout().build()?;
+    ///     let mut events = Box::pin(client.sse_stream("https://example.com/events", None));
+    ///     while let Some(event) = events.next().await {
+    ///         let event = event?;
+    ///         println!("{}: {}", event.event, event.data);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn sse_stream(
+        &self,
+        target: &str,
+        headers: Option<&HashMap<&str, &str>>,
+    ) -> impl Stream<Item = Result<SseEvent>> + Send + 'static {
+        let request = self.request(Method::GET, target).map(|mut request| {
+            request = request
+                .header(header::ACCEPT, "text/event-stream")
+                .header(header::CACHE_CONTROL, "no-cache");
+            if let Some(headers) = headers {
+                for (key, value) in headers {
+                    request = request.header(*key, *value);
+                }
+            }
+            request
+        });
+
+        let state = SseState {
+            client: self.clone(),
+            request: Some(request),
+            response: None,
+            parser: SseParser::new(),
+            pending: VecDeque::new(),
+            done: false,
+        };
+        futures::stream::unfold(state, |mut state| async move {
+            let item = state.next().await?;
+            Some((item, state))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::client::tests::{ok_response, serve};
+    use futures::StreamExt;
+
+    const BODY: &str = "\u{feff}: keep-alive comment\n\
+        data: first\n\
+        \n\
+        event: update\r\n\
+        id: 42\r\n\
+        data: line one\r\n\
+        data:line two\r\n\
+        data\r\n\
+        \r\n\
+        : no data, so nothing is dispatched\n\
+        event: ignored\n\
+        \n\
+        data: {\"done\": true}\n\
+        \n\
+        data: incomplete event at end of stream\n";
+
+    fn expected() -> Vec<SseEvent> {
+        vec![
+            SseEvent {
+                event: "message".to_string(),
+                data: "first".to_string(),
+                id: None,
+            },
+            SseEvent {
+                event: "update".to_string(),
+                data: "line one\nline two\n".to_string(),
+                id: Some("42".to_string()),
+            },
+            SseEvent {
+                event: "message".to_string(),
+                data: "{\"done\": true}".to_string(),
+                id: Some("42".to_string()),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_parser_canned_body() {
+        let mut parser = SseParser::new();
+        assert_eq!(parser.feed(BODY.as_bytes()), expected());
+    }
+
+    #[test]
+    fn test_parser_byte_by_byte() {
+        let mut parser = SseParser::new();
+        let events: Vec<SseEvent> = BODY
+            .as_bytes()
+            .iter()
+            .flat_map(|byte| parser.feed(std::slice::from_ref(byte)))
+            .collect();
+        assert_eq!(events, expected());
+    }
+
+    #[tokio::test]
+    async fn test_sse_stream() {
+        let (base, server) = serve(vec![ok_response("text/event-stream", BODY)]);
+        let client = HttpClient::builder().base_url(&base).build().unwrap();
+        let mut headers = HashMap::new();
+        headers.insert("Last-Event-ID", "41");
+
+        let events: Vec<SseEvent> = client
+            .sse_stream("events", Some(&headers))
+            .map(|event| event.unwrap())
+            .collect()
+            .await;
+        assert_eq!(events, expected());
+
+        let request = server.join().unwrap().remove(0).to_ascii_lowercase();
+        assert!(request.starts_with("get /events http/1.1"));
+        assert!(request.contains("accept: text/event-stream"));
+        assert!(request.contains("last-event-id: 41"));
+    }
+
+    #[tokio::test]
+    async fn test_sse_stream_error_status() {
+        let (base, server) = serve(vec![
+            "HTTP/1.1 401 Unauthorized\r\nContent-Length: 6\r\nConnection: close\r\n\r\ndenied"
+                .to_string(),
+        ]);
+        let client = HttpClient::builder().base_url(&base).build().unwrap();
+
+        let items: Vec<Result<SseEvent>> = client.sse_stream("events", None).collect().await;
+        assert_eq!(items.len(), 1);
+        assert!(items[0].as_ref().unwrap_err().to_string().contains("401"));
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,389 @@
+//! WebSocket client connections
+//!
+//! [`WebSocketConn`] wraps a `tokio-tungstenite` client stream with a small
+//! text/binary message API. Control frames are handled internally: pings
+//! from the server are answered while reading, and the client pings the
+//! server every [`DEFAULT_PING_INTERVAL`] so idle connections are kept open
+//! and dead peers are detected.
+//!
+//! Connections are opened with [`HttpUtil::websocket_connect`] or
+//! [`WebSocketConn::connect`]; `wss://` URLs use the same TLS backend as the
+//! HTTP client.
+//!
+//! [`HttpUtil::websocket_connect`]: crate::http::HttpUtil::websocket_connect
+
+use super::response::{ERROR_BODY_LIMIT, truncate_body};
+use crate::error::{Error, Result};
+use futures::{SinkExt, StreamExt};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::time::{Instant, Interval, MissedTickBehavior};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::{HeaderName, HeaderValue};
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+/// How often an idle connection pings the server by default
+pub const DEFAULT_PING_INTERVAL: Duration = Duration::from_secs(30);
+
+/// A data message received from or sent to a WebSocket peer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WsMessage {
+    /// UTF-8 text message
+    Text(String),
+    /// Binary message
+    Binary(Vec<u8>),
+}
+
+/// A client WebSocket connection
+///
+/// Keepalive pings are sent while [`recv`](Self::recv) is waiting for a
+/// message. If the server has not answered the previous ping, or sent
+/// anything else, by the time the next one is due, `recv` fails with a
+/// timeout error.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use yimi_rutool::http::{HttpUtil, WsMessage};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let mut conn = HttpUtil::websocket_connect("wss://echo.example.com", None).await?;
+///     conn.send_text("hello").await?;
+///     if let Some(WsMessage::Text(reply)) = conn.recv().await? {
+///         println!("{reply}");
+///     }
+///     conn.close().await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct WebSocketConn {
+    stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    keepalive: Option<Interval>,
+    awaiting_pong: bool,
+}
+
+impl WebSocketConn {
+    /// Open a WebSocket connection to `url` (`ws://` or `wss://`)
+    ///
+    /// `headers` are added to the opening handshake request, e.g. for
+    /// authentication.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::HttpStatus`] if the server rejects the handshake,
+    /// and a validation error for invalid URLs or headers.
+    pub async fn connect(url: &str, headers: Option<&HashMap<&str, &str>>) -> Result<Self> {
+        let mut request = url
+            .into_client_request()
+            .map_err(|e| Error::validation(format!("Invalid WebSocket URL {url}: {e}")))?;
+        if let Some(headers) = headers {
+            for (key, value) in headers {
+                let name = HeaderName::from_bytes(key.as_bytes())
+                    .map_err(|e| Error::validation(format!("Invalid header name {key}: {e}")))?;
+                let value = HeaderValue::from_str(value).map_err(|e| {
+                    Error::validation(format!("Invalid value for header {key}: {e}"))
+                })?;
+                request.headers_mut().insert(name, value);
+            }
+        }
+
+        // Nagle's algorithm would hold back small frames such as pings
+        let (stream, _) = tokio_tungstenite::connect_async_with_config(request, None, true)
+            .await
+            .map_err(|err| match err {
+                WsError::Http(response) => {
+                    let body = response
+                        .body()
+                        .as_deref()
+                        .map(String::from_utf8_lossy)
+                        .unwrap_or_default();
+                    Error::HttpStatus {
+                        status: response.status().as_u16(),
+                        url: url.to_string(),
+                        body: truncate_body(&body, ERROR_BODY_LIMIT),
+                    }
+                }
+                err => ws_error(err),
+            })?;
+
+        let mut conn = Self {
+            stream,
+            keepalive: None,
+            awaiting_pong: false,
+        };
+        conn.set_ping_interval(Some(DEFAULT_PING_INTERVAL));
+        Ok(conn)
+    }
+
+    /// Change how often keepalive pings are sent, or disable them with `None`
+    pub fn set_ping_interval(&mut self, interval: Option<Duration>) {
+        self.awaiting_pong = false;
+        self.keepalive = interval.map(|period| {
+            let mut keepalive = tokio::time::interval_at(Instant::now() + period, period);
+            keepalive.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            keepalive
+        });
+    }
+
+    /// Current keepalive ping interval, `None` when disabled
+    pub fn ping_interval(&self) -> Option<Duration> {
+        self.keepalive.as_ref().map(Interval::period)
+    }
+
+    /// Send a text message
+    pub async fn send_text(&mut self, text: &str) -> Result<()> {
+        self.send(Message::text(text)).await
+    }
+
+    /// Send a binary message
+    pub async fn send_binary(&mut self, data: &[u8]) -> Result<()> {
+        self.send(Message::binary(data.to_vec())).await
+    }
+
+    /// Wait for the next text or binary message
+    ///
+    /// Returns `Ok(None)` once the server has closed the connection.
+    pub async fn recv(&mut self) -> Result<Option<WsMessage>> {
+        loop {
+            let message = tokio::select! {
+                message = self.stream.next() => message,
+                () = next_tick(&mut self.keepalive) => {
+                    if self.awaiting_pong {
+                        return Err(Error::timeout("WebSocket server did not answer ping"));
+                    }
+                    self.awaiting_pong = true;
+                    self.send(Message::Ping(Vec::new().into())).await?;
+                    continue;
+                }
+            };
+
+            // Any frame shows the server is still alive
+            self.awaiting_pong = false;
+            match message {
+                None | Some(Err(WsError::ConnectionClosed)) => return Ok(None),
+                Some(Err(err)) => return Err(ws_error(err)),
+                Some(Ok(Message::Text(text))) => {
+                    return Ok(Some(WsMessage::Text(text.to_string())));
+                }
+                Some(Ok(Message::Binary(data))) => return Ok(Some(WsMessage::Binary(data.into()))),
+                Some(Ok(Message::Close(_))) => {
+                    // tungstenite queues the close reply; flush it before reporting
+                    let _ = self.stream.flush().await;
+                    return Ok(None);
+                }
+                // Pongs to server pings are sent automatically while reading
+                Some(Ok(Message::Ping(_) | Message::Pong(_) | Message::Frame(_))) => {}
+            }
+        }
+    }
+
+    /// Close the connection, waiting for the server to acknowledge
+    ///
+    /// Messages still arriving before the server's close frame are dropped.
+    pub async fn close(mut self) -> Result<()> {
+        match self.stream.close(None).await {
+            Ok(()) | Err(WsError::ConnectionClosed | WsError::AlreadyClosed) => {}
+            Err(err) => return Err(ws_error(err)),
+        }
+        while let Some(message) = self.stream.next().await {
+            match message {
+                Ok(_) => {}
+                Err(WsError::ConnectionClosed | WsError::AlreadyClosed) => break,
+                Err(err) => return Err(ws_error(err)),
+            }
+        }
+        Ok(())
+    }
+
+    async fn send(&mut self, message: Message) -> Result<()> {
+        self.stream.send(message).await.map_err(ws_error)
+    }
+}
+
+async fn next_tick(keepalive: &mut Option<Interval>) {
+    match keepalive {
+        Some(keepalive) => {
+            keepalive.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+fn ws_error(err: WsError) -> Error {
+    match err {
+        WsError::Io(err) => Error::Io(err),
+        err => Error::custom(format!("WebSocket error: {err}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::HttpUtil;
+    use tokio::net::TcpListener;
+    use tokio::task::JoinHandle;
+    use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+
+    /// Accept one connection and run `handler` on it, returning the URL and
+    /// the headers of the handshake request
+    async fn serve<F, Fut>(handler: F) -> (String, JoinHandle<HashMap<String, String>>)
+    where
+        F: FnOnce(WebSocketStream<TcpStream>) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/socket", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut headers = HashMap::new();
+            let callback = |request: &Request, response: Response| {
+                for (key, value) in request.headers() {
+                    headers.insert(key.to_string(), value.to_str().unwrap().to_string());
+                }
+                Ok(response)
+            };
+            let stream = tokio_tungstenite::accept_hdr_async(socket, callback)
+                .await
+                .unwrap();
+            handler(stream).await;
+            headers
+        });
+        (url, server)
+    }
+
+    async fn echo(mut stream: WebSocketStream<TcpStream>) {
+        while let Some(Ok(message)) = stream.next().await {
+            if message.is_text() || message.is_binary() {
+                stream.send(message).await.unwrap();
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_echo_round_trip() {
+        let (url, server) = serve(echo).await;
+        let mut headers = HashMap::new();
+        headers.insert("Authorization", "Bearer token");
+
+        let mut conn = HttpUtil::websocket_connect(&url, Some(&headers))
+            .await
+            .unwrap();
+        assert_eq!(conn.ping_interval(), Some(DEFAULT_PING_INTERVAL));
+
+        conn.send_text("hello").await.unwrap();
+        assert_eq!(
+            conn.recv().await.unwrap(),
+            Some(WsMessage::Text("hello".to_string()))
+        );
+        conn.send_binary(&[0, 1, 2, 255]).await.unwrap();
+        assert_eq!(
+            conn.recv().await.unwrap(),
+            Some(WsMessage::Binary(vec![0, 1, 2, 255]))
+        );
+        conn.close().await.unwrap();
+
+        let headers = server.await.unwrap();
+        assert_eq!(headers["authorization"], "Bearer token");
+    }
+
+    #[tokio::test]
+    async fn test_server_close_ends_recv() {
+        let (url, server) = serve(|mut stream| async move {
+            stream.send(Message::text("bye")).await.unwrap();
+            stream.close(None).await.unwrap();
+            while stream.next().await.is_some() {}
+        })
+        .await;
+
+        let mut conn = WebSocketConn::connect(&url, None).await.unwrap();
+        assert_eq!(
+            conn.recv().await.unwrap(),
+            Some(WsMessage::Text("bye".to_string()))
+        );
+        assert_eq!(conn.recv().await.unwrap(), None);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_ping_pong() {
+        let (url, server) = serve(|mut stream| async move {
+            // The client answers server pings
+            stream
+                .send(Message::Ping(b"server".to_vec().into()))
+                .await
+                .unwrap();
+            let mut saw_pong = false;
+            let mut client_pinged = false;
+            while let Some(Ok(message)) = stream.next().await {
+                match message {
+                    Message::Pong(data) => saw_pong = data.as_ref() == b"server",
+                    Message::Ping(_) => client_pinged = true,
+                    _ => {}
+                }
+                if saw_pong && client_pinged {
+                    break;
+                }
+            }
+            assert!(saw_pong && client_pinged);
+            stream.send(Message::text("done")).await.unwrap();
+        })
+        .await;
+
+        let mut conn = WebSocketConn::connect(&url, None).await.unwrap();
+        // Keepalive pings from the client are answered by the server too
+        conn.set_ping_interval(Some(Duration::from_millis(100)));
+        assert_eq!(
+            conn.recv().await.unwrap(),
+            Some(WsMessage::Text("done".to_string()))
+        );
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unanswered_ping_times_out() {
+        let (url, server) = serve(|stream| async move {
+            // Never read, so pings are never answered
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            drop(stream);
+        })
+        .await;
+
+        let mut conn = WebSocketConn::connect(&url, None).await.unwrap();
+        conn.set_ping_interval(Some(Duration::from_millis(20)));
+        let err = conn.recv().await.unwrap_err();
+        assert_eq!(err.kind(), crate::error::ErrorKind::Timeout);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_connect_errors() {
+        assert!(WebSocketConn::connect("not a url", None).await.is_err());
+
+        let mut headers = HashMap::new();
+        headers.insert("Bad Header", "value");
+        assert!(
+            WebSocketConn::connect("ws://127.0.0.1:1/", Some(&headers))
+                .await
+                .is_err()
+        );
+
+        // A plain HTTP server rejects the upgrade
+        let (base, server) = crate::http::client::tests::serve(vec![
+            "HTTP/1.1 403 Forbidden\r\nContent-Length: 6\r\nConnection: close\r\n\r\ndenied"
+                .to_string(),
+        ]);
+        let url = base.replacen("http://", "ws://", 1);
+        match WebSocketConn::connect(&url, None).await.unwrap_err() {
+            Error::HttpStatus { status, body, .. } => {
+                assert_eq!(status, 403);
+                assert_eq!(body, "denied");
+            }
+            err => panic!("unexpected error: {err}"),
+        }
+        server.join().unwrap();
+    }
+}
@@ -6,7 +6,44 @@
 use crate::error::{Error, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+
+/// How arrays are combined when merging JSON values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArrayMergeStrategy {
+    /// The overlay array replaces the base array
+    #[default]
+    Replace,
+    /// Overlay elements are appended to the base array
+    Concat,
+    /// Overlay elements are appended only if not already present in the base array
+    Union,
+}
+
+/// Options controlling [`JsonUtil::merge_with`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MergeOptions {
+    /// How arrays present in both values are combined
+    pub array_strategy: ArrayMergeStrategy,
+    /// Whether a `null` in the overlay removes the key from the base
+    pub null_deletes: bool,
+}
+
+/// Intermediate tree used while rebuilding values in [`JsonUtil::unflatten`]
+enum FlatNode {
+    Leaf(String),
+    Branch(BTreeMap<String, FlatNode>),
+}
+
+/// Whether the integer part of a number-like string has a leading zero,
+/// as in `"007"`; `"0"` and `"0.5"` do not
+fn has_leading_zero(raw: &str) -> bool {
+    let digits = raw.strip_prefix('-').unwrap_or(raw);
+    let integer = digits.split(['.', 'e', 'E']).next().unwrap_or(digits);
+    integer.len() > 1 && integer.starts_with('0') && integer.bytes().all(|b| b.is_ascii_digit())
+}
 
 /// JSON utility functions
 pub struct JsonUtil;
@@ -178,6 +215,38 @@ impl JsonUtil {
             .map_err(|e| Error::conversion(format!("JSON prettify failed: {}", e)))
     }
 
+    /// Serialize a value in canonical form for hashing and signing
+    ///
+    /// Follows the JSON Canonicalization Scheme (RFC 8785): object keys are
+    /// sorted by their UTF-16 code units, there is no whitespace, strings
+    /// use the minimal escaping and numbers are written in the shortest
+    /// form that round-trips, as JavaScript would print them. Equal
+    /// documents therefore produce identical bytes regardless of key order
+    /// or formatting.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for integers that cannot be represented exactly as
+    /// a 64-bit float, since JCS numbers are IEEE 754 doubles.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::json::JsonUtil;
+    /// use serde_json::json;
+    ///
+    /// let value = json!({"b": [1.0, 1e-7, "x"], "a": {"z": null, "y": true}});
+    /// assert_eq!(
+    ///     JsonUtil::to_canonical_string(&value).unwrap(),
+    ///     r#"{"a":{"y":true,"z":null},"b":[1,1e-7,"x"]}"#
+    /// );
+    /// ```
+    pub fn to_canonical_string(value: &Value) -> Result<String> {
+        let mut out = String::new();
+        write_canonical(value, &mut out)?;
+        Ok(out)
+    }
+
     /// Get value by JSON path (simplified dot notation)
     ///
     /// # Examples
@@ -291,6 +360,122 @@ impl JsonUtil {
         Ok(())
     }
 
+    /// Get value by JSON pointer (RFC 6901)
+    ///
+    /// Unlike [`get_by_path`](Self::get_by_path), every reference token is
+    /// delimited by `/`, with `~1` standing for a literal `/` and `~0` for a
+    /// literal `~`, so keys containing dots or slashes can be addressed.
+    /// The empty pointer refers to the whole document. Returns `None` when
+    /// the target does not exist or the pointer is malformed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::json::JsonUtil;
+    /// use serde_json::json;
+    ///
+    /// let value = json!({"user": {"name": "Alice", "a.b": 1, "c/d": [10, 20]}});
+    ///
+    /// assert_eq!(JsonUtil::get_pointer(&value, "/user/name").unwrap(), "Alice");
+    /// assert_eq!(JsonUtil::get_pointer(&value, "/user/a.b").unwrap(), 1);
+    /// assert_eq!(JsonUtil::get_pointer(&value, "/user/c~1d/1").unwrap(), 20);
+    /// assert!(JsonUtil::get_pointer(&value, "/user/missing").is_none());
+    /// ```
+    pub fn get_pointer<'a>(value: &'a Value, pointer: &str) -> Option<&'a Value> {
+        let tokens = parse_pointer(pointer).ok()?;
+        let mut current = value;
+
+        for token in &tokens {
+            current = match current {
+                Value::Object(map) => map.get(token.as_ref())?,
+                Value::Array(arr) => arr.get(parse_array_index(token)?)?,
+                _ => return None,
+            };
+        }
+
+        Some(current)
+    }
+
+    /// Set value by JSON pointer (RFC 6901)
+    ///
+    /// The parent of the target must already exist. Object members are
+    /// inserted or replaced; array elements are replaced in place, and the
+    /// index equal to the array length (or `-`) appends. The empty pointer
+    /// replaces the whole document.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pointer is malformed, the parent does not
+    /// exist, or an array index is invalid or out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::json::JsonUtil;
+    /// use serde_json::json;
+    ///
+    /// let mut value = json!({"user": {"tags": ["a"]}});
+    /// JsonUtil::set_pointer(&mut value, "/user/name", json!("Alice")).unwrap();
+    /// JsonUtil::set_pointer(&mut value, "/user/tags/-", json!("b")).unwrap();
+    /// JsonUtil::set_pointer(&mut value, "/user/m~0n", json!(true)).unwrap();
+    ///
+    /// assert_eq!(value, json!({"user": {"name": "Alice", "tags": ["a", "b"], "m~n": true}}));
+    /// ```
+    pub fn set_pointer(value: &mut Value, pointer: &str, new_value: Value) -> Result<()> {
+        let mut tokens = parse_pointer(pointer)?;
+        let Some(last) = tokens.pop() else {
+            *value = new_value;
+            return Ok(());
+        };
+
+        let mut current = value;
+        for token in &tokens {
+            current = match current {
+                Value::Object(map) => map
+                    .get_mut(token.as_ref())
+                    .ok_or_else(|| Error::not_found(format!("Path not found: {}", token)))?,
+                Value::Array(arr) => {
+                    let index = parse_array_index(token)
+                        .ok_or_else(|| Error::validation("Invalid array index".to_string()))?;
+                    arr.get_mut(index).ok_or_else(|| {
+                        Error::not_found(format!("Array index not found: {}", index))
+                    })?
+                }
+                _ => {
+                    return Err(Error::validation(
+                        "Cannot navigate through non-object/array".to_string(),
+                    ));
+                }
+            };
+        }
+
+        match current {
+            Value::Object(map) => {
+                map.insert(last.into_owned(), new_value);
+                Ok(())
+            }
+            Value::Array(arr) => {
+                let index = if last == "-" {
+                    arr.len()
+                } else {
+                    parse_array_index(&last)
+                        .ok_or_else(|| Error::validation("Invalid array index".to_string()))?
+                };
+                match index.cmp(&arr.len()) {
+                    std::cmp::Ordering::Less => arr[index] = new_value,
+                    std::cmp::Ordering::Equal => arr.push(new_value),
+                    std::cmp::Ordering::Greater => {
+                        return Err(Error::validation("Array index out of bounds".to_string()));
+                    }
+                }
+                Ok(())
+            }
+            _ => Err(Error::validation(
+                "Cannot set value on non-object/array".to_string(),
+            )),
+        }
+    }
+
     /// Remove value by JSON path
     ///
     /// # Examples
@@ -382,16 +567,60 @@ impl JsonUtil {
     /// assert_eq!(base["e"], 4);
     /// ```
     pub fn merge(base: &mut Value, overlay: &Value) {
+        Self::merge_with(base, overlay, MergeOptions::default());
+    }
+
+    /// Merge two JSON values with configurable array and null handling
+    ///
+    /// Objects are always merged recursively. Arrays are combined according to
+    /// [`MergeOptions::array_strategy`], and when [`MergeOptions::null_deletes`]
+    /// is set, a `null` in the overlay removes the corresponding key from the base.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::json::{ArrayMergeStrategy, JsonUtil, MergeOptions};
+    /// use serde_json::json;
+    ///
+    /// let mut base = json!({"tags": ["a", "b"], "debug": true});
+    /// let overlay = json!({"tags": ["b", "c"], "debug": null});
+    ///
+    /// let opts = MergeOptions {
+    ///     array_strategy: ArrayMergeStrategy::Union,
+    ///     null_deletes: true,
+    /// };
+    /// JsonUtil::merge_with(&mut base, &overlay, opts);
+    ///
+    /// assert_eq!(base, json!({"tags": ["a", "b", "c"]}));
+    /// ```
+    pub fn merge_with(base: &mut Value, overlay: &Value, opts: MergeOptions) {
         match (base, overlay) {
             (Value::Object(base_map), Value::Object(overlay_map)) => {
                 for (key, value) in overlay_map {
-                    if base_map.contains_key(key) {
-                        Self::merge(base_map.get_mut(key).unwrap(), value);
+                    if value.is_null() && opts.null_deletes {
+                        base_map.remove(key);
+                    } else if let Some(existing) = base_map.get_mut(key) {
+                        Self::merge_with(existing, value, opts);
                     } else {
                         base_map.insert(key.clone(), value.clone());
                     }
                 }
             }
+            (Value::Array(base_arr), Value::Array(overlay_arr)) => match opts.array_strategy {
+                ArrayMergeStrategy::Replace => {
+                    base_arr.clone_from(overlay_arr);
+                }
+                ArrayMergeStrategy::Concat => {
+                    base_arr.extend(overlay_arr.iter().cloned());
+                }
+                ArrayMergeStrategy::Union => {
+                    for item in overlay_arr {
+                        if !base_arr.contains(item) {
+                            base_arr.push(item.clone());
+                        }
+                    }
+                }
+            },
             (base_value, overlay_value) => {
                 *base_value = overlay_value.clone();
             }
@@ -454,6 +683,155 @@ impl JsonUtil {
         }
     }
 
+    /// Rebuild a nested JSON value from a flattened map (inverse of `to_flat_map`)
+    ///
+    /// Keys are split on `.`; a group of keys whose segments are the contiguous
+    /// indices `0..n` becomes an array, anything else becomes an object. Leaf
+    /// values that look like `null`, booleans or numbers are parsed back into
+    /// the matching JSON type. Use [`JsonUtil::unflatten_with`] to keep every
+    /// leaf as a string instead.
+    ///
+    /// Returns a validation error when keys conflict, e.g. both `a` and `a.b`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::json::JsonUtil;
+    /// use serde_json::json;
+    ///
+    /// let value = json!({"user": {"name": "Alice", "age": 30, "tags": ["a", "b"]}});
+    /// let map = JsonUtil::to_flat_map(&value);
+    ///
+    /// let rebuilt = JsonUtil::unflatten(&map).unwrap();
+    /// assert_eq!(rebuilt, value);
+    /// ```
+    pub fn unflatten(map: &HashMap<String, String>) -> Result<Value> {
+        Self::unflatten_with(map, true)
+    }
+
+    /// Rebuild a nested JSON value from a flattened map
+    ///
+    /// When `infer_types` is `false`, every leaf is kept as a JSON string,
+    /// which avoids turning values such as `"42"` or `"true"` into numbers or
+    /// booleans. Numbers with leading zeros such as `"007"` are kept as
+    /// strings either way. See [`JsonUtil::unflatten`] for the key rules.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::json::JsonUtil;
+    /// use serde_json::json;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// map.insert("user.age".to_string(), "30".to_string());
+    ///
+    /// let typed = JsonUtil::unflatten_with(&map, true).unwrap();
+    /// assert_eq!(typed, json!({"user": {"age": 30}}));
+    ///
+    /// let raw = JsonUtil::unflatten_with(&map, false).unwrap();
+    /// assert_eq!(raw, json!({"user": {"age": "30"}}));
+    /// ```
+    pub fn unflatten_with(map: &HashMap<String, String>, infer_types: bool) -> Result<Value> {
+        if let Some(root) = map.get("") {
+            if map.len() > 1 {
+                return Err(Error::validation(
+                    "Conflicting flattened keys: root value mixed with nested keys".to_string(),
+                ));
+            }
+            return Ok(Self::unflatten_leaf(root, infer_types));
+        }
+
+        let mut root = BTreeMap::new();
+        for (key, raw) in map {
+            let parts: Vec<&str> = key.split('.').collect();
+            let mut current = &mut root;
+
+            for (i, part) in parts.iter().enumerate() {
+                if i == parts.len() - 1 {
+                    if current.contains_key(*part) {
+                        return Err(Error::validation(format!(
+                            "Conflicting flattened keys at '{key}'"
+                        )));
+                    }
+                    current.insert((*part).to_string(), FlatNode::Leaf(raw.clone()));
+                    break;
+                }
+
+                let node = current
+                    .entry((*part).to_string())
+                    .or_insert_with(|| FlatNode::Branch(BTreeMap::new()));
+                match node {
+                    FlatNode::Branch(children) => current = children,
+                    FlatNode::Leaf(_) => {
+                        return Err(Error::validation(format!(
+                            "Conflicting flattened keys at '{}'",
+                            parts[..=i].join(".")
+                        )));
+                    }
+                }
+            }
+        }
+
+        Ok(Self::build_unflattened(root, infer_types))
+    }
+
+    fn build_unflattened(children: BTreeMap<String, FlatNode>, infer_types: bool) -> Value {
+        let mut indices: Vec<usize> = children
+            .keys()
+            .filter_map(|k| k.parse::<usize>().ok())
+            .collect();
+        indices.sort_unstable();
+        let is_array =
+            indices.len() == children.len() && indices.iter().enumerate().all(|(i, &idx)| i == idx);
+
+        let convert = |node: FlatNode| match node {
+            FlatNode::Leaf(raw) => Self::unflatten_leaf(&raw, infer_types),
+            FlatNode::Branch(nested) => Self::build_unflattened(nested, infer_types),
+        };
+
+        if is_array {
+            let mut items: Vec<(usize, FlatNode)> = children
+                .into_iter()
+                .map(|(k, v)| (k.parse::<usize>().unwrap_or_default(), v))
+                .collect();
+            items.sort_by_key(|(idx, _)| *idx);
+            Value::Array(items.into_iter().map(|(_, v)| convert(v)).collect())
+        } else {
+            Value::Object(children.into_iter().map(|(k, v)| (k, convert(v))).collect())
+        }
+    }
+
+    pub(crate) fn unflatten_leaf(raw: &str, infer_types: bool) -> Value {
+        if !infer_types {
+            return Value::String(raw.to_string());
+        }
+
+        match raw {
+            "null" => Value::Null,
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            // Leading zeros mark zip codes and IDs, not numbers
+            _ if has_leading_zero(raw) => Value::String(raw.to_string()),
+            _ => {
+                if let Ok(n) = raw.parse::<i64>() {
+                    Value::from(n)
+                } else if let Ok(n) = raw.parse::<u64>() {
+                    Value::from(n)
+                } else if let Some(n) = raw
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|_| raw.contains(['.', 'e', 'E']))
+                    .and_then(serde_json::Number::from_f64)
+                {
+                    Value::Number(n)
+                } else {
+                    Value::String(raw.to_string())
+                }
+            }
+        }
+    }
+
     /// Create JSON object from key-value pairs
     ///
     /// # Examples
@@ -587,6 +965,192 @@ impl JsonUtil {
     }
 }
 
+fn write_canonical(value: &Value, out: &mut String) -> Result<()> {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(number) => out.push_str(&canonical_number(number)?),
+        Value::String(text) => write_canonical_string(text, out),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out)?;
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical_string(key, out);
+                out.push(':');
+                write_canonical(item, out)?;
+            }
+            out.push('}');
+        }
+    }
+    Ok(())
+}
+
+/// Write a string with JCS escaping: only `"`, `\\` and control characters
+fn write_canonical_string(text: &str, out: &mut String) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{08}' => out.push_str("\\b"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\u{0C}' => out.push_str("\\f"),
+            '\r' => out.push_str("\\r"),
+            c if c < ' ' => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Format a number the way ECMAScript's `Number.prototype.toString` does
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+fn canonical_number(number: &serde_json::Number) -> Result<String> {
+    let float = if let Some(int) = number.as_i64() {
+        let float = int as f64;
+        if float as i64 != int || float == 9_223_372_036_854_775_808.0 {
+            return Err(Error::conversion(format!(
+                "Integer {} cannot be represented exactly in canonical JSON",
+                int
+            )));
+        }
+        float
+    } else if let Some(int) = number.as_u64() {
+        let float = int as f64;
+        if float >= 18_446_744_073_709_551_616.0 || float as u64 != int {
+            return Err(Error::conversion(format!(
+                "Integer {} cannot be represented exactly in canonical JSON",
+                int
+            )));
+        }
+        float
+    } else {
+        number
+            .as_f64()
+            .ok_or_else(|| Error::conversion(format!("Unsupported number {}", number)))?
+    };
+
+    if float == 0.0 {
+        return Ok("0".to_string());
+    }
+
+    // Shortest round-trip digits and exponent, e.g. "1.2345e-7"
+    let scientific = format!("{:e}", float.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let digits: String = mantissa.chars().filter(char::is_ascii_digit).collect();
+    let exponent: i32 = exponent.parse().unwrap_or(0);
+    let k = digits.len() as i32;
+    // The value is 0.DIGITS x 10^n
+    let n = exponent + 1;
+
+    let mut out = String::new();
+    if float < 0.0 {
+        out.push('-');
+    }
+    if k <= n && n <= 21 {
+        out.push_str(&digits);
+        out.push_str(&"0".repeat((n - k) as usize));
+    } else if 0 < n && n <= 21 {
+        out.push_str(&digits[..n as usize]);
+        out.push('.');
+        out.push_str(&digits[n as usize..]);
+    } else if -6 < n && n <= 0 {
+        out.push_str("0.");
+        out.push_str(&"0".repeat((-n) as usize));
+        out.push_str(&digits);
+    } else {
+        out.push_str(&digits[..1]);
+        if k > 1 {
+            out.push('.');
+            out.push_str(&digits[1..]);
+        }
+        out.push('e');
+        out.push(if n > 0 { '+' } else { '-' });
+        out.push_str(&(n - 1).abs().to_string());
+    }
+    Ok(out)
+}
+
+/// Escape an object key for use as a JSON pointer reference token (RFC 6901)
+pub(crate) fn escape_pointer_token(token: &str) -> Cow<'_, str> {
+    if token.contains(['~', '/']) {
+        Cow::Owned(token.replace('~', "~0").replace('/', "~1"))
+    } else {
+        Cow::Borrowed(token)
+    }
+}
+
+/// Unescape a JSON pointer reference token (RFC 6901)
+///
+/// `~1` is decoded before `~0` so that `~01` yields `~1` rather than `/`.
+pub(crate) fn unescape_pointer_token(token: &str) -> Result<Cow<'_, str>> {
+    if !token.contains('~') {
+        return Ok(Cow::Borrowed(token));
+    }
+
+    let mut out = String::with_capacity(token.len());
+    let mut chars = token.chars();
+    while let Some(c) = chars.next() {
+        if c == '~' {
+            match chars.next() {
+                Some('0') => out.push('~'),
+                Some('1') => out.push('/'),
+                _ => {
+                    return Err(Error::validation(format!(
+                        "Invalid escape sequence in JSON pointer token: {}",
+                        token
+                    )));
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    Ok(Cow::Owned(out))
+}
+
+/// Split a JSON pointer into its unescaped reference tokens
+fn parse_pointer(pointer: &str) -> Result<Vec<Cow<'_, str>>> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(rest) = pointer.strip_prefix('/') else {
+        return Err(Error::validation(format!(
+            "JSON pointer must be empty or start with '/': {}",
+            pointer
+        )));
+    };
+    rest.split('/').map(unescape_pointer_token).collect()
+}
+
+/// Parse an array index token, rejecting leading zeros and signs
+fn parse_array_index(token: &str) -> Option<usize> {
+    if token.is_empty()
+        || !token.bytes().all(|b| b.is_ascii_digit())
+        || (token.len() > 1 && token.starts_with('0'))
+    {
+        return None;
+    }
+    token.parse().ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -661,6 +1225,127 @@ mod tests {
         assert!(prettified.contains("  \"name\": \"Alice\""));
     }
 
+    #[test]
+    fn test_canonical_key_order_independent() {
+        let a =
+            JsonUtil::parse(r#"{"b": 2, "a": {"y": [3, {"k": 1, "j": 2}], "x": "s"}, "c": null}"#)
+                .unwrap();
+        let b = JsonUtil::parse(
+            r#"{
+                "c": null,
+                "a": {"x": "s", "y": [3, {"j": 2, "k": 1}]},
+                "b": 2.0
+            }"#,
+        )
+        .unwrap();
+
+        let canonical = JsonUtil::to_canonical_string(&a).unwrap();
+        assert_eq!(canonical, JsonUtil::to_canonical_string(&b).unwrap());
+        assert_eq!(
+            canonical,
+            r#"{"a":{"x":"s","y":[3,{"j":2,"k":1}]},"b":2,"c":null}"#
+        );
+    }
+
+    #[test]
+    fn test_canonical_numbers() {
+        let cases = [
+            (json!(0), "0"),
+            (json!(-0.0), "0"),
+            (json!(1.5), "1.5"),
+            (json!(-42), "-42"),
+            (json!(100.0), "100"),
+            (json!(0.000_001), "0.000001"),
+            (json!(0.000_000_1), "1e-7"),
+            (json!(123_456_789_012.5), "123456789012.5"),
+            (json!(1e20), "100000000000000000000"),
+            (json!(1e21), "1e+21"),
+            (json!(-1.5e300), "-1.5e+300"),
+            (json!(5e-324), "5e-324"),
+            (json!(0.1 + 0.2), "0.30000000000000004"),
+            (json!(9_007_199_254_740_992_u64), "9007199254740992"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(JsonUtil::to_canonical_string(&value).unwrap(), expected);
+        }
+        assert!(JsonUtil::to_canonical_string(&json!(9_007_199_254_740_993_u64)).is_err());
+        assert!(JsonUtil::to_canonical_string(&json!(i64::MAX)).is_err());
+    }
+
+    #[test]
+    fn test_canonical_strings() {
+        let value = json!({"\u{20ac}": "tab\there \"quoted\" \\ /\u{1}\u{7f}", "\u{1f600}": 1, "\u{ff61}": 2});
+        // U+1F600 sorts before U+FF61 by UTF-16 code units (0xD83D < 0xFF61)
+        assert_eq!(
+            JsonUtil::to_canonical_string(&value).unwrap(),
+            "{\"\u{20ac}\":\"tab\\there \\\"quoted\\\" \\\\ /\\u0001\u{7f}\",\"\u{1f600}\":1,\"\u{ff61}\":2}"
+        );
+    }
+
+    #[test]
+    fn test_pointer_get() {
+        let value = json!({
+            "a/b": 1,
+            "m~n": 2,
+            "x.y": {"": 3, "~1": 4},
+            "list": [10, 20, {"k": 30}]
+        });
+
+        assert_eq!(JsonUtil::get_pointer(&value, ""), Some(&value));
+        assert_eq!(JsonUtil::get_pointer(&value, "/a~1b"), Some(&json!(1)));
+        assert_eq!(JsonUtil::get_pointer(&value, "/m~0n"), Some(&json!(2)));
+        assert_eq!(JsonUtil::get_pointer(&value, "/x.y/"), Some(&json!(3)));
+        assert_eq!(JsonUtil::get_pointer(&value, "/x.y/~01"), Some(&json!(4)));
+        assert_eq!(JsonUtil::get_pointer(&value, "/list/2/k"), Some(&json!(30)));
+
+        // The dot-notation API treats these keys differently
+        assert!(JsonUtil::get_by_path(&value, "x.y").is_none());
+
+        assert!(JsonUtil::get_pointer(&value, "/a/b").is_none());
+        assert!(JsonUtil::get_pointer(&value, "/list/01").is_none());
+        assert!(JsonUtil::get_pointer(&value, "/list/3").is_none());
+        assert!(JsonUtil::get_pointer(&value, "/list/-").is_none());
+        assert!(JsonUtil::get_pointer(&value, "/m~2n").is_none());
+        assert!(JsonUtil::get_pointer(&value, "a~1b").is_none());
+    }
+
+    #[test]
+    fn test_pointer_set() {
+        let mut value = json!({"list": [1], "obj": {}});
+
+        JsonUtil::set_pointer(&mut value, "/obj/a~1b", json!("slash")).unwrap();
+        JsonUtil::set_pointer(&mut value, "/obj/c~0d", json!("tilde")).unwrap();
+        JsonUtil::set_pointer(&mut value, "/list/0", json!(2)).unwrap();
+        JsonUtil::set_pointer(&mut value, "/list/1", json!(3)).unwrap();
+        JsonUtil::set_pointer(&mut value, "/list/-", json!(4)).unwrap();
+        assert_eq!(
+            value,
+            json!({"list": [2, 3, 4], "obj": {"a/b": "slash", "c~d": "tilde"}})
+        );
+        assert_eq!(
+            JsonUtil::get_pointer(&value, "/obj/a~1b"),
+            Some(&json!("slash"))
+        );
+
+        assert!(JsonUtil::set_pointer(&mut value, "/list/5", json!(0)).is_err());
+        assert!(JsonUtil::set_pointer(&mut value, "/missing/key", json!(0)).is_err());
+        assert!(JsonUtil::set_pointer(&mut value, "/obj/~", json!(0)).is_err());
+        assert!(JsonUtil::set_pointer(&mut value, "obj", json!(0)).is_err());
+
+        JsonUtil::set_pointer(&mut value, "", json!(null)).unwrap();
+        assert!(value.is_null());
+    }
+
+    #[test]
+    fn test_pointer_token_escaping() {
+        for key in ["plain", "a/b", "m~n", "~1", "/~0/"] {
+            let escaped = escape_pointer_token(key);
+            assert_eq!(unescape_pointer_token(&escaped).unwrap(), key);
+        }
+        assert_eq!(escape_pointer_token("~1"), "~01");
+        assert_eq!(unescape_pointer_token("~01").unwrap(), "~1");
+    }
+
     #[test]
     fn test_path_operations() {
         let mut value = json!({
@@ -702,6 +1387,51 @@ mod tests {
         assert_eq!(base["e"], 4);
     }
 
+    #[test]
+    fn test_merge_with_array_strategies() {
+        let base = json!({"list": [1, 2], "nested": {"list": ["x"]}});
+        let overlay = json!({"list": [2, 3], "nested": {"list": ["y"]}});
+
+        let mut replaced = base.clone();
+        JsonUtil::merge_with(&mut replaced, &overlay, MergeOptions::default());
+        assert_eq!(replaced["list"], json!([2, 3]));
+        assert_eq!(replaced["nested"]["list"], json!(["y"]));
+
+        let mut concatenated = base.clone();
+        let opts = MergeOptions {
+            array_strategy: ArrayMergeStrategy::Concat,
+            ..MergeOptions::default()
+        };
+        JsonUtil::merge_with(&mut concatenated, &overlay, opts);
+        assert_eq!(concatenated["list"], json!([1, 2, 2, 3]));
+        assert_eq!(concatenated["nested"]["list"], json!(["x", "y"]));
+
+        let mut unioned = base;
+        let opts = MergeOptions {
+            array_strategy: ArrayMergeStrategy::Union,
+            ..MergeOptions::default()
+        };
+        JsonUtil::merge_with(&mut unioned, &overlay, opts);
+        assert_eq!(unioned["list"], json!([1, 2, 3]));
+    }
+
+    #[test]
+    fn test_merge_with_null_handling() {
+        let overlay = json!({"a": null, "b": {"c": null}});
+
+        let mut kept = json!({"a": 1, "b": {"c": 2, "d": 3}});
+        JsonUtil::merge_with(&mut kept, &overlay, MergeOptions::default());
+        assert_eq!(kept, json!({"a": null, "b": {"c": null, "d": 3}}));
+
+        let mut deleted = json!({"a": 1, "b": {"c": 2, "d": 3}});
+        let opts = MergeOptions {
+            null_deletes: true,
+            ..MergeOptions::default()
+        };
+        JsonUtil::merge_with(&mut deleted, &overlay, opts);
+        assert_eq!(deleted, json!({"b": {"d": 3}}));
+    }
+
     #[test]
     fn test_to_flat_map() {
         let value = json!({
@@ -719,6 +1449,76 @@ mod tests {
         assert_eq!(flat_map.get("status"), Some(&"active".to_string()));
     }
 
+    #[test]
+    fn test_unflatten_round_trip() {
+        let value = json!({
+            "user": {
+                "name": "Alice",
+                "age": 30,
+                "score": 9.5,
+                "active": true,
+                "nickname": null,
+                "roles": ["admin", "dev"]
+            },
+            "matrix": [[1, 2], [3, 4]]
+        });
+
+        let flat_map = JsonUtil::to_flat_map(&value);
+        let rebuilt = JsonUtil::unflatten(&flat_map).unwrap();
+        assert_eq!(rebuilt, value);
+    }
+
+    #[test]
+    fn test_unflatten_keep_strings() {
+        let mut map = HashMap::new();
+        map.insert("code".to_string(), "007".to_string());
+        map.insert("flag".to_string(), "true".to_string());
+
+        let typed = JsonUtil::unflatten(&map).unwrap();
+        assert_eq!(typed, json!({"code": "007", "flag": true}));
+
+        let raw = JsonUtil::unflatten_with(&map, false).unwrap();
+        assert_eq!(raw, json!({"code": "007", "flag": "true"}));
+    }
+
+    #[test]
+    fn test_unflatten_leading_zeros_stay_strings() {
+        for raw in ["007", "-007", "00", "00.5", "0123e2"] {
+            assert_eq!(JsonUtil::unflatten_leaf(raw, true), json!(raw), "{raw}");
+        }
+        assert_eq!(JsonUtil::unflatten_leaf("0", true), json!(0));
+        assert_eq!(JsonUtil::unflatten_leaf("-0", true), json!(0));
+        assert_eq!(JsonUtil::unflatten_leaf("0.25", true), json!(0.25));
+        assert_eq!(JsonUtil::unflatten_leaf("0e1", true), json!(0.0));
+        assert_eq!(JsonUtil::unflatten_leaf("100", true), json!(100));
+    }
+
+    #[test]
+    fn test_unflatten_non_contiguous_indices_become_object() {
+        let mut map = HashMap::new();
+        map.insert("items.0".to_string(), "a".to_string());
+        map.insert("items.2".to_string(), "c".to_string());
+
+        let value = JsonUtil::unflatten(&map).unwrap();
+        assert_eq!(value, json!({"items": {"0": "a", "2": "c"}}));
+    }
+
+    #[test]
+    fn test_unflatten_conflicting_keys() {
+        let mut map = HashMap::new();
+        map.insert("a".to_string(), "1".to_string());
+        map.insert("a.b".to_string(), "2".to_string());
+
+        let err = JsonUtil::unflatten(&map).unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+        assert!(err.to_string().contains("'a"));
+
+        let mut map = HashMap::new();
+        map.insert(String::new(), "1".to_string());
+        map.insert("b".to_string(), "2".to_string());
+        assert!(JsonUtil::unflatten(&map).is_err());
+    }
+
     #[test]
     fn test_from_pairs() {
         let pairs = vec![("name", "Alice"), ("city", "New York")];
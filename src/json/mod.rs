@@ -7,6 +7,8 @@
 //! - JSON streaming and parsing
 
 pub mod json_util;
+pub mod schema;
 
 /// Re-export commonly used types for convenience
-pub use json_util::JsonUtil;
+pub use json_util::{ArrayMergeStrategy, JsonUtil, MergeOptions};
+pub use schema::ValidationError;
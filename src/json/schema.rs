@@ -0,0 +1,535 @@
+//! Validation against a subset of JSON Schema
+//!
+//! Supported keywords: `type`, `required`, `properties`, `items`, `enum`,
+//! `minimum`, `maximum`, `minLength`, `maxLength` and `pattern`. Boolean
+//! schemas (`true` / `false`) are also understood.
+//!
+//! Every other keyword is ignored, including `$ref`, `$defs`, `allOf`,
+//! `anyOf`, `oneOf`, `not`, `if`/`then`/`else`, `additionalProperties`,
+//! `patternProperties`, `const`, `format`, `exclusiveMinimum`,
+//! `exclusiveMaximum`, `multipleOf`, `minItems`, `maxItems`,
+//! `uniqueItems`, `minProperties` and `maxProperties`.
+
+use super::json_util::{JsonUtil, escape_pointer_token};
+use crate::error::{Error, Result};
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// A single schema violation found by [`JsonUtil::validate_schema`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// JSON pointer (RFC 6901) to the offending value, `""` for the root
+    pub pointer: String,
+    /// Schema keyword that failed, e.g. `"required"`
+    pub keyword: String,
+    /// Human-readable description of the failure
+    pub message: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pointer = if self.pointer.is_empty() {
+            "/"
+        } else {
+            &self.pointer
+        };
+        write!(f, "{}: {}", pointer, self.message)
+    }
+}
+
+impl JsonUtil {
+    /// Validate a value against a JSON Schema
+    ///
+    /// Returns every violation found, each with a JSON pointer to the value
+    /// it concerns; an empty list means the value is valid. See the
+    /// [module documentation](crate::json::schema) for the supported
+    /// keywords; unsupported keywords are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the schema itself is malformed, e.g. a `type`
+    /// that is not a string or an invalid `pattern` regex.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::json::JsonUtil;
+    /// use serde_json::json;
+    ///
+    /// let schema = json!({
+    ///     "type": "object",
+    ///     "required": ["name"],
+    ///     "properties": {"age": {"type": "integer", "minimum": 0}}
+    /// });
+    ///
+    /// let errors = JsonUtil::validate_schema(&json!({"age": -1}), &schema).unwrap();
+    /// let pointers: Vec<&str> = errors.iter().map(|e| e.pointer.as_str()).collect();
+    /// assert_eq!(pointers, vec!["", "/age"]);
+    /// ```
+    pub fn validate_schema(value: &Value, schema: &Value) -> Result<Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        validate(value, schema, "", &mut errors)?;
+        Ok(errors)
+    }
+}
+
+fn validate(
+    value: &Value,
+    schema: &Value,
+    pointer: &str,
+    errors: &mut Vec<ValidationError>,
+) -> Result<()> {
+    let schema = match schema {
+        Value::Bool(true) => return Ok(()),
+        Value::Bool(false) => {
+            push(
+                errors,
+                pointer,
+                "false",
+                "No value is allowed here".to_string(),
+            );
+            return Ok(());
+        }
+        Value::Object(schema) => schema,
+        _ => {
+            return Err(Error::validation(format!(
+                "Invalid schema at {}: expected an object or boolean",
+                display_pointer(pointer)
+            )));
+        }
+    };
+
+    // Skip further checks on a type mismatch; they would only add noise
+    if !check_type(value, schema, pointer, errors)? {
+        return Ok(());
+    }
+
+    if let Some(allowed) = schema.get("enum") {
+        let allowed = allowed
+            .as_array()
+            .ok_or_else(|| schema_error(pointer, "enum", "an array"))?;
+        if !allowed.contains(value) {
+            push(
+                errors,
+                pointer,
+                "enum",
+                format!("Value must be one of {}", Value::Array(allowed.clone())),
+            );
+        }
+    }
+
+    match value {
+        Value::Number(number) => {
+            let number = number.as_f64().unwrap_or(f64::NAN);
+            if let Some(minimum) = number_keyword(schema, "minimum", pointer)?
+                && number < minimum
+            {
+                push(
+                    errors,
+                    pointer,
+                    "minimum",
+                    format!("Value must be at least {}", minimum),
+                );
+            }
+            if let Some(maximum) = number_keyword(schema, "maximum", pointer)?
+                && number > maximum
+            {
+                push(
+                    errors,
+                    pointer,
+                    "maximum",
+                    format!("Value must be at most {}", maximum),
+                );
+            }
+        }
+        Value::String(text) => check_string(text, schema, pointer, errors)?,
+        Value::Object(object) => check_object(object, schema, pointer, errors)?,
+        Value::Array(items) => match schema.get("items") {
+            Some(Value::Array(schemas)) => {
+                for (i, (item, item_schema)) in items.iter().zip(schemas).enumerate() {
+                    validate(item, item_schema, &format!("{}/{}", pointer, i), errors)?;
+                }
+            }
+            Some(item_schema) => {
+                for (i, item) in items.iter().enumerate() {
+                    validate(item, item_schema, &format!("{}/{}", pointer, i), errors)?;
+                }
+            }
+            None => {}
+        },
+        Value::Null | Value::Bool(_) => {}
+    }
+
+    Ok(())
+}
+
+/// Check the `type` keyword, returning whether the value matched
+fn check_type(
+    value: &Value,
+    schema: &Map<String, Value>,
+    pointer: &str,
+    errors: &mut Vec<ValidationError>,
+) -> Result<bool> {
+    let types: Vec<&str> = match schema.get("type") {
+        None => return Ok(true),
+        Some(Value::String(name)) => vec![name.as_str()],
+        Some(Value::Array(names)) => names
+            .iter()
+            .map(|name| {
+                name.as_str()
+                    .ok_or_else(|| schema_error(pointer, "type", "a string or array of strings"))
+            })
+            .collect::<Result<_>>()?,
+        Some(_) => {
+            return Err(schema_error(
+                pointer,
+                "type",
+                "a string or array of strings",
+            ));
+        }
+    };
+
+    for name in &types {
+        let matches = match *name {
+            "null" => value.is_null(),
+            "boolean" => value.is_boolean(),
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "string" => value.is_string(),
+            "number" => value.is_number(),
+            "integer" => is_integer(value),
+            other => {
+                return Err(Error::validation(format!(
+                    "Invalid schema at {}: unknown type '{}'",
+                    display_pointer(pointer),
+                    other
+                )));
+            }
+        };
+        if matches {
+            return Ok(true);
+        }
+    }
+
+    push(
+        errors,
+        pointer,
+        "type",
+        format!(
+            "Expected {}, found {}",
+            types.join(" or "),
+            type_name(value)
+        ),
+    );
+    Ok(false)
+}
+
+fn check_string(
+    text: &str,
+    schema: &Map<String, Value>,
+    pointer: &str,
+    errors: &mut Vec<ValidationError>,
+) -> Result<()> {
+    let length = text.chars().count();
+    if let Some(min_length) = length_keyword(schema, "minLength", pointer)?
+        && length < min_length
+    {
+        push(
+            errors,
+            pointer,
+            "minLength",
+            format!("String must have at least {} characters", min_length),
+        );
+    }
+    if let Some(max_length) = length_keyword(schema, "maxLength", pointer)?
+        && length > max_length
+    {
+        push(
+            errors,
+            pointer,
+            "maxLength",
+            format!("String must have at most {} characters", max_length),
+        );
+    }
+    if let Some(pattern) = schema.get("pattern") {
+        let pattern = pattern
+            .as_str()
+            .ok_or_else(|| schema_error(pointer, "pattern", "a string"))?;
+        let regex = Regex::new(pattern).map_err(|e| {
+            Error::validation(format!(
+                "Invalid schema at {}: bad pattern '{}': {}",
+                display_pointer(pointer),
+                pattern,
+                e
+            ))
+        })?;
+        if !regex.is_match(text) {
+            push(
+                errors,
+                pointer,
+                "pattern",
+                format!("String must match pattern '{}'", pattern),
+            );
+        }
+    }
+    Ok(())
+}
+
+fn check_object(
+    object: &Map<String, Value>,
+    schema: &Map<String, Value>,
+    pointer: &str,
+    errors: &mut Vec<ValidationError>,
+) -> Result<()> {
+    if let Some(required) = schema.get("required") {
+        let required = required
+            .as_array()
+            .ok_or_else(|| schema_error(pointer, "required", "an array of strings"))?;
+        for name in required {
+            let name = name
+                .as_str()
+                .ok_or_else(|| schema_error(pointer, "required", "an array of strings"))?;
+            if !object.contains_key(name) {
+                push(
+                    errors,
+                    pointer,
+                    "required",
+                    format!("Missing required property '{}'", name),
+                );
+            }
+        }
+    }
+
+    if let Some(properties) = schema.get("properties") {
+        let properties = properties
+            .as_object()
+            .ok_or_else(|| schema_error(pointer, "properties", "an object"))?;
+        for (name, property_schema) in properties {
+            if let Some(property) = object.get(name) {
+                let child = format!("{}/{}", pointer, escape_pointer_token(name));
+                validate(property, property_schema, &child, errors)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn is_integer(value: &Value) -> bool {
+    match value {
+        Value::Number(number) => {
+            number.is_i64() || number.is_u64() || number.as_f64().is_some_and(|f| f.fract() == 0.0)
+        }
+        _ => false,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) if is_integer(value) => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn number_keyword(
+    schema: &Map<String, Value>,
+    keyword: &str,
+    pointer: &str,
+) -> Result<Option<f64>> {
+    schema
+        .get(keyword)
+        .map(|limit| {
+            limit
+                .as_f64()
+                .ok_or_else(|| schema_error(pointer, keyword, "a number"))
+        })
+        .transpose()
+}
+
+fn length_keyword(
+    schema: &Map<String, Value>,
+    keyword: &str,
+    pointer: &str,
+) -> Result<Option<usize>> {
+    schema
+        .get(keyword)
+        .map(|limit| {
+            limit
+                .as_u64()
+                .and_then(|limit| usize::try_from(limit).ok())
+                .ok_or_else(|| schema_error(pointer, keyword, "a non-negative integer"))
+        })
+        .transpose()
+}
+
+fn push(errors: &mut Vec<ValidationError>, pointer: &str, keyword: &str, message: String) {
+    errors.push(ValidationError {
+        pointer: pointer.to_string(),
+        keyword: keyword.to_string(),
+        message,
+    });
+}
+
+fn schema_error(pointer: &str, keyword: &str, expected: &str) -> Error {
+    Error::validation(format!(
+        "Invalid schema at {}: '{}' must be {}",
+        display_pointer(pointer),
+        keyword,
+        expected
+    ))
+}
+
+fn display_pointer(pointer: &str) -> &str {
+    if pointer.is_empty() { "/" } else { pointer }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn user_schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["name", "address"],
+            "properties": {
+                "name": {"type": "string", "minLength": 1, "maxLength": 20},
+                "age": {"type": "integer", "minimum": 0, "maximum": 150},
+                "role": {"enum": ["admin", "user"]},
+                "address": {
+                    "type": "object",
+                    "required": ["city"],
+                    "properties": {
+                        "city": {"type": "string"},
+                        "zip": {"type": "string", "pattern": "^[0-9]{5}$"}
+                    }
+                },
+                "tags": {"type": "array", "items": {"type": "string"}},
+                "a/b~c": {"type": "boolean"}
+            }
+        })
+    }
+
+    fn pointers(errors: &[ValidationError]) -> Vec<(&str, &str)> {
+        errors
+            .iter()
+            .map(|e| (e.pointer.as_str(), e.keyword.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_document() {
+        let value = json!({
+            "name": "Alice",
+            "age": 30,
+            "role": "admin",
+            "address": {"city": "Paris", "zip": "75001"},
+            "tags": ["a", "b"],
+            "unknown": {"anything": true}
+        });
+        assert!(
+            JsonUtil::validate_schema(&value, &user_schema())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_nested_failures_reported_with_pointers() {
+        let value = json!({
+            "name": "",
+            "age": 200,
+            "role": "root",
+            "address": {"zip": "7500A"},
+            "tags": ["ok", 7],
+            "a/b~c": "yes"
+        });
+        let errors = JsonUtil::validate_schema(&value, &user_schema()).unwrap();
+
+        let mut found = pointers(&errors);
+        found.sort_unstable();
+        assert_eq!(
+            found,
+            vec![
+                ("/address", "required"),
+                ("/address/zip", "pattern"),
+                ("/age", "maximum"),
+                ("/a~1b~0c", "type"),
+                ("/name", "minLength"),
+                ("/role", "enum"),
+                ("/tags/1", "type"),
+            ]
+        );
+        let missing = errors.iter().find(|e| e.keyword == "required").unwrap();
+        assert_eq!(
+            missing.to_string(),
+            "/address: Missing required property 'city'"
+        );
+    }
+
+    #[test]
+    fn test_type_checks() {
+        let schema = json!({"type": ["integer", "null"]});
+        assert!(
+            JsonUtil::validate_schema(&json!(3), &schema)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            JsonUtil::validate_schema(&json!(3.0), &schema)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            JsonUtil::validate_schema(&Value::Null, &schema)
+                .unwrap()
+                .is_empty()
+        );
+
+        let errors = JsonUtil::validate_schema(&json!(3.5), &schema).unwrap();
+        assert_eq!(errors[0].message, "Expected integer or null, found number");
+        assert_eq!(
+            errors[0].to_string(),
+            "/: Expected integer or null, found number"
+        );
+
+        // A type mismatch suppresses the remaining checks for that value
+        let schema = json!({"type": "object", "required": ["id"]});
+        assert_eq!(
+            JsonUtil::validate_schema(&json!("x"), &schema)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_tuple_items_and_boolean_schemas() {
+        let schema = json!({"items": [{"type": "string"}, false]});
+        let errors = JsonUtil::validate_schema(&json!(["a", 1, 2]), &schema).unwrap();
+        assert_eq!(pointers(&errors), vec![("/1", "false")]);
+    }
+
+    #[test]
+    fn test_unsupported_keywords_ignored() {
+        let schema = json!({"type": "string", "format": "email", "$ref": "#/nothing"});
+        assert!(
+            JsonUtil::validate_schema(&json!("not an email"), &schema)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_malformed_schema() {
+        assert!(JsonUtil::validate_schema(&json!(1), &json!({"type": "decimal"})).is_err());
+        assert!(JsonUtil::validate_schema(&json!(1), &json!({"minimum": "0"})).is_err());
+        assert!(JsonUtil::validate_schema(&json!("a"), &json!({"pattern": "("})).is_err());
+        assert!(JsonUtil::validate_schema(&json!(1), &json!([])).is_err());
+    }
+}
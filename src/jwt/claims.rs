@@ -1,8 +1,9 @@
 //! JWT claims management and validation
 
 use crate::jwt::errors::{JwtError, JwtResult};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use std::collections::HashMap;
+use std::time::Duration;
 
 /// Standard JWT claims according to RFC 7519
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -156,6 +157,51 @@ impl Claims {
         self
     }
 
+    /// Set a custom claim from any serializable value
+    ///
+    /// # Errors
+    ///
+    /// Returns `JwtError::JsonError` if the value cannot be serialized
+    pub fn set_custom<T: Serialize>(&mut self, key: impl Into<String>, value: T) -> JwtResult<()> {
+        self.custom.insert(key.into(), serde_json::to_value(value)?);
+        Ok(())
+    }
+
+    /// Get a custom claim deserialized into `T`
+    ///
+    /// Returns `Ok(None)` if the claim is absent.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::jwt::ClaimsBuilder;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let claims = ClaimsBuilder::new()
+    ///     .subject("user123")
+    ///     .custom("roles", vec!["admin", "editor"])?
+    ///     .build();
+    ///
+    /// let roles: Option<Vec<String>> = claims.get_custom("roles")?;
+    /// assert_eq!(roles, Some(vec!["admin".to_string(), "editor".to_string()]));
+    /// assert_eq!(claims.get_custom::<u32>("missing")?, None);
+    /// assert!(claims.get_custom::<u32>("roles").is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `JwtError::InvalidClaim` if the claim exists but does not match `T`
+    pub fn get_custom<T: DeserializeOwned>(&self, key: &str) -> JwtResult<Option<T>> {
+        self.custom
+            .get(key)
+            .map(|value| {
+                T::deserialize(value).map_err(|e| JwtError::invalid_claim(key, e.to_string()))
+            })
+            .transpose()
+    }
+
     /// Get custom claim as string
     pub fn get_custom_string(&self, key: &str) -> Option<&str> {
         self.custom.get(key)?.as_str()
@@ -233,6 +279,60 @@ impl Claims {
             .map(|exp| exp - chrono::Utc::now().timestamp())
     }
 
+    /// Time left until `exp`, clamped to zero once the token has expired
+    ///
+    /// Returns `None` when the claims have no expiration. Useful for
+    /// scheduling a refresh; see [`Claims::from_token_unverified`] for
+    /// reading the claims of a token the client holds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use yimi_rutool::jwt::Claims;
+    ///
+    /// let now = chrono::Utc::now().timestamp();
+    /// let claims = Claims::new().with_expiration(now - 30);
+    /// assert_eq!(claims.remaining_ttl(), Some(Duration::ZERO));
+    /// assert_eq!(Claims::new().remaining_ttl(), None);
+    /// ```
+    pub fn remaining_ttl(&self) -> Option<Duration> {
+        self.remaining_ttl_at(chrono::Utc::now().timestamp())
+    }
+
+    fn remaining_ttl_at(&self, now: i64) -> Option<Duration> {
+        self.expires_at
+            .map(|exp| Duration::from_secs(u64::try_from(exp.saturating_sub(now)).unwrap_or(0)))
+    }
+
+    /// Read the claims of a token WITHOUT checking its signature or expiry
+    ///
+    /// Meant for clients inspecting their own tokens, e.g. to decide when to
+    /// refresh with [`Claims::remaining_ttl`]. The same warning as for
+    /// [`JwtUtil::decode_unverified`] applies: never trust the result for
+    /// authentication or authorization.
+    ///
+    /// [`JwtUtil::decode_unverified`]: crate::jwt::JwtUtil::decode_unverified
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use yimi_rutool::jwt::{Claims, JwtUtil};
+    ///
+    /// let claims = Claims::new().with_expiration_from_now(chrono::Duration::minutes(5));
+    /// let token = JwtUtil::create_token(&claims, "secret").unwrap();
+    ///
+    /// let ttl = Claims::from_token_unverified(&token).unwrap().remaining_ttl().unwrap();
+    /// assert!(ttl.as_secs() > 240);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `JwtError::InvalidToken` if the token is structurally malformed
+    pub fn from_token_unverified(token: &str) -> JwtResult<Self> {
+        crate::jwt::JwtUtil::decode_unverified(token).map(|(_, claims)| claims)
+    }
+
     /// Convert claims to JSON string
     ///
     /// # Errors
@@ -353,6 +453,16 @@ impl ClaimsBuilder {
         self
     }
 
+    /// Add a custom claim from any serializable value
+    ///
+    /// # Errors
+    ///
+    /// Returns `JwtError::JsonError` if the value cannot be serialized
+    pub fn custom<T: Serialize>(mut self, key: impl Into<String>, value: T) -> JwtResult<Self> {
+        self.claims.set_custom(key, value)?;
+        Ok(self)
+    }
+
     /// Build the claims
     pub fn build(self) -> Claims {
         self.claims
@@ -443,4 +553,102 @@ mod tests {
         let claims = Claims::new();
         assert!(claims.time_until_expiration().is_none());
     }
+
+    #[test]
+    fn test_remaining_ttl_at_expiry_boundary() {
+        let exp = 1_700_000_000;
+        let claims = Claims::new().with_expiration(exp);
+
+        assert_eq!(
+            claims.remaining_ttl_at(exp - 3600),
+            Some(Duration::from_secs(3600))
+        );
+        assert_eq!(
+            claims.remaining_ttl_at(exp - 1),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(claims.remaining_ttl_at(exp), Some(Duration::ZERO));
+        assert_eq!(claims.remaining_ttl_at(exp + 1), Some(Duration::ZERO));
+        assert_eq!(claims.remaining_ttl_at(i64::MAX), Some(Duration::ZERO));
+        assert_eq!(
+            Claims::new()
+                .with_expiration(i64::MAX)
+                .remaining_ttl_at(i64::MIN),
+            Some(Duration::from_secs(i64::MAX as u64))
+        );
+
+        assert_eq!(Claims::new().remaining_ttl_at(exp), None);
+        assert_eq!(Claims::new().remaining_ttl(), None);
+        assert!(claims.remaining_ttl().is_some());
+    }
+
+    #[test]
+    fn test_from_token_unverified() {
+        let now = chrono::Utc::now().timestamp();
+        let claims = Claims::new().with_subject("user").with_expiration(now - 10);
+        let token = crate::jwt::JwtUtil::create_token(&claims, "secret").unwrap();
+
+        // Expired and signed with an unknown secret, but still readable
+        let decoded = Claims::from_token_unverified(&token).unwrap();
+        assert_eq!(decoded.subject.as_deref(), Some("user"));
+        assert_eq!(decoded.expires_at, Some(now - 10));
+        assert_eq!(decoded.remaining_ttl(), Some(Duration::ZERO));
+
+        assert!(matches!(
+            Claims::from_token_unverified("not-a-token"),
+            Err(JwtError::InvalidToken(_))
+        ));
+    }
+
+    #[test]
+    fn test_typed_custom_claims() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Profile {
+            name: String,
+            level: u8,
+        }
+
+        let profile = Profile {
+            name: "Alice".to_string(),
+            level: 3,
+        };
+        let claims = ClaimsBuilder::new()
+            .subject("user")
+            .custom("profile", &profile)
+            .unwrap()
+            .custom("scopes", ["read", "write"])
+            .unwrap()
+            .build();
+
+        // Survives a JSON round trip
+        let parsed = Claims::from_json(&claims.to_json().unwrap()).unwrap();
+        assert_eq!(
+            parsed.get_custom::<Profile>("profile").unwrap(),
+            Some(profile)
+        );
+        assert_eq!(
+            parsed.get_custom::<Vec<String>>("scopes").unwrap(),
+            Some(vec!["read".to_string(), "write".to_string()])
+        );
+        assert_eq!(parsed.get_custom::<Profile>("absent").unwrap(), None);
+
+        let err = parsed.get_custom::<Profile>("scopes").unwrap_err();
+        assert!(matches!(err, JwtError::InvalidClaim(ref key, _) if key == "scopes"));
+
+        let mut claims = Claims::new();
+        claims.set_custom("count", 5u32).unwrap();
+        assert_eq!(claims.get_custom::<u32>("count").unwrap(), Some(5));
+    }
+
+    #[test]
+    fn test_custom_claim_serialization_error() {
+        use std::collections::BTreeMap;
+
+        // Maps with non-string keys cannot be represented as JSON
+        let mut bad = BTreeMap::new();
+        bad.insert(vec![1u8], "value");
+
+        let result = ClaimsBuilder::new().custom("bad", bad);
+        assert!(matches!(result, Err(JwtError::JsonError(_))));
+    }
 }
@@ -28,6 +28,15 @@ pub enum JwtError {
     #[error("Invalid algorithm: {0}")]
     InvalidAlgorithm(String),
 
+    /// Token header names an algorithm that is not accepted, including `none`
+    #[error("Algorithm mismatch: expected {expected}, got '{actual}'")]
+    AlgorithmMismatch {
+        /// Accepted algorithms, comma separated
+        expected: String,
+        /// Algorithm named in the token header
+        actual: String,
+    },
+
     /// Invalid key format
     #[error("Invalid key format: {0}")]
     InvalidKey(String),
@@ -40,6 +49,32 @@ pub enum JwtError {
     #[error("Invalid claim value for '{0}': {1}")]
     InvalidClaim(String, String),
 
+    /// Issuer (iss) does not match the expected value
+    #[error("Invalid issuer: expected '{expected}', got '{actual}'")]
+    InvalidIssuer {
+        /// Expected issuer
+        expected: String,
+        /// Issuer found in the token (empty if missing)
+        actual: String,
+    },
+
+    /// Audience (aud) does not match the expected value
+    #[error("Invalid audience: expected '{expected}', got '{actual}'")]
+    InvalidAudience {
+        /// Expected audience
+        expected: String,
+        /// Audience found in the token (empty if missing)
+        actual: String,
+    },
+
+    /// Token header has no key ID (kid) but one is needed to select the key
+    #[error("Token header has no key ID (kid)")]
+    MissingKeyId,
+
+    /// Token key ID (kid) does not match any known key
+    #[error("Unknown key ID: {0}")]
+    UnknownKeyId(String),
+
     /// JSON serialization/deserialization error
     #[error("JSON error: {0}")]
     JsonError(#[from] serde_json::Error),
@@ -1,14 +1,14 @@
 //! JWT (JSON Web Token) utilities for yimi-rutool
 //!
 //! This module provides functionality for creating, validating, and managing JWT tokens.
-//! It supports various signing algorithms including HMAC, RSA, and ECDSA.
+//! It supports various signing algorithms including HMAC, RSA, ECDSA, and EdDSA.
 //!
 //! # Features
 //!
 //! - JWT token creation and validation
-//! - Multiple signing algorithms (HS256, HS384, HS512, RS256, RS384, RS512, ES256, ES384, ES512)
+//! - Multiple signing algorithms (HS256, HS384, HS512, RS256, RS384, RS512, ES256, ES384, ES512, EdDSA)
 //! - Claims management with standard and custom claims
-//! - Token expiration and validation
+//! - Token expiration and validation, with clock-skew leeway and issuer/audience checks
 //! - Base64 encoding/decoding utilities
 //!
 //! # Quick Start
@@ -37,12 +37,14 @@ pub mod algorithms;
 pub mod claims;
 pub mod errors;
 pub mod jwt_util;
+pub mod validation;
 
 // Re-export main types for convenience
-pub use algorithms::{Algorithm, SigningKey};
+pub use algorithms::{Algorithm, KeySet, SigningKey};
 pub use claims::{Claims, ClaimsBuilder};
 pub use errors::{JwtError, JwtResult};
-pub use jwt_util::JwtUtil;
+pub use jwt_util::{JwtHeader, JwtUtil};
+pub use validation::ValidationOptions;
 
 #[cfg(test)]
 mod tests {
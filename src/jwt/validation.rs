@@ -0,0 +1,246 @@
+//! Configurable JWT claim validation
+
+use crate::jwt::{Algorithm, Claims, JwtError, JwtResult};
+
+/// Options controlling which claims [`JwtUtil::validate_token_with`] checks
+///
+/// The default tolerates 60 seconds of clock skew, checks `exp` and `nbf`,
+/// does not check issuer or audience, and accepts any algorithm that fits
+/// the verification key.
+///
+/// [`JwtUtil::validate_token_with`]: crate::jwt::JwtUtil::validate_token_with
+///
+/// # Examples
+///
+/// ```rust
+/// use yimi_rutool::jwt::{Algorithm, ValidationOptions};
+///
+/// let options = ValidationOptions::new()
+///     .allow_algorithm(Algorithm::RS256)
+///     .with_leeway(chrono::Duration::seconds(30))
+///     .with_issuer("https://auth.example.com")
+///     .with_audience("api")
+///     .require_claim("sub");
+/// assert_eq!(options.required_claims, vec!["sub".to_string()]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ValidationOptions {
+    /// Allowed clock skew applied to `exp` and `nbf` (negative values count as zero)
+    pub leeway: chrono::Duration,
+    /// Reject tokens whose `exp` is in the past
+    pub validate_exp: bool,
+    /// Reject tokens whose `nbf` is in the future
+    pub validate_nbf: bool,
+    /// Required `iss` value
+    pub expected_iss: Option<String>,
+    /// Required `aud` value
+    pub expected_aud: Option<String>,
+    /// Claims that must be present, by JSON name (e.g. `"sub"`, `"exp"`, or a custom key)
+    pub required_claims: Vec<String>,
+    /// Algorithms accepted in the token header; empty accepts any algorithm
+    /// compatible with the key. `none` is never accepted.
+    pub allowed_algorithms: Vec<Algorithm>,
+}
+
+impl ValidationOptions {
+    /// Create default validation options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the allowed clock skew
+    #[must_use]
+    pub fn with_leeway(mut self, leeway: chrono::Duration) -> Self {
+        self.leeway = leeway;
+        self
+    }
+
+    /// Require a specific issuer
+    #[must_use]
+    pub fn with_issuer(mut self, issuer: impl Into<String>) -> Self {
+        self.expected_iss = Some(issuer.into());
+        self
+    }
+
+    /// Require a specific audience
+    #[must_use]
+    pub fn with_audience(mut self, audience: impl Into<String>) -> Self {
+        self.expected_aud = Some(audience.into());
+        self
+    }
+
+    /// Require a claim to be present
+    #[must_use]
+    pub fn require_claim(mut self, claim: impl Into<String>) -> Self {
+        self.required_claims.push(claim.into());
+        self
+    }
+
+    /// Accept tokens signed with `algorithm`
+    ///
+    /// Once any algorithm is allowed, tokens using other algorithms are
+    /// rejected with `AlgorithmMismatch`, even if they fit the key.
+    #[must_use]
+    pub fn allow_algorithm(mut self, algorithm: Algorithm) -> Self {
+        if !self.allowed_algorithms.contains(&algorithm) {
+            self.allowed_algorithms.push(algorithm);
+        }
+        self
+    }
+
+    /// Check claims against these options
+    ///
+    /// Checks run in order: required claims, `exp`, `nbf`, `iss`, `aud`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `JwtError` if:
+    /// - A required claim is missing (`MissingClaim`)
+    /// - Token has expired beyond the leeway (`TokenExpired`)
+    /// - Token is not yet valid beyond the leeway (`TokenNotYetValid`)
+    /// - Issuer does not match (`InvalidIssuer`)
+    /// - Audience does not match (`InvalidAudience`)
+    pub fn validate(&self, claims: &Claims) -> JwtResult<()> {
+        for name in &self.required_claims {
+            if !Self::has_claim(claims, name) {
+                return Err(JwtError::missing_claim(name.clone()));
+            }
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let leeway = self.leeway.num_seconds().max(0);
+
+        if self.validate_exp
+            && let Some(exp) = claims.expires_at
+            && now > exp + leeway
+        {
+            return Err(JwtError::TokenExpired);
+        }
+
+        if self.validate_nbf
+            && let Some(nbf) = claims.not_before
+            && now < nbf - leeway
+        {
+            return Err(JwtError::TokenNotYetValid);
+        }
+
+        if let Some(expected) = &self.expected_iss
+            && claims.issuer.as_ref() != Some(expected)
+        {
+            return Err(JwtError::InvalidIssuer {
+                expected: expected.clone(),
+                actual: claims.issuer.clone().unwrap_or_default(),
+            });
+        }
+
+        if let Some(expected) = &self.expected_aud
+            && claims.audience.as_ref() != Some(expected)
+        {
+            return Err(JwtError::InvalidAudience {
+                expected: expected.clone(),
+                actual: claims.audience.clone().unwrap_or_default(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn has_claim(claims: &Claims, name: &str) -> bool {
+        match name {
+            "iss" => claims.issuer.is_some(),
+            "sub" => claims.subject.is_some(),
+            "aud" => claims.audience.is_some(),
+            "exp" => claims.expires_at.is_some(),
+            "nbf" => claims.not_before.is_some(),
+            "iat" => claims.issued_at.is_some(),
+            "jti" => claims.jwt_id.is_some(),
+            custom => claims.custom.contains_key(custom),
+        }
+    }
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        Self {
+            leeway: chrono::Duration::seconds(60),
+            validate_exp: true,
+            validate_nbf: true,
+            expected_iss: None,
+            expected_aud: None,
+            required_claims: Vec::new(),
+            allowed_algorithms: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leeway() {
+        let now = chrono::Utc::now().timestamp();
+        let expired = Claims::new().with_expiration(now - 30);
+        let future = Claims::new().with_not_before(now + 30);
+
+        let strict = ValidationOptions::new().with_leeway(chrono::Duration::zero());
+        assert!(matches!(
+            strict.validate(&expired),
+            Err(JwtError::TokenExpired)
+        ));
+        assert!(matches!(
+            strict.validate(&future),
+            Err(JwtError::TokenNotYetValid)
+        ));
+
+        let lenient = ValidationOptions::new();
+        assert!(lenient.validate(&expired).is_ok());
+        assert!(lenient.validate(&future).is_ok());
+
+        let unchecked = ValidationOptions {
+            leeway: chrono::Duration::zero(),
+            validate_exp: false,
+            validate_nbf: false,
+            ..ValidationOptions::default()
+        };
+        assert!(unchecked.validate(&expired).is_ok());
+        assert!(unchecked.validate(&future).is_ok());
+    }
+
+    #[test]
+    fn test_issuer_audience_and_required_claims() {
+        let claims = Claims::new()
+            .with_issuer("issuer-a")
+            .with_audience("api")
+            .with_subject("user")
+            .with_custom_string("tenant", "t1");
+
+        let options = ValidationOptions::new()
+            .with_issuer("issuer-a")
+            .with_audience("api")
+            .require_claim("sub")
+            .require_claim("tenant");
+        assert!(options.validate(&claims).is_ok());
+
+        let err = options
+            .clone()
+            .with_issuer("issuer-b")
+            .validate(&claims)
+            .unwrap_err();
+        assert!(matches!(err, JwtError::InvalidIssuer { .. }));
+        assert_eq!(
+            err.to_string(),
+            "Invalid issuer: expected 'issuer-b', got 'issuer-a'"
+        );
+
+        let err = options
+            .clone()
+            .with_audience("web")
+            .validate(&claims)
+            .unwrap_err();
+        assert!(matches!(err, JwtError::InvalidAudience { .. }));
+
+        let err = options.require_claim("jti").validate(&claims).unwrap_err();
+        assert!(matches!(err, JwtError::MissingClaim(ref c) if c == "jti"));
+    }
+}
@@ -110,7 +110,7 @@ pub mod text;
 pub mod error;
 
 /// Re-export commonly used types for convenience
-pub use error::{Error, Result};
+pub use error::{Error, ErrorKind, Result};
 
 /// Version information
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
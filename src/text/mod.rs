@@ -11,7 +11,7 @@
 //! - **DFA Sensitive Word Filter**: High-performance sensitive word detection and filtering
 //! - **Flexible Replacement**: Multiple replacement strategies (mask, replace, highlight)
 //! - **Batch Processing**: Efficient processing of large text datasets
-//! - **Custom Word Lists**: Support for custom sensitive word dictionaries
+//! - **Custom Word Lists**: Support for custom sensitive word dictionaries, with per-word categories and severities
 //!
 //! # Quick Start
 //!
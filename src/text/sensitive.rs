@@ -3,38 +3,115 @@
 //! This module provides high-performance sensitive word detection and filtering
 //! using a DFA-based approach for optimal text processing performance.
 
+use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::ops::Range;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Category of words added without a tag
+pub const DEFAULT_CATEGORY: &str = "general";
+
+/// Severity of words added without a tag
+pub const DEFAULT_SEVERITY: u8 = 1;
 
 /// A match found in the text
+///
+/// `start` and `end` are **character** indices into the original text
+/// (`text.chars()`), not byte offsets. The equivalent byte offsets, usable
+/// for slicing the `&str`, are available through
+/// [`byte_range`](Self::byte_range).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct WordMatch {
     /// The matched sensitive word
     pub word: String,
-    /// Start position in the original text
+    /// Start character index in the original text
     pub start: usize,
-    /// End position in the original text
+    /// End character index (exclusive) in the original text
     pub end: usize,
+    /// Start byte offset in the original text
+    pub byte_start: usize,
+    /// End byte offset (exclusive) in the original text
+    pub byte_end: usize,
     /// The original matched text (may differ from word due to case)
     pub matched_text: String,
+    /// Category of the matched word, [`DEFAULT_CATEGORY`] if untagged
+    pub category: String,
+    /// Severity of the matched word (higher is more severe)
+    pub severity: u8,
 }
 
 impl WordMatch {
-    /// Create a new word match
-    pub fn new(word: String, start: usize, end: usize, matched_text: String) -> Self {
+    /// Create a new word match from character indices into `text`
+    ///
+    /// The byte offsets and matched text are derived from `text`, so
+    /// `start..end` must be a character range within it.
+    pub fn new(word: String, text: &str, start: usize, end: usize) -> Self {
+        let mut offsets = text
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .chain(std::iter::once(text.len()));
+        let byte_start = offsets.nth(start).unwrap_or(text.len());
+        let byte_end = if end > start {
+            offsets.nth(end - start - 1).unwrap_or(text.len())
+        } else {
+            byte_start
+        };
+
         WordMatch {
             word,
             start,
             end,
-            matched_text,
+            byte_start,
+            byte_end,
+            matched_text: text[byte_start..byte_end].to_string(),
+            category: DEFAULT_CATEGORY.to_string(),
+            severity: DEFAULT_SEVERITY,
         }
     }
 
-    /// Get the length of the matched text
+    /// Set the category and severity of the matched word
+    pub fn with_tag(mut self, category: &str, severity: u8) -> Self {
+        self.category = category.to_string();
+        self.severity = severity;
+        self
+    }
+
+    /// Get the range of character indices covered by the match
+    pub fn char_range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Get the range of byte offsets covered by the match
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::text::SensitiveWordFilter;
+    ///
+    /// let mut filter = SensitiveWordFilter::new();
+    /// filter.add_word("坏");
+    ///
+    /// let text = "这是坏文本";
+    /// let matches = filter.find_matches(text);
+    /// assert_eq!(matches[0].char_range(), 2..3);
+    /// assert_eq!(matches[0].byte_range(), 6..9);
+    /// assert_eq!(&text[matches[0].byte_range()], "坏");
+    /// ```
+    pub fn byte_range(&self) -> Range<usize> {
+        self.byte_start..self.byte_end
+    }
+
+    /// Get the length of the matched text in characters
     pub fn len(&self) -> usize {
         self.end - self.start
     }
 
+    /// Get the length of the matched text in bytes
+    pub fn byte_len(&self) -> usize {
+        self.byte_end - self.byte_start
+    }
+
     /// Check if the match is empty
     pub fn is_empty(&self) -> bool {
         self.len() == 0
@@ -66,6 +143,17 @@ pub enum FilterStrategy {
     Remove,
     /// Keep original but record the match
     KeepOriginal,
+    /// Keep the first and last character and mask the middle ("b*d")
+    ///
+    /// Two-character matches keep only the first character and
+    /// single-character matches are fully masked.
+    MaskKeepingEdges,
+    /// Mask with a character repeated to match the display width
+    ///
+    /// Wide characters such as CJK take two terminal columns, so "坏人"
+    /// becomes four mask characters. If the mask character is itself wide,
+    /// a leftover odd column is padded with a space.
+    MaskPerChar(char),
 }
 
 impl Default for FilterStrategy {
@@ -86,10 +174,36 @@ impl FilterStrategy {
             }
             FilterStrategy::Remove => String::new(),
             FilterStrategy::KeepOriginal => word_match.matched_text.clone(),
+            FilterStrategy::MaskKeepingEdges => {
+                let chars: Vec<char> = word_match.matched_text.chars().collect();
+                match chars.len() {
+                    0 => String::new(),
+                    1 => "*".to_string(),
+                    2 => format!("{}*", chars[0]),
+                    n => format!("{}{}{}", chars[0], "*".repeat(n - 2), chars[n - 1]),
+                }
+            }
+            FilterStrategy::MaskPerChar(mask) => {
+                let width = display_width(&word_match.matched_text);
+                let mask_width = char_display_width(*mask).max(1);
+                let mut masked = mask.to_string().repeat(width / mask_width);
+                masked.push_str(&" ".repeat(width % mask_width));
+                masked
+            }
         }
     }
 }
 
+/// Terminal display width of a string
+fn display_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// Terminal display width of a character; 0 for control characters
+fn char_display_width(ch: char) -> usize {
+    UnicodeWidthChar::width(ch).unwrap_or(0)
+}
+
 /// Result of filtering operation
 #[derive(Debug, Clone)]
 pub struct FilterResult {
@@ -97,9 +211,9 @@ pub struct FilterResult {
     pub filtered_text: String,
     /// List of matches found
     pub matches: Vec<WordMatch>,
-    /// Original text length
+    /// Original text length in bytes
     pub original_length: usize,
-    /// Filtered text length
+    /// Filtered text length in bytes
     pub filtered_length: usize,
 }
 
@@ -130,13 +244,65 @@ impl FilterResult {
         self.matches.iter().map(|m| m.word.as_str()).collect()
     }
 
+    /// Group the matches by the category of the matched word
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::text::{FilterStrategy, SensitiveWordFilter};
+    ///
+    /// let mut filter = SensitiveWordFilter::new();
+    /// filter.add_word_tagged("kill", "violence", 3);
+    /// filter.add_word_tagged("buy now", "spam", 1);
+    /// filter.add_word("darn");
+    ///
+    /// let result = filter.filter_with_strategy("darn, buy now or I kill it", &FilterStrategy::Mask);
+    /// let by_category = result.matches_by_category();
+    /// assert_eq!(by_category["violence"][0].word, "kill");
+    /// assert_eq!(by_category["spam"].len(), 1);
+    /// assert_eq!(by_category["general"].len(), 1);
+    /// assert_eq!(result.max_severity(), Some(3));
+    /// ```
+    pub fn matches_by_category(&self) -> HashMap<&str, Vec<&WordMatch>> {
+        let mut groups: HashMap<&str, Vec<&WordMatch>> = HashMap::new();
+        for word_match in &self.matches {
+            groups
+                .entry(word_match.category.as_str())
+                .or_default()
+                .push(word_match);
+        }
+        groups
+    }
+
+    /// Get the highest severity among the matches, or `None` without matches
+    pub fn max_severity(&self) -> Option<u8> {
+        self.matches.iter().map(|m| m.severity).max()
+    }
+
     /// Calculate the percentage of text that was filtered
+    ///
+    /// Measured in bytes of the original text. Overlapping matches are
+    /// counted once, so the result never exceeds 100.
     pub fn filter_percentage(&self) -> f64 {
         if self.original_length == 0 {
             return 0.0;
         }
-        let filtered_chars: usize = self.matches.iter().map(|m| m.len()).sum();
-        (filtered_chars as f64 / self.original_length as f64) * 100.0
+
+        let mut ranges: Vec<Range<usize>> =
+            self.matches.iter().map(WordMatch::byte_range).collect();
+        ranges.sort_by_key(|range| range.start);
+
+        let mut filtered_bytes = 0;
+        let mut covered_until = 0;
+        for range in ranges {
+            let start = range.start.max(covered_until);
+            if range.end > start {
+                filtered_bytes += range.end - start;
+                covered_until = range.end;
+            }
+        }
+
+        (filtered_bytes as f64 / self.original_length as f64) * 100.0
     }
 }
 
@@ -203,7 +369,36 @@ struct DfaNode {
     /// Failure link for AC automaton
     failure: usize,
     /// Words that end at this node
-    output: Vec<String>,
+    output: Vec<NodeOutput>,
+}
+
+/// A dictionary entry ending at a DFA node
+#[derive(Debug, Clone)]
+struct NodeOutput {
+    /// The word as stored in the dictionary
+    word: String,
+    /// Length of the normalized word in chars
+    len: usize,
+    /// Whether the word comes from the allowlist
+    allowed: bool,
+    /// Category and severity of a sensitive word
+    tag: WordTag,
+}
+
+/// Category and severity attached to a sensitive word
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct WordTag {
+    category: String,
+    severity: u8,
+}
+
+impl Default for WordTag {
+    fn default() -> Self {
+        WordTag {
+            category: DEFAULT_CATEGORY.to_string(),
+            severity: DEFAULT_SEVERITY,
+        }
+    }
 }
 
 impl DfaNode {
@@ -237,12 +432,23 @@ impl DfaNode {
 pub struct SensitiveWordFilter {
     /// DFA nodes
     nodes: Vec<DfaNode>,
-    /// Set of sensitive words for quick lookup
-    word_set: HashSet<String>,
+    /// Sensitive words for quick lookup, with their category and severity
+    word_set: HashMap<String, WordTag>,
+    /// Allowlisted words whose matches suppress overlapping sensitive matches
+    allowed_words: HashSet<String>,
     /// Whether the automaton has been built
     built: bool,
+    /// Whether matching rebuilds a stale automaton automatically
+    auto_rebuild: bool,
     /// Case sensitivity setting
     case_sensitive: bool,
+    /// Characters ignored while matching (e.g. spaces and punctuation)
+    noise_chars: HashSet<char>,
+    /// Look-alike characters mapped to a canonical form before matching
+    homoglyphs: HashMap<char, char>,
+    /// Whether Han characters are matched by their pinyin
+    #[cfg(feature = "pinyin")]
+    pinyin_matching: bool,
     /// Processing statistics
     stats: ProcessingStats,
 }
@@ -260,9 +466,15 @@ impl SensitiveWordFilter {
     pub fn new() -> Self {
         let mut filter = SensitiveWordFilter {
             nodes: Vec::new(),
-            word_set: HashSet::new(),
+            word_set: HashMap::new(),
+            allowed_words: HashSet::new(),
             built: false,
+            auto_rebuild: true,
             case_sensitive: false,
+            noise_chars: HashSet::new(),
+            homoglyphs: HashMap::new(),
+            #[cfg(feature = "pinyin")]
+            pinyin_matching: false,
             stats: ProcessingStats::default(),
         };
 
@@ -290,8 +502,166 @@ impl SensitiveWordFilter {
         self.built = false; // Need to rebuild
     }
 
+    /// Set characters to ignore while matching
+    ///
+    /// Noise characters are skipped in both the text and the words, so a
+    /// filter for "bad" also catches "b.a.d" or "b a d". Match positions
+    /// still index into the original text and the matched text includes the
+    /// skipped characters. Pass an empty iterator to disable skipping.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::text::SensitiveWordFilter;
+    ///
+    /// let mut filter = SensitiveWordFilter::new();
+    /// filter.set_noise_chars(" .-_*".chars());
+    /// filter.add_word("bad");
+    ///
+    /// let matches = filter.find_matches("so b.a.d!");
+    /// assert_eq!(matches[0].matched_text, "b.a.d");
+    /// assert_eq!((matches[0].start, matches[0].end), (3, 8));
+    /// ```
+    pub fn set_noise_chars<I: IntoIterator<Item = char>>(&mut self, chars: I) {
+        self.noise_chars = chars.into_iter().collect();
+        self.built = false; // Words are normalized when building
+    }
+
+    /// Map a look-alike character to its canonical form before matching
+    ///
+    /// The mapping applies to both the text and the words, after case
+    /// folding when matching is case insensitive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::text::SensitiveWordFilter;
+    ///
+    /// let mut filter = SensitiveWordFilter::new();
+    /// filter.add_homoglyph('@', 'a');
+    /// filter.add_word("bad");
+    /// assert!(filter.contains_sensitive_words("b@d"));
+    /// ```
+    pub fn add_homoglyph(&mut self, from: char, to: char) {
+        self.homoglyphs.insert(from, to);
+        self.built = false;
+    }
+
+    /// Add a built-in set of common homoglyph mappings
+    ///
+    /// Covers full-width ASCII forms, Cyrillic and Greek letters that look
+    /// like Latin ones, and common leetspeak substitutions such as `0` for
+    /// `o`, `3` for `e` and `@` for `a`.
+    pub fn add_default_homoglyphs(&mut self) {
+        const LOOKALIKES: &[(char, char)] = &[
+            // Cyrillic
+            ('а', 'a'),
+            ('в', 'b'),
+            ('е', 'e'),
+            ('к', 'k'),
+            ('м', 'm'),
+            ('н', 'h'),
+            ('о', 'o'),
+            ('р', 'p'),
+            ('с', 'c'),
+            ('т', 't'),
+            ('у', 'y'),
+            ('х', 'x'),
+            ('і', 'i'),
+            ('ј', 'j'),
+            ('ѕ', 's'),
+            // Greek
+            ('α', 'a'),
+            ('ο', 'o'),
+            ('ρ', 'p'),
+            ('ν', 'v'),
+            ('ι', 'i'),
+            ('κ', 'k'),
+            // Leetspeak
+            ('0', 'o'),
+            ('1', 'i'),
+            ('3', 'e'),
+            ('4', 'a'),
+            ('5', 's'),
+            ('7', 't'),
+            ('@', 'a'),
+            ('$', 's'),
+        ];
+
+        // Full-width ASCII (U+FF01..=U+FF5E) to ASCII
+        for code in 0xFF01..=0xFF5E_u32 {
+            if let (Some(from), Some(to)) = (char::from_u32(code), char::from_u32(code - 0xFEE0)) {
+                self.homoglyphs.insert(from, to);
+            }
+        }
+
+        self.homoglyphs.extend(LOOKALIKES.iter().copied());
+        self.built = false;
+    }
+
+    /// Match Han characters by their pinyin (requires the `pinyin` feature)
+    ///
+    /// When enabled, Han characters in both the dictionary and the text are
+    /// transliterated to toneless pinyin before matching, so a word added as
+    /// "赌博" is also caught when written "dubo", "赌bo" or "du博". Matches
+    /// report positions in the original text and never start or end in the
+    /// middle of a character's syllable.
+    ///
+    /// Limitations: characters sharing a pinyin (homophones) match each
+    /// other, heteronyms always use their most common reading, and pinyin
+    /// typed in the text must be lowercase unless the filter is case
+    /// insensitive. Add `' '` to the [noise characters](Self::set_noise_chars)
+    /// to also catch syllables separated by spaces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::text::SensitiveWordFilter;
+    ///
+    /// let mut filter = SensitiveWordFilter::new();
+    /// filter.set_pinyin_matching(true);
+    /// filter.add_word("赌博");
+    ///
+    /// let matches = filter.find_matches("一起Dubo吧");
+    /// assert_eq!(matches[0].word, "赌博");
+    /// assert_eq!(matches[0].char_range(), 2..6);
+    /// ```
+    #[cfg(feature = "pinyin")]
+    pub fn set_pinyin_matching(&mut self, enabled: bool) {
+        self.pinyin_matching = enabled;
+        self.built = false;
+    }
+
+    /// Normalize one character for matching, appending the result to `out`
+    ///
+    /// Noise characters produce nothing; case folding may produce several
+    /// characters.
+    fn normalize_char(&self, ch: char, out: &mut Vec<char>) {
+        if self.noise_chars.contains(&ch) {
+            return;
+        }
+        #[cfg(feature = "pinyin")]
+        if self.pinyin_matching {
+            use pinyin::ToPinyin;
+            if let Some(pinyin) = ch.to_pinyin() {
+                out.extend(pinyin.plain().chars());
+                return;
+            }
+        }
+        if self.case_sensitive {
+            out.push(self.homoglyphs.get(&ch).copied().unwrap_or(ch));
+        } else {
+            for lower in ch.to_lowercase() {
+                out.push(self.homoglyphs.get(&lower).copied().unwrap_or(lower));
+            }
+        }
+    }
+
     /// Add a sensitive word to the filter
     ///
+    /// The word is tagged with [`DEFAULT_CATEGORY`] and [`DEFAULT_SEVERITY`];
+    /// adding a word that is already present keeps its existing tag.
+    ///
     /// # Arguments
     ///
     /// * `word` - The sensitive word to add
@@ -316,8 +686,55 @@ impl SensitiveWordFilter {
             word.to_lowercase()
         };
 
-        self.word_set.insert(processed_word);
-        self.built = false; // Need to rebuild automaton
+        if let Entry::Vacant(entry) = self.word_set.entry(processed_word) {
+            entry.insert(WordTag::default());
+            self.built = false; // Need to rebuild automaton
+        }
+    }
+
+    /// Add a sensitive word with a category and severity
+    ///
+    /// Matches of the word carry the tag in [`WordMatch::category`] and
+    /// [`WordMatch::severity`], so callers can act differently per category.
+    /// Adding a word that is already present replaces its tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - The sensitive word to add
+    /// * `category` - Category such as "profanity", "violence" or "spam"
+    /// * `severity` - Severity of the word (higher is more severe)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::text::SensitiveWordFilter;
+    ///
+    /// let mut filter = SensitiveWordFilter::new();
+    /// filter.add_word_tagged("scam", "spam", 2);
+    ///
+    /// let matches = filter.find_matches("not a scam");
+    /// assert_eq!(matches[0].category, "spam");
+    /// assert_eq!(matches[0].severity, 2);
+    /// ```
+    pub fn add_word_tagged(&mut self, word: &str, category: &str, severity: u8) {
+        if word.is_empty() {
+            return;
+        }
+
+        let processed_word = if self.case_sensitive {
+            word.to_string()
+        } else {
+            word.to_lowercase()
+        };
+
+        let tag = WordTag {
+            category: category.to_string(),
+            severity,
+        };
+        if self.word_set.get(&processed_word) != Some(&tag) {
+            self.word_set.insert(processed_word, tag);
+            self.built = false;
+        }
     }
 
     /// Add multiple words at once
@@ -366,11 +783,76 @@ impl SensitiveWordFilter {
             word.to_lowercase()
         };
 
-        if self.word_set.remove(&processed_word) {
+        if self.word_set.remove(&processed_word).is_some() {
             self.built = false; // Need to rebuild
         }
     }
 
+    /// Add a word or phrase to the allowlist
+    ///
+    /// Legitimate words can contain sensitive substrings, such as the town
+    /// "Scunthorpe". Wherever an allowlisted word occurs in the text, every
+    /// sensitive match overlapping it, fully or partially, is dropped: the
+    /// allowlist always wins. Filtering therefore leaves allowlisted regions
+    /// untouched. Allowlisted words are matched with the same case and
+    /// normalization settings as sensitive words.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::text::SensitiveWordFilter;
+    ///
+    /// let mut filter = SensitiveWordFilter::new();
+    /// filter.add_word("ass");
+    /// filter.add_allowed_word("class");
+    ///
+    /// assert_eq!(filter.filter("first class ass"), "first class ***");
+    /// ```
+    pub fn add_allowed_word(&mut self, word: &str) {
+        if word.is_empty() {
+            return;
+        }
+
+        let processed_word = if self.case_sensitive {
+            word.to_string()
+        } else {
+            word.to_lowercase()
+        };
+
+        if self.allowed_words.insert(processed_word) {
+            self.built = false;
+        }
+    }
+
+    /// Add multiple words to the allowlist
+    pub fn add_allowed_words<I>(&mut self, words: I)
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        for word in words {
+            self.add_allowed_word(word.as_ref());
+        }
+    }
+
+    /// Remove a word from the allowlist
+    pub fn remove_allowed_word(&mut self, word: &str) {
+        let processed_word = if self.case_sensitive {
+            word.to_string()
+        } else {
+            word.to_lowercase()
+        };
+
+        if self.allowed_words.remove(&processed_word) {
+            self.built = false;
+        }
+    }
+
+    /// Get the number of words in the allowlist
+    pub fn allowed_word_count(&self) -> usize {
+        self.allowed_words.len()
+    }
+
     /// Clear all words from the filter
     ///
     /// # Examples
@@ -409,7 +891,7 @@ impl SensitiveWordFilter {
         } else {
             word.to_lowercase()
         };
-        self.word_set.contains(&processed_word)
+        self.word_set.contains_key(&processed_word)
     }
 
     /// Get the number of words in the filter
@@ -442,7 +924,50 @@ impl SensitiveWordFilter {
     /// assert_eq!(words.len(), 2);
     /// ```
     pub fn get_words(&self) -> Vec<&str> {
-        self.word_set.iter().map(|s| s.as_str()).collect()
+        self.word_set.keys().map(|s| s.as_str()).collect()
+    }
+
+    /// Control whether matching rebuilds the automaton after word changes
+    ///
+    /// Every added or removed word invalidates the automaton, and by default
+    /// the next match rebuilds it from the whole dictionary. When updating a
+    /// large dictionary word by word while still matching text, disable
+    /// auto-rebuild: matching then keeps using the last built automaton
+    /// (ignoring pending changes) until [`commit`](Self::commit) is called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use yimi_rutool::text::SensitiveWordFilter;
+    ///
+    /// let mut filter = SensitiveWordFilter::new();
+    /// filter.set_auto_rebuild(false);
+    /// filter.add_word("bad");
+    /// filter.commit();
+    ///
+    /// filter.add_word("evil");
+    /// assert!(filter.needs_rebuild());
+    /// assert!(!filter.contains_sensitive_words("evil")); // not committed yet
+    /// assert!(filter.contains_sensitive_words("bad"));
+    ///
+    /// filter.commit();
+    /// assert!(filter.contains_sensitive_words("evil"));
+    /// ```
+    pub fn set_auto_rebuild(&mut self, auto_rebuild: bool) {
+        self.auto_rebuild = auto_rebuild;
+    }
+
+    /// Check whether word changes are waiting for a rebuild
+    pub fn needs_rebuild(&self) -> bool {
+        !self.built
+    }
+
+    /// Apply pending word changes by rebuilding the automaton once
+    ///
+    /// Equivalent to [`build`](Self::build); does nothing if there are no
+    /// pending changes.
+    pub fn commit(&mut self) {
+        self.build();
     }
 
     /// Build the DFA automaton
@@ -468,10 +993,24 @@ impl SensitiveWordFilter {
         self.nodes.push(DfaNode::new());
 
         // Build trie
-        for word in &self.word_set {
+        let mut normalized = Vec::new();
+        let entries = self
+            .word_set
+            .iter()
+            .map(|(word, tag)| (word, Some(tag)))
+            .chain(self.allowed_words.iter().map(|word| (word, None)));
+        for (word, tag) in entries {
             let mut current = 0;
 
+            normalized.clear();
             for ch in word.chars() {
+                self.normalize_char(ch, &mut normalized);
+            }
+            if normalized.is_empty() {
+                continue; // Word consists only of noise characters
+            }
+
+            for &ch in &normalized {
                 if let Some(&next) = self.nodes[current].children.get(&ch) {
                     current = next;
                 } else {
@@ -483,7 +1022,12 @@ impl SensitiveWordFilter {
             }
 
             // Mark end of word
-            self.nodes[current].output.push(word.clone());
+            self.nodes[current].output.push(NodeOutput {
+                word: word.clone(),
+                len: normalized.len(),
+                allowed: tag.is_none(),
+                tag: tag.cloned().unwrap_or_default(),
+            });
         }
 
         // Build failure links using BFS
@@ -551,7 +1095,7 @@ impl SensitiveWordFilter {
     /// assert_eq!(matches.len(), 2);
     /// ```
     pub fn find_matches(&mut self, text: &str) -> Vec<WordMatch> {
-        if !self.built {
+        if !self.built && self.auto_rebuild {
             self.build();
         }
 
@@ -559,14 +1103,24 @@ impl SensitiveWordFilter {
         let mut matches = Vec::new();
         let mut current = 0;
 
-        let processed_text = if self.case_sensitive {
-            text.to_string()
-        } else {
-            text.to_lowercase()
-        };
-
-        let chars: Vec<char> = processed_text.chars().collect();
+        // Normalized characters, each paired with its index in the original text
+        let original_chars: Vec<char> = text.chars().collect();
+        let mut byte_offsets = Vec::with_capacity(original_chars.len() + 1);
+        byte_offsets.push(0);
+        for ch in &original_chars {
+            byte_offsets.push(byte_offsets[byte_offsets.len() - 1] + ch.len_utf8());
+        }
+        let mut chars = Vec::with_capacity(original_chars.len());
+        let mut origins = Vec::with_capacity(original_chars.len());
+        let mut buffer = Vec::new();
+        for (index, &ch) in original_chars.iter().enumerate() {
+            buffer.clear();
+            self.normalize_char(ch, &mut buffer);
+            origins.extend(std::iter::repeat_n(index, buffer.len()));
+            chars.append(&mut buffer);
+        }
 
+        let mut allowed_ranges = Vec::new();
         for (i, &ch) in chars.iter().enumerate() {
             // Follow failure links until we find a match or reach root
             while current != 0 && !self.nodes[current].children.contains_key(&ch) {
@@ -578,28 +1132,51 @@ impl SensitiveWordFilter {
             }
 
             // Check for matches at current position
-            for word in &self.nodes[current].output {
-                let word_len = word.chars().count();
-                let start_pos = i + 1 - word_len;
-                let end_pos = i + 1;
-
-                // Get original text for the match
-                let original_chars: Vec<char> = text.chars().collect();
-                let matched_text: String = original_chars[start_pos..end_pos].iter().collect();
-
-                matches.push(WordMatch::new(
-                    word.clone(),
-                    start_pos,
-                    end_pos,
-                    matched_text,
-                ));
+            for output in &self.nodes[current].output {
+                // Skip matches covering only part of a character's expansion,
+                // such as "an" inside the pinyin "gan"
+                let start = i + 1 - output.len;
+                if (start > 0 && origins[start - 1] == origins[start])
+                    || origins.get(i + 1) == Some(&origins[i])
+                {
+                    continue;
+                }
+
+                let start_pos = origins[start];
+                let end_pos = origins[i] + 1;
+
+                if output.allowed {
+                    allowed_ranges.push((start_pos, end_pos));
+                    continue;
+                }
+
+                let (byte_start, byte_end) = (byte_offsets[start_pos], byte_offsets[end_pos]);
+                matches.push(WordMatch {
+                    word: output.word.clone(),
+                    start: start_pos,
+                    end: end_pos,
+                    byte_start,
+                    byte_end,
+                    matched_text: text[byte_start..byte_end].to_string(),
+                    category: output.tag.category.clone(),
+                    severity: output.tag.severity,
+                });
             }
         }
 
+        // The allowlist wins over any overlapping sensitive match
+        if !allowed_ranges.is_empty() {
+            matches.retain(|m| {
+                !allowed_ranges
+                    .iter()
+                    .any(|&(start, end)| start < m.end && m.start < end)
+            });
+        }
+
         // Update statistics
         let elapsed = start_time.elapsed();
         self.stats.texts_processed += 1;
-        self.stats.chars_processed += text.len();
+        self.stats.chars_processed += original_chars.len();
         self.stats.total_matches += matches.len();
         self.stats.processing_time_us += elapsed.as_micros() as u64;
 
@@ -690,22 +1267,34 @@ impl SensitiveWordFilter {
             return FilterResult::new(text.to_string(), matches, original_length);
         }
 
-        // Sort matches by position (reverse order for proper replacement)
-        let mut sorted_matches = matches.clone();
-        sorted_matches.sort_by(|a, b| b.start.cmp(&a.start));
-
-        let mut result = text.to_string();
-        let chars: Vec<char> = text.chars().collect();
-
-        for word_match in &sorted_matches {
-            let replacement = strategy.apply(word_match);
-
-            // Convert character positions to byte positions
-            let start_byte: usize = chars[..word_match.start].iter().map(|c| c.len_utf8()).sum();
-            let end_byte: usize = chars[..word_match.end].iter().map(|c| c.len_utf8()).sum();
+        // Overlapping matches such as "she" and "he" are merged into one
+        // span so that each part of the text is replaced exactly once
+        let mut sorted_matches: Vec<&WordMatch> = matches.iter().collect();
+        sorted_matches.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+
+        let mut spans: Vec<WordMatch> = Vec::new();
+        for word_match in sorted_matches {
+            match spans.last_mut() {
+                Some(span) if word_match.start < span.end => {
+                    if word_match.end > span.end {
+                        span.end = word_match.end;
+                        span.byte_end = word_match.byte_end;
+                        span.matched_text = text[span.byte_range()].to_string();
+                    }
+                    span.severity = span.severity.max(word_match.severity);
+                }
+                _ => spans.push(word_match.clone()),
+            }
+        }
 
-            result.replace_range(start_byte..end_byte, &replacement);
+        let mut result = String::with_capacity(original_length);
+        let mut copied_until = 0;
+        for span in &spans {
+            result.push_str(&text[copied_until..span.byte_start]);
+            result.push_str(&strategy.apply(span));
+            copied_until = span.byte_end;
         }
+        result.push_str(&text[copied_until..]);
 
         FilterResult::new(result, matches, original_length)
     }
@@ -786,12 +1375,50 @@ impl FilterBuilder {
         self
     }
 
+    /// Set characters to ignore while matching
+    pub fn noise_chars<I: IntoIterator<Item = char>>(mut self, chars: I) -> Self {
+        self.filter.set_noise_chars(chars);
+        self
+    }
+
+    /// Map a look-alike character to its canonical form
+    pub fn homoglyph(mut self, from: char, to: char) -> Self {
+        self.filter.add_homoglyph(from, to);
+        self
+    }
+
+    /// Add the built-in homoglyph mappings
+    pub fn default_homoglyphs(mut self) -> Self {
+        self.filter.add_default_homoglyphs();
+        self
+    }
+
+    /// Match Han characters by their pinyin (requires the `pinyin` feature)
+    #[cfg(feature = "pinyin")]
+    pub fn pinyin_matching(mut self, enabled: bool) -> Self {
+        self.filter.set_pinyin_matching(enabled);
+        self
+    }
+
+    /// Set whether matching rebuilds the automaton after word changes
+    pub fn auto_rebuild(mut self, auto_rebuild: bool) -> Self {
+        self.filter.set_auto_rebuild(auto_rebuild);
+        self
+    }
+
     /// Add a word to the filter
     pub fn add_word<S: AsRef<str>>(mut self, word: S) -> Self {
         self.filter.add_word(word.as_ref());
         self
     }
 
+    /// Add a word with a category and severity
+    pub fn add_word_tagged<S: AsRef<str>>(mut self, word: S, category: &str, severity: u8) -> Self {
+        self.filter
+            .add_word_tagged(word.as_ref(), category, severity);
+        self
+    }
+
     /// Add multiple words to the filter
     pub fn add_words<I>(mut self, words: I) -> Self
     where
@@ -802,6 +1429,22 @@ impl FilterBuilder {
         self
     }
 
+    /// Add a word to the allowlist
+    pub fn allow_word<S: AsRef<str>>(mut self, word: S) -> Self {
+        self.filter.add_allowed_word(word.as_ref());
+        self
+    }
+
+    /// Add multiple words to the allowlist
+    pub fn allow_words<I>(mut self, words: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.filter.add_allowed_words(words);
+        self
+    }
+
     /// Load words from a file (one word per line)
     pub fn load_from_file<P: AsRef<std::path::Path>>(
         mut self,
@@ -836,7 +1479,7 @@ mod tests {
 
     #[test]
     fn test_word_match() {
-        let word_match = WordMatch::new("test".to_string(), 0, 4, "Test".to_string());
+        let word_match = WordMatch::new("test".to_string(), "Test", 0, 4);
         assert_eq!(word_match.word, "test");
         assert_eq!(word_match.start, 0);
         assert_eq!(word_match.end, 4);
@@ -846,35 +1489,35 @@ mod tests {
 
     #[test]
     fn test_filter_strategy_mask() {
-        let word_match = WordMatch::new("bad".to_string(), 0, 3, "bad".to_string());
+        let word_match = WordMatch::new("bad".to_string(), "bad", 0, 3);
         let strategy = FilterStrategy::Mask;
         assert_eq!(strategy.apply(&word_match), "***");
     }
 
     #[test]
     fn test_filter_strategy_replace() {
-        let word_match = WordMatch::new("bad".to_string(), 0, 3, "bad".to_string());
+        let word_match = WordMatch::new("bad".to_string(), "bad", 0, 3);
         let strategy = FilterStrategy::Replace("good".to_string());
         assert_eq!(strategy.apply(&word_match), "good");
     }
 
     #[test]
     fn test_filter_strategy_char() {
-        let word_match = WordMatch::new("bad".to_string(), 0, 3, "bad".to_string());
+        let word_match = WordMatch::new("bad".to_string(), "bad", 0, 3);
         let strategy = FilterStrategy::Char('X');
         assert_eq!(strategy.apply(&word_match), "XXX");
     }
 
     #[test]
     fn test_filter_strategy_highlight() {
-        let word_match = WordMatch::new("bad".to_string(), 0, 3, "bad".to_string());
+        let word_match = WordMatch::new("bad".to_string(), "bad", 0, 3);
         let strategy = FilterStrategy::Highlight("[".to_string(), "]".to_string());
         assert_eq!(strategy.apply(&word_match), "[bad]");
     }
 
     #[test]
     fn test_filter_strategy_remove() {
-        let word_match = WordMatch::new("bad".to_string(), 0, 3, "bad".to_string());
+        let word_match = WordMatch::new("bad".to_string(), "bad", 0, 3);
         let strategy = FilterStrategy::Remove;
         assert_eq!(strategy.apply(&word_match), "");
     }
@@ -933,7 +1576,7 @@ mod tests {
         let matches = filter.find_matches(text);
 
         // Should find "she" and "he" within "she"
-        assert!(matches.len() >= 1);
+        assert!(!matches.is_empty());
     }
 
     #[test]
@@ -1080,7 +1723,7 @@ mod tests {
         assert!(!result.has_matches());
         assert_eq!(result.match_count(), 0);
         assert_eq!(result.filtered_text, text);
-        assert_eq!(result.filter_percentage(), 0.0);
+        assert!(result.filter_percentage().abs() < f64::EPSILON);
     }
 
     #[test]
@@ -1127,4 +1770,361 @@ mod tests {
         let keep_result = filter.filter_with_strategy(text, &FilterStrategy::KeepOriginal);
         assert_eq!(keep_result.filtered_text, "This is bad text");
     }
+
+    #[test]
+    fn test_deferred_rebuild() {
+        let mut filter = SensitiveWordFilter::new();
+        assert!(filter.needs_rebuild());
+        filter.add_word("bad");
+        filter.build();
+        assert!(!filter.needs_rebuild());
+
+        // Re-adding an existing word or removing a missing one changes nothing
+        filter.add_word("BAD");
+        filter.remove_word("missing");
+        assert!(!filter.needs_rebuild());
+
+        filter.set_auto_rebuild(false);
+        filter.remove_word("bad");
+        filter.add_word("evil");
+        assert!(filter.needs_rebuild());
+        assert!(filter.contains_sensitive_words("bad"));
+        assert!(!filter.contains_sensitive_words("evil"));
+
+        filter.commit();
+        assert!(!filter.needs_rebuild());
+        assert!(!filter.contains_sensitive_words("bad"));
+        assert!(filter.contains_sensitive_words("evil"));
+    }
+
+    #[test]
+    fn test_add_words_one_at_a_time() {
+        let mut filter = FilterBuilder::new().auto_rebuild(false).build();
+
+        let start = std::time::Instant::now();
+        for i in 0..10_000 {
+            filter.add_word(&format!("word{i:05}"));
+            // Matching between updates must not rebuild the whole dictionary
+            filter.find_matches("some text to scan");
+        }
+        filter.commit();
+        let elapsed = start.elapsed();
+
+        assert_eq!(filter.word_count(), 10_000);
+        assert!(filter.contains_sensitive_words("contains word09999 here"));
+        assert!(filter.contains_sensitive_words("word00000"));
+        assert!(!filter.contains_sensitive_words("word1000"));
+        // A full rebuild per add is quadratic; incremental adds take well
+        // under a second even in debug builds
+        assert!(
+            elapsed < std::time::Duration::from_secs(20),
+            "adding 10k words took {elapsed:?}"
+        );
+    }
+
+    #[test]
+    fn test_noise_char_skipping() {
+        let mut filter = FilterBuilder::new()
+            .noise_chars(" .-_".chars())
+            .add_word("bad")
+            .build();
+
+        let text = "Not b.a.d, but b a d and b-a_d";
+        let matches = filter.find_matches(text);
+        assert_eq!(matches.len(), 3);
+
+        let chars: Vec<char> = text.chars().collect();
+        for word_match in &matches {
+            let original: String = chars[word_match.start..word_match.end].iter().collect();
+            assert_eq!(original, word_match.matched_text);
+            assert_eq!(word_match.word, "bad");
+        }
+        assert_eq!(matches[0].matched_text, "b.a.d");
+        assert_eq!((matches[0].start, matches[0].end), (4, 9));
+        assert_eq!(matches[1].matched_text, "b a d");
+
+        let result = filter.filter_with_strategy("Not b.a.d!", &FilterStrategy::Mask);
+        assert_eq!(result.filtered_text, "Not *****!");
+
+        // Without noise skipping the same text is clean
+        let mut plain = FilterBuilder::new().add_word("bad").build();
+        assert!(!plain.contains_sensitive_words("b.a.d"));
+    }
+
+    #[test]
+    fn test_homoglyph_normalization() {
+        let mut filter = FilterBuilder::new()
+            .default_homoglyphs()
+            .add_word("bad")
+            .build();
+
+        // Cyrillic 'а', full-width letters and leetspeak
+        assert!(filter.contains_sensitive_words("so bаd"));
+        assert!(filter.contains_sensitive_words("ＢＡＤ"));
+        assert!(filter.contains_sensitive_words("b4d"));
+        assert!(!filter.contains_sensitive_words("bed"));
+
+        let text = "很ＢＡＤ的";
+        let matches = filter.find_matches(text);
+        assert_eq!(matches.len(), 1);
+        assert_eq!((matches[0].start, matches[0].end), (1, 4));
+        assert_eq!(matches[0].matched_text, "ＢＡＤ");
+
+        // Case-sensitive matching applies the mapping without folding
+        let mut sensitive = FilterBuilder::new()
+            .case_sensitive(true)
+            .homoglyph('@', 'a')
+            .add_word("bad")
+            .build();
+        assert!(sensitive.contains_sensitive_words("b@d"));
+        assert!(!sensitive.contains_sensitive_words("B@D"));
+    }
+
+    #[test]
+    fn test_allowlist_suppresses_overlapping_matches() {
+        let mut filter = FilterBuilder::new()
+            .add_words(vec!["cunt", "horpe", "thor"])
+            .allow_word("Scunthorpe")
+            .build();
+
+        // Every sensitive match overlaps the allowlisted region
+        let text = "Welcome to Scunthorpe";
+        assert!(filter.find_matches(text).is_empty());
+        let result = filter.filter_with_strategy(text, &FilterStrategy::Mask);
+        assert_eq!(result.filtered_text, text);
+        assert!(!result.has_matches());
+
+        // Matches outside allowlisted regions are still filtered
+        let result = filter.filter_with_strategy("SCUNTHORPE is not thor", &FilterStrategy::Mask);
+        assert_eq!(result.filtered_text, "SCUNTHORPE is not ****");
+        assert_eq!(result.match_count(), 1);
+        assert_eq!(result.matches[0].start, 18);
+
+        // Removing the allowlist entry restores the matches
+        filter.remove_allowed_word("scunthorpe");
+        assert_eq!(filter.allowed_word_count(), 0);
+        assert_eq!(filter.find_matches(text).len(), 3);
+    }
+
+    #[test]
+    fn test_allowlist_partial_overlap() {
+        // "grape" is allowed; "apex" only partially overlaps it but is dropped
+        let mut filter = FilterBuilder::new()
+            .add_words(vec!["apex", "ex"])
+            .allow_word("grape")
+            .build();
+
+        let matches = filter.find_matches("grapex");
+        assert!(matches.iter().all(|m| m.word != "apex"));
+        // "ex" at 4..6 overlaps "grape" at 0..5 as well
+        assert!(matches.is_empty());
+
+        let matches = filter.find_matches("grape apex");
+        let words: Vec<&str> = matches.iter().map(|m| m.word.as_str()).collect();
+        assert_eq!(words, vec!["apex", "ex"]);
+        assert_eq!((matches[0].start, matches[0].end), (6, 10));
+
+        // A word that is both sensitive and allowed is never reported
+        let mut both = FilterBuilder::new()
+            .add_word("same")
+            .allow_word("same")
+            .build();
+        assert!(!both.contains_sensitive_words("the same"));
+    }
+
+    #[test]
+    fn test_unicode_positions_and_filter_rate() {
+        let mut filter = SensitiveWordFilter::new();
+        filter.add_word("坏");
+        filter.add_word("bad");
+        filter.build();
+
+        // 6 chars, 9 bytes: "坏" is 3 bytes, "é" is 2
+        let text = "é坏 bad";
+        let result = filter.filter_with_strategy(text, &FilterStrategy::Mask);
+        assert_eq!(result.filtered_text, "é* ***");
+        assert_eq!(result.original_length, 9);
+
+        let cjk = &result.matches[0];
+        assert_eq!(cjk.char_range(), 1..2);
+        assert_eq!(cjk.byte_range(), 2..5);
+        assert_eq!((cjk.len(), cjk.byte_len()), (1, 3));
+        assert_eq!(&text[cjk.byte_range()], "坏");
+
+        let ascii = &result.matches[1];
+        assert_eq!(ascii.char_range(), 3..6);
+        assert_eq!(ascii.byte_range(), 6..9);
+
+        // 6 of the 9 bytes were filtered
+        assert!((result.filter_percentage() - 6.0 / 9.0 * 100.0).abs() < 1e-9);
+
+        // A fully sensitive CJK text is 100% filtered, not 33%
+        let result = filter.filter_with_strategy("坏坏", &FilterStrategy::Mask);
+        assert!((result.filter_percentage() - 100.0).abs() < 1e-9);
+
+        assert_eq!(filter.get_stats().chars_processed, 6 + 2);
+    }
+
+    #[test]
+    fn test_filter_overlapping_words() {
+        let mut filter = FilterBuilder::new().add_words(vec!["坏人", "人"]).build();
+        let result = filter.filter_with_strategy("坏人", &FilterStrategy::Mask);
+        assert_eq!(result.match_count(), 2);
+        assert_eq!(result.filtered_text, "**");
+
+        let mut filter = FilterBuilder::new().add_words(vec!["she", "he"]).build();
+        let result =
+            filter.filter_with_strategy("she said", &FilterStrategy::Replace("[x]".into()));
+        assert_eq!(result.filtered_text, "[x] said");
+
+        // Partially overlapping matches are joined into one span
+        let mut filter = FilterBuilder::new().add_words(vec!["abc", "cde"]).build();
+        let result = filter.filter_with_strategy(
+            "xabcdex",
+            &FilterStrategy::Highlight("<".into(), ">".into()),
+        );
+        assert_eq!(result.filtered_text, "x<abcde>x");
+    }
+
+    #[test]
+    fn test_word_match_byte_offsets() {
+        let word_match = WordMatch::new("坏".to_string(), "é坏 bad", 1, 2);
+        assert_eq!(word_match.byte_range(), 2..5);
+        assert_eq!(word_match.matched_text, "坏");
+
+        let word_match = WordMatch::new("bad".to_string(), "é坏 bad", 3, 6);
+        assert_eq!(word_match.byte_range(), 6..9);
+        assert_eq!(word_match.matched_text, "bad");
+    }
+
+    #[test]
+    fn test_filter_percentage_counts_overlaps_once() {
+        let mut filter = FilterBuilder::new().add_words(vec!["she", "he"]).build();
+        let result = filter.filter_with_strategy("she", &FilterStrategy::KeepOriginal);
+        assert_eq!(result.match_count(), 2);
+        assert!((result.filter_percentage() - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_mask_keeping_edges() {
+        let strategy = FilterStrategy::MaskKeepingEdges;
+        let apply = |text: &str| {
+            let word_match = WordMatch::new(text.to_lowercase(), text, 0, text.chars().count());
+            strategy.apply(&word_match)
+        };
+
+        assert_eq!(apply("bad"), "b*d");
+        assert_eq!(apply("Badword"), "B*****d");
+        assert_eq!(apply("坏东西"), "坏*西");
+        assert_eq!(apply("坏人"), "坏*");
+        assert_eq!(apply("x"), "*");
+
+        let mut filter = FilterBuilder::new()
+            .add_words(vec!["bad", "坏东西"])
+            .build();
+        let result =
+            filter.filter_with_strategy("你这个坏东西 is BAD", &FilterStrategy::MaskKeepingEdges);
+        assert_eq!(result.filtered_text, "你这个坏*西 is B*D");
+    }
+
+    #[test]
+    fn test_mask_per_char_preserves_display_width() {
+        let mut filter = FilterBuilder::new().add_words(vec!["bad", "坏人"]).build();
+
+        let text = "你是坏人, bad guy";
+        let result = filter.filter_with_strategy(text, &FilterStrategy::MaskPerChar('*'));
+        assert_eq!(result.filtered_text, "你是****, *** guy");
+        assert_eq!(display_width(&result.filtered_text), display_width(text));
+
+        // Plain Char masking keeps the char count, not the width
+        let result = filter.filter_with_strategy(text, &FilterStrategy::Char('*'));
+        assert_eq!(result.filtered_text, "你是**, *** guy");
+
+        // A wide mask character is repeated per two columns, padding odd widths
+        let result = filter.filter_with_strategy(text, &FilterStrategy::MaskPerChar('＊'));
+        assert_eq!(result.filtered_text, "你是＊＊, ＊  guy");
+        assert_eq!(display_width(&result.filtered_text), display_width(text));
+    }
+
+    #[test]
+    fn test_tagged_words() {
+        let mut filter = FilterBuilder::new()
+            .add_word_tagged("idiot", "profanity", 2)
+            .add_word_tagged("kill", "violence", 5)
+            .add_word("meh")
+            .build();
+
+        let result = filter.filter_with_strategy(
+            "Meh, that idiot said he would KILL the idiot plant",
+            &FilterStrategy::Mask,
+        );
+        assert_eq!(result.match_count(), 4);
+        assert_eq!(result.max_severity(), Some(5));
+
+        let by_category = result.matches_by_category();
+        assert_eq!(by_category.len(), 3);
+        assert_eq!(by_category["profanity"].len(), 2);
+        assert!(by_category["profanity"].iter().all(|m| m.severity == 2));
+        assert_eq!(by_category["violence"][0].matched_text, "KILL");
+        assert_eq!(by_category[DEFAULT_CATEGORY][0].severity, DEFAULT_SEVERITY);
+
+        let clean = filter.filter_with_strategy("nothing here", &FilterStrategy::Mask);
+        assert_eq!(clean.max_severity(), None);
+        assert!(clean.matches_by_category().is_empty());
+    }
+
+    #[test]
+    fn test_retagging_words() {
+        let mut filter = SensitiveWordFilter::new();
+        filter.add_word_tagged("spam", "spam", 1);
+        filter.build();
+
+        // Plain add_word keeps the existing tag
+        filter.add_word("SPAM");
+        assert!(!filter.needs_rebuild());
+        assert_eq!(filter.find_matches("spam")[0].category, "spam");
+
+        // Tagging again replaces it
+        filter.add_word_tagged("spam", "scam", 4);
+        assert!(filter.needs_rebuild());
+        let matches = filter.find_matches("spam");
+        assert_eq!(matches[0].category, "scam");
+        assert_eq!(matches[0].severity, 4);
+        assert_eq!(filter.word_count(), 1);
+    }
+
+    #[cfg(feature = "pinyin")]
+    #[test]
+    fn test_pinyin_matching() {
+        let mut filter = FilterBuilder::new()
+            .pinyin_matching(true)
+            .noise_chars([' '])
+            .add_word_tagged("赌博", "gambling", 3)
+            .add_word("安")
+            .build();
+
+        let text = "周末去DUBO吗";
+        let matches = filter.find_matches(text);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].word, "赌博");
+        assert_eq!(matches[0].category, "gambling");
+        assert_eq!(matches[0].matched_text, "DUBO");
+        assert_eq!(matches[0].char_range(), 3..7);
+        assert_eq!(&text[matches[0].byte_range()], "DUBO");
+
+        // Original characters, mixed spellings and spaced syllables
+        for text in ["去赌博吗", "去赌bo吗", "去du博吗", "去 du bo 吗"] {
+            assert_eq!(filter.find_matches(text).len(), 1, "{text}");
+        }
+        assert_eq!(filter.filter("去赌bo吗"), "去***吗");
+
+        // "an" inside the syllable "gan" (干) is not a match, but "an" is
+        assert!(!filter.contains_sensitive_words("干"));
+        assert!(filter.contains_sensitive_words("an"));
+
+        // Without the mode only the characters themselves match
+        filter.set_pinyin_matching(false);
+        assert!(!filter.contains_sensitive_words("去dubo吗"));
+        assert!(filter.contains_sensitive_words("去赌博吗"));
+    }
 }